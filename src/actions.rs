@@ -0,0 +1,27 @@
+use tetra::Context;
+use tetra::input::{self, Key};
+
+use crate::game_state::GameState;
+
+/// Inputs read through a binding instead of a hard-coded key.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Action {
+    ToggleMute,
+    Attack, // Overworld bone throw
+}
+
+impl Action {
+    /// The key bound to this action. Only the defaults for now, there is no
+    /// rebinding yet.
+    pub fn key(self) -> Key {
+        match self {
+            Action::ToggleMute => Key::M,
+            Action::Attack => Key::Q,
+        }
+    }
+}
+
+/// Pressed this frame, and no text field has the keyboard.
+pub fn is_action_pressed(ctx: &Context, state: &GameState, action: Action) -> bool {
+    !state.input_captured() && input::is_key_pressed(ctx, action.key())
+}
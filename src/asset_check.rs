@@ -0,0 +1,173 @@
+use std::fs;
+
+use crate::assets::{ASSET_LIST, AssetDefinition, AssetType};
+
+/// What's wrong with an asset on disk, if anything.
+pub enum AssetStatus {
+    Ok,
+    Missing,
+    NotInstalled,    // Missing but optional, not a failure
+    Corrupt(String), // Unreadable, undecodable or the wrong size
+}
+
+pub struct AssetResult {
+    pub name: &'static str,
+    pub path: &'static str,
+    pub status: AssetStatus,
+}
+
+impl AssetResult {
+    pub fn is_ok(&self) -> bool {
+        matches!(self.status, AssetStatus::Ok | AssetStatus::NotInstalled)
+    }
+
+    /// One line for the log, the console output and the report panel.
+    pub fn line(&self) -> String {
+        match &self.status {
+            AssetStatus::Ok => format!("OK       {} ({})", self.name, self.path),
+            AssetStatus::Missing => format!("MISSING  {} ({})", self.name, self.path),
+            AssetStatus::NotInstalled => format!("OPTIONAL {} ({})", self.name, self.path),
+            AssetStatus::Corrupt(reason) => {
+                format!("CORRUPT  {} ({}): {}", self.name, self.path, reason)
+            }
+        }
+    }
+}
+
+/// Checks every asset in `ASSET_LIST`: it's there, it reads, it's the size
+/// the list expects and it decodes. Sounds only get their header checked,
+/// a full decode would take as long as loading the game.
+pub fn verify() -> Vec<AssetResult> {
+    ASSET_LIST
+        .iter()
+        .map(|def| AssetResult {
+            name: def.name,
+            path: def.path,
+            status: check(def),
+        })
+        .collect()
+}
+
+fn check(def: &AssetDefinition) -> AssetStatus {
+    let data = match fs::read(def.path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return if def.optional {
+                AssetStatus::NotInstalled
+            } else {
+                AssetStatus::Missing
+            };
+        }
+        Err(e) => return AssetStatus::Corrupt(format!("can't read: {}", e)),
+    };
+    if data.len() as u64 != def.size {
+        return AssetStatus::Corrupt(format!("{} bytes, expected {}", data.len(), def.size));
+    }
+    match def.asset_type {
+        AssetType::Texture => match tetra::graphics::ImageData::from_encoded(&data) {
+            Ok(_) => AssetStatus::Ok,
+            Err(e) => AssetStatus::Corrupt(format!("doesn't decode: {}", e)),
+        },
+        AssetType::Sound if is_sound(&data) => AssetStatus::Ok,
+        AssetType::Sound => AssetStatus::Corrupt("not a WAV, MP3, OGG or FLAC file".to_string()),
+    }
+}
+
+/// Looks for the magic bytes of the formats tetra plays.
+fn is_sound(data: &[u8]) -> bool {
+    let wav = data.len() >= 12 && &data[0..4] == b"RIFF" && &data[8..12] == b"WAVE";
+    let mp3 =
+        data.starts_with(b"ID3") || (data.len() >= 2 && data[0] == 0xFF && data[1] & 0xE0 == 0xE0);
+    wav || mp3 || data.starts_with(b"OggS") || data.starts_with(b"fLaC")
+}
+
+/// "38 OK, 2 missing, 1 corrupt" for the end of the report.
+pub fn totals(results: &[AssetResult]) -> String {
+    let missing = results
+        .iter()
+        .filter(|r| matches!(r.status, AssetStatus::Missing))
+        .count();
+    let corrupt = results
+        .iter()
+        .filter(|r| matches!(r.status, AssetStatus::Corrupt(_)))
+        .count();
+    let ok = results.len() - missing - corrupt;
+    format!("{} OK, {} missing, {} corrupt", ok, missing, corrupt)
+}
+
+/// Writes every result to the log, problems as warnings.
+pub fn log_results(results: &[AssetResult]) {
+    for result in results {
+        if result.is_ok() {
+            log::info!("{}", result.line());
+        } else {
+            log::warn!("{}", result.line());
+        }
+    }
+    log::info!("Asset check: {}", totals(results));
+}
+
+/// `--verify-assets`, prints the report and returns whether everything
+/// passed, for the exit code.
+pub fn run_cli() -> bool {
+    let results = verify();
+    for result in &results {
+        println!("{}", result.line());
+    }
+    println!("{}", totals(&results));
+    log_results(&results);
+    results.iter().all(AssetResult::is_ok)
+}
+
+/// A run kept for the in-game report panel, see scenes::asset_report.
+pub struct AssetReport {
+    pub results: Vec<AssetResult>,
+    pub scroll: usize, // First row shown
+}
+
+impl AssetReport {
+    pub fn run() -> Self {
+        let results = verify();
+        log_results(&results);
+        Self { results, scroll: 0 }
+    }
+
+    pub fn scroll_up(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+
+    /// Stops once the last row is at the bottom of the `visible` rows.
+    pub fn scroll_down(&mut self, rows: usize, visible: usize) {
+        let max = self.results.len().saturating_sub(visible);
+        self.scroll = (self.scroll + rows).min(max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shipped_assets_match_the_manifest() {
+        let failed: Vec<String> = verify()
+            .iter()
+            .filter(|result| !result.is_ok())
+            .map(AssetResult::line)
+            .collect();
+        assert!(failed.is_empty(), "{:#?}", failed);
+    }
+
+    #[test]
+    fn a_missing_optional_asset_is_not_a_failure() {
+        let mut def = AssetDefinition {
+            name: "Nowhere",
+            path: "./assets/not_there.png",
+            asset_type: AssetType::Texture,
+            size: 1,
+            optional: true,
+        };
+        assert!(matches!(check(&def), AssetStatus::NotInstalled));
+        def.optional = false;
+        assert!(matches!(check(&def), AssetStatus::Missing));
+    }
+}
@@ -7,6 +7,8 @@ pub struct AssetDefinition {
     pub name: &'static str,
     pub path: &'static str,
     pub asset_type: AssetType,
+    pub size: u64,      // Expected bytes, checked by --verify-assets
+    pub optional: bool, // Missing is fine, something else is drawn in its place
 }
 
 pub const ASSET_LIST: &[AssetDefinition] = &[
@@ -14,100 +16,282 @@ pub const ASSET_LIST: &[AssetDefinition] = &[
         name: "Player Front",
         path: "./assets/chara1.png",
         asset_type: AssetType::Texture,
-    },
-    AssetDefinition {
-        name: "Player Left",
-        path: "./assets/chara_left.png",
-        asset_type: AssetType::Texture,
+        size: 12903,
+        optional: false,
     },
     AssetDefinition {
         name: "Player Right",
         path: "./assets/chara_right.png",
         asset_type: AssetType::Texture,
+        size: 11045,
+        optional: false,
     },
     AssetDefinition {
         name: "City Background",
         path: "./assets/city_bg.png",
         asset_type: AssetType::Texture,
+        size: 62046,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Snow Layer",
+        path: "./assets/snow_layer.png",
+        asset_type: AssetType::Texture,
+        size: 1790,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Haze Layer",
+        path: "./assets/haze_layer.png",
+        asset_type: AssetType::Texture,
+        size: 20717,
+        optional: false,
     },
     AssetDefinition {
         name: "Gaster Standing",
         path: "./assets/npc_gaster_standing.png",
         asset_type: AssetType::Texture,
+        size: 11737,
+        optional: false,
     },
     AssetDefinition {
         name: "Gaster Talking",
         path: "./assets/npc_gaster_talking.png",
         asset_type: AssetType::Texture,
+        size: 11689,
+        optional: false,
     },
     AssetDefinition {
         name: "Rarity",
         path: "./assets/rarity_galla_right.png",
         asset_type: AssetType::Texture,
+        size: 2119,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Rarity Stab1",
+        path: "./assets/rarity_stab1.png",
+        asset_type: AssetType::Texture,
+        size: 880,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Rarity Stab2",
+        path: "./assets/rarity_stab2.png",
+        asset_type: AssetType::Texture,
+        size: 875,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Rarity Stab3",
+        path: "./assets/rarity_stab3.png",
+        asset_type: AssetType::Texture,
+        size: 781,
+        optional: false,
     },
     AssetDefinition {
         name: "Eilish",
         path: "./assets/eilish.png",
         asset_type: AssetType::Texture,
+        size: 303961,
+        optional: false,
+    },
+    // Side views, the front sprite mirrored stands in without them
+    AssetDefinition {
+        name: "Eilish Left",
+        path: "./assets/eilish_left.png",
+        asset_type: AssetType::Texture,
+        size: 298533,
+        optional: true,
+    },
+    AssetDefinition {
+        name: "Eilish Right",
+        path: "./assets/eilish_right.png",
+        asset_type: AssetType::Texture,
+        size: 303961,
+        optional: true,
     },
     AssetDefinition {
         name: "Sans",
         path: "./assets/sans1.png",
         asset_type: AssetType::Texture,
+        size: 6112,
+        optional: false,
     },
     AssetDefinition {
         name: "Sans Combat",
         path: "./assets/sans1.png",
         asset_type: AssetType::Texture,
+        size: 6112,
+        optional: false,
     },
     AssetDefinition {
         name: "Sans Shrug",
         path: "./assets/sans_shrug.png",
         asset_type: AssetType::Texture,
+        size: 5999,
+        optional: false,
     },
     AssetDefinition {
         name: "Sans Handshake",
         path: "./assets/sans_frisk_handshake.png",
         asset_type: AssetType::Texture,
+        size: 17228,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Pedestrian 1",
+        path: "./assets/pedestrian1.png",
+        asset_type: AssetType::Texture,
+        size: 148,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Pedestrian 2",
+        path: "./assets/pedestrian2.png",
+        asset_type: AssetType::Texture,
+        size: 147,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Survivor",
+        path: "./assets/survivor.png",
+        asset_type: AssetType::Texture,
+        size: 161,
+        optional: false,
     },
     AssetDefinition {
         name: "Heart",
         path: "./assets/heart.png",
         asset_type: AssetType::Texture,
+        size: 1849,
+        optional: false,
     },
     AssetDefinition {
         name: "Music Box",
         path: "./assets/musicbox.png",
         asset_type: AssetType::Texture,
+        size: 5985,
+        optional: false,
     },
     AssetDefinition {
         name: "Music Track",
         path: "./assets/g6_rmx.mp3",
         asset_type: AssetType::Sound,
+        size: 3665157,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Gaster Static",
+        path: "./assets/gaster_static.wav",
+        asset_type: AssetType::Sound,
+        size: 19888,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Graze Tick",
+        path: "./assets/graze.wav",
+        asset_type: AssetType::Sound,
+        size: 2690,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Stab SFX",
+        path: "./assets/stab.wav",
+        asset_type: AssetType::Sound,
+        size: 11068,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Eilish Song",
+        path: "./assets/eilish_song.wav",
+        asset_type: AssetType::Sound,
+        size: 507194,
+        optional: false,
     },
     AssetDefinition {
         name: "Ayasofya Entrance",
         path: "./assets/ayasofya_giris.jpg",
         asset_type: AssetType::Texture,
+        size: 308284,
+        optional: false,
     },
     AssetDefinition {
         name: "Ayasofya Interior",
         path: "./assets/ayasofya_ici.jpg",
         asset_type: AssetType::Texture,
+        size: 218222,
+        optional: false,
     },
     AssetDefinition {
         name: "Bone",
         path: "./assets/bone.png",
         asset_type: AssetType::Texture,
+        size: 20632,
+        optional: false,
     },
     AssetDefinition {
         name: "Player Fes",
         path: "./assets/chara_fes.png",
         asset_type: AssetType::Texture,
+        size: 1046,
+        optional: false,
     },
     AssetDefinition {
         name: "Player Takke",
         path: "./assets/chara_mavi_takke.png",
         asset_type: AssetType::Texture,
+        size: 1235,
+        optional: false,
+    },
+    // Main menu, one per option in order
+    AssetDefinition {
+        name: "Icon Start Game",
+        path: "./assets/icons/sword.png",
+        asset_type: AssetType::Texture,
+        size: 128,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Create Save",
+        path: "./assets/icons/floppy.png",
+        asset_type: AssetType::Texture,
+        size: 122,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Select Save",
+        path: "./assets/icons/magnifier.png",
+        asset_type: AssetType::Texture,
+        size: 144,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Settings",
+        path: "./assets/icons/gear.png",
+        asset_type: AssetType::Texture,
+        size: 128,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Credits",
+        path: "./assets/icons/star.png",
+        asset_type: AssetType::Texture,
+        size: 128,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Statistics",
+        path: "./assets/icons/chart.png",
+        asset_type: AssetType::Texture,
+        size: 107,
+        optional: false,
+    },
+    AssetDefinition {
+        name: "Icon Quit Game",
+        path: "./assets/icons/door.png",
+        asset_type: AssetType::Texture,
+        size: 126,
+        optional: false,
     },
 ];
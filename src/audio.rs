@@ -0,0 +1,83 @@
+use std::collections::VecDeque;
+
+use tetra::Context;
+use tetra::audio::{Sound, SoundInstance, SoundState};
+
+use crate::game_state::GameState;
+
+const SFX_GAP: f32 = 0.05; // Seconds of quiet between two queued sounds
+
+/// Short sound effects, played one after another instead of on top of
+/// each other. Sounds are named the same as in the asset list.
+pub struct SoundQueue {
+    queue: VecDeque<String>,
+    current: Option<SoundInstance>,
+    cooldown: f32,
+}
+
+impl SoundQueue {
+    pub fn new() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            current: None,
+            cooldown: 0.0,
+        }
+    }
+
+    /// Queues a sound. One that's already waiting isn't queued again, so
+    /// mashing a key doesn't leave a backlog playing for seconds after.
+    pub fn enqueue(&mut self, name: &str) {
+        if !self.queue.iter().any(|queued| queued == name) {
+            self.queue.push_back(name.to_string());
+        }
+    }
+
+    fn is_playing(&self) -> bool {
+        self.current
+            .as_ref()
+            .is_some_and(|instance| instance.state() == SoundState::Playing)
+    }
+}
+
+/// The loaded sound for a queued name and its volume relative to the
+/// master volume.
+fn sound<'a>(state: &'a GameState, name: &str) -> Option<(&'a Sound, f32)> {
+    let sound = match name {
+        "Graze Tick" => state.graze_sound.as_ref().map(|s| (s, 0.4)),
+        "Stab SFX" => state.world.stab_sound.as_ref().map(|s| (s, 1.0)),
+        "Gaster Static" => state.world.gaster_static_sound.as_ref().map(|s| (s, 1.0)),
+        _ => None,
+    };
+    if sound.is_none() {
+        log::debug!("No sound loaded for '{}'", name);
+    }
+    sound
+}
+
+/// Starts the next queued sound once the last one has finished.
+pub fn update(ctx: &mut Context, state: &mut GameState, dt: f32) {
+    let queue = &mut state.world.sound_queue;
+    if queue.cooldown > 0.0 {
+        queue.cooldown -= dt;
+        return;
+    }
+    if queue.is_playing() {
+        return;
+    }
+    queue.current = None;
+
+    let Some(name) = queue.queue.pop_front() else {
+        return;
+    };
+    let Some((sound, volume)) = sound(state, &name) else {
+        return;
+    };
+    match sound.play_with(ctx, state.system.volume * volume, 1.0) {
+        Ok(instance) => {
+            let queue = &mut state.world.sound_queue;
+            queue.current = Some(instance);
+            queue.cooldown = SFX_GAP;
+        }
+        Err(e) => log::warn!("Failed to play '{}': {}", name, e),
+    }
+}
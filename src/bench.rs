@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use tetra::graphics::DrawParams;
+use tetra::input::Key;
+
+use crate::combat::{AttackPattern, CombatData, CombatEnemy, CombatTurn};
+use crate::defs::Scene;
+use crate::game_state::GameState;
+use crate::objectives::WorldFlag;
+use crate::render_util::{MAX_TEXT_DRAWS, TextStyle};
+use crate::sim::{Effect, UpdateInput};
+
+const TICKS: u32 = 1000;
+const DT: f32 = 1.0 / 60.0;
+const TARGET_MS: f64 = 0.5; // Per tick, leaves plenty of a 16 ms frame on weak hardware
+const SWEEP_TICKS: u32 = 60; // The soul crosses the box back and forth, into bones
+const BENCH_SEED: u64 = 0xb0e5;
+
+struct CombatRun {
+    average_ms: f64,
+    worst_ms: f64,
+    peak_bones: usize,
+}
+
+/// Times the combat update headlessly, run with `--bench-combat`. Sans's
+/// turns are the real ones, always the corridor pattern with its board
+/// events, and the soul sweeps through it so hits and grazes happen too.
+pub fn run_combat() {
+    let run = measure_combat();
+    log::info!(
+        "Combat bench: {} ticks, {:.4} ms average, {:.4} ms worst, {} bones at peak",
+        TICKS,
+        run.average_ms,
+        run.worst_ms,
+        run.peak_bones
+    );
+    if run.average_ms > TARGET_MS {
+        log::warn!("Combat bench is over the {} ms target", TARGET_MS);
+    }
+}
+
+fn measure_combat() -> CombatRun {
+    let mut state = GameState::headless();
+    state.scene = Scene::Combat;
+    state.combat_data = CombatData::for_enemy(CombatEnemy::Sans);
+    // The tutorial would hold every turn at its first frame
+    state.world.flags |= WorldFlag::CombatTutorialDone.bit();
+    let mut rng = StdRng::seed_from_u64(BENCH_SEED);
+    let mut effects = Vec::new();
+
+    let mut total = Duration::ZERO;
+    let mut worst = Duration::ZERO;
+    let mut peak_bones = 0;
+    for i in 0..TICKS {
+        if state.combat_data.turn != CombatTurn::SansTurn {
+            start_corridor(&mut state, &mut rng, &mut effects);
+        }
+        // A death would end the fight, so the soul never runs out of HP
+        state.player.player_hp = state.player.player_max_hp;
+        effects.clear();
+
+        let mut input = UpdateInput::new(DT, &mut rng);
+        let sweep = if (i / SWEEP_TICKS).is_multiple_of(2) {
+            Key::Left
+        } else {
+            Key::Right
+        };
+        input.down.push(sweep);
+
+        let start = Instant::now();
+        crate::scenes::combat::step(&mut input, &mut state, &mut effects);
+        let elapsed = start.elapsed();
+
+        total += elapsed;
+        worst = worst.max(elapsed);
+        peak_bones = peak_bones.max(state.combat_data.bones.len());
+    }
+
+    CombatRun {
+        average_ms: total.as_secs_f64() * 1000.0 / TICKS as f64,
+        worst_ms: worst.as_secs_f64() * 1000.0,
+        peak_bones,
+    }
+}
+
+/// Starts Sans's turns until one rolls the corridor, the densest pattern
+/// he has. The opening frames aren't timed.
+fn start_corridor(state: &mut GameState, rng: &mut StdRng, effects: &mut Vec<Effect>) {
+    loop {
+        state.combat_data.turn = CombatTurn::SansTurn;
+        state.combat_data.timer = 0.0;
+        let mut input = UpdateInput::new(DT, rng);
+        crate::scenes::combat::step(&mut input, state, effects);
+        if state.combat_data.pattern == AttackPattern::Corridor {
+            return;
+        }
+    }
+}
+
+/// Counts the draws each text style takes at every text scale the settings
+/// allow, run with `--check-text-draws`. No window is opened, so the draws
+/// are worked out rather than issued and nothing is timed.
+pub fn check_text_draws() {
+    let styles = [
+        TextStyle::Plain,
+        TextStyle::SHADOW,
+        TextStyle::OUTLINE,
+        TextStyle::Bold,
+    ];
+    let mut most_draws = 0;
+    for style in styles {
+        for text_scale in [0.5, 1.0, 2.0] {
+            let draws = style.passes(&DrawParams::new(), text_scale).len();
+            most_draws = most_draws.max(draws);
+        }
+    }
+    log::info!("Text draws: at most {} per styled string", most_draws);
+    if most_draws > MAX_TEXT_DRAWS {
+        log::warn!(
+            "A text style takes {} draws, over the {} limit",
+            most_draws,
+            MAX_TEXT_DRAWS
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::combat::BONE_POOL_SIZE;
+
+    #[test]
+    fn densest_spawning_fits_the_bone_pool() {
+        let run = measure_combat();
+        assert!(run.peak_bones > 0, "The corridor never spawned a bone");
+        assert!(
+            run.peak_bones < BONE_POOL_SIZE,
+            "{} bones at peak, the pool holds {}",
+            run.peak_bones,
+            BONE_POOL_SIZE
+        );
+    }
+
+    // Timing depends on the machine and the build, run it in release with
+    // `cargo test --release -- --ignored`
+    #[test]
+    #[ignore]
+    fn combat_tick_stays_under_budget() {
+        let run = measure_combat();
+        assert!(
+            run.average_ms <= TARGET_MS,
+            "{:.4} ms per tick, the budget is {} ms",
+            run.average_ms,
+            TARGET_MS
+        );
+    }
+}
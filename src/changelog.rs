@@ -0,0 +1,29 @@
+/// One release's notes for the "What's new" panel. Bullets stay English-only,
+/// only the panel's headings are translated.
+pub struct ChangelogEntry {
+    pub version: &'static str,
+    pub changes: &'static [&'static str],
+}
+
+/// Newest first. Add an entry whenever the Cargo version is bumped.
+pub const CHANGELOG: &[ChangelogEntry] = &[ChangelogEntry {
+    version: "0.1.0",
+    changes: &[
+        "Press L to read back everything that was said this session.",
+        "Stage exits glow, keep walking into one to leave.",
+        "Only the closest character answers when you press F.",
+        "Sans chases you if a spare goes wrong.",
+        "Eilish will sing for you in stage 4, press L next to her.",
+        "Gaster teleports around stage 2 and mutters to himself.",
+        "The Ayasofya courtyard has a crowd now.",
+        "Dead space fills an exposure meter before it starts hurting.",
+        "Grazing bones in combat builds up TP.",
+        "Progress is saved if the game crashes.",
+        "F3 shows a performance overlay.",
+        "Saves moved to the data directory, --portable keeps them local.",
+    ],
+}];
+
+pub fn current_version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
@@ -1,5 +1,16 @@
+use std::collections::VecDeque;
+
+use rand::{Rng, RngCore};
+use tetra::graphics::mesh::Mesh;
+use tetra::graphics::{Color, Rectangle};
 use tetra::math::Vec2;
 
+use crate::dialogue_log::Speaker;
+use crate::graphics_quality::GraphicsQuality;
+use crate::particles::{ParticleStyle, Particles};
+use crate::pool::Pool;
+use crate::system::DeathCause;
+
 #[derive(PartialEq, Clone, Copy)]
 pub enum CombatTurn {
     Menu,
@@ -9,24 +20,222 @@ pub enum CombatTurn {
     ItemSubMenu,
     ResultText,
     SansTurn,
+    TpSubMenu,
+}
+
+pub const TP_MAX: f32 = 100.0;
+pub const GRAZE_DISTANCE: f32 = 8.0; // How close a bone has to pass to count as a graze
+pub const GRAZE_TP: f32 = 5.0;
+const TP_HEAL_SHARE: f32 = 0.5; // Of the player's max HP, a full bar heals half
+pub const BONE_POOL_SIZE: usize = 256; // Spawns past this are dropped
+pub const SPARK_POOL_SIZE: usize = 128;
+const SPARKS_PER_GRAZE: usize = 4;
+const SPARK_RNG_SEED: u64 = 0x5a4c;
+const DAMAGE_NUMBER_LIFETIME: f32 = 0.8; // Seconds
+const DAMAGE_NUMBER_RISE: f32 = 40.0; // Pixels per second
+const DIALOGUE_DISPLAY_SECONDS: f32 = 3.0;
+const FLEE_BASE_CHANCE: f32 = 0.5;
+const FLEE_FAILURE_PENALTY: f32 = 0.1; // Per failed attempt this fight
+const FLEE_CIHAD_BONUS_MAX: f32 = 0.3;
+
+/// What a full TP bar is spent on.
+#[derive(PartialEq, Clone, Copy)]
+pub enum TpEffect {
+    Heal,
+    DoubleDamage,
+}
+
+/// Damage popping up over whoever took it, fading out as it rises.
+pub struct DamageNumber {
+    pub value: i32,
+    pub pos: Vec2<f32>,
+    pub vel: Vec2<f32>,
+    pub age: f32, // Seconds
+    pub lifetime: f32,
+    pub color: Color,
 }
 
+/// Default bullet board, also used for the menu/result text.
+pub const DEFAULT_COMBAT_BOX: Rectangle = Rectangle {
+    x: 50.0,
+    y: 320.0,
+    width: 700.0,
+    height: 150.0,
+};
+
+#[derive(Default)]
 pub struct Bone {
     pub pos: Vec2<f32>,
     pub size: Vec2<f32>,
     pub velocity: Vec2<f32>,
+    pub anchored: bool, // Moves and scales along with the bullet board
+    pub grazed: bool,   // Each bone only awards graze TP once
+}
+
+impl Bone {
+    /// Bone placed relative to the board's top-left corner that follows the
+    /// board when it moves or resizes.
+    pub fn in_box(
+        combat_box: Rectangle,
+        offset: Vec2<f32>,
+        size: Vec2<f32>,
+        velocity: Vec2<f32>,
+    ) -> Self {
+        Self {
+            pos: Vec2::new(combat_box.x, combat_box.y) + offset,
+            size,
+            velocity,
+            anchored: true,
+            grazed: false,
+        }
+    }
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub enum AttackPattern {
+    Standard, // Random bones, board stays put
+    Corridor, // Board squeezes into a corridor, then slides left
+}
+
+/// Scripted board change during Sans's turn.
+pub struct BoxEvent {
+    pub frame: f32, // SansTurn timer value that starts it
+    pub target: Rectangle,
+    pub duration: f32, // Seconds
+}
+
+/// Who the next fight is against, set by whatever starts the encounter.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum CombatEnemy {
+    Sans,
+    Eilish, // Catches the player if they linger in stage 4's dead space
+}
+
+impl CombatEnemy {
+    pub fn max_hp(self) -> i32 {
+        match self {
+            CombatEnemy::Sans => 500,
+            CombatEnemy::Eilish => 200,
+        }
+    }
+
+    pub fn exp_reward(self) -> u32 {
+        match self {
+            CombatEnemy::Sans => 50,
+            CombatEnemy::Eilish => 30,
+        }
+    }
+
+    /// Which kernel panic a death in this fight gets.
+    pub fn death_cause(self) -> DeathCause {
+        match self {
+            CombatEnemy::Sans => DeathCause::SansBone,
+            CombatEnemy::Eilish => DeathCause::Eilish,
+        }
+    }
+
+    /// Bosses can't be fled from, the Kaç option is left out of the menu.
+    pub fn is_boss(self) -> bool {
+        matches!(self, CombatEnemy::Sans | CombatEnemy::Eilish)
+    }
+
+    pub fn phase_count(self) -> u32 {
+        match self {
+            CombatEnemy::Sans => 3,
+            CombatEnemy::Eilish => 2,
+        }
+    }
+
+    /// Shown over the HP bar.
+    pub fn name(self) -> &'static str {
+        match self {
+            CombatEnemy::Sans => "SANS",
+            CombatEnemy::Eilish => "EILISH",
+        }
+    }
+
+    pub fn speaker(self) -> Speaker {
+        match self {
+            CombatEnemy::Sans => Speaker::Sans,
+            CombatEnemy::Eilish => Speaker::Eilish,
+        }
+    }
+
+    pub fn opening_line(self) -> &'static str {
+        match self {
+            CombatEnemy::Sans => "You feel like you're gonna have a bad time.",
+            CombatEnemy::Eilish => "Eilish blocks the way. The dead space hums behind her.",
+        }
+    }
+
+    pub fn defeat_line(self) -> &'static str {
+        match self {
+            CombatEnemy::Sans => "welp... i'm going to grillby's.",
+            CombatEnemy::Eilish => "Fine... just stay out of the red, okay?",
+        }
+    }
+
+    /// One is picked at random before each of the enemy's turns.
+    pub fn taunts(self) -> &'static [&'static str] {
+        match self {
+            CombatEnemy::Sans => &[
+                "heh heh heh...",
+                "you're gonna have a bad time.",
+                "it's a beautiful day outside.",
+                "birds are singing, flowers are blooming...",
+                "on days like these, kids like you...",
+                "should be burning in hell.",
+                "take it easy, kid.",
+                "don't you have anything better to do?",
+                "i'm rooting for ya, kid.",
+                "geeeeeet dunked on!",
+            ],
+            CombatEnemy::Eilish => &[
+                "I told you not to go there.",
+                "It's in your head now, isn't it?",
+                "Why won't you listen?",
+                "The red side took someone else too.",
+                "Let me sing you back out.",
+            ],
+        }
+    }
+}
+
+/// How a fight was played, judged once it's over.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum PlayStyle {
+    Pacifist, // Left without hurting the enemy
+    Genocide, // Brought the enemy down
+    Neutral,
+}
+
+/// Record of the last fight, kept until the next one starts.
+#[derive(Clone, Copy, Debug)]
+pub struct CombatOutcome {
+    pub path: PlayStyle,
+    pub enemy_spared: bool,
+    pub damage_taken: i32,
+    pub turns_taken: u32,
 }
 
 pub struct CombatData {
+    pub enemy: CombatEnemy,
+    pub enemy_hp: i32,
+    pub enemy_max_hp: i32,
+    pub phase: u32, // 1-based, see update_phase
+    pub phase_count: u32,
     #[allow(dead_code)]
-    pub sans_hp: i32,
+    pub mercy_progress: f32, // 0.0 - 1.0
+    pub turn_number: u32, // Enemy turns taken so far
     #[allow(dead_code)]
-    pub sans_max_hp: i32,
+    pub exp_reward: u32,
     pub turn: CombatTurn,
     pub menu_selection: usize, // 0: Fight, 1: Act, 2: Mercy
     #[allow(dead_code)]
     pub sub_menu_selection: usize,
     pub dialogue_text: String,
+    pub combat_dialogue_queue: VecDeque<String>, // Mid-fight lines, shown one at a time in the menu
+    pub dialogue_display_timer: f32,             // Seconds the front line has been up
     pub action_text: String,
     pub timer: f32,
     pub sans_shake: f32,
@@ -35,20 +244,52 @@ pub struct CombatData {
     pub attack_bar_active: bool,
     pub heart_pos: Vec2<f32>,
     pub heart_velocity: Vec2<f32>,
-    pub mode: u8, // 0: Gravity, 1: Free Flight
+    pub heart_rotation: f32, // Radians
+    pub heart_scale_pulse: f32,
+    pub heart_spin_timer: f32, // > 0 while the heart spins from a hit
+    pub mode: u8,              // 0: Gravity, 1: Free Flight
     pub can_jump: bool,
-    pub bones: Vec<Bone>,
+    pub bones: Pool<Bone>,
+    pub tp: f32, // 0 - TP_MAX, filled by grazing
+    pub double_next_fight: bool,
+    pub graze_sparks: Particles,
+    pub active_damage_numbers: Vec<DamageNumber>,
+    pub pattern: AttackPattern,
+    pub box_events: Vec<BoxEvent>, // Pending, in frame order
+    pub combat_box_from: Rectangle,
+    pub combat_box_target: Rectangle,
+    pub combat_box_current: Rectangle,
+    pub box_tween_timer: f32,
+    pub box_tween_duration: f32,
+    pub box_border_mesh: Option<(Rectangle, Mesh, Mesh)>, // Border and halo, rebuilt when the board changes
+    pub box_pulse_timer: f32,
+    pub mercy_opened: bool, // Attacking after this makes him run off
+    pub damage_taken: i32,  // Player HP lost this fight
+    pub outcome: Option<CombatOutcome>, // Set when the fight ends back on the desktop
+    pub flee_failures: u32,
+    pub sans_escaped: bool,
+    pub tutorial_cards: VecDeque<String>, // Front one showing, see scenes::combat_tutorial
+    pub return_pos: Option<Vec2<f32>>, // Where the player stood on the desktop, a flee goes back there
 }
 
 impl CombatData {
-    pub fn new() -> Self {
+    /// Fresh fight state for `enemy`. Called every time combat starts.
+    pub fn for_enemy(enemy: CombatEnemy) -> Self {
         CombatData {
-            sans_hp: 500,
-            sans_max_hp: 500,
+            enemy,
+            enemy_hp: enemy.max_hp(),
+            enemy_max_hp: enemy.max_hp(),
+            phase: 1,
+            phase_count: enemy.phase_count(),
+            mercy_progress: 0.0,
+            turn_number: 0,
+            exp_reward: enemy.exp_reward(),
             turn: CombatTurn::Menu,
             menu_selection: 0,
             sub_menu_selection: 0,
-            dialogue_text: "You feel like you're gonna have a bad time.".to_string(),
+            dialogue_text: enemy.opening_line().to_string(),
+            combat_dialogue_queue: VecDeque::new(),
+            dialogue_display_timer: 0.0,
             action_text: String::new(),
             timer: 0.0,
             sans_shake: 0.0,
@@ -57,9 +298,369 @@ impl CombatData {
             attack_bar_active: false,
             heart_pos: Vec2::new(400.0, 400.0),
             heart_velocity: Vec2::zero(),
+            heart_rotation: 0.0,
+            heart_scale_pulse: 1.0,
+            heart_spin_timer: 0.0,
             mode: 0,
             can_jump: true,
-            bones: Vec::new(),
+            bones: Pool::new(BONE_POOL_SIZE),
+            tp: 0.0,
+            double_next_fight: false,
+            graze_sparks: Particles::new(SPARK_POOL_SIZE, SPARK_RNG_SEED),
+            active_damage_numbers: Vec::new(),
+            pattern: AttackPattern::Standard,
+            box_events: Vec::new(),
+            combat_box_from: DEFAULT_COMBAT_BOX,
+            combat_box_target: DEFAULT_COMBAT_BOX,
+            combat_box_current: DEFAULT_COMBAT_BOX,
+            box_tween_timer: 0.0,
+            box_tween_duration: 0.0,
+            box_border_mesh: None,
+            box_pulse_timer: 0.0,
+            mercy_opened: false,
+            damage_taken: 0,
+            outcome: None,
+            flee_failures: 0,
+            sans_escaped: false,
+            tutorial_cards: VecDeque::new(),
+            return_pos: None,
+        }
+    }
+
+    /// Recomputes the phase from remaining HP, splitting the bar evenly
+    /// between the enemy's phases. Phases never go back down. Returns whether
+    /// a new phase started.
+    pub fn update_phase(&mut self) -> bool {
+        let lost = 1.0 - self.enemy_hp.max(0) as f32 / self.enemy_max_hp as f32;
+        let phase = (lost * self.phase_count as f32) as u32 + 1;
+        let previous = self.phase;
+        self.phase = self.phase.max(phase.min(self.phase_count));
+        self.phase != previous
+    }
+
+    /// The queued line on screen right now, if any.
+    pub fn queued_dialogue(&self) -> Option<&str> {
+        self.combat_dialogue_queue.front().map(String::as_str)
+    }
+
+    /// Counts the front line's time on screen and drops it after
+    /// DIALOGUE_DISPLAY_SECONDS so the next one can show.
+    pub fn update_dialogue_queue(&mut self, dt: f32) {
+        if self.combat_dialogue_queue.is_empty() {
+            return;
+        }
+        self.dialogue_display_timer += dt;
+        if self.dialogue_display_timer >= DIALOGUE_DISPLAY_SECONDS {
+            self.combat_dialogue_queue.pop_front();
+            self.dialogue_display_timer = 0.0;
         }
     }
+
+    /// Records how the fight went. Call on every way out of combat that
+    /// isn't a death.
+    pub fn finish(&mut self) {
+        let defeated = self.enemy_hp <= 0;
+        let path = if defeated {
+            PlayStyle::Genocide
+        } else if self.enemy_hp >= self.enemy_max_hp {
+            PlayStyle::Pacifist
+        } else {
+            PlayStyle::Neutral
+        };
+        self.outcome = Some(CombatOutcome {
+            path,
+            enemy_spared: !defeated,
+            damage_taken: self.damage_taken,
+            turns_taken: self.turn_number,
+        });
+    }
+
+    pub fn add_graze(&mut self) {
+        self.tp = (self.tp + GRAZE_TP).min(TP_MAX);
+    }
+
+    /// Moves every bone, checks it against the heart and frees the ones that
+    /// left the arena. Returns whether the heart was hit and how many bones
+    /// grazed it this tick. No Context needed, so it can be timed headless.
+    pub fn update_bones(&mut self) -> (bool, u32) {
+        let combat_box = self.combat_box_current;
+        let heart_rect = Rectangle::new(self.heart_pos.x, self.heart_pos.y, 10.0, 10.0);
+
+        // Anything passing within GRAZE_DISTANCE without touching is a graze
+        let graze_rect = Rectangle::new(
+            heart_rect.x - GRAZE_DISTANCE,
+            heart_rect.y - GRAZE_DISTANCE,
+            heart_rect.width + GRAZE_DISTANCE * 2.0,
+            heart_rect.height + GRAZE_DISTANCE * 2.0,
+        );
+
+        let mut hit = false;
+        let mut grazes = 0;
+        self.bones.retain_mut(|bone| {
+            bone.pos += bone.velocity;
+
+            let bone_rect = Rectangle::new(bone.pos.x, bone.pos.y, bone.size.x, bone.size.y);
+            if heart_rect.intersects(&bone_rect) && combat_box.intersects(&bone_rect) {
+                hit = true;
+                bone.grazed = true; // A bone that hit you can't also graze
+            } else if !bone.grazed
+                && graze_rect.intersects(&bone_rect)
+                && combat_box.intersects(&bone_rect)
+            {
+                bone.grazed = true;
+                grazes += 1;
+            }
+
+            // Free once out of bounds
+            bone.pos.x >= -50.0 && bone.pos.x <= 850.0 && bone.pos.y >= 200.0 && bone.pos.y <= 600.0
+        });
+
+        (hit, grazes)
+    }
+
+    /// Bursts a few sparks off the heart for one graze.
+    pub fn emit_graze_sparks(&mut self, quality: GraphicsQuality) {
+        self.graze_sparks.spawn_burst(
+            self.heart_pos + Vec2::new(5.0, 5.0),
+            SPARKS_PER_GRAZE,
+            ParticleStyle::GrazeSpark,
+            quality,
+        );
+    }
+
+    pub fn push_damage_number(&mut self, value: i32, pos: Vec2<f32>, color: Color) {
+        self.active_damage_numbers.push(DamageNumber {
+            value,
+            pos,
+            vel: Vec2::new(0.0, -DAMAGE_NUMBER_RISE),
+            age: 0.0,
+            lifetime: DAMAGE_NUMBER_LIFETIME,
+            color,
+        });
+    }
+
+    pub fn update_damage_numbers(&mut self, dt: f32) {
+        self.active_damage_numbers.retain_mut(|number| {
+            number.pos += number.vel * dt;
+            number.age += dt;
+            number.age < number.lifetime
+        });
+    }
+
+    /// 50%, minus 10% per failed try this fight, plus a tenth of a percent
+    /// per cihad up to +30%.
+    pub fn flee_chance(&self, cihad_count: u32) -> f32 {
+        let bonus = (cihad_count as f32 / 10.0 / 100.0).min(FLEE_CIHAD_BONUS_MAX);
+        (FLEE_BASE_CHANCE - self.flee_failures as f32 * FLEE_FAILURE_PENALTY + bonus)
+            .clamp(0.0, 1.0)
+    }
+
+    /// Rolls a flee. Failures make the next try harder.
+    pub fn flee_attempt(&mut self, stage: u8, cihad_count: u32, rng: &mut dyn RngCore) -> bool {
+        let chance = self.flee_chance(cihad_count);
+        let fled = rng.random::<f32>() < chance;
+        if !fled {
+            self.flee_failures += 1;
+        }
+        log::info!(
+            "Flee attempt on stage {} at {:.0}%: {}",
+            stage,
+            chance * 100.0,
+            if fled { "escaped" } else { "failed" }
+        );
+        fled
+    }
+
+    pub fn tp_full(&self) -> bool {
+        self.tp >= TP_MAX
+    }
+
+    /// Empties a full TP bar. Returns false (and spends nothing) if it isn't full.
+    /// Healing is up to the caller since the player's HP lives outside combat.
+    pub fn spend_tp(&mut self, effect: TpEffect) -> bool {
+        if !self.tp_full() {
+            return false;
+        }
+        self.tp = 0.0;
+        if effect == TpEffect::DoubleDamage {
+            self.double_next_fight = true;
+        }
+        true
+    }
+
+    /// What a full bar spent on Heal gives back, at least 1 HP.
+    pub fn tp_heal(max_hp: i32) -> i32 {
+        ((max_hp as f32 * TP_HEAL_SHARE).ceil() as i32).max(1)
+    }
+
+    /// Applies and consumes a pending double damage bonus.
+    pub fn fight_damage(&mut self, base: i32) -> i32 {
+        if self.double_next_fight {
+            self.double_next_fight = false;
+            base * 2
+        } else {
+            base
+        }
+    }
+
+    /// Bullet board for the given phase. The walls close in 20px per side
+    /// at phase 2 and another 15px at phase 3.
+    pub fn combat_box_for_phase(phase: u32) -> Rectangle {
+        let inset = match phase {
+            2 => 20.0,
+            3 => 35.0,
+            _ => 0.0,
+        };
+        Rectangle::new(
+            DEFAULT_COMBAT_BOX.x + inset,
+            DEFAULT_COMBAT_BOX.y + inset,
+            DEFAULT_COMBAT_BOX.width - inset * 2.0,
+            DEFAULT_COMBAT_BOX.height - inset * 2.0,
+        )
+    }
+
+    /// Showcase pattern: squeeze into a narrow corridor while bones fall,
+    /// slide left while bones fly in from the right, then open back up.
+    pub fn corridor_events(phase_box: Rectangle) -> Vec<BoxEvent> {
+        let corridor_width = 160.0;
+        let corridor = Rectangle::new(
+            phase_box.center().x - corridor_width / 2.0,
+            phase_box.y,
+            corridor_width,
+            phase_box.height,
+        );
+        let slid = Rectangle::new(
+            phase_box.x + 20.0,
+            phase_box.y,
+            corridor_width,
+            phase_box.height,
+        );
+
+        vec![
+            BoxEvent {
+                frame: 1.0,
+                target: corridor,
+                duration: 0.5,
+            },
+            BoxEvent {
+                frame: 200.0,
+                target: slid,
+                duration: 1.0,
+            },
+            BoxEvent {
+                frame: 360.0,
+                target: phase_box,
+                duration: 0.5,
+            },
+        ]
+    }
+
+    /// Starts tweening the board from where it is now to `target`.
+    pub fn tween_combat_box(&mut self, target: Rectangle, duration: f32) {
+        self.combat_box_from = self.combat_box_current;
+        self.combat_box_target = target;
+        self.box_tween_timer = 0.0;
+        self.box_tween_duration = duration;
+    }
+
+    /// Jumps straight to `rect`, dropping any queued events.
+    pub fn snap_combat_box(&mut self, rect: Rectangle) {
+        self.box_events.clear();
+        self.combat_box_from = rect;
+        self.combat_box_target = rect;
+        self.combat_box_current = rect;
+        self.box_tween_duration = 0.0;
+    }
+
+    /// Fires due box events and advances the tween. Anchored bones are
+    /// carried along so they keep their place inside the board.
+    pub fn animate_combat_box(&mut self, dt: f32) {
+        while self
+            .box_events
+            .first()
+            .is_some_and(|event| event.frame <= self.timer)
+        {
+            let event = self.box_events.remove(0);
+            self.tween_combat_box(event.target, event.duration);
+        }
+
+        let previous = self.combat_box_current;
+        self.box_tween_timer += dt;
+        let t = if self.box_tween_duration > 0.0 {
+            (self.box_tween_timer / self.box_tween_duration).min(1.0)
+        } else {
+            1.0
+        };
+        let t = t * t * (3.0 - 2.0 * t); // Smoothstep
+
+        let from = self.combat_box_from;
+        let to = self.combat_box_target;
+        self.combat_box_current = Rectangle::new(
+            from.x + (to.x - from.x) * t,
+            from.y + (to.y - from.y) * t,
+            from.width + (to.width - from.width) * t,
+            from.height + (to.height - from.height) * t,
+        );
+
+        let current = self.combat_box_current;
+        if current == previous || previous.width <= 0.0 || previous.height <= 0.0 {
+            return;
+        }
+        let scale = Vec2::new(
+            current.width / previous.width,
+            current.height / previous.height,
+        );
+        for bone in self.bones.iter_mut().filter(|bone| bone.anchored) {
+            let local = bone.pos - Vec2::new(previous.x, previous.y);
+            bone.pos = Vec2::new(current.x, current.y) + local * scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn graze_gain_caps_at_tp_max() {
+        let mut data = CombatData::for_enemy(CombatEnemy::Sans);
+        let grazes_to_fill = (TP_MAX / GRAZE_TP).ceil() as u32;
+        for _ in 0..grazes_to_fill + 5 {
+            data.add_graze();
+        }
+        assert_eq!(data.tp, TP_MAX);
+        assert!(data.tp_full());
+    }
+
+    #[test]
+    fn spend_tp_refuses_a_partial_bar() {
+        let mut data = CombatData::for_enemy(CombatEnemy::Sans);
+        data.tp = TP_MAX - GRAZE_TP;
+        assert!(!data.spend_tp(TpEffect::Heal));
+        assert!(!data.spend_tp(TpEffect::DoubleDamage));
+        assert_eq!(data.tp, TP_MAX - GRAZE_TP);
+        assert!(!data.double_next_fight);
+
+        data.add_graze();
+        assert!(data.spend_tp(TpEffect::Heal));
+        assert_eq!(data.tp, 0.0);
+        assert!(!data.double_next_fight);
+    }
+
+    #[test]
+    fn double_damage_is_used_up_by_one_hit() {
+        let mut data = CombatData::for_enemy(CombatEnemy::Sans);
+        data.tp = TP_MAX;
+        assert!(data.spend_tp(TpEffect::DoubleDamage));
+        assert_eq!(data.fight_damage(7), 14);
+        assert_eq!(data.fight_damage(7), 7);
+        assert_eq!(data.fight_damage(7), 7);
+    }
+
+    #[test]
+    fn tp_heal_scales_with_max_hp() {
+        assert_eq!(CombatData::tp_heal(20), 10);
+        assert_eq!(CombatData::tp_heal(99), 50);
+        assert_eq!(CombatData::tp_heal(1), 1);
+    }
 }
@@ -0,0 +1,63 @@
+use std::backtrace::Backtrace;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::paths;
+use crate::storage;
+
+pub const SNAPSHOT_SECONDS: f32 = 5.0; // How often a running session refreshes the snapshot
+
+/// users.db content as of the last save or snapshot, written out by the panic
+/// hook since the game state itself is gone by then.
+static USERS_SNAPSHOT: Mutex<Option<String>> = Mutex::new(None);
+
+fn marker_path() -> PathBuf {
+    paths::data_path("crash.marker")
+}
+
+pub fn store_snapshot(users_content: String) {
+    if let Ok(mut snapshot) = USERS_SNAPSHOT.lock() {
+        *snapshot = Some(users_content);
+    }
+}
+
+/// Writes the latest snapshot to users.db. Uses `try_lock` because the panic
+/// may have happened while the snapshot was being stored.
+fn save_snapshot() -> bool {
+    let Ok(snapshot) = USERS_SNAPSHOT.try_lock() else {
+        return false;
+    };
+    match snapshot.as_ref() {
+        Some(content) => storage::write_atomic(&paths::data_path("users.db"), content).is_ok(),
+        None => false,
+    }
+}
+
+/// Logs panics with a backtrace, saves what it can and leaves a marker so the
+/// next launch can tell the player. Call after `logging::init`.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        log::error!("Panic: {}", info);
+        log::error!("Backtrace:\n{}", Backtrace::force_capture());
+
+        let saved = save_snapshot();
+        if saved {
+            log::error!("Progress saved from the last snapshot");
+        } else {
+            log::error!("No snapshot could be saved");
+        }
+        let _ = fs::write(marker_path(), if saved { "saved" } else { "unsaved" });
+
+        default_hook(info);
+    }));
+}
+
+/// Checks for a crash marker left by the previous run and removes it.
+/// Returns whether that run's progress was saved.
+pub fn take_marker() -> Option<bool> {
+    let content = fs::read_to_string(marker_path()).ok()?;
+    let _ = fs::remove_file(marker_path());
+    Some(content.trim() == "saved")
+}
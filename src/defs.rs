@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 pub const SCREEN_WIDTH: i32 = 800;
 pub const SCREEN_HEIGHT: i32 = 600;
 
-#[derive(PartialEq, Clone, Copy)]
+#[derive(PartialEq, Clone, Copy, Debug)]
 pub enum Scene {
     Boot,
     Menu,
@@ -11,11 +13,16 @@ pub enum Scene {
     Combat,
     KernelPanic,
     AyasofyaInside,
+    Credits,
+    Statistics,
+    SessionSummary,
 }
 
-#[derive(PartialEq, Clone, Copy, Debug)]
+#[derive(PartialEq, Clone, Copy, Debug, Serialize, Deserialize)]
 pub enum Language {
+    #[serde(rename = "en")]
     English,
+    #[serde(rename = "tr")]
     Turkish,
 }
 
@@ -25,3 +32,14 @@ pub enum Direction {
     Left,
     Right,
 }
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy, Debug)]
+pub enum NpcId {
+    Sans,
+    MusicBox,
+    Gaster,
+    Rarity,
+    Eilish,
+    Survivor,
+    Crowd(usize), // Index into WorldState::crowd
+}
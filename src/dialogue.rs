@@ -0,0 +1,69 @@
+/// What happens when a dialogue choice is confirmed.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum DialogueAction {
+    Close,
+    ShakeHands,
+    Fight,
+}
+
+pub struct DialogueChoice {
+    pub label: String,
+    pub action: DialogueAction,
+    pub next: Option<usize>, // Node to continue with, None ends the dialogue
+}
+
+pub struct DialogueNode {
+    pub text: String,
+    pub choices: Vec<DialogueChoice>,
+}
+
+pub struct DialogueTree {
+    pub nodes: Vec<DialogueNode>,
+    pub current: usize,
+    pub selected: usize,
+}
+
+impl DialogueTree {
+    pub fn new(nodes: Vec<DialogueNode>) -> Self {
+        Self {
+            nodes,
+            current: 0,
+            selected: 0,
+        }
+    }
+
+    pub fn current_node(&self) -> &DialogueNode {
+        &self.nodes[self.current]
+    }
+
+    pub fn select_prev(&mut self) {
+        let count = self.current_node().choices.len();
+        if count > 0 {
+            self.selected = (self.selected + count - 1) % count;
+        }
+    }
+
+    pub fn select_next(&mut self) {
+        let count = self.current_node().choices.len();
+        if count > 0 {
+            self.selected = (self.selected + 1) % count;
+        }
+    }
+
+    /// Confirms the highlighted choice. Returns its action and whether the
+    /// dialogue is still open afterwards.
+    pub fn confirm(&mut self) -> (DialogueAction, bool) {
+        let Some(choice) = self.current_node().choices.get(self.selected) else {
+            return (DialogueAction::Close, false);
+        };
+        let action = choice.action;
+        match choice.next {
+            Some(next) if next < self.nodes.len() => {
+                self.current = next;
+                self.selected = 0;
+                (action, true)
+            }
+            _ => (action, false),
+        }
+    }
+}
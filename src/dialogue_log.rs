@@ -0,0 +1,116 @@
+use std::collections::VecDeque;
+
+use tetra::graphics::Color;
+
+const MAX_ENTRIES: usize = 100; // Oldest lines drop off past this
+
+/// Who said a logged line. Names aren't translated, so they're kept here
+/// rather than in TextResources.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Speaker {
+    Narrator,
+    Player,
+    Sans,
+    Gaster,
+    Eilish,
+    Survivor,
+}
+
+impl Speaker {
+    pub fn name(self) -> &'static str {
+        match self {
+            Speaker::Narrator => "*",
+            Speaker::Player => "You",
+            Speaker::Sans => "Sans",
+            Speaker::Gaster => "Gaster",
+            Speaker::Eilish => "Eilish",
+            Speaker::Survivor => "Survivor",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Speaker::Narrator => Color::rgb(0.8, 0.8, 0.8),
+            Speaker::Player => Color::rgb(1.0, 1.0, 0.0),
+            Speaker::Sans => Color::rgb(0.4, 0.7, 1.0),
+            Speaker::Gaster => Color::rgb(0.7, 0.5, 1.0),
+            Speaker::Eilish => Color::rgb(0.4, 1.0, 0.6),
+            Speaker::Survivor => Color::rgb(0.9, 0.8, 0.3),
+        }
+    }
+}
+
+pub struct LogEntry {
+    pub speaker: Speaker,
+    pub text: String, // Copied, so a language switch doesn't rewrite history
+    pub time: f32,    // Session seconds when it was said
+}
+
+/// Lines said during the current session, for the L viewer.
+pub struct DialogueLog {
+    entries: VecDeque<LogEntry>,
+    session_time: f32,
+    pub open: bool,
+    pub scroll: usize, // Entries hidden below the viewer, 0 shows the newest
+}
+
+impl DialogueLog {
+    pub fn new() -> Self {
+        Self {
+            entries: VecDeque::with_capacity(MAX_ENTRIES),
+            session_time: 0.0,
+            open: false,
+            scroll: 0,
+        }
+    }
+
+    pub fn push(&mut self, speaker: Speaker, text: &str) {
+        if text.trim().is_empty() {
+            return;
+        }
+        if self.entries.len() == MAX_ENTRIES {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(LogEntry {
+            speaker,
+            text: text.to_string(),
+            time: self.session_time,
+        });
+        // Keep the viewer on the same lines if it's scrolled back
+        if self.scroll > 0 {
+            self.scroll = (self.scroll + 1).min(self.entries.len() - 1);
+        }
+    }
+
+    /// Forgets the session, called when a new one starts.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.session_time = 0.0;
+        self.open = false;
+        self.scroll = 0;
+    }
+
+    pub fn tick(&mut self, dt: f32) {
+        self.session_time += dt;
+    }
+
+    pub fn entries(&self) -> &VecDeque<LogEntry> {
+        &self.entries
+    }
+
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+        self.scroll = 0;
+    }
+
+    /// Towards older lines.
+    pub fn scroll_up(&mut self, rows: usize) {
+        let max = self.entries.len().saturating_sub(1);
+        self.scroll = (self.scroll + rows).min(max);
+    }
+
+    /// Towards newer lines.
+    pub fn scroll_down(&mut self, rows: usize) {
+        self.scroll = self.scroll.saturating_sub(rows);
+    }
+}
@@ -16,14 +16,14 @@ impl DiscordRpc {
         
         match client.connect() {
             Ok(_) => {
-                println!("Discord IPC connected successfully.");
+                log::info!("Discord IPC connected successfully.");
                 Self {
                     client: Some(client),
                     start_time,
                 }
             },
             Err(e) => {
-                eprintln!("Failed to connect to Discord IPC: {:?}", e);
+                log::warn!("Failed to connect to Discord IPC: {:?}", e);
                 Self {
                     client: None,
                     start_time,
@@ -45,7 +45,7 @@ impl DiscordRpc {
                 );
 
             if let Err(e) = client.set_activity(payload) {
-                eprintln!("Failed to set Discord activity: {:?}", e);
+                log::warn!("Failed to set Discord activity: {:?}", e);
             }
         }
     }
@@ -0,0 +1,8 @@
+/// Slow at both ends, fast through the middle. `t` goes 0.0 - 1.0.
+pub fn ease_in_out_cubic(t: f32) -> f32 {
+    if t < 0.5 {
+        4.0 * t * t * t
+    } else {
+        1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+    }
+}
@@ -0,0 +1,164 @@
+use std::path::PathBuf;
+
+use tetra::Context;
+use tetra::graphics::text::Font;
+
+use crate::defs::Language;
+
+/// DejaVu Sans Mono, see assets/fonts/LICENSE-DejaVu. Used when nothing on
+/// disk loads, so the game still boots on a system without fonts.
+const BUNDLED_FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+const BUNDLED_NAME: &str = "DejaVu Sans Mono (bundled)";
+
+/// Monospace fonts worth trying, by file name under the font directories.
+const FONT_FILES: [&str; 6] = [
+    "DejaVuSansMono.ttf",
+    "LiberationMono-Regular.ttf",
+    "FreeMono.ttf",
+    "NotoSansMono-Regular.ttf",
+    "UbuntuMono-R.ttf",
+    "consola.ttf",
+];
+
+/// Letters a font has to have before it's used for Turkish, the ones
+/// outside ASCII.
+const TURKISH_GLYPHS: &str = "çğıİöşüÇĞÖŞÜ";
+
+/// Where the UI font came from, kept so other sizes load from the same place.
+pub enum FontSource {
+    File(PathBuf),
+    Bundled,
+}
+
+impl FontSource {
+    pub fn load(&self, ctx: &mut Context, size_pt: f32) -> tetra::Result<Font> {
+        match self {
+            FontSource::File(path) => Font::vector(ctx, path, size_pt),
+            FontSource::Bundled => Font::from_vector_file_data(ctx, BUNDLED_FONT, size_pt),
+        }
+    }
+
+    /// Shown in the log and on the debug overlay.
+    pub fn label(&self) -> String {
+        match self {
+            FontSource::File(path) => path.display().to_string(),
+            FontSource::Bundled => BUNDLED_NAME.to_string(),
+        }
+    }
+
+    /// Whether the font has every letter the language needs.
+    pub fn covers(&self, language: Language) -> bool {
+        match self {
+            FontSource::File(path) => match std::fs::read(path) {
+                Ok(data) => missing_glyphs(&data, language).is_ok_and(|m| m.is_empty()),
+                Err(_) => false,
+            },
+            FontSource::Bundled => {
+                missing_glyphs(BUNDLED_FONT, language).is_ok_and(|m| m.is_empty())
+            }
+        }
+    }
+}
+
+/// The letters the language needs that the font file doesn't have.
+fn missing_glyphs(data: &[u8], language: Language) -> Result<String, String> {
+    let required = match language {
+        Language::English => return Ok(String::new()),
+        Language::Turkish => TURKISH_GLYPHS,
+    };
+    let face = ttf_parser::Face::parse(data, 0).map_err(|e| e.to_string())?;
+    Ok(required
+        .chars()
+        .filter(|c| face.glyph_index(*c).is_none())
+        .collect())
+}
+
+/// `resources/font.ttf` first so players can override it, then the usual
+/// system locations, then the font built into the binary.
+fn candidates() -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = Vec::new();
+    if let Some(user_fonts) = dirs::font_dir() {
+        dirs.push(user_fonts);
+    }
+    if cfg!(windows) {
+        let windir = std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string());
+        dirs.push(PathBuf::from(windir).join("Fonts"));
+    } else if cfg!(target_os = "macos") {
+        dirs.push(PathBuf::from("/Library/Fonts"));
+        dirs.push(PathBuf::from("/System/Library/Fonts"));
+    } else {
+        for dir in [
+            "/usr/share/fonts/truetype/dejavu",
+            "/usr/share/fonts/truetype/liberation",
+            "/usr/share/fonts/truetype/freefont",
+            "/usr/share/fonts/truetype/noto",
+            "/usr/share/fonts/truetype/ubuntu",
+            "/usr/share/fonts/dejavu",
+            "/usr/share/fonts/liberation",
+            "/usr/share/fonts/liberation-mono",
+            "/usr/share/fonts/TTF",
+            "/usr/share/fonts/noto",
+            "/usr/local/share/fonts",
+        ] {
+            dirs.push(PathBuf::from(dir));
+        }
+    }
+
+    let mut paths = vec![PathBuf::from("resources/font.ttf")];
+    for file in FONT_FILES {
+        paths.extend(dirs.iter().map(|dir| dir.join(file)));
+    }
+    paths
+}
+
+/// The UI font at `size_pt` and where it was loaded from, falling back to
+/// the bundled font when nothing on disk loads. Fonts missing letters the
+/// language needs are skipped, they'd draw them as boxes.
+pub fn load_ui_font(
+    ctx: &mut Context,
+    size_pt: f32,
+    language: Language,
+) -> tetra::Result<(Font, FontSource)> {
+    for path in candidates() {
+        if !path.exists() {
+            continue;
+        }
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(e) => {
+                log::warn!("Failed to read font {}: {}", path.display(), e);
+                continue;
+            }
+        };
+        match missing_glyphs(&data, language) {
+            Ok(missing) if !missing.is_empty() => {
+                log::info!(
+                    "Skipping font {}, it's missing {} needed for {:?}",
+                    path.display(),
+                    missing,
+                    language
+                );
+                continue;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                log::warn!("Failed to read font {}: {}", path.display(), e);
+                continue;
+            }
+        }
+        match Font::vector(ctx, &path, size_pt) {
+            Ok(font) => {
+                log::info!("Using font {} for {:?}", path.display(), language);
+                return Ok((font, FontSource::File(path)));
+            }
+            Err(e) => log::warn!("Failed to load font {}: {}", path.display(), e),
+        }
+    }
+
+    log::info!("No usable font on disk, using {}", BUNDLED_NAME);
+    if !FontSource::Bundled.covers(language) {
+        log::warn!("{} is missing {:?} glyphs", BUNDLED_NAME, language);
+    }
+    let font = FontSource::Bundled.load(ctx, size_pt)?;
+    Ok((font, FontSource::Bundled))
+}
@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use tetra::Context;
+
+use crate::global_db::GlobalSettings;
+
+/// Game logic ticks per second. Updates run at this rate whatever the
+/// frame rate, tetra runs extra or fewer updates per frame to keep up.
+pub const TICK_RATE: f64 = 60.0;
+
+/// Settings menu choices, 0 is no cap.
+pub const FPS_CAPS: [u32; 4] = [30, 60, 120, 0];
+
+/// Sleeps out the rest of the frame when an FPS cap is set. tetra has no
+/// frame limiter of its own, so this runs at the end of every draw.
+pub struct FramePacer {
+    next_frame: Option<Instant>,
+}
+
+impl FramePacer {
+    pub fn new() -> Self {
+        Self { next_frame: None }
+    }
+
+    pub fn wait(&mut self, fps_cap: u32) {
+        if fps_cap == 0 {
+            self.next_frame = None;
+            return;
+        }
+
+        let frame = Duration::from_secs_f64(1.0 / fps_cap as f64);
+        let now = Instant::now();
+        let target = self.next_frame.unwrap_or(now);
+        if target > now {
+            std::thread::sleep(target - now);
+        }
+        // A frame that ran long restarts the schedule instead of rushing the next ones
+        self.next_frame = Some(if now > target + frame {
+            now + frame
+        } else {
+            target + frame
+        });
+    }
+}
+
+/// Turns vsync on or off on the live window, nothing else is recreated.
+pub fn apply_vsync(ctx: &mut Context, vsync: bool) {
+    if let Err(e) = tetra::window::set_vsync(ctx, vsync) {
+        log::warn!("Failed to set vsync to {}: {}", vsync, e);
+    }
+}
+
+/// The next cap in the menu order. A cap that isn't one of the choices
+/// snaps to the nearest one in that direction.
+pub fn next_cap(current: u32, forward: bool) -> u32 {
+    let index = FPS_CAPS.iter().position(|cap| *cap == current);
+    let index = match (index, forward) {
+        (Some(i), true) => (i + 1) % FPS_CAPS.len(),
+        (Some(i), false) => (i + FPS_CAPS.len() - 1) % FPS_CAPS.len(),
+        (None, true) => FPS_CAPS
+            .iter()
+            .position(|cap| *cap > current)
+            .unwrap_or(FPS_CAPS.len() - 1),
+        (None, false) => FPS_CAPS
+            .iter()
+            .rposition(|cap| *cap != 0 && *cap < current)
+            .unwrap_or(0),
+    };
+    FPS_CAPS[index]
+}
+
+pub fn cap_label(fps_cap: u32) -> String {
+    if fps_cap == 0 {
+        "Unlimited".to_string()
+    } else {
+        fps_cap.to_string()
+    }
+}
+
+/// What the frame rate should settle at, for the perf overlay.
+pub fn target_label(settings: &GlobalSettings) -> String {
+    match (settings.fps_cap, settings.vsync) {
+        (0, true) => "vsync".to_string(),
+        (0, false) => "unlimited".to_string(),
+        (cap, true) => format!("{} + vsync", cap),
+        (cap, false) => cap.to_string(),
+    }
+}
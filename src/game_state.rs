@@ -8,15 +8,49 @@ use tetra::graphics::{self, Color, DrawParams, Rectangle, Texture};
 use tetra::math::Vec2;
 use tetra::{Context, State};
 
-use crate::combat::CombatData;
+use crate::asset_check::AssetReport;
+use crate::combat::{CombatData, CombatEnemy, PlayStyle};
 use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::dialogue_log::{DialogueLog, Speaker};
 use crate::discord_rpc::DiscordRpc;
+use crate::fonts::FontSource;
+use crate::frame_pacing::FramePacer;
+use crate::graphics_quality::GraphicsQuality;
+use crate::perf::{EntityCounts, PerfStats, Render, RenderMut};
 use crate::player::PlayerState;
-use crate::system::SystemState;
-use crate::texts::TextResources;
-use crate::world::WorldState;
+use crate::session_stats::SessionStats;
+use crate::system::{DeathCause, SystemState};
+use crate::texts::{TextKey, TextResources};
+use crate::world::{STAGE_COUNT, WorldState};
+
+pub const GAME_OVER_OPTION_COUNT: usize = 3; // Return, Export Report, Quit
+pub const EXPORT_NOTICE_SECONDS: f32 = 3.0;
+
+// The kernel log sits under the options, as many rows as fit above the bottom
+const PANIC_LOG_Y: f32 = 470.0;
+const PANIC_LOG_LINE_HEIGHT: f32 = 22.0;
+const PANIC_LOG_ROWS: usize =
+    ((SCREEN_HEIGHT as f32 - PANIC_LOG_Y) / PANIC_LOG_LINE_HEIGHT) as usize;
+
+// Returning from the panic screen goes through a fake reboot
+const REBOOT_FADE_SECONDS: f32 = 0.5;
+const REBOOT_POST_SECONDS: f32 = 2.0;
+const POST_CHARS_PER_SECOND: f32 = 40.0;
+const POST_LINES: [&str; 3] = [
+    "Checking RAM... OK",
+    "Loading bootloader...",
+    "Starting kernel...",
+];
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum KernelPanicSubState {
+    Report,
+    Rebooting, // Fading out into the POST screen, then back to Boot
+}
 
 pub struct GameOverState {
+    pub sub_state: KernelPanicSubState,
+    pub reboot_timer: f32, // Seconds since the reboot started
     pub current_stat_index: usize,
     pub stat_pos: Vec2<f32>,
     pub stat_alpha: f32,
@@ -28,11 +62,26 @@ pub struct GameOverState {
     pub message_alpha: f32,
     pub message_fading_in: bool,
     pub message_timer: f32,
+
+    // Shown after exporting the panic report
+    pub export_notice: Option<String>,
+    pub export_notice_timer: f32,
+
+    // First kernel log line shown. Follows the end of the log until the
+    // player scrolls it themselves.
+    pub panic_scroll_offset: usize,
+    pub panic_scrolled: bool,
+
+    // Player died, as opposed to a scene error. Locks hardcore profiles.
+    pub died: bool,
+    pub cause: DeathCause, // Unknown after a scene error
 }
 
 impl GameOverState {
     pub fn new() -> Self {
         Self {
+            sub_state: KernelPanicSubState::Report,
+            reboot_timer: 0.0,
             current_stat_index: 0,
             stat_pos: Vec2::new(50.0, 50.0),
             stat_alpha: 0.0,
@@ -43,76 +92,137 @@ impl GameOverState {
             message_alpha: 0.0,
             message_fading_in: true,
             message_timer: 0.0,
+
+            export_notice: None,
+            export_notice_timer: 0.0,
+
+            panic_scroll_offset: 0,
+            panic_scrolled: false,
+
+            died: false,
+            cause: DeathCause::Unknown,
         }
     }
+
+    /// The game over screen for a player death.
+    pub fn after_death(cause: DeathCause) -> Self {
+        Self {
+            died: true,
+            cause,
+            ..Self::new()
+        }
+    }
+
+    /// Moves the kernel log a line up or down, within `line_count` lines.
+    pub fn scroll_panic_log(&mut self, up: bool, line_count: usize) {
+        self.panic_scrolled = true;
+        let max = line_count.saturating_sub(PANIC_LOG_ROWS);
+        self.panic_scroll_offset = if up {
+            self.panic_scroll_offset.saturating_sub(1)
+        } else {
+            (self.panic_scroll_offset + 1).min(max)
+        };
+    }
+
+    pub fn start_reboot(&mut self) {
+        self.sub_state = KernelPanicSubState::Rebooting;
+        self.reboot_timer = 0.0;
+    }
+
+    pub fn rebooting(&self) -> bool {
+        self.sub_state == KernelPanicSubState::Rebooting
+    }
+
+    fn showing_post(&self) -> bool {
+        self.rebooting() && self.reboot_timer >= REBOOT_FADE_SECONDS
+    }
 }
 
 pub struct GameState {
     pub scene: Scene,
     pub previous_scene: Scene,
-    pub font: Font,
+    font: Option<Font>, // None only in headless tests, see font()
+    pub font_source: FontSource,
+    font_cache: HashMap<u32, Font>, // Keyed by size in pt * 10, see get_font
     pub texts: TextResources,
 
     pub system: SystemState,
     pub player: PlayerState,
     pub world: WorldState,
 
-    pub discord: DiscordRpc,
+    pub discord: Option<DiscordRpc>, // Not connected in headless tests
 
     pub boot_state: crate::scenes::boot::BootState,
     pub menu_state: crate::scenes::menu::MenuState,
+    pub credits_state: crate::scenes::credits::CreditsState,
+    pub whats_new: crate::scenes::whats_new::WhatsNewState,
     pub game_over_state: GameOverState,
 
     // Assets Cache
     pub texture_cache: HashMap<String, Texture>,
     pub sound_cache: HashMap<String, Sound>,
 
+    // Debug
+    pub perf: PerfStats,
+    pub frame_pacer: FramePacer,
+    pub dialogue_log: DialogueLog,
+    pub asset_report: Option<AssetReport>, // F4 panel, open while Some
+
+    // Cursor, the heart stands in for the OS one while the window is focused
+    pub cursor_pos: Vec2<f32>,
+    pub window_focused: bool,
+
     // Transition
     pub transition_timer: f32,
     pub session_started: bool,
+    pub session_stats: SessionStats,
+    snapshot_timer: f32, // Until the next crash snapshot, see crash::SNAPSHOT_SECONDS
 
     // Combat
     pub combat_data: CombatData,
     pub heart_texture: Option<Texture>,
     pub bone_texture: Option<Texture>,
+    pub graze_sound: Option<Sound>,
     pub fade_alpha: f32,
     pub fade_out: bool,
 }
 
 impl GameState {
     pub fn new(ctx: &mut Context) -> tetra::Result<GameState> {
-        // Try to load a font.
-        let font_paths = [
-            "resources/font.ttf",
-            "/usr/share/fonts/truetype/dejavu/DejaVuSansMono.ttf",
-            "/usr/share/fonts/truetype/freefont/FreeMono.ttf",
-            "/usr/share/fonts/liberation/LiberationMono-Regular.ttf",
-            "C:\\Windows\\Fonts\\consola.ttf", // Just in case
-        ];
-
-        let mut font = None;
-        for path in &font_paths {
-            #[allow(clippy::collapsible_if)]
-            if std::path::Path::new(path).exists() {
-                if let Ok(f) = Font::vector(ctx, path, 16.0) {
-                    font = Some(f);
-                    break;
-                }
-            }
+        if let Err(e) = tetra::window::set_mouse_visible(ctx, false) {
+            log::warn!("Failed to hide the OS cursor: {}", e);
         }
 
-        let font = match font {
-            Some(f) => f,
-            None => panic!(
-                "Could not find a suitable font! Please place 'font.ttf' in the 'resources' folder."
-            ),
-        };
+        let system = SystemState::new(ctx)?;
+        crate::frame_pacing::apply_vsync(ctx, system.global_settings.vsync);
+        let (font, font_source) = crate::fonts::load_ui_font(ctx, 16.0, system.language)?;
+
+        let discord = DiscordRpc::new("1456952639702040659");
 
+        let mut state = Self::with_parts(system, Some(font), font_source, Some(discord));
+        state.update_presence();
+        Ok(state)
+    }
+
+    /// No window, font, disk or Discord, for driving `sim::tick` in tests
+    /// and the benches.
+    pub fn headless() -> GameState {
+        Self::with_parts(SystemState::in_memory(), None, FontSource::Bundled, None)
+    }
+
+    /// Everything but the window, font and Discord, which `new` sets up
+    /// with the Context.
+    fn with_parts(
+        system: SystemState,
+        font: Option<Font>,
+        font_source: FontSource,
+        discord: Option<DiscordRpc>,
+    ) -> GameState {
         let boot_state = crate::scenes::boot::BootState::new();
+        let mut whats_new = crate::scenes::whats_new::WhatsNewState::new();
 
         let mut world = WorldState::new();
-        let system = SystemState::new(ctx)?;
-        
+
         // Initialize texts based on language
         let texts = match system.language {
             crate::defs::Language::English => TextResources::new_english(),
@@ -120,20 +230,25 @@ impl GameState {
         };
         world.gaster_dialogues = texts.gaster_dialogues.clone();
 
-        let mut discord = DiscordRpc::new("1456952639702040659");
-        discord.update_status("Booting up...", "Starting System");
-
-        let mut menu_state = crate::scenes::menu::MenuState::new();
+        let mut menu_state = crate::scenes::menu::MenuState::new(
+            crate::scenes::menu::snow_particle_count(&system.global_settings),
+        );
 
         // If no users, default to Create Save
-        if system.users.is_empty() {
+        if !system.can_start() {
             menu_state.selected_index = 1;
         }
 
-        Ok(GameState {
+        // First launch after an update, shown once the menu comes up
+        whats_new.open =
+            system.global_settings.last_seen_version != crate::changelog::current_version();
+
+        GameState {
             scene: Scene::Boot,
             previous_scene: Scene::Boot,
+            font_cache: font.iter().map(|font| (160, font.clone())).collect(),
             font,
+            font_source,
             texts,
 
             system,
@@ -144,36 +259,66 @@ impl GameState {
 
             boot_state,
             menu_state,
+            credits_state: crate::scenes::credits::CreditsState::new(),
+            whats_new,
             game_over_state: GameOverState::new(),
 
             texture_cache: HashMap::new(),
             sound_cache: HashMap::new(),
 
+            perf: PerfStats::new(),
+            frame_pacer: FramePacer::new(),
+            dialogue_log: DialogueLog::new(),
+            asset_report: None,
+
+            cursor_pos: Vec2::new(-100.0, -100.0), // Off screen until the mouse moves
+            window_focused: true,
+
             transition_timer: 0.0,
             session_started: false,
+            session_stats: SessionStats::default(),
+            snapshot_timer: crate::crash::SNAPSHOT_SECONDS,
 
-            combat_data: CombatData::new(),
+            combat_data: CombatData::for_enemy(CombatEnemy::Sans),
             heart_texture: None,
             bone_texture: None,
+            graze_sound: None,
             fade_alpha: 0.0,
             fade_out: false,
-        })
+        }
+    }
+
+    /// The 16pt UI font. Only headless tests go without one, and they never
+    /// draw.
+    pub fn font(&self) -> Font {
+        self.font
+            .clone()
+            .expect("the UI font is loaded in GameState::new")
     }
 
     pub fn assign_texture(&mut self, name: &str, texture: Texture) {
         match name {
             "Player Front" => self.player.texture_front = Some(texture),
-            "Player Left" => self.player.texture_left = Some(texture),
             "Player Right" => self.player.texture_right = Some(texture),
             "City Background" => self.world.bg_texture = Some(texture),
+            "Snow Layer" => self.world.snow_layer_texture = Some(texture),
+            "Haze Layer" => self.world.haze_layer_texture = Some(texture),
             "Gaster Standing" => self.world.npc_gaster_standing = Some(texture),
             "Gaster Talking" => self.world.npc_gaster_talking = Some(texture),
             "Rarity" => self.world.rarity_texture = Some(texture),
+            "Rarity Stab1" => self.world.rarity_stab_textures[0] = Some(texture),
+            "Rarity Stab2" => self.world.rarity_stab_textures[1] = Some(texture),
+            "Rarity Stab3" => self.world.rarity_stab_textures[2] = Some(texture),
             "Eilish" => self.world.eilish_texture = Some(texture),
+            "Eilish Left" => self.world.eilish_texture_left = Some(texture),
+            "Eilish Right" => self.world.eilish_texture_right = Some(texture),
             "Sans" => self.world.sans_texture = Some(texture),
             "Sans Combat" => self.world.sans_combat_texture = Some(texture),
-            "Sans Shrug" => self.world._sans_shrug_texture = Some(texture),
+            "Sans Shrug" => self.world.sans_shrug_texture = Some(texture),
             "Sans Handshake" => self.world.sans_handshake_texture = Some(texture),
+            "Pedestrian 1" => self.world.pedestrian_textures[0] = Some(texture),
+            "Pedestrian 2" => self.world.pedestrian_textures[1] = Some(texture),
+            "Survivor" => self.world.survivor_texture = Some(texture),
             "Heart" => self.heart_texture = Some(texture),
             "Music Box" => self.world.musicbox_texture = Some(texture),
             "Ayasofya Entrance" => self.world.ayasofya_giris_texture = Some(texture),
@@ -181,47 +326,209 @@ impl GameState {
             "Bone" => self.bone_texture = Some(texture),
             "Player Fes" => self.player.texture_fes = Some(texture),
             "Player Takke" => self.player.texture_takke = Some(texture),
+            "Icon Start Game" => self.menu_state.menu_icons[0] = Some(texture),
+            "Icon Create Save" => self.menu_state.menu_icons[1] = Some(texture),
+            "Icon Select Save" => self.menu_state.menu_icons[2] = Some(texture),
+            "Icon Settings" => self.menu_state.menu_icons[3] = Some(texture),
+            "Icon Credits" => self.menu_state.menu_icons[4] = Some(texture),
+            "Icon Statistics" => self.menu_state.menu_icons[5] = Some(texture),
+            "Icon Quit Game" => self.menu_state.menu_icons[7] = Some(texture),
             _ => {}
         }
     }
 
     pub fn assign_sound(&mut self, name: &str, sound: Sound) {
-        if name == "Music Track" {
-            self.world.music_track = Some(sound);
+        match name {
+            "Music Track" => self.world.music_track = Some(sound),
+            "Eilish Song" => self.world.eilish_song = Some(sound),
+            "Gaster Static" => self.world.gaster_static_sound = Some(sound),
+            "Graze Tick" => self.graze_sound = Some(sound),
+            "Stab SFX" => self.world.stab_sound = Some(sound),
+            _ => {}
         }
     }
-}
 
-impl State for GameState {
-    fn event(&mut self, ctx: &mut Context, event: Event) -> tetra::Result {
-        crate::input_handler::handle_event(ctx, self, event);
-        Ok(())
+    /// The UI font rasterized at `size_pt`, so large text stays crisp instead
+    /// of scaling up the 16pt glyphs. Each size is only loaded once.
+    pub fn get_font(&mut self, ctx: &mut Context, size_pt: f32) -> &Font {
+        let key = (size_pt * 10.0).round() as u32;
+        if !self.font_cache.contains_key(&key) {
+            let font = match self.font_source.load(ctx, size_pt) {
+                Ok(font) => font,
+                Err(e) => {
+                    // Cached anyway so the warning isn't repeated every frame
+                    log::warn!("Failed to load the font at {}pt: {}", size_pt, e);
+                    self.font()
+                }
+            };
+            self.font_cache.insert(key, font);
+        }
+        &self.font_cache[&key]
     }
 
-    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
-        if self.scene != self.previous_scene {
-            let details = match self.scene {
-                Scene::Boot => "Booting up...",
-                Scene::Menu => "In Main Menu",
-                Scene::TransitionToDesktop => "Loading Desktop...",
-                Scene::Desktop => "Exploring Desktop",
-                Scene::CombatTransition => "Encounter!",
-                Scene::Combat => "Fighting!",
-                Scene::KernelPanic => "System Crash!",
-                Scene::AyasofyaInside => "Visiting Ayasofya",
-            };
-            let state = match self.scene {
-                Scene::Boot => "System Initialization",
-                Scene::Menu => "Selecting Option",
-                Scene::TransitionToDesktop => "Please wait...",
-                Scene::Desktop => "Wandering around",
-                Scene::CombatTransition => "Preparing for battle",
-                Scene::Combat => "In Combat",
-                Scene::KernelPanic => "Critical Error",
-                Scene::AyasofyaInside => "Praying",
-            };
-            self.discord.update_status(details, state);
-            self.previous_scene = self.scene;
+    /// Swaps the UI font for one that has the new language's letters, if
+    /// the current one doesn't. Call after changing the language.
+    pub fn refresh_font(&mut self, ctx: &mut Context) {
+        if self.font_source.covers(self.system.language) {
+            return;
+        }
+        match crate::fonts::load_ui_font(ctx, 16.0, self.system.language) {
+            Ok((font, font_source)) => {
+                self.font = Some(font.clone());
+                self.font_source = font_source;
+                self.font_cache = HashMap::from([(160, font)]);
+            }
+            Err(e) => log::warn!("Failed to reload the UI font: {}", e),
+        }
+    }
+
+    /// Which expensive effects the current settings allow.
+    pub fn graphics_quality(&self) -> GraphicsQuality {
+        GraphicsQuality::from_settings(&self.system.global_settings)
+    }
+
+    /// For `render_util::draw_styled_text`.
+    pub fn text_scale(&self) -> f32 {
+        self.system.global_settings.text_scale
+    }
+
+    /// Whether a text field has the keyboard, so typed letters don't also
+    /// trigger whatever they're bound to.
+    pub fn input_captured(&self) -> bool {
+        self.scene == Scene::Menu
+            && matches!(
+                self.menu_state.sub_state,
+                crate::scenes::menu::MenuSubState::CreateSave
+                    | crate::scenes::menu::MenuSubState::RenameProfile
+            )
+    }
+
+    /// Sets the Discord presence for the current scene. Desktop shows the
+    /// stage, so the desktop scene also calls this after a stage change.
+    pub fn update_presence(&mut self) {
+        let details = self.texts.fill(TextKey::PresenceDetails(self.scene), &[]);
+        let state = match (self.scene, &self.system.current_user) {
+            (Scene::Menu, Some(user)) => self
+                .texts
+                .fill(TextKey::PresenceProfile, &[("username", &user.username)]),
+            (scene, _) => self.texts.fill(
+                TextKey::PresenceState(scene),
+                &[
+                    ("stage_current", &self.world.current_stage),
+                    ("stage_total", &STAGE_COUNT),
+                ],
+            ),
+        };
+        if let Some(discord) = &mut self.discord {
+            discord.update_status(&details, &state);
+        }
+    }
+
+    /// The heart cursor, 16x16 and centered on the mouse. Skipped while the
+    /// window is unfocused, the OS cursor is back then.
+    fn draw_cursor(&self, ctx: &mut Context) {
+        if !self.window_focused {
+            return;
+        }
+        if let Some(heart) = &self.heart_texture {
+            let size = Vec2::new(heart.width() as f32, heart.height() as f32);
+            heart.render(
+                ctx,
+                DrawParams::new()
+                    .position(self.cursor_pos)
+                    .origin(size / 2.0)
+                    .scale(Vec2::new(16.0, 16.0) / size),
+            );
+        }
+    }
+
+    /// Copies the session's progress into the current user and refreshes the
+    /// snapshot the panic hook saves.
+    fn snapshot_progress(&mut self) {
+        if let Some(user) = &mut self.system.current_user {
+            user.current_stage = self.world.current_stage as u32;
+            user.exposure = self.player.exposure;
+            user.flags = self.world.flags;
+            user.music_on = self.world.music_playing;
+            user.outfit = self.player.owned_outfit;
+        }
+        self.system.snapshot_users();
+    }
+
+    /// Counts the fight that just ended towards the profile's stats and puts
+    /// up a banner for it. Neutral fights are only logged.
+    fn apply_combat_outcome(&mut self) {
+        let Some(outcome) = self.combat_data.outcome else {
+            return;
+        };
+        log::info!(
+            "Combat outcome: {:?}, spared: {}, {} damage taken over {} turns",
+            outcome.path,
+            outcome.enemy_spared,
+            outcome.damage_taken,
+            outcome.turns_taken
+        );
+        if let Some(user) = &mut self.system.current_user {
+            match outcome.path {
+                PlayStyle::Pacifist => {
+                    user.teblig_count += 1;
+                    self.session_stats.teblig += 1;
+                }
+                PlayStyle::Genocide => {
+                    user.tekfir_count += 1;
+                    self.session_stats.tekfir += 1;
+                }
+                PlayStyle::Neutral => return,
+            }
+        }
+        self.system.save_users();
+        self.world.outcome_banner = Some(outcome.path);
+        self.world.outcome_banner_timer = crate::scenes::combat::OUTCOME_BANNER_SECONDS;
+    }
+
+    /// Sends a scene that returned an error to the Kernel Panic screen with the
+    /// error in the log, instead of letting it end the game loop.
+    fn recover_from_scene_error(&mut self, error: tetra::TetraError) {
+        log::error!("{:?} scene failed: {}", self.scene, error);
+        if self.session_started {
+            self.snapshot_progress();
+            self.system.save_users();
+        }
+        self.system.generate_error_panic(
+            &self.system.current_user.clone(),
+            &format!("scene_error: {}", error),
+        );
+        self.world.stop_music();
+        self.game_over_state = GameOverState::new();
+        self.scene = Scene::KernelPanic;
+        self.session_started = false;
+    }
+
+    /// The fake BIOS POST, each line typed out after the one before it.
+    fn draw_post_screen(&self, ctx: &mut Context) {
+        graphics::clear(ctx, Color::BLACK);
+        let elapsed = self.game_over_state.reboot_timer - REBOOT_FADE_SECONDS;
+        let mut chars_left = (elapsed * POST_CHARS_PER_SECOND) as usize;
+        for (i, line) in POST_LINES.iter().enumerate() {
+            if chars_left == 0 {
+                break;
+            }
+            let shown: String = line.chars().take(chars_left).collect();
+            chars_left = chars_left.saturating_sub(line.chars().count());
+            let mut text = Text::new(shown, self.font());
+            text.render(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(20.0, 20.0 + i as f32 * 22.0))
+                    .color(Color::WHITE),
+            );
+        }
+    }
+
+    fn update_scene(&mut self, ctx: &mut Context) -> tetra::Result {
+        // The dialogue log, the travel map and the asset report pause whatever is under them
+        if self.dialogue_log.open || self.world.map_open || self.asset_report.is_some() {
+            return Ok(());
         }
 
         match self.scene {
@@ -239,7 +546,7 @@ impl State for GameState {
                 }
             }
             Scene::Desktop => {
-                crate::scenes::desktop::update(ctx, self)?;
+                crate::scenes::desktop::update(ctx, self);
             }
             Scene::CombatTransition => {
                 if self.fade_out {
@@ -248,48 +555,78 @@ impl State for GameState {
                         self.fade_alpha = 1.0;
                         self.scene = Scene::Combat;
                         self.fade_out = false;
-                        // Reset combat data
-                        self.combat_data = CombatData::new();
+                        // Reset combat data for whoever started the fight
+                        let enemy = self.world.pending_combat_enemy.take().unwrap_or_else(|| {
+                            log::warn!("Combat started without an enemy, defaulting to Sans");
+                            CombatEnemy::Sans
+                        });
+                        log::info!("Combat started against {:?}", enemy);
+                        self.combat_data = CombatData::for_enemy(enemy);
+                        self.combat_data.return_pos = Some(self.player.pos);
+                        self.dialogue_log
+                            .push(Speaker::Narrator, &self.combat_data.dialogue_text);
+                        crate::scenes::combat::queue_sans_phase_line(self, &mut rand::rng());
                     }
                 }
             }
             Scene::Combat => {
-                crate::scenes::combat::update(ctx, self)?;
+                crate::sim::update(ctx, self);
+            }
+            Scene::KernelPanic if self.game_over_state.rebooting() => {
+                self.game_over_state.reboot_timer += tetra::time::get_delta_time(ctx).as_secs_f32();
+                if self.game_over_state.reboot_timer >= REBOOT_FADE_SECONDS + REBOOT_POST_SECONDS {
+                    // Starts over from the first asset, like a real boot
+                    log::info!("Rebooting after kernel panic");
+                    self.boot_state = crate::scenes::boot::BootState::new();
+                    self.scene = Scene::Boot;
+                }
             }
             Scene::KernelPanic => {
-                // Update Stats Animation
+                if !self.game_over_state.panic_scrolled {
+                    // Keep the last line, the actual panic, in view
+                    self.game_over_state.panic_scroll_offset = self
+                        .system
+                        .kernel_panic_lines
+                        .len()
+                        .saturating_sub(PANIC_LOG_ROWS);
+                }
+
+                // Update Stats Animation, Performance mode keeps it still
+                let animate = self.graphics_quality().panic_animation;
                 let mut rng = rand::rng();
-                if self.game_over_state.stat_fading_in {
-                    self.game_over_state.stat_alpha += 0.02;
-                    if self.game_over_state.stat_alpha >= 1.5 {
-                        // Go a bit over 1.0 for a pause
-                        self.game_over_state.stat_fading_in = false;
-                    }
-                } else {
-                    self.game_over_state.stat_alpha -= 0.02;
-                    if self.game_over_state.stat_alpha <= 0.0 {
-                        self.game_over_state.stat_fading_in = true;
-                        self.game_over_state.current_stat_index =
-                            (self.game_over_state.current_stat_index + 1) % 4; // 4 stats
-
-                        // Generate position avoiding the center box
-                        // Center Box: X: 150-650, Y: 150-450 (Approx)
-                        let pos;
-                        loop {
-                            let p = Vec2::new(
-                                rng.random_range(50.0..SCREEN_WIDTH as f32 - 150.0),
-                                rng.random_range(50.0..SCREEN_HEIGHT as f32 - 50.0),
-                            );
-
-                            let in_center_x = p.x > 150.0 && p.x < 650.0;
-                            let in_center_y = p.y > 150.0 && p.y < 450.0;
-
-                            if !(in_center_x && in_center_y) {
-                                pos = p;
-                                break;
+                if animate {
+                    if self.game_over_state.stat_fading_in {
+                        self.game_over_state.stat_alpha += 0.02;
+                        if self.game_over_state.stat_alpha >= 1.5 {
+                            // Go a bit over 1.0 for a pause
+                            self.game_over_state.stat_fading_in = false;
+                        }
+                    } else {
+                        self.game_over_state.stat_alpha -= 0.02;
+                        if self.game_over_state.stat_alpha <= 0.0 {
+                            self.game_over_state.stat_fading_in = true;
+                            self.game_over_state.current_stat_index =
+                                (self.game_over_state.current_stat_index + 1) % 6; // 6 stats
+
+                            // Generate position avoiding the center box
+                            // Center Box: X: 150-650, Y: 150-450 (Approx)
+                            let pos;
+                            loop {
+                                let p = Vec2::new(
+                                    rng.random_range(50.0..SCREEN_WIDTH as f32 - 150.0),
+                                    rng.random_range(50.0..SCREEN_HEIGHT as f32 - 50.0),
+                                );
+
+                                let in_center_x = p.x > 150.0 && p.x < 650.0;
+                                let in_center_y = p.y > 150.0 && p.y < 450.0;
+
+                                if !(in_center_x && in_center_y) {
+                                    pos = p;
+                                    break;
+                                }
                             }
+                            self.game_over_state.stat_pos = pos;
                         }
-                        self.game_over_state.stat_pos = pos;
                     }
                 }
 
@@ -307,12 +644,17 @@ impl State for GameState {
                         0
                     };
 
-                    println!(
-                        "DEBUG: Game Over Stats - Teblig: {}, Tekfir: {}",
-                        teblig, tekfir
-                    );
+                    log::debug!("Game Over Stats - Teblig: {}, Tekfir: {}", teblig, tekfir);
 
-                    let messages = if teblig > tekfir {
+                    // Half the time a line about whatever killed them
+                    let cause_messages = self
+                        .texts
+                        .game_over_messages
+                        .for_cause(self.game_over_state.cause)
+                        .filter(|messages| !messages.is_empty() && rng.random_bool(0.5));
+                    let messages = if let Some(messages) = cause_messages {
+                        messages
+                    } else if teblig > tekfir {
                         &self.texts.game_over_messages.teblig_high
                     } else if tekfir > teblig {
                         &self.texts.game_over_messages.tekfir_high
@@ -324,7 +666,16 @@ impl State for GameState {
                         messages[rng.random_range(0..messages.len())].clone();
                 }
 
-                if self.game_over_state.message_fading_in {
+                if self.game_over_state.export_notice_timer > 0.0 {
+                    self.game_over_state.export_notice_timer -= 0.016;
+                    if self.game_over_state.export_notice_timer <= 0.0 {
+                        self.game_over_state.export_notice = None;
+                    }
+                }
+
+                if !animate {
+                    self.game_over_state.message_alpha = 1.0;
+                } else if self.game_over_state.message_fading_in {
                     self.game_over_state.message_alpha += 0.01;
                     if self.game_over_state.message_alpha >= 1.0 {
                         self.game_over_state.message_fading_in = false;
@@ -341,13 +692,62 @@ impl State for GameState {
                 }
             }
             Scene::AyasofyaInside => {
-                crate::scenes::ayasofya::update(ctx, self)?;
+                crate::sim::update(ctx, self);
+            }
+            Scene::Credits => {
+                crate::scenes::credits::update(ctx, self)?;
             }
+            Scene::Statistics => {
+                crate::scenes::statistics::update(ctx, self)?;
+            }
+            Scene::SessionSummary => {}
+        }
+        Ok(())
+    }
+}
+
+impl State for GameState {
+    fn event(&mut self, ctx: &mut Context, event: Event) -> tetra::Result {
+        crate::input_handler::handle_event(ctx, self, event);
+        Ok(())
+    }
+
+    fn update(&mut self, ctx: &mut Context) -> tetra::Result {
+        if self.scene != self.previous_scene {
+            if self.previous_scene == Scene::Combat && self.scene == Scene::Desktop {
+                self.apply_combat_outcome();
+            }
+            self.update_presence();
+            log::info!(
+                "Scene changed: {:?} -> {:?}",
+                self.previous_scene,
+                self.scene
+            );
+            self.previous_scene = self.scene;
+        }
+
+        if self.session_started {
+            let dt = tetra::time::get_delta_time(ctx).as_secs_f32();
+            self.dialogue_log.tick(dt);
+            if let Some(user) = &mut self.system.current_user {
+                user.playtime += dt;
+            }
+            self.session_stats.time_played += dt;
+            self.snapshot_timer -= dt;
+            if self.snapshot_timer <= 0.0 {
+                self.snapshot_timer = crate::crash::SNAPSHOT_SECONDS;
+                self.snapshot_progress();
+            }
+        }
+
+        if let Err(e) = self.update_scene(ctx) {
+            self.recover_from_scene_error(e);
         }
         Ok(())
     }
 
     fn draw(&mut self, ctx: &mut Context) -> tetra::Result {
+        self.perf.begin_frame(ctx);
         graphics::clear(ctx, Color::BLACK);
 
         match self.scene {
@@ -371,7 +771,7 @@ impl State for GameState {
                     Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
                 )
                 .unwrap();
-                fade_rect.draw(
+                fade_rect.render(
                     ctx,
                     DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, self.fade_alpha)),
                 );
@@ -379,33 +779,34 @@ impl State for GameState {
             Scene::Combat => {
                 crate::scenes::combat::draw(ctx, self)?;
             }
+            Scene::KernelPanic if self.game_over_state.showing_post() => {
+                self.draw_post_screen(ctx);
+            }
             Scene::KernelPanic => {
                 graphics::clear(ctx, Color::BLACK);
 
                 // Draw Title
+                let title_font = self.get_font(ctx, 32.0).clone();
                 let title = &self.texts.ui.game_over_title;
-                let mut title_text = Text::new(title, self.font.clone());
-                let title_bounds = title_text.get_bounds(ctx).unwrap();
-                let title_scale = 2.0;
-                let title_width = title_bounds.width * title_scale;
+                let mut title_text = Text::new(title, title_font);
+                let title_width = title_text.get_bounds(ctx).unwrap().width;
 
-                title_text.draw(
+                title_text.render(
                     ctx,
                     DrawParams::new()
                         .position(Vec2::new((SCREEN_WIDTH as f32 - title_width) / 2.0, 200.0))
-                        .color(Color::RED)
-                        .scale(Vec2::new(title_scale, title_scale)),
+                        .color(Color::RED),
                 );
 
                 // Draw Username
-                let username = if let Some(user) = &self.system.current_user {
-                    format!("{}{}", self.texts.ui.user_label, user.username)
-                } else {
-                    format!("{}Unknown", self.texts.ui.user_label)
+                let user = match &self.system.current_user {
+                    Some(user) => &user.username,
+                    None => &self.texts.ui.unknown_user,
                 };
-                let mut user_text = Text::new(username, self.font.clone());
+                let username = self.texts.fill(TextKey::UserLine, &[("user", user)]);
+                let mut user_text = Text::new(username, self.font());
                 let user_bounds = user_text.get_bounds(ctx).unwrap();
-                user_text.draw(
+                user_text.render(
                     ctx,
                     DrawParams::new()
                         .position(Vec2::new(
@@ -417,10 +818,9 @@ impl State for GameState {
 
                 // Draw Dynamic Message
                 if !self.game_over_state.message_text.is_empty() {
-                    let mut msg_text =
-                        Text::new(&self.game_over_state.message_text, self.font.clone());
+                    let mut msg_text = Text::new(&self.game_over_state.message_text, self.font());
                     let msg_bounds = msg_text.get_bounds(ctx).unwrap();
-                    msg_text.draw(
+                    msg_text.render(
                         ctx,
                         DrawParams::new()
                             .position(Vec2::new(
@@ -462,6 +862,7 @@ impl State for GameState {
                             0
                         }
                     ),
+                    format!("Max HP: {}", self.player.player_max_hp),
                     format!(
                         "Stage: {}",
                         if let Some(u) = &self.system.current_user {
@@ -470,14 +871,24 @@ impl State for GameState {
                             1
                         }
                     ),
+                    format!("Cause: {}", self.game_over_state.cause.label()),
                 ];
 
-                if self.game_over_state.current_stat_index < stats.len() {
-                    let mut stat_text = Text::new(
-                        &stats[self.game_over_state.current_stat_index],
-                        self.font.clone(),
-                    );
-                    stat_text.draw(
+                if !self.graphics_quality().panic_animation {
+                    // All at once in the corner instead of fading around the screen
+                    for (i, stat) in stats.iter().enumerate() {
+                        let mut stat_text = Text::new(stat.as_str(), self.font());
+                        stat_text.render(
+                            ctx,
+                            DrawParams::new()
+                                .position(Vec2::new(20.0, 50.0 + i as f32 * 24.0))
+                                .color(Color::WHITE),
+                        );
+                    }
+                } else if self.game_over_state.current_stat_index < stats.len() {
+                    let mut stat_text =
+                        Text::new(&stats[self.game_over_state.current_stat_index], self.font());
+                    stat_text.render(
                         ctx,
                         DrawParams::new()
                             .position(self.game_over_state.stat_pos)
@@ -490,9 +901,49 @@ impl State for GameState {
                     );
                 }
 
+                // Draw Kernel Log
+                let lines = &self.system.kernel_panic_lines;
+                let offset = self.game_over_state.panic_scroll_offset;
+                for (i, line) in lines.iter().skip(offset).take(PANIC_LOG_ROWS).enumerate() {
+                    let mut log_text = Text::new(line.as_str(), self.font());
+                    log_text.render(
+                        ctx,
+                        DrawParams::new()
+                            .position(Vec2::new(
+                                20.0,
+                                PANIC_LOG_Y + (i as f32 * PANIC_LOG_LINE_HEIGHT),
+                            ))
+                            .color(Color::rgb(0.5, 0.5, 0.5)),
+                    );
+                }
+                let more_above = offset > 0;
+                let more_below = offset + PANIC_LOG_ROWS < lines.len();
+                for (shown, arrow, y) in [
+                    (more_above, "↑", PANIC_LOG_Y),
+                    (
+                        more_below,
+                        "↓",
+                        PANIC_LOG_Y + (PANIC_LOG_ROWS - 1) as f32 * PANIC_LOG_LINE_HEIGHT,
+                    ),
+                ] {
+                    if shown {
+                        let mut arrow_text = Text::new(arrow, self.font());
+                        arrow_text.render(
+                            ctx,
+                            DrawParams::new()
+                                .position(Vec2::new(SCREEN_WIDTH as f32 - 30.0, y))
+                                .color(Color::rgb(0.5, 0.5, 0.5)),
+                        );
+                    }
+                }
+
                 // Draw Options
-                let options = [&self.texts.ui.return_menu, &self.texts.ui.quit_game];
-                let total_width = 400.0; // Approximate width for both options
+                let options = [
+                    &self.texts.ui.return_menu,
+                    &self.texts.ui.export_report,
+                    &self.texts.ui.quit_game,
+                ];
+                let total_width = 620.0; // Approximate width for all options
                 let start_x = (SCREEN_WIDTH as f32 - total_width) / 2.0;
 
                 let mut x_offset = start_x;
@@ -502,21 +953,82 @@ impl State for GameState {
                     } else {
                         Color::rgb(0.5, 0.5, 0.5) // Gray
                     };
-                    let mut text = Text::new(*opt, self.font.clone());
-                    text.draw(
+                    let mut text = Text::new(*opt, self.font());
+                    text.render(
                         ctx,
                         DrawParams::new()
                             .position(Vec2::new(x_offset, 400.0))
                             .color(color),
                     );
-                    x_offset += 250.0; // Spacing
+                    x_offset += 220.0; // Spacing
+                }
+
+                // Export Notice
+                if let Some(notice) = &self.game_over_state.export_notice {
+                    let mut notice_text = Text::new(notice.as_str(), self.font());
+                    let notice_width = notice_text.get_bounds(ctx).map(|b| b.width).unwrap_or(0.0);
+                    notice_text.render(
+                        ctx,
+                        DrawParams::new()
+                            .position(Vec2::new((SCREEN_WIDTH as f32 - notice_width) / 2.0, 435.0))
+                            .color(Color::GREEN),
+                    );
+                }
+
+                // Screen clearing before the reboot
+                if self.game_over_state.rebooting() {
+                    let alpha = self.game_over_state.reboot_timer / REBOOT_FADE_SECONDS;
+                    let fade = Mesh::rectangle(
+                        ctx,
+                        ShapeStyle::Fill,
+                        Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+                    )?;
+                    fade.render(
+                        ctx,
+                        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, alpha)),
+                    );
                 }
             }
             Scene::AyasofyaInside => {
                 crate::scenes::ayasofya::draw(ctx, self)?;
             }
+            Scene::Credits => {
+                crate::scenes::credits::draw(ctx, self)?;
+            }
+            Scene::Statistics => {
+                crate::scenes::statistics::draw(ctx, self)?;
+            }
+            Scene::SessionSummary => {
+                crate::scenes::session_summary::draw(ctx, self)?;
+            }
         }
 
+        crate::scenes::travel_map::draw(ctx, self)?;
+        crate::scenes::dialogue_log::draw(ctx, self)?;
+        crate::scenes::whats_new::draw(ctx, self)?;
+        crate::scenes::asset_report::draw(ctx, self)?;
+        self.draw_cursor(ctx);
+
+        // Debug overlay (F3), its own draws aren't counted
+        self.perf.end_frame(EntityCounts {
+            particles: self.menu_state.snow_particles.len(),
+            bones: self.combat_data.bones.len(),
+            sparks: self.combat_data.graze_sparks.len(),
+            crowd: self.world.crowd.len(),
+            toasts: usize::from(self.world.toast.is_some()),
+            textures: self.texture_cache.len(),
+            sounds: self.sound_cache.len(),
+        });
+        self.perf.draw_overlay(
+            ctx,
+            &self.font(),
+            &self.font_source.label(),
+            self.graphics_quality(),
+            &crate::frame_pacing::target_label(&self.system.global_settings),
+        )?;
+
+        self.frame_pacer.wait(self.system.global_settings.fps_cap);
+
         Ok(())
     }
 }
@@ -1,8 +1,89 @@
-use std::fs;
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
 
+use serde::{Deserialize, Serialize};
+
+use crate::defs::Language;
+use crate::paths;
+use crate::storage;
+
+/// Bump when the on-disk layout changes and add a migration step in `load`.
+/// Version 0 is the old "lang,volume[,difficulty]" CSV.
+pub const SETTINGS_VERSION: u32 = 1;
+
+const MAX_VOLUME: u32 = 100;
+const MAX_SNOW_PARTICLES: u32 = 500;
+const TEXT_SCALE_RANGE: RangeInclusive<f32> = 0.5..=3.0;
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct GlobalSettings {
-    pub language: String,
-    pub volume: u32,
+    pub version: u32,
+    pub language: Language,
+    pub volume: u32,       // Master, 0-100
+    pub music_volume: u32, // 0-100
+    pub sfx_volume: u32,   // 0-100
+    pub hard_mode: bool,
+    pub fullscreen: bool,
+    pub text_scale: f32, // 0.5 - 3.0
+    pub subtitles: bool,
+    pub autosave: bool,
+    pub reduce_motion: bool,
+    pub performance_mode: bool, // See GraphicsQuality
+    pub discord_rpc: bool,
+    pub show_objectives: bool, // The HUD tracker, the pause menu list always shows
+    pub snow_particle_count: u32, // Menu snow, 0-500
+    pub bindings: BTreeMap<String, String>, // Action name -> key name
+    // Tap a direction to keep walking, see overworld::move_player
+    pub auto_walk: bool,
+    pub vsync: bool,
+    pub fps_cap: u32,                 // 0 is uncapped, see frame_pacing::FPS_CAPS
+    pub replay_combat_tutorial: bool, // Cleared once the next fight has shown it
+    // Empty in files from before the field existed, so those players see the panel
+    #[serde(default)]
+    pub last_seen_version: String, // For the "What's new" panel
+
+    // Keys written by a newer version, kept so downgrading doesn't wipe them
+    #[serde(flatten)]
+    pub unknown: toml::Table,
+
+    #[serde(skip)]
+    pub restored_from_backup: bool,
+    // Values `from_table` dropped, reported by `validate`
+    #[serde(skip)]
+    pub invalid_values: Vec<String>,
+}
+
+impl Default for GlobalSettings {
+    fn default() -> Self {
+        Self {
+            version: SETTINGS_VERSION,
+            language: Language::English,
+            volume: 100,
+            music_volume: 100,
+            sfx_volume: 100,
+            hard_mode: false,
+            fullscreen: false,
+            text_scale: 1.0,
+            subtitles: true,
+            autosave: true,
+            reduce_motion: false,
+            performance_mode: false,
+            discord_rpc: true,
+            show_objectives: true,
+            auto_walk: false,
+            vsync: true,
+            fps_cap: 0,
+            replay_combat_tutorial: false,
+            snow_particle_count: 100,
+            bindings: BTreeMap::new(),
+            // A fresh install has nothing to catch up on
+            last_seen_version: crate::changelog::current_version().to_string(),
+            unknown: toml::Table::new(),
+            restored_from_backup: false,
+            invalid_values: Vec::new(),
+        }
+    }
 }
 
 impl GlobalSettings {
@@ -11,23 +92,273 @@ impl GlobalSettings {
     }
 
     pub fn load() -> Self {
-        if let Ok(content) = fs::read_to_string("global.db") {
-            let parts: Vec<&str> = content.trim().split(',').collect();
-            if parts.len() >= 2 {
-                let language = parts[0].to_string();
-                let volume = parts[1].parse().unwrap_or(100);
-                return Self { language, volume };
+        let path = paths::data_path("global.db");
+        let Some((mut settings, restored)) = storage::read_with_backup(&path, Self::parse) else {
+            return Self::default();
+        };
+        settings.restored_from_backup = restored;
+
+        // Hand edits can leave values out of range, say which and pull them back in
+        let problems = settings.validate();
+        if !problems.is_empty() {
+            for problem in &problems {
+                log::warn!("global.db: {}", problem);
             }
+            settings.clamp();
         }
-        // Defaults
-        Self {
-            language: "en".to_string(),
-            volume: 100,
+
+        if settings.version < SETTINGS_VERSION {
+            log::info!(
+                "Migrating global.db from version {} to {}",
+                settings.version,
+                SETTINGS_VERSION
+            );
+            settings.version = SETTINGS_VERSION;
+            settings.save();
+        }
+        settings
+    }
+
+    fn parse(content: &str) -> Option<Self> {
+        match content.parse::<toml::Table>() {
+            // An empty file is a failed write, not a valid config
+            Ok(table) if table.is_empty() => None,
+            Ok(table) => Some(Self::from_table(table)),
+            Err(_) => Self::parse_legacy(content),
+        }
+    }
+
+    fn from_table(mut table: toml::Table) -> Self {
+        // Drop known keys whose value doesn't deserialize (wrong type, negative
+        // volume, unknown language) so one bad value doesn't reset everything
+        let mut invalid_values = Vec::new();
+        if let Ok(defaults) = toml::Table::try_from(Self::default()) {
+            for key in defaults.keys() {
+                let Some(value) = table.get(key) else {
+                    continue;
+                };
+                let mut probe = defaults.clone();
+                probe.insert(key.clone(), value.clone());
+                if Self::deserialize(toml::Value::Table(probe)).is_err() {
+                    invalid_values.push(invalid_value(key, value));
+                    table.remove(key);
+                }
+            }
+        }
+
+        let mut settings = match Self::deserialize(toml::Value::Table(table)) {
+            Ok(settings) => settings,
+            Err(e) => {
+                log::warn!("global.db: {}, using defaults", e);
+                Self::default()
+            }
+        };
+        settings.invalid_values = invalid_values;
+        settings
+    }
+
+    /// Version 0: "lang,volume[,hard|normal]"
+    fn parse_legacy(content: &str) -> Option<Self> {
+        let parts: Vec<&str> = content.trim().split(',').collect();
+        if parts.len() >= 2 && !parts[0].is_empty() {
+            let settings = Self {
+                version: 0,
+                language: if parts[0] == "tr" {
+                    Language::Turkish
+                } else {
+                    Language::English
+                },
+                volume: parts[1].parse().ok()?,
+                hard_mode: parts.get(2) == Some(&"hard"),
+                ..Self::default()
+            };
+            return Some(settings);
+        }
+        None
+    }
+
+    /// One message per out-of-range value. Negative numbers and unknown
+    /// languages can't be stored in the fields, `from_table` drops them
+    /// while parsing and they're listed here first.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = self.invalid_values.clone();
+        for (name, volume) in [
+            ("volume", self.volume),
+            ("music_volume", self.music_volume),
+            ("sfx_volume", self.sfx_volume),
+        ] {
+            if volume > MAX_VOLUME {
+                problems.push(format!("{} is {}, expected 0-{}", name, volume, MAX_VOLUME));
+            }
+        }
+        if self.snow_particle_count > MAX_SNOW_PARTICLES {
+            problems.push(format!(
+                "snow_particle_count is {}, expected at most {}",
+                self.snow_particle_count, MAX_SNOW_PARTICLES
+            ));
+        }
+        if !TEXT_SCALE_RANGE.contains(&self.text_scale) {
+            problems.push(format!(
+                "text_scale is {}, expected {}-{}",
+                self.text_scale,
+                TEXT_SCALE_RANGE.start(),
+                TEXT_SCALE_RANGE.end()
+            ));
+        }
+        problems
+    }
+
+    /// Clamps numeric settings into range, garbage floats go back to the default.
+    pub fn clamp(&mut self) {
+        self.volume = self.volume.min(MAX_VOLUME);
+        self.music_volume = self.music_volume.min(MAX_VOLUME);
+        self.sfx_volume = self.sfx_volume.min(MAX_VOLUME);
+        self.snow_particle_count = self.snow_particle_count.min(MAX_SNOW_PARTICLES);
+        if self.text_scale.is_finite() {
+            self.text_scale = self
+                .text_scale
+                .clamp(*TEXT_SCALE_RANGE.start(), *TEXT_SCALE_RANGE.end());
+        } else {
+            self.text_scale = 1.0;
         }
     }
 
     pub fn save(&self) {
-        let content = format!("{},{}", self.language, self.volume);
-        let _ = fs::write("global.db", content);
+        match toml::to_string(self) {
+            Ok(content) => match storage::write_atomic(&paths::data_path("global.db"), &content) {
+                Ok(()) => log::info!("Saved global.db"),
+                Err(e) => log::error!("Failed to save global.db: {}", e),
+            },
+            Err(e) => log::error!("Failed to serialize global.db: {}", e),
+        }
+    }
+}
+
+/// What `validate` says about a value that couldn't be read at all.
+fn invalid_value(key: &str, value: &toml::Value) -> String {
+    match key {
+        "language" => format!("language is {}, expected \"en\" or \"tr\"", value),
+        "volume" | "music_volume" | "sfx_volume" => {
+            format!("{} is {}, expected 0-{}", key, value, MAX_VOLUME)
+        }
+        "snow_particle_count" => format!(
+            "snow_particle_count is {}, expected 0-{}",
+            value, MAX_SNOW_PARTICLES
+        ),
+        _ => format!("{} is {}, which isn't a valid value", key, value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_the_old_csv() {
+        let settings = GlobalSettings::parse("tr,80,hard").unwrap();
+        assert_eq!(settings.version, 0);
+        assert_eq!(settings.language, Language::Turkish);
+        assert_eq!(settings.volume, 80);
+        assert!(settings.hard_mode);
+
+        let settings = GlobalSettings::parse("en,35\n").unwrap();
+        assert_eq!(settings.language, Language::English);
+        assert_eq!(settings.volume, 35);
+        assert!(!settings.hard_mode);
+    }
+
+    #[test]
+    fn partial_file_defaults_the_rest() {
+        let settings =
+            GlobalSettings::parse("version = 1\nvolume = 40\nsubtitles = false\n").unwrap();
+        let defaults = GlobalSettings::default();
+        assert_eq!(settings.volume, 40);
+        assert!(!settings.subtitles);
+        assert_eq!(settings.music_volume, defaults.music_volume);
+        assert_eq!(settings.language, defaults.language);
+        assert_eq!(settings.text_scale, defaults.text_scale);
+    }
+
+    #[test]
+    fn invalid_value_falls_back_to_its_default() {
+        let settings = GlobalSettings::parse("volume = -5\nmusic_volume = 20\n").unwrap();
+        assert_eq!(settings.volume, GlobalSettings::default().volume);
+        assert_eq!(settings.music_volume, 20);
+    }
+
+    #[test]
+    fn unknown_keys_survive_a_round_trip() {
+        let content = "volume = 40\nfuture_setting = \"kept\"\n\n[future_table]\nlevel = 3\n";
+        let settings = GlobalSettings::parse(content).unwrap();
+        let saved = toml::to_string(&settings).unwrap();
+        let reloaded = GlobalSettings::parse(&saved).unwrap();
+        assert_eq!(reloaded.volume, 40);
+        assert_eq!(
+            reloaded
+                .unknown
+                .get("future_setting")
+                .and_then(|v| v.as_str()),
+            Some("kept")
+        );
+        assert_eq!(
+            reloaded.unknown["future_table"]
+                .get("level")
+                .and_then(|v| v.as_integer()),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn empty_or_garbage_file_is_rejected() {
+        assert!(GlobalSettings::parse("").is_none());
+        assert!(GlobalSettings::parse("not settings").is_none());
+    }
+
+    #[test]
+    fn validate_lists_every_bad_field() {
+        let settings = GlobalSettings {
+            volume: 150,
+            sfx_volume: 101,
+            snow_particle_count: 900,
+            text_scale: 3.5,
+            ..GlobalSettings::default()
+        };
+        let problems = settings.validate();
+        assert_eq!(problems.len(), 4, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.starts_with("volume is 150")));
+        assert!(problems.iter().any(|p| p.starts_with("sfx_volume is 101")));
+        assert!(
+            problems
+                .iter()
+                .any(|p| p.starts_with("snow_particle_count is 900"))
+        );
+        assert!(problems.iter().any(|p| p.starts_with("text_scale is 3.5")));
+    }
+
+    #[test]
+    fn validate_reports_unreadable_values() {
+        let settings =
+            GlobalSettings::parse("language = \"fr\"\nmusic_volume = -3\nvolume = 120\n").unwrap();
+        let problems = settings.validate();
+        assert_eq!(problems.len(), 3, "{:?}", problems);
+        assert!(problems.iter().any(|p| p.starts_with("language is \"fr\"")));
+        assert!(problems.iter().any(|p| p.starts_with("music_volume is -3")));
+        assert!(problems.iter().any(|p| p.starts_with("volume is 120")));
+    }
+
+    #[test]
+    fn clamp_pulls_values_back_in_range() {
+        let mut settings = GlobalSettings {
+            volume: 150,
+            snow_particle_count: 900,
+            text_scale: f32::NAN,
+            ..GlobalSettings::default()
+        };
+        settings.clamp();
+        assert_eq!(settings.volume, MAX_VOLUME);
+        assert_eq!(settings.snow_particle_count, MAX_SNOW_PARTICLES);
+        assert_eq!(settings.text_scale, 1.0);
+        settings.text_scale = 2.5;
+        assert!(settings.validate().is_empty());
     }
 }
@@ -0,0 +1,56 @@
+use crate::global_db::GlobalSettings;
+
+/// Which expensive effects are drawn. Every effect site asks this instead of
+/// checking the Performance mode setting itself, so the list of what the mode
+/// turns off lives in one place.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub struct GraphicsQuality {
+    pub intro_animation: bool, // Off: first GIF frame only, the audio still plays
+    pub particle_scale: f32,   // Multiplier for menu snow and combat sparks
+    pub fullscreen_overlays: bool, // Off: the disco lights tint the background instead
+    pub screen_shake: bool,
+    pub panic_animation: bool, // Off: the game over stats are listed, nothing fades
+    pub interact_highlight: bool, // Outline on whatever F would act on
+}
+
+impl GraphicsQuality {
+    pub const FULL: Self = Self {
+        intro_animation: true,
+        particle_scale: 1.0,
+        fullscreen_overlays: true,
+        screen_shake: true,
+        panic_animation: true,
+        interact_highlight: true,
+    };
+
+    pub const PERFORMANCE: Self = Self {
+        intro_animation: false,
+        particle_scale: 0.25,
+        fullscreen_overlays: false,
+        screen_shake: false,
+        panic_animation: false,
+        interact_highlight: false,
+    };
+
+    pub fn from_settings(settings: &GlobalSettings) -> Self {
+        if settings.performance_mode {
+            Self::PERFORMANCE
+        } else {
+            Self::FULL
+        }
+    }
+
+    /// `full` scaled down, rounded up so an effect never disappears entirely.
+    pub fn particle_count(&self, full: usize) -> usize {
+        (full as f32 * self.particle_scale).ceil() as usize
+    }
+
+    /// For the perf overlay, so frame times can be compared between modes.
+    pub fn label(&self) -> &'static str {
+        if *self == Self::PERFORMANCE {
+            "Performance"
+        } else {
+            "Full"
+        }
+    }
+}
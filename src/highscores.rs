@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+use crate::storage;
+use crate::system::User;
+
+const LEADERBOARD_SIZE: usize = 10;
+
+/// One finished run, written when the player dies.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct RunRecord {
+    pub username: String,
+    pub teblig: u32,
+    pub tekfir: u32,
+    pub love: u32, // Cihad count, the run's violence
+    pub stage_reached: u32,
+    pub run_time_secs: f64,
+}
+
+impl RunRecord {
+    pub fn from_user(user: &User) -> Self {
+        Self {
+            username: user.username.clone(),
+            teblig: user.teblig_count,
+            tekfir: user.tekfir_count,
+            love: user.cihad_count,
+            stage_reached: user.current_stage,
+            run_time_secs: user.playtime as f64,
+        }
+    }
+}
+
+/// Every run on this machine, kept in highscores.json next to users.db.
+#[derive(Serialize, Deserialize, Default)]
+pub struct Highscores {
+    pub records: Vec<RunRecord>,
+}
+
+impl Highscores {
+    pub fn load() -> Self {
+        let path = paths::data_path("highscores.json");
+        match storage::read_with_backup(&path, |content| serde_json::from_str(content).ok()) {
+            Some((highscores, _)) => highscores,
+            None => Self::default(),
+        }
+    }
+
+    pub fn save(&self) {
+        match serde_json::to_string_pretty(self) {
+            Ok(content) => {
+                match storage::write_atomic(&paths::data_path("highscores.json"), &content) {
+                    Ok(()) => log::info!("Saved highscores.json"),
+                    Err(e) => log::error!("Failed to save highscores.json: {}", e),
+                }
+            }
+            Err(e) => log::error!("Failed to serialize highscores.json: {}", e),
+        }
+    }
+
+    /// Most teblig first. Ties go to fewer tekfir, then the faster run.
+    pub fn top10_by_teblig(&self) -> Vec<RunRecord> {
+        let mut records = self.records.clone();
+        records.sort_by(|a, b| {
+            b.teblig
+                .cmp(&a.teblig)
+                .then(a.tekfir.cmp(&b.tekfir))
+                .then(a.run_time_secs.total_cmp(&b.run_time_secs))
+        });
+        records.truncate(LEADERBOARD_SIZE);
+        records
+    }
+}
+
+/// Adds the user's run to highscores.json.
+pub fn record_run(user: &User) {
+    let mut highscores = Highscores::load();
+    highscores.records.push(RunRecord::from_user(user));
+    highscores.save();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(username: &str, teblig: u32, tekfir: u32, run_time_secs: f64) -> RunRecord {
+        RunRecord {
+            username: username.to_string(),
+            teblig,
+            tekfir,
+            love: 0,
+            stage_reached: 1,
+            run_time_secs,
+        }
+    }
+
+    #[test]
+    fn top10_keeps_the_ten_best() {
+        let highscores = Highscores {
+            records: (0..15)
+                .map(|i| record(&format!("user{}", i), i, 0, 60.0))
+                .collect(),
+        };
+        let top = highscores.top10_by_teblig();
+        assert_eq!(top.len(), 10);
+        let teblig: Vec<u32> = top.iter().map(|r| r.teblig).collect();
+        assert_eq!(teblig, (5..15).rev().collect::<Vec<u32>>());
+    }
+
+    #[test]
+    fn ties_go_to_fewer_tekfir_then_the_faster_run() {
+        let highscores = Highscores {
+            records: vec![
+                record("slow", 3, 1, 200.0),
+                record("tekfir", 3, 2, 50.0),
+                record("fast", 3, 1, 100.0),
+                record("best", 4, 9, 900.0),
+            ],
+        };
+        let names: Vec<String> = highscores
+            .top10_by_teblig()
+            .into_iter()
+            .map(|r| r.username)
+            .collect();
+        assert_eq!(names, ["best", "fast", "slow", "tekfir"]);
+    }
+}
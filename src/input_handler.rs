@@ -1,9 +1,9 @@
-use crate::defs::{Direction, Language, Scene};
-use crate::game_state::GameState;
+use crate::defs::{Direction, Language, NpcId, Scene};
+use crate::game_state::{EXPORT_NOTICE_SECONDS, GAME_OVER_OPTION_COUNT, GameState};
+use crate::randomizer::Layout;
 use crate::scenes::menu::MenuSubState;
-use crate::system::User;
-use crate::texts::TextResources;
-use rand::Rng;
+use crate::system::{DeathCause, User};
+use crate::texts::{TextKey, TextResources};
 use tetra::Context;
 use tetra::Event;
 use tetra::input::Key;
@@ -15,14 +15,38 @@ pub fn handle_event(ctx: &mut Context, state: &mut GameState, event: Event) {
             handle_text_input(state, text);
         }
         Event::KeyPressed { key } => {
+            // Only editing keys reach the game while a text field is focused
+            if state.input_captured()
+                && !matches!(key, Key::Backspace | Key::Enter | Key::Escape | Key::Tab)
+            {
+                return;
+            }
             handle_key_pressed(ctx, state, key);
         }
+        Event::MouseMoved { position, .. } => {
+            state.cursor_pos = position;
+        }
+        Event::FocusGained => set_window_focused(ctx, state, true),
+        Event::FocusLost => set_window_focused(ctx, state, false),
         _ => {}
     }
 }
 
+/// The heart cursor only makes sense over the game, give the OS cursor back
+/// while another window has focus.
+fn set_window_focused(ctx: &mut Context, state: &mut GameState, focused: bool) {
+    state.window_focused = focused;
+    if let Err(e) = tetra::window::set_mouse_visible(ctx, !focused) {
+        log::warn!("Failed to toggle the OS cursor: {}", e);
+    }
+}
+
 fn handle_text_input(state: &mut GameState, text: String) {
-    if state.scene == Scene::Menu && state.menu_state.sub_state == MenuSubState::CreateSave {
+    // The R that opened Rename comes through as text right after
+    if std::mem::take(&mut state.menu_state.skip_rename_key) && text.eq_ignore_ascii_case("r") {
+        return;
+    }
+    if state.input_captured() {
         // Limit length to 32 chars
         if state.menu_state.input_buffer.len() < 32 {
             // Filter for safe characters (Alphanumeric + Space + Underscore + Hyphen)
@@ -38,12 +62,26 @@ fn handle_text_input(state: &mut GameState, text: String) {
 }
 
 fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
+    // Once the credits have rolled, any key leaves them
+    if state.scene == Scene::Credits && state.credits_state.finished() && !whats_new_open(state) {
+        state.scene = Scene::Menu;
+        state.menu_state.sub_state = MenuSubState::Main;
+        return;
+    }
+
+    // The asset report only takes its own keys
+    if state.asset_report.is_some()
+        && !matches!(
+            key,
+            Key::F4 | Key::Escape | Key::Up | Key::Down | Key::PageUp | Key::PageDown
+        )
+    {
+        return;
+    }
+
     match key {
-        Key::Backspace => {
-            if state.scene == Scene::Menu && state.menu_state.sub_state == MenuSubState::CreateSave
-            {
-                state.menu_state.input_buffer.pop();
-            }
+        Key::Backspace if state.input_captured() => {
+            state.menu_state.input_buffer.pop();
         }
         Key::Enter => {
             handle_enter_key(ctx, state);
@@ -51,11 +89,94 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
         Key::Escape => {
             handle_escape_key(state);
         }
+        Key::F3 => {
+            state.perf.overlay_visible = !state.perf.overlay_visible;
+        }
+        Key::F4 => {
+            crate::scenes::asset_report::toggle(state);
+        }
+        Key::Up if state.asset_report.is_some() => {
+            crate::scenes::asset_report::scroll_up(state, 1);
+        }
+        Key::Down if state.asset_report.is_some() => {
+            crate::scenes::asset_report::scroll_down(state, 1);
+        }
+        Key::PageUp if state.asset_report.is_some() => {
+            let rows = crate::scenes::asset_report::VISIBLE_ROWS;
+            crate::scenes::asset_report::scroll_up(state, rows);
+        }
+        Key::PageDown if state.asset_report.is_some() => {
+            let rows = crate::scenes::asset_report::VISIBLE_ROWS;
+            crate::scenes::asset_report::scroll_down(state, rows);
+        }
+        Key::M if crate::scenes::travel_map::can_open(state) => {
+            crate::scenes::travel_map::toggle(state);
+        }
+        Key::Left if state.world.map_open => {
+            crate::scenes::travel_map::move_selection(state, false);
+        }
+        Key::Right if state.world.map_open => {
+            crate::scenes::travel_map::move_selection(state, true);
+        }
+        Key::Tab if in_menu(state, MenuSubState::CreateSave) => {
+            state.menu_state.new_profile_hardcore = !state.menu_state.new_profile_hardcore;
+        }
+        Key::F2 if in_menu(state, MenuSubState::CreateSave) => {
+            state.menu_state.new_profile_randomizer = !state.menu_state.new_profile_randomizer;
+        }
+        Key::X if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::export_selected_profile(state);
+        }
+        Key::R if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::open_rename(state);
+        }
+        Key::I if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::open_import(state);
+        }
+        Key::S if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::cycle_profile_sort(state);
+        }
+        Key::Left | Key::PageUp if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::change_profile_page(state, false);
+        }
+        Key::Right | Key::PageDown if in_menu(state, MenuSubState::SaveSelect) => {
+            crate::scenes::menu::change_profile_page(state, true);
+        }
+        // L is Eilish's listen key while she's in reach
+        Key::L
+            if crate::scenes::dialogue_log::can_open(state)
+                && !crate::scenes::eilish::can_listen(state) =>
+        {
+            state.dialogue_log.toggle();
+        }
+        Key::PageUp if whats_new_open(state) => {
+            crate::scenes::whats_new::scroll_up(state, crate::scenes::whats_new::VISIBLE_ROWS);
+        }
+        Key::PageDown if whats_new_open(state) => {
+            crate::scenes::whats_new::scroll_down(state, crate::scenes::whats_new::VISIBLE_ROWS);
+        }
+        Key::PageUp if state.dialogue_log.open => {
+            state
+                .dialogue_log
+                .scroll_up(crate::scenes::dialogue_log::VISIBLE_ROWS);
+        }
+        Key::PageDown if state.dialogue_log.open => {
+            state
+                .dialogue_log
+                .scroll_down(crate::scenes::dialogue_log::VISIBLE_ROWS);
+        }
         Key::Up => {
-            if state.scene == Scene::Menu {
+            if whats_new_open(state) {
+                crate::scenes::whats_new::scroll_up(state, 1);
+            } else if state.dialogue_log.open {
+                state.dialogue_log.scroll_up(1);
+            } else if state.scene == Scene::KernelPanic {
+                let line_count = state.system.kernel_panic_lines.len();
+                state.game_over_state.scroll_panic_log(true, line_count);
+            } else if state.scene == Scene::Menu {
                 match state.menu_state.sub_state {
                     MenuSubState::Main => {
-                        let min_index = if state.system.users.is_empty() { 1 } else { 0 };
+                        let min_index = if state.system.can_start() { 0 } else { 1 };
                         if state.menu_state.selected_index > min_index {
                             state.menu_state.selected_index -= 1;
                         } else {
@@ -71,11 +192,19 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                             state.menu_state.selected_index = max_idx;
                         }
                     }
+                    MenuSubState::ImportProfile => {
+                        let count = state.menu_state.import_files.len();
+                        if state.menu_state.selected_index > 0 {
+                            state.menu_state.selected_index -= 1;
+                        } else if count > 0 {
+                            state.menu_state.selected_index = count - 1;
+                        }
+                    }
                     MenuSubState::Settings => {
                         if state.menu_state.selected_index > 0 {
                             state.menu_state.selected_index -= 1;
                         } else {
-                            state.menu_state.selected_index = 1; // 2 options (0, 1)
+                            state.menu_state.selected_index = 8; // 9 options (0-8)
                         }
                     }
                     _ => {}
@@ -83,10 +212,17 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
             }
         }
         Key::Down => {
-            if state.scene == Scene::Menu {
+            if whats_new_open(state) {
+                crate::scenes::whats_new::scroll_down(state, 1);
+            } else if state.dialogue_log.open {
+                state.dialogue_log.scroll_down(1);
+            } else if state.scene == Scene::KernelPanic {
+                let line_count = state.system.kernel_panic_lines.len();
+                state.game_over_state.scroll_panic_log(false, line_count);
+            } else if state.scene == Scene::Menu {
                 match state.menu_state.sub_state {
                     MenuSubState::Main => {
-                        let min_index = if state.system.users.is_empty() { 1 } else { 0 };
+                        let min_index = if state.system.can_start() { 0 } else { 1 };
                         if state.menu_state.selected_index < state.menu_state.options.len() - 1 {
                             state.menu_state.selected_index += 1;
                         } else {
@@ -101,8 +237,16 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                             state.menu_state.selected_index = 0;
                         }
                     }
+                    MenuSubState::ImportProfile => {
+                        let count = state.menu_state.import_files.len();
+                        if state.menu_state.selected_index + 1 < count {
+                            state.menu_state.selected_index += 1;
+                        } else {
+                            state.menu_state.selected_index = 0;
+                        }
+                    }
                     MenuSubState::Settings => {
-                        if state.menu_state.selected_index < 1 {
+                        if state.menu_state.selected_index < 9 {
                             state.menu_state.selected_index += 1;
                         } else {
                             state.menu_state.selected_index = 0;
@@ -113,8 +257,9 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
             }
         }
         Key::Left => {
-            if state.scene == Scene::KernelPanic {
-                state.game_over_state.selected_option = 1 - state.game_over_state.selected_option;
+            if state.scene == Scene::KernelPanic && !state.game_over_state.rebooting() {
+                let option = &mut state.game_over_state.selected_option;
+                *option = (*option + GAME_OVER_OPTION_COUNT - 1) % GAME_OVER_OPTION_COUNT;
             } else if state.scene == Scene::Menu && state.menu_state.sub_state == MenuSubState::Settings {
                 match state.menu_state.selected_index {
                     0 => {
@@ -129,6 +274,7 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                             Language::Turkish => TextResources::new_turkish(),
                         };
                         state.world.gaster_dialogues = state.texts.gaster_dialogues.clone();
+                        state.refresh_font(ctx);
                         state.system.save_global_settings();
                     }
                     1 => {
@@ -139,13 +285,58 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                         }
                         state.system.save_global_settings();
                     }
+                    2 => {
+                        // Difficulty
+                        state.system.hard_mode = !state.system.hard_mode;
+                        state.system.save_global_settings();
+                    }
+                    3 => {
+                        // Particles
+                        crate::scenes::menu::cycle_particle_level(state, false);
+                    }
+                    4 => {
+                        // Objective tracker
+                        let settings = &mut state.system.global_settings;
+                        settings.show_objectives = !settings.show_objectives;
+                        state.system.save_global_settings();
+                    }
+                    5 => {
+                        // Performance mode
+                        crate::scenes::menu::toggle_performance_mode(state);
+                    }
+                    6 => {
+                        // Auto-walk assist
+                        let settings = &mut state.system.global_settings;
+                        settings.auto_walk = !settings.auto_walk;
+                        state.system.save_global_settings();
+                    }
+                    7 => {
+                        // VSync
+                        let settings = &mut state.system.global_settings;
+                        settings.vsync = !settings.vsync;
+                        crate::frame_pacing::apply_vsync(ctx, settings.vsync);
+                        state.system.save_global_settings();
+                    }
+                    8 => {
+                        // FPS cap
+                        let settings = &mut state.system.global_settings;
+                        settings.fps_cap = crate::frame_pacing::next_cap(settings.fps_cap, false);
+                        state.system.save_global_settings();
+                    }
+                    9 => {
+                        // Combat tutorial
+                        let settings = &mut state.system.global_settings;
+                        settings.replay_combat_tutorial = !settings.replay_combat_tutorial;
+                        state.system.save_global_settings();
+                    }
                     _ => {}
                 }
             }
         }
         Key::Right => {
-            if state.scene == Scene::KernelPanic {
-                state.game_over_state.selected_option = 1 - state.game_over_state.selected_option;
+            if state.scene == Scene::KernelPanic && !state.game_over_state.rebooting() {
+                let option = &mut state.game_over_state.selected_option;
+                *option = (*option + 1) % GAME_OVER_OPTION_COUNT;
             } else if state.scene == Scene::Menu && state.menu_state.sub_state == MenuSubState::Settings {
                 match state.menu_state.selected_index {
                     0 => {
@@ -160,6 +351,7 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                             Language::Turkish => TextResources::new_turkish(),
                         };
                         state.world.gaster_dialogues = state.texts.gaster_dialogues.clone();
+                        state.refresh_font(ctx);
                         state.system.save_global_settings();
                     }
                     1 => {
@@ -170,6 +362,50 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
                         }
                         state.system.save_global_settings();
                     }
+                    2 => {
+                        // Difficulty
+                        state.system.hard_mode = !state.system.hard_mode;
+                        state.system.save_global_settings();
+                    }
+                    3 => {
+                        // Particles
+                        crate::scenes::menu::cycle_particle_level(state, true);
+                    }
+                    4 => {
+                        // Objective tracker
+                        let settings = &mut state.system.global_settings;
+                        settings.show_objectives = !settings.show_objectives;
+                        state.system.save_global_settings();
+                    }
+                    5 => {
+                        // Performance mode
+                        crate::scenes::menu::toggle_performance_mode(state);
+                    }
+                    6 => {
+                        // Auto-walk assist
+                        let settings = &mut state.system.global_settings;
+                        settings.auto_walk = !settings.auto_walk;
+                        state.system.save_global_settings();
+                    }
+                    7 => {
+                        // VSync
+                        let settings = &mut state.system.global_settings;
+                        settings.vsync = !settings.vsync;
+                        crate::frame_pacing::apply_vsync(ctx, settings.vsync);
+                        state.system.save_global_settings();
+                    }
+                    8 => {
+                        // FPS cap
+                        let settings = &mut state.system.global_settings;
+                        settings.fps_cap = crate::frame_pacing::next_cap(settings.fps_cap, true);
+                        state.system.save_global_settings();
+                    }
+                    9 => {
+                        // Combat tutorial
+                        let settings = &mut state.system.global_settings;
+                        settings.replay_combat_tutorial = !settings.replay_combat_tutorial;
+                        state.system.save_global_settings();
+                    }
                     _ => {}
                 }
             }
@@ -178,9 +414,64 @@ fn handle_key_pressed(ctx: &mut Context, state: &mut GameState, key: Key) {
     }
 }
 
+/// The "What's new" panel takes the menu keys while it's up.
+fn whats_new_open(state: &GameState) -> bool {
+    state.whats_new.open && crate::scenes::whats_new::can_open(state)
+}
+
+fn in_menu(state: &GameState, sub_state: MenuSubState) -> bool {
+    state.scene == Scene::Menu && state.menu_state.sub_state == sub_state && !whats_new_open(state)
+}
+
 fn handle_escape_key(state: &mut GameState) {
+    if state.asset_report.is_some() {
+        state.asset_report = None;
+        return;
+    }
+    if whats_new_open(state) {
+        crate::scenes::whats_new::close(state);
+        return;
+    }
+    if state.dialogue_log.open {
+        state.dialogue_log.toggle();
+        return;
+    }
+    if state.world.map_open {
+        state.world.map_open = false;
+        return;
+    }
+
     match state.scene {
+        Scene::Desktop if state.world.eilish_listening => {
+            crate::scenes::eilish::skip_song(state);
+        }
+        Scene::Desktop if state.world.sans_dialogue.is_some() => {
+            crate::scenes::sans::close_dialogue(state);
+        }
+        Scene::Desktop if state.world.gaster_dialogue.is_some() => {
+            crate::scenes::gaster::close_dialogue(state);
+        }
         Scene::Desktop => {
+            // Keep dead space exposure with the profile
+            // A chase in progress picks up where it left off on the next load
+            let chase_left = if crate::scenes::sans::is_chasing(state) {
+                state.world.sans_chase_timer
+            } else {
+                0.0
+            };
+            if let Some(user) = &mut state.system.current_user {
+                user.exposure = state.player.exposure;
+                user.sans_chase = chase_left;
+                user.music_on = state.world.music_playing;
+            }
+            state.system.save_users();
+            state.world.stop_music();
+            state.player.stop_auto_walk();
+            state.menu_state.sub_state = MenuSubState::Main;
+            crate::scenes::session_summary::open(state);
+        }
+        Scene::SessionSummary => crate::scenes::session_summary::close(state),
+        Scene::Credits | Scene::Statistics => {
             state.scene = Scene::Menu;
             state.menu_state.sub_state = MenuSubState::Main;
         }
@@ -190,11 +481,28 @@ fn handle_escape_key(state: &mut GameState) {
                     state.scene = Scene::Desktop;
                 }
             }
-            MenuSubState::SaveSelect | MenuSubState::Settings | MenuSubState::Credits => {
+            MenuSubState::SaveSelect | MenuSubState::Settings => {
                 state.menu_state.sub_state = MenuSubState::Main;
                 state.menu_state.selected_index = 0;
+                state.menu_state.error_message = None;
+            }
+            MenuSubState::Leaderboard => {
+                state.menu_state.sub_state = MenuSubState::Main;
+                state.menu_state.leaderboard.clear();
+            }
+            MenuSubState::ImportProfile => {
+                state.menu_state.sub_state = MenuSubState::SaveSelect;
+                state.menu_state.selected_index = 0;
+                state.menu_state.error_message = None;
             }
             MenuSubState::CreateSave => {
+                state.menu_state.sub_state = MenuSubState::SaveSelect;
+                state.menu_state.input_buffer.clear();
+                state.menu_state.new_profile_hardcore = false;
+                state.menu_state.new_profile_randomizer = false;
+                state.menu_state.error_message = None;
+            }
+            MenuSubState::RenameProfile => {
                 state.menu_state.sub_state = MenuSubState::SaveSelect;
                 state.menu_state.input_buffer.clear();
                 state.menu_state.error_message = None;
@@ -205,8 +513,20 @@ fn handle_escape_key(state: &mut GameState) {
 }
 
 fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
+    if whats_new_open(state) {
+        crate::scenes::whats_new::close(state);
+        return;
+    }
+    if state.world.map_open {
+        crate::scenes::travel_map::confirm(state);
+        return;
+    }
+
     match state.scene {
         Scene::Menu => {
+            // Any confirmation dismisses the backup restore notice
+            state.system.restore_notice = None;
+
             match state.menu_state.sub_state {
                 MenuSubState::Main => {
                     match state.menu_state.selected_index {
@@ -215,7 +535,7 @@ fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
                             if state.system.users.is_empty() {
                                 state.menu_state.sub_state = MenuSubState::CreateSave;
                                 state.menu_state.input_buffer.clear();
-                            } else {
+                            } else if state.system.can_start() {
                                 // Use top user
                                 state.system.current_user = Some(state.system.users[0].clone());
                                 start_game(state);
@@ -237,9 +557,17 @@ fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
                         }
                         4 => {
                             // Credits
-                            state.menu_state.sub_state = MenuSubState::Credits;
+                            state.scene = Scene::Credits;
+                            state.credits_state = crate::scenes::credits::CreditsState::new();
                         }
                         5 => {
+                            // Statistics
+                            state.scene = Scene::Statistics;
+                        }
+                        6 => {
+                            crate::scenes::menu::open_leaderboard(state);
+                        }
+                        7 => {
                             // Exit
                             std::process::exit(0);
                         }
@@ -247,12 +575,9 @@ fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
                     }
                 }
                 MenuSubState::SaveSelect => {
-                    let users_len = state.system.users.len();
-                    if state.menu_state.selected_index < users_len {
+                    if let Some(index) = crate::scenes::menu::selected_profile(state) {
                         // Select existing user and move to top
-                        state
-                            .system
-                            .set_user_as_top(state.menu_state.selected_index);
+                        state.system.set_user_as_top(index);
                         state.system.current_user = Some(state.system.users[0].clone());
                         state.menu_state.sub_state = MenuSubState::Main;
                         state.menu_state.selected_index = 0;
@@ -261,33 +586,52 @@ fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
                         state.menu_state.sub_state = MenuSubState::Main;
                         state.menu_state.selected_index = 0;
                     }
+                    state.menu_state.error_message = None;
+                }
+                MenuSubState::ImportProfile => {
+                    crate::scenes::menu::import_selected_profile(state);
+                }
+                MenuSubState::RenameProfile => {
+                    crate::scenes::menu::confirm_rename(state);
                 }
                 MenuSubState::CreateSave => {
                     let name = state.menu_state.input_buffer.trim().to_string();
-                    let lower_name = name.to_lowercase();
 
-                    if name.is_empty() {
-                        state.menu_state.error_message = Some("Name cannot be empty".to_string());
-                    } else if lower_name == "gece" || lower_name == "gecee" {
-                        let warnings = [
-                            "Bu isim yasaklı bölgede.",
-                            "Gece çöktü, ama bu isim olmaz.",
-                            "Başka bir isim dene, karanlık yolcu.",
-                            "Sistem bu ismi reddediyor.",
-                        ];
-                        let mut rng = rand::rng();
-                        state.menu_state.error_message =
-                            Some(warnings[rng.random_range(0..warnings.len())].to_string());
-                    } else if state.system.users.iter().any(|u| u.username == name) {
-                        state.menu_state.error_message = Some("Name already exists".to_string());
+                    if let Err(e) = state.system.check_username(&name, None) {
+                        state.menu_state.error_message = Some(e);
+                    } else if state.system.users.len() >= crate::scenes::menu::MAX_PROFILES {
+                        state.menu_state.error_message = Some(state.texts.fill(
+                            TextKey::ProfileLimit,
+                            &[("max", &crate::scenes::menu::MAX_PROFILES)],
+                        ));
                     } else {
+                        // Shuffled once here, the profile keeps this world for good
+                        let randomizer = state
+                            .menu_state
+                            .new_profile_randomizer
+                            .then(|| Layout::generate(rand::random()));
                         let new_user = User {
                             username: name,
                             teblig_count: 0,
                             cihad_count: 0,
                             tekfir_count: 0,
-                            current_stage: 1,
+                            current_stage: randomizer
+                                .as_ref()
+                                .map_or(1, |l| l.first_stage() as u32),
+                            exposure: 0.0,
+                            sans_chase: 0.0,
+                            flags: 0,
+                            music_on: false,
+                            outfit: 0,
+                            playtime: 0.0,
+                            hardcore: state.menu_state.new_profile_hardcore,
+                            locked: false,
+                            deaths: [0; DeathCause::COUNT],
+                            visited_stages: 0,
+                            randomizer,
                         };
+                        state.menu_state.new_profile_hardcore = false;
+                        state.menu_state.new_profile_randomizer = false;
                         state.system.users.insert(0, new_user); // Insert at top
                         state.system.save_users();
                         state.system.current_user = Some(state.system.users[0].clone());
@@ -301,31 +645,92 @@ fn handle_enter_key(_ctx: &mut Context, state: &mut GameState) {
                 _ => {}
             }
         }
-        Scene::KernelPanic => {
-            if state.game_over_state.selected_option == 0 {
-                // Return to Menu
-                state.scene = Scene::Menu;
+        Scene::KernelPanic if state.game_over_state.rebooting() => {}
+        Scene::KernelPanic => match state.game_over_state.selected_option {
+            0 => {
+                // Return to Menu, by way of a reboot and the boot screen
+                lock_after_death(state);
+                state.game_over_state.start_reboot();
                 state.menu_state.sub_state = MenuSubState::Main;
-            } else {
+                state.session_stats.summary_after_boot = true;
+            }
+            1 => {
+                // Export Report
+                let notice = match crate::logging::append_lines(&state.system.kernel_panic_lines) {
+                    Ok(path) => {
+                        log::info!("Exported panic report to {}", path.display());
+                        format!("{}{}", state.texts.ui.report_exported, path.display())
+                    }
+                    Err(e) => {
+                        log::error!("Failed to export panic report: {}", e);
+                        state.texts.ui.report_failed.clone()
+                    }
+                };
+                state.game_over_state.export_notice = Some(notice);
+                state.game_over_state.export_notice_timer = EXPORT_NOTICE_SECONDS;
+            }
+            _ => {
                 // Quit Game
+                lock_after_death(state);
                 std::process::exit(0);
             }
-        }
+        },
+        Scene::Credits => crate::scenes::whats_new::open(state),
+        Scene::SessionSummary => crate::scenes::session_summary::close(state),
         _ => {}
     }
 }
 
+/// A death on a hardcore profile is final. Written before the menu comes
+/// back, so the locked profile can't be started again.
+fn lock_after_death(state: &mut GameState) {
+    if state.game_over_state.died && state.system.lock_current_user() {
+        state.session_started = false;
+        state.menu_state.selected_index = 1;
+    }
+}
+
 fn start_game(state: &mut GameState) {
     state.scene = Scene::TransitionToDesktop;
     state.transition_timer = 0.0;
     state.session_started = true;
+    state.session_stats.start();
+    state.dialogue_log.clear();
     // Reset game state on start
-    state.player.health = 100.0;
+    state.player.player_hp = state.player.player_max_hp;
+    state.player.damage_tick = 0;
+    state.player.pending_damage = 0.0;
+    state.player.invuln_timer = 0.0;
+    state.world.edge_bark_shown = false;
+    state.world.gaster_hat_remarked = false;
 
+    let mut chase_left = 0.0;
+    let mut stage = 1;
     if let Some(user) = &state.system.current_user {
-        state.world.current_stage = user.current_stage as u8;
+        stage = user.current_stage as u8;
+        state.player.exposure = user.exposure;
+        state.world.flags = user.flags;
+        chase_left = user.sans_chase;
+        state.world.music_resume = user.music_on;
+        state.player.owned_outfit = user.outfit;
+        state.world.layout = user.randomizer.clone().unwrap_or_else(Layout::standard);
     } else {
-        state.world.current_stage = 1;
+        state.world.layout = Layout::standard();
+        state.player.exposure = 0.0;
+        state.world.flags = 0;
+        state.world.music_resume = false;
+        state.player.owned_outfit = 0;
+    }
+    state.player.outfit = state.player.owned_outfit;
+    crate::scenes::rescue::apply_gas_mask(state);
+    state.world.objective_banner = None;
+    state.world.reset_for_stage(stage);
+    state.player.player_fade_alpha = 0.0;
+    crate::scenes::travel_map::mark_visited(state);
+
+    // Chases only happen in Sans's stage, and the edges are locked while one runs
+    if chase_left > 0.0 && state.world.in_current_stage(NpcId::Sans) {
+        crate::scenes::sans::start_chase(state, chase_left);
     }
 
     state.player.pos = Vec2::new(400.0, 300.0);
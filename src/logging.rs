@@ -0,0 +1,123 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+use crate::paths;
+
+const LOG_FILE: &str = "gorkitale.log";
+const KEPT_RUNS: usize = 3; // Current run plus the two before it
+
+static LOGGER: OnceLock<FileLogger> = OnceLock::new();
+
+/// Writes every record to stdout and to `<data_dir>/logs/gorkitale.log`.
+struct FileLogger {
+    file: Mutex<Option<File>>,
+    started: Instant,
+}
+
+impl FileLogger {
+    fn write_lines(&self, lines: &[String]) -> std::io::Result<()> {
+        let Ok(mut file) = self.file.lock() else {
+            return Ok(());
+        };
+        if let Some(file) = file.as_mut() {
+            for line in lines {
+                writeln!(file, "{}", line)?;
+            }
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+impl Log for FileLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = format!(
+            "[{:>10.3}] {:<5} {}: {}",
+            self.started.elapsed().as_secs_f32(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        println!("{}", line);
+        let _ = self.write_lines(&[line]);
+    }
+
+    fn flush(&self) {}
+}
+
+pub fn log_dir() -> PathBuf {
+    paths::data_dir().join("logs")
+}
+
+pub fn log_path() -> PathBuf {
+    log_dir().join(LOG_FILE)
+}
+
+/// Path of an older run's log, 1 being the previous run.
+fn rotated_path(run: usize) -> PathBuf {
+    log_dir().join(format!("gorkitale.{}.log", run))
+}
+
+/// Shifts older logs down one slot, dropping the oldest, so a fresh file
+/// is started for this run.
+fn rotate() {
+    let _ = fs::remove_file(rotated_path(KEPT_RUNS - 1));
+    for run in (1..KEPT_RUNS - 1).rev() {
+        let _ = fs::rename(rotated_path(run), rotated_path(run + 1));
+    }
+    let _ = fs::rename(log_path(), rotated_path(1));
+}
+
+/// Starts logging to a new file. Needs the data directory, so call it after
+/// `paths::init`. `verbose` also lets debug records through.
+pub fn init(verbose: bool) {
+    let file = if fs::create_dir_all(log_dir()).is_ok() {
+        rotate();
+        File::create(log_path()).ok()
+    } else {
+        None
+    };
+    let no_file = file.is_none();
+
+    let logger = LOGGER.get_or_init(|| FileLogger {
+        file: Mutex::new(file),
+        started: Instant::now(),
+    });
+    if log::set_logger(logger).is_ok() {
+        log::set_max_level(if verbose {
+            LevelFilter::Debug
+        } else {
+            LevelFilter::Info
+        });
+    }
+
+    if no_file {
+        log::warn!(
+            "Could not open '{}', logging to stdout only",
+            log_path().display()
+        );
+    }
+}
+
+/// Appends lines to the current log as-is, without the record prefix.
+/// Returns the log file's path.
+pub fn append_lines(lines: &[String]) -> std::io::Result<PathBuf> {
+    match LOGGER.get() {
+        Some(logger) => logger.write_lines(lines)?,
+        None => return Err(std::io::Error::other("logging isn't initialized")),
+    }
+    Ok(log_path())
+}
@@ -1,13 +1,39 @@
+mod actions;
+mod asset_check;
 mod assets;
+mod audio;
+mod bench;
+mod changelog;
 mod combat;
+mod crash;
 mod defs;
+mod dialogue;
+mod dialogue_log;
 mod discord_rpc;
+mod easing;
+mod fonts;
+mod frame_pacing;
 mod game_state;
 mod global_db;
+mod graphics_quality;
+mod highscores;
 mod input_handler;
+mod logging;
+mod objectives;
+mod particles;
+mod paths;
+mod perf;
 mod physics;
 mod player;
+mod pool;
+mod profile_file;
+mod randomizer;
+mod render_util;
 mod scenes;
+mod session_stats;
+mod sim;
+mod storage;
+mod subtitles;
 mod system;
 mod texts;
 mod world;
@@ -15,10 +41,34 @@ mod world;
 use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH};
 use crate::game_state::GameState;
 use tetra::ContextBuilder;
+use tetra::time::Timestep;
 
 fn main() -> tetra::Result {
+    let portable = std::env::args().any(|arg| arg == "--portable");
+    let verbose = std::env::args().any(|arg| arg == "--verbose");
+    crate::paths::init(portable);
+    crate::logging::init(verbose);
+    crate::crash::install_panic_hook();
+    if std::env::args().any(|arg| arg == "--bench-combat") {
+        crate::bench::run_combat();
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--check-text-draws") {
+        crate::bench::check_text_draws();
+        return Ok(());
+    }
+    if std::env::args().any(|arg| arg == "--verify-assets") {
+        let passed = crate::asset_check::run_cli();
+        std::process::exit(if passed { 0 } else { 1 });
+    }
+    log::info!("Data directory: {}", crate::paths::data_dir().display());
+    if !portable {
+        crate::paths::migrate_from_cwd();
+    }
+
     ContextBuilder::new("Gorkitale", SCREEN_WIDTH, SCREEN_HEIGHT)
         .quit_on_escape(false)
+        .timestep(Timestep::Fixed(crate::frame_pacing::TICK_RATE))
         .build()?
         .run(GameState::new)
 }
@@ -0,0 +1,87 @@
+/// Milestones reached in a save. Stored as bits in users.db, so only ever
+/// append to this list.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum WorldFlag {
+    FoundMusicBox,
+    MetSans,
+    ReachedColdDistrict,
+    EnteredAyasofya,
+    FacedRedZone,
+    DefeatedSans,
+    MissedTenBones, // Joke achievement for the bone throw
+    // Also means the player has the gas mask
+    RescuedSurvivor,
+    CombatTutorialDone,
+}
+
+impl WorldFlag {
+    pub fn bit(self) -> u32 {
+        1 << self as u32
+    }
+
+    pub fn is_set(self, flags: u32) -> bool {
+        flags & self.bit() != 0
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ObjectiveId {
+    FindMusicBox,
+    TalkToSkeleton,
+    ReachColdDistrict,
+    EnterAyasofya,
+    ConfrontRedZone,
+}
+
+pub struct ObjectiveDef {
+    pub id: ObjectiveId,
+    pub requires: &'static [WorldFlag], // All set before it shows up
+    pub completed_by: WorldFlag,
+}
+
+impl ObjectiveDef {
+    pub fn is_complete(&self, flags: u32) -> bool {
+        self.completed_by.is_set(flags)
+    }
+
+    pub fn is_available(&self, flags: u32) -> bool {
+        self.requires.iter().all(|flag| flag.is_set(flags))
+    }
+}
+
+/// In the order the tracker suggests them.
+pub const OBJECTIVES: [ObjectiveDef; 5] = [
+    ObjectiveDef {
+        id: ObjectiveId::FindMusicBox,
+        requires: &[],
+        completed_by: WorldFlag::FoundMusicBox,
+    },
+    ObjectiveDef {
+        id: ObjectiveId::TalkToSkeleton,
+        requires: &[WorldFlag::FoundMusicBox],
+        completed_by: WorldFlag::MetSans,
+    },
+    ObjectiveDef {
+        id: ObjectiveId::ReachColdDistrict,
+        requires: &[WorldFlag::MetSans],
+        completed_by: WorldFlag::ReachedColdDistrict,
+    },
+    ObjectiveDef {
+        id: ObjectiveId::EnterAyasofya,
+        requires: &[WorldFlag::ReachedColdDistrict],
+        completed_by: WorldFlag::EnteredAyasofya,
+    },
+    ObjectiveDef {
+        id: ObjectiveId::ConfrontRedZone,
+        requires: &[WorldFlag::EnteredAyasofya],
+        completed_by: WorldFlag::FacedRedZone,
+    },
+];
+
+/// The first objective that's unlocked and not done yet. Skipping ahead
+/// completes later ones early, the tracker still points at the earliest gap.
+pub fn current(flags: u32) -> Option<&'static ObjectiveDef> {
+    OBJECTIVES
+        .iter()
+        .find(|def| !def.is_complete(flags) && def.is_available(flags))
+}
@@ -0,0 +1,180 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use tetra::Context;
+use tetra::graphics::mesh::{GeometryBuilder, ShapeStyle};
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::graphics_quality::GraphicsQuality;
+use crate::pool::Pool;
+
+/// How a burst looks and moves. Every burst in the game picks one of these
+/// instead of carrying its own numbers.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum ParticleStyle {
+    GrazeSpark, // Off the heart when a bone passes close
+    Stab,       // Rarity's blood
+    Sparkle,    // Picking something up
+    SnowPuff,   // Footsteps in the snow stage
+}
+
+struct StyleDef {
+    spread: f32,  // Max speed on each axis, pixels per second
+    rise: f32,    // Added upwards on top of the spread
+    gravity: f32, // Pixels per second squared, negative floats up
+    life: f32,    // Seconds
+    size: f32,
+    color: Color, // Alpha at spawn, fades to 0 over the life
+}
+
+impl ParticleStyle {
+    fn def(self) -> StyleDef {
+        match self {
+            Self::GrazeSpark => StyleDef {
+                spread: 60.0,
+                rise: 0.0,
+                gravity: 0.0,
+                life: 0.4,
+                size: 3.0,
+                color: Color::rgba(1.0, 1.0, 0.4, 0.6),
+            },
+            Self::Stab => StyleDef {
+                spread: 70.0,
+                rise: 40.0,
+                gravity: 300.0,
+                life: 0.5,
+                size: 4.0,
+                color: Color::rgba(0.8, 0.0, 0.0, 0.9),
+            },
+            Self::Sparkle => StyleDef {
+                spread: 45.0,
+                rise: 20.0,
+                gravity: -30.0,
+                life: 0.8,
+                size: 3.0,
+                color: Color::rgba(1.0, 0.85, 0.3, 0.9),
+            },
+            Self::SnowPuff => StyleDef {
+                spread: 20.0,
+                rise: 10.0,
+                gravity: 0.0,
+                life: 0.5,
+                size: 4.0,
+                color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+            },
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Particle {
+    pub pos: Vec2<f32>,
+    pub velocity: Vec2<f32>,
+    pub gravity: f32,
+    pub life: f32, // Seconds left
+    pub max_life: f32,
+    pub size: f32,
+    pub color: Color,
+}
+
+impl Particle {
+    /// Square at `pos`, faded by how much life is left.
+    pub fn rect(&self) -> (Rectangle, Color) {
+        let alpha = self.color.a * self.life / self.max_life;
+        (
+            Rectangle::new(self.pos.x, self.pos.y, self.size, self.size),
+            self.color.with_alpha(alpha),
+        )
+    }
+}
+
+/// Short-lived bursts in a fixed pool, a burst past the cap is cut short
+/// instead of allocating. Velocities come from the emitter's own seeded RNG,
+/// so the same seed and the same bursts always move the same way.
+pub struct Particles {
+    pool: Pool<Particle>,
+    capacity: usize,
+    rng: StdRng,
+}
+
+impl Particles {
+    pub fn new(capacity: usize, seed: u64) -> Self {
+        Self {
+            pool: Pool::new(capacity),
+            capacity,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// `count` particles of `style` from `pos`. Performance mode scales the
+    /// count down and only lets the bursts fill part of the pool.
+    pub fn spawn_burst(
+        &mut self,
+        pos: Vec2<f32>,
+        count: usize,
+        style: ParticleStyle,
+        quality: GraphicsQuality,
+    ) {
+        let def = style.def();
+        let cap = quality.particle_count(self.capacity);
+        for _ in 0..quality.particle_count(count) {
+            if self.pool.len() >= cap {
+                break;
+            }
+            let spread = def.spread;
+            let velocity = Vec2::new(
+                self.rng.random_range(-spread..=spread),
+                self.rng.random_range(-spread..=spread) - def.rise,
+            );
+            self.pool.spawn(Particle {
+                pos,
+                velocity,
+                gravity: def.gravity,
+                life: def.life,
+                max_life: def.life,
+                size: def.size,
+                color: def.color,
+            });
+        }
+    }
+
+    pub fn update(&mut self, dt: f32) {
+        self.pool.retain_mut(|particle| {
+            particle.velocity.y += particle.gravity * dt;
+            particle.pos += particle.velocity * dt;
+            particle.life -= dt;
+            particle.life > 0.0
+        });
+    }
+
+    pub fn clear(&mut self) {
+        self.pool.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.pool.len()
+    }
+
+    pub fn rects(&self) -> impl Iterator<Item = (Rectangle, Color)> + '_ {
+        self.pool.iter().map(Particle::rect)
+    }
+}
+
+/// Every square in one mesh, ambient particles and bursts alike, a mesh per
+/// particle costs more than drawing them.
+pub fn draw_batch(
+    ctx: &mut Context,
+    rects: impl IntoIterator<Item = (Rectangle, Color)>,
+) -> tetra::Result {
+    let mut builder = GeometryBuilder::new();
+    let mut empty = true;
+    for (rect, color) in rects {
+        builder.set_color(color);
+        builder.rectangle(ShapeStyle::Fill, rect)?;
+        empty = false;
+    }
+    if !empty {
+        builder.build_mesh(ctx)?.draw(ctx, DrawParams::new());
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Resolves the data directory once at startup. With `portable` everything
+/// stays in the working directory like older versions did.
+pub fn init(portable: bool) {
+    let dir = if portable {
+        PathBuf::from(".")
+    } else {
+        resolve_data_dir()
+    };
+    let _ = DATA_DIR.set(dir);
+}
+
+/// XDG data dir on Linux, %APPDATA% on Windows, otherwise next to the executable.
+/// Tests get a temporary one so they never touch real saves.
+fn resolve_data_dir() -> PathBuf {
+    if cfg!(test) {
+        let dir = std::env::temp_dir().join(format!("gorkitale-data-{}", std::process::id()));
+        let _ = fs::create_dir_all(&dir);
+        return dir;
+    }
+    if let Some(base) = dirs::data_dir() {
+        let dir = base.join("gorkitale");
+        if fs::create_dir_all(&dir).is_ok() {
+            return dir;
+        }
+    }
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+pub fn data_dir() -> &'static Path {
+    DATA_DIR.get_or_init(resolve_data_dir)
+}
+
+pub fn data_path(file_name: &str) -> PathBuf {
+    data_dir().join(file_name)
+}
+
+/// Copies saves left in the working directory by older versions into the data
+/// directory, then leaves a `<file>.migrated` marker so it only happens once.
+/// Runs after logging is set up so the result ends up in the log.
+pub fn migrate_from_cwd() {
+    let dir = data_dir();
+    for name in ["users.db", "global.db"] {
+        let old_path = PathBuf::from(name);
+        let marker = PathBuf::from(format!("{}.migrated", name));
+        let new_path = dir.join(name);
+
+        if !old_path.exists() || marker.exists() || new_path.exists() {
+            continue;
+        }
+
+        match fs::copy(&old_path, &new_path) {
+            Ok(_) => {
+                log::info!("Migrated '{}' to '{}'", name, new_path.display());
+                let _ = fs::write(&marker, new_path.display().to_string());
+            }
+            Err(e) => log::warn!("Failed to migrate '{}': {}", name, e),
+        }
+    }
+}
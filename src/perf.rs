@@ -0,0 +1,195 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use tetra::Context;
+use tetra::graphics::mesh::{GeometryBuilder, Mesh, ShapeStyle};
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{Color, DrawParams, Rectangle, Texture};
+use tetra::math::Vec2;
+
+use crate::defs::SCREEN_WIDTH;
+use crate::graphics_quality::GraphicsQuality;
+
+const FRAME_HISTORY: usize = 240;
+const TARGET_MS: f32 = 1000.0 / 60.0;
+const SLOW_MS: f32 = 1000.0 / 30.0;
+const GRAPH_HEIGHT: f32 = 60.0;
+const GRAPH_MAX_MS: f32 = 50.0; // Anything slower is clipped to the top
+
+static DRAW_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Draw wrapper for textures and meshes that counts the call.
+pub trait Render {
+    fn render<P: Into<DrawParams>>(&self, ctx: &mut Context, params: P);
+}
+
+/// Same as `Render`, for text which needs `&mut self` to lay itself out.
+pub trait RenderMut {
+    fn render<P: Into<DrawParams>>(&mut self, ctx: &mut Context, params: P);
+}
+
+impl Render for Texture {
+    fn render<P: Into<DrawParams>>(&self, ctx: &mut Context, params: P) {
+        DRAW_CALLS.fetch_add(1, Ordering::Relaxed);
+        self.draw(ctx, params);
+    }
+}
+
+impl Render for Mesh {
+    fn render<P: Into<DrawParams>>(&self, ctx: &mut Context, params: P) {
+        DRAW_CALLS.fetch_add(1, Ordering::Relaxed);
+        self.draw(ctx, params);
+    }
+}
+
+impl RenderMut for Text {
+    fn render<P: Into<DrawParams>>(&mut self, ctx: &mut Context, params: P) {
+        DRAW_CALLS.fetch_add(1, Ordering::Relaxed);
+        self.draw(ctx, params);
+    }
+}
+
+/// Entity counts sampled from the game state each frame.
+#[derive(Default, Clone, Copy)]
+pub struct EntityCounts {
+    pub particles: usize,
+    pub bones: usize,
+    pub sparks: usize,
+    pub crowd: usize,
+    pub toasts: usize, // Notes over the player's head, see scenes::bone::show_toast
+    pub textures: usize,
+    pub sounds: usize,
+}
+
+/// Frame timing and draw stats. Collected every frame whether or not the
+/// overlay is showing, and the overlay's own draws are left out, so
+/// toggling it doesn't change the numbers.
+pub struct PerfStats {
+    pub overlay_visible: bool,
+    frame_times: [f32; FRAME_HISTORY], // Milliseconds, ring buffer
+    head: usize,                       // Slot the next frame goes into
+    pub draw_calls: u32,               // Issued during the last finished frame
+    pub counts: EntityCounts,
+}
+
+impl PerfStats {
+    pub fn new() -> Self {
+        Self {
+            overlay_visible: false,
+            frame_times: [0.0; FRAME_HISTORY],
+            head: 0,
+            draw_calls: 0,
+            counts: EntityCounts::default(),
+        }
+    }
+
+    /// Call before the scene draws.
+    pub fn begin_frame(&mut self, ctx: &Context) {
+        self.frame_times[self.head] = tetra::time::get_delta_time(ctx).as_secs_f32() * 1000.0;
+        self.head = (self.head + 1) % FRAME_HISTORY;
+        DRAW_CALLS.store(0, Ordering::Relaxed);
+    }
+
+    /// Call after the scene draws, before the overlay.
+    pub fn end_frame(&mut self, counts: EntityCounts) {
+        self.draw_calls = DRAW_CALLS.load(Ordering::Relaxed);
+        self.counts = counts;
+    }
+
+    /// Frame times from oldest to newest.
+    pub fn frame_times(&self) -> impl Iterator<Item = f32> + '_ {
+        self.frame_times[self.head..]
+            .iter()
+            .chain(&self.frame_times[..self.head])
+            .copied()
+    }
+
+    pub fn draw_overlay(
+        &self,
+        ctx: &mut Context,
+        font: &Font,
+        font_name: &str,
+        quality: GraphicsQuality,
+        fps_target: &str,
+    ) -> tetra::Result {
+        if !self.overlay_visible {
+            return Ok(());
+        }
+
+        let x = SCREEN_WIDTH as f32 - FRAME_HISTORY as f32 - 10.0;
+        let y = 10.0;
+        let ms_to_px = GRAPH_HEIGHT / GRAPH_MAX_MS;
+
+        // One mesh for the whole graph, a mesh per bar would skew the numbers we're showing
+        let mut builder = GeometryBuilder::new();
+        builder.set_color(Color::rgba(0.0, 0.0, 0.0, 0.7));
+        builder.rectangle(
+            ShapeStyle::Fill,
+            Rectangle::new(x, y, FRAME_HISTORY as f32, GRAPH_HEIGHT),
+        )?;
+        for (i, ms) in self.frame_times().enumerate() {
+            let color = if ms > SLOW_MS {
+                Color::RED
+            } else if ms > TARGET_MS {
+                Color::rgb(1.0, 1.0, 0.0)
+            } else {
+                Color::GREEN
+            };
+            let height = (ms * ms_to_px).min(GRAPH_HEIGHT);
+            builder.set_color(color);
+            builder.rectangle(
+                ShapeStyle::Fill,
+                Rectangle::new(x + i as f32, y + GRAPH_HEIGHT - height, 1.0, height),
+            )?;
+        }
+        for guide_ms in [TARGET_MS, SLOW_MS] {
+            builder.set_color(Color::rgba(1.0, 1.0, 1.0, 0.5));
+            builder.rectangle(
+                ShapeStyle::Fill,
+                Rectangle::new(
+                    x,
+                    y + GRAPH_HEIGHT - guide_ms * ms_to_px,
+                    FRAME_HISTORY as f32,
+                    1.0,
+                ),
+            )?;
+        }
+        builder.build_mesh(ctx)?.draw(ctx, DrawParams::new());
+
+        let last_ms = self.frame_times[(self.head + FRAME_HISTORY - 1) % FRAME_HISTORY];
+        let worst_ms = self.frame_times().fold(0.0, f32::max);
+        let lines = [
+            format!("Frame: {:.1} ms (worst {:.1})", last_ms, worst_ms),
+            format!(
+                "FPS: {:.0} (target {})",
+                tetra::time::get_fps(ctx),
+                fps_target
+            ),
+            format!("Draw calls: {}", self.draw_calls),
+            format!("Graphics: {}", quality.label()),
+            format!("Font: {}", font_name),
+            format!(
+                "Textures: {}  Sounds: {}",
+                self.counts.textures, self.counts.sounds
+            ),
+            format!(
+                "Particles: {}  Bones: {}",
+                self.counts.particles, self.counts.bones
+            ),
+            format!(
+                "Sparks: {}  Crowd: {}  Toasts: {}",
+                self.counts.sparks, self.counts.crowd, self.counts.toasts
+            ),
+        ];
+        for (i, line) in lines.iter().enumerate() {
+            let mut text = Text::new(line.as_str(), font.clone());
+            text.draw(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(x, y + GRAPH_HEIGHT + 5.0 + i as f32 * 20.0))
+                    .color(Color::rgb(1.0, 1.0, 0.0)),
+            );
+        }
+
+        Ok(())
+    }
+}
@@ -1,11 +1,22 @@
-use crate::world::WorldState;
+use crate::defs::{NpcId, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::scenes::crowd::CROWD_RADIUS;
+use crate::world::{SansChase, StageDef, WorldState};
+use std::f32::consts::TAU;
 use tetra::graphics::Rectangle;
 use tetra::math::Vec2;
 
+const SEPARATION_TOLERANCE: f32 = 2.0; // Overlap allowed before pushing apart
+const NPC_PUSH_SHARE: f32 = 0.7; // NPCs give way more than the player
+const SPAWN_SEARCH_STEP: f32 = 10.0; // Pixels between rings
+const SPAWN_SEARCH_RINGS: u32 = 40;
+
 /// Circular collider for NPCs
+#[derive(Clone, Copy)]
 pub struct CircleCollider {
+    pub npc: NpcId,
     pub pos: Vec2<f32>,
     pub radius: f32,
+    pub active: bool, // In this stage, alive and blocking right now
 }
 
 /// Rectangular collider for walls/doors
@@ -13,28 +24,256 @@ pub struct RectCollider {
     pub rect: Rectangle,
 }
 
+/// The stage the colliders were built for, then its NPCs and walls.
+pub type ColliderCache = (u8, Vec<CircleCollider>, Vec<RectCollider>);
+
 pub fn check_collision(pos: Vec2<f32>, radius: f32, world: &WorldState) -> bool {
-    // Check circle colliders (NPCs)
-    let circle_colliders = get_circle_colliders(world);
-    for collider in &circle_colliders {
-        let dx = pos.x - collider.pos.x;
-        let dy = pos.y - collider.pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < (radius + collider.radius) {
-            return true;
+    with_colliders(world, |circles, rects| {
+        // Check circle colliders (NPCs)
+        for collider in active(circles) {
+            let dx = pos.x - collider.pos.x;
+            let dy = pos.y - collider.pos.y;
+            let distance = (dx * dx + dy * dy).sqrt();
+
+            if distance < (radius + collider.radius) {
+                return true;
+            }
+        }
+
+        // Check rect colliders (walls)
+        hits_rect(pos, radius, rects)
+    })
+}
+
+/// Minimum translation that pushes a circle out of the first collider it
+/// overlaps, or zero if it overlaps nothing.
+pub fn separation_vector(pos: Vec2<f32>, radius: f32, world: &WorldState) -> Vec2<f32> {
+    with_colliders(world, |circles, rects| {
+        for collider in active(circles) {
+            let delta = pos - collider.pos;
+            let distance = delta.magnitude();
+            if distance < radius + collider.radius {
+                let normal = if distance > 0.001 {
+                    delta / distance
+                } else {
+                    Vec2::new(0.0, 1.0)
+                };
+                return normal * (radius + collider.radius - distance);
+            }
+        }
+
+        for collider in rects {
+            if circle_rect_collision(pos, radius, &collider.rect) {
+                return rect_separation(pos, radius, &collider.rect);
+            }
         }
+
+        Vec2::zero()
+    })
+}
+
+/// Same as `separation_vector`, against a list of walls only.
+pub fn wall_separation(pos: Vec2<f32>, radius: f32, walls: &[Rectangle]) -> Vec2<f32> {
+    walls
+        .iter()
+        .find(|rect| circle_rect_collision(pos, radius, rect))
+        .map_or(Vec2::zero(), |rect| rect_separation(pos, radius, rect))
+}
+
+fn rect_separation(pos: Vec2<f32>, radius: f32, rect: &Rectangle) -> Vec2<f32> {
+    let closest = Vec2::new(
+        pos.x.clamp(rect.x, rect.x + rect.width),
+        pos.y.clamp(rect.y, rect.y + rect.height),
+    );
+    let delta = pos - closest;
+    let distance = delta.magnitude();
+    if distance > 0.001 {
+        // Center is outside, push away from the nearest point
+        return delta / distance * (radius - distance);
     }
-    
-    // Check rect colliders (walls)
-    let rect_colliders = get_rect_colliders(world);
-    for collider in &rect_colliders {
-        if circle_rect_collision(pos, radius, &collider.rect) {
-            return true;
+
+    // Center is inside, leave through the closest edge. Edges on the screen
+    // border are skipped, pushing out there would only lead off screen.
+    let edges = [
+        (pos.x - rect.x, Vec2::new(-1.0, 0.0), rect.x > 0.0),
+        (
+            rect.x + rect.width - pos.x,
+            Vec2::new(1.0, 0.0),
+            rect.x + rect.width < SCREEN_WIDTH as f32,
+        ),
+        (pos.y - rect.y, Vec2::new(0.0, -1.0), rect.y > 0.0),
+        (
+            rect.y + rect.height - pos.y,
+            Vec2::new(0.0, 1.0),
+            rect.y + rect.height < SCREEN_HEIGHT as f32,
+        ),
+    ];
+    edges
+        .iter()
+        .filter(|(_, _, open)| *open)
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .map_or(Vec2::zero(), |(depth, normal, _)| {
+            *normal * (depth + radius)
+        })
+}
+
+/// Closest NPC collider to `pos` and its distance, center to center.
+pub fn query_nearest_npc(pos: Vec2<f32>, world: &WorldState) -> Option<(NpcId, f32)> {
+    with_colliders(world, |circles, _| {
+        active(circles)
+            .map(|collider| (collider.npc, pos.distance(collider.pos)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+    })
+}
+
+/// First NPC whose collider overlaps the circle, for projectiles.
+pub fn npc_at(pos: Vec2<f32>, radius: f32, world: &WorldState) -> Option<NpcId> {
+    with_colliders(world, |circles, _| {
+        active(circles)
+            .find(|collider| pos.distance(collider.pos) < radius + collider.radius)
+            .map(|collider| collider.npc)
+    })
+}
+
+/// `pos` if a circle fits there clear of NPCs, walls and hazards, otherwise
+/// the closest spot that does, searched in rings around it. Falls back to
+/// `pos` when nothing in reach is free.
+pub fn nearest_free_spot(pos: Vec2<f32>, radius: f32, world: &WorldState) -> Vec2<f32> {
+    nearest_free_spot_in(world.stage_def(), pos, |spot| {
+        !check_collision(spot, radius, world) && world.hazard_intensity(spot) == 0.0
+    })
+}
+
+/// Same search inside `room`'s bounds, with `clear` saying what else has to
+/// hold. The Ayasofya interior has no colliders, only its bounds.
+pub fn nearest_free_spot_in(
+    room: &StageDef,
+    pos: Vec2<f32>,
+    clear: impl Fn(Vec2<f32>) -> bool,
+) -> Vec2<f32> {
+    let free = |spot: Vec2<f32>| {
+        (0.0..=SCREEN_WIDTH as f32).contains(&spot.x)
+            && (room.top_bound..=room.bottom_bound).contains(&spot.y)
+            && clear(spot)
+    };
+    if free(pos) {
+        return pos;
+    }
+    for ring in 1..=SPAWN_SEARCH_RINGS {
+        let distance = ring as f32 * SPAWN_SEARCH_STEP;
+        let points = ring * 8;
+        for i in 0..points {
+            let angle = i as f32 / points as f32 * TAU;
+            let spot = pos + Vec2::new(angle.cos(), angle.sin()) * distance;
+            if free(spot) {
+                log::debug!("Spawn point {:?} blocked, moved to {:?}", pos, spot);
+                return spot;
+            }
+        }
+    }
+    log::warn!("No free spot near spawn point {:?}", pos);
+    pos
+}
+
+fn hits_rect(pos: Vec2<f32>, radius: f32, rects: &[RectCollider]) -> bool {
+    rects
+        .iter()
+        .any(|collider| circle_rect_collision(pos, radius, &collider.rect))
+}
+
+fn hits_rect_collider(pos: Vec2<f32>, radius: f32, world: &WorldState) -> bool {
+    with_colliders(world, |_, rects| hits_rect(pos, radius, rects))
+}
+
+/// Moves an NPC by `delta` one axis at a time so it slides along walls
+/// and around other NPCs instead of stopping dead.
+pub fn move_and_collide(
+    pos: Vec2<f32>,
+    delta: Vec2<f32>,
+    radius: f32,
+    world: &WorldState,
+    mover: NpcId,
+) -> Vec2<f32> {
+    with_colliders(world, |circles, rects| {
+        let blocked = |p: Vec2<f32>| {
+            hits_rect(p, radius, rects)
+                || active(circles)
+                    .filter(|c| c.npc != mover)
+                    .any(|c| p.distance(c.pos) < radius + c.radius)
+        };
+
+        let mut result = pos;
+        let step_x = Vec2::new(pos.x + delta.x, pos.y);
+        if !blocked(step_x) {
+            result = step_x;
+        }
+        let step_y = Vec2::new(result.x, result.y + delta.y);
+        if !blocked(step_y) {
+            result = step_y;
+        }
+        result
+    })
+}
+
+/// Pushes the player and overlapping NPCs apart so nobody gets pinned.
+/// Never moves anyone into a wall, outside the vertical bounds, or the
+/// player past a stage transition edge.
+pub fn separate_from_npcs(
+    player_pos: &mut Vec2<f32>,
+    radius: f32,
+    world: &mut WorldState,
+    top_bound: f32,
+    bottom_bound: f32,
+) {
+    let in_bounds = |pos: Vec2<f32>| {
+        pos.y >= top_bound && pos.y <= bottom_bound && pos.x >= 0.0 && pos.x <= SCREEN_WIDTH as f32
+    };
+
+    // By index, pushing an NPC needs the world back between colliders
+    let count = with_colliders(world, |circles, _| circles.len());
+    for index in 0..count {
+        let collider = with_colliders(world, |circles, _| circles[index]);
+        if !collider.active {
+            continue;
+        }
+        let delta = *player_pos - collider.pos;
+        let distance = delta.magnitude();
+        let overlap = radius + collider.radius - distance;
+        if overlap <= SEPARATION_TOLERANCE {
+            continue;
+        }
+
+        // Standing exactly on top of each other: push straight down the band
+        let normal = if distance > 0.001 {
+            delta / distance
+        } else {
+            Vec2::new(0.0, 1.0)
+        };
+
+        // The music box is an object, it doesn't get shoved around
+        let npc_share = if collider.npc == NpcId::MusicBox {
+            0.0
+        } else {
+            NPC_PUSH_SHARE
+        };
+
+        let npc_target = collider.pos - normal * overlap * npc_share;
+        let npc_moved = npc_share > 0.0
+            && in_bounds(npc_target)
+            && !hits_rect_collider(npc_target, collider.radius, world);
+        if npc_moved {
+            *world.npc_pos_mut(collider.npc) = npc_target;
+        }
+
+        // Whatever the NPC couldn't take, the player takes
+        let player_share = if npc_moved { 1.0 - npc_share } else { 1.0 };
+        let mut player_target = *player_pos + normal * overlap * player_share;
+        player_target.x = player_target.x.clamp(0.0, SCREEN_WIDTH as f32);
+        player_target.y = player_target.y.clamp(top_bound, bottom_bound);
+        if !hits_rect_collider(player_target, radius, world) {
+            *player_pos = player_target;
         }
     }
-    
-    false
 }
 
 /// Check collision between a circle and a rectangle
@@ -51,67 +290,81 @@ fn circle_rect_collision(circle_pos: Vec2<f32>, radius: f32, rect: &Rectangle) -
     distance_sq < radius * radius
 }
 
-fn get_circle_colliders(world: &WorldState) -> Vec<CircleCollider> {
-    let mut colliders = Vec::new();
-
-    match world.current_stage {
-        1 => {
-            // Sans
-            colliders.push(CircleCollider {
-                pos: world.sans_pos,
-                radius: 40.0,
-            });
-            // MusicBox
-            colliders.push(CircleCollider {
-                pos: world.musicbox_pos,
-                radius: 30.0,
-            });
-        }
-        2 => {
-            // Rarity
-            if world.rarity_alive {
-                colliders.push(CircleCollider {
-                    pos: world.rarity_pos,
-                    radius: 40.0,
-                });
-            }
-            // Gaster
-            colliders.push(CircleCollider {
-                pos: world.gaster_pos,
-                radius: 40.0,
-            });
-        }
-        4 => {
-            // Eilish
-            colliders.push(CircleCollider {
-                pos: world.eilish_pos,
-                radius: 40.0,
-            });
-        }
-        _ => {}
+/// Runs `f` on the colliders of the current stage. They're built on the
+/// first call after entering a stage, later calls only refresh the NPC
+/// positions and who's active in place. `f` mustn't touch the physics again.
+fn with_colliders<R>(
+    world: &WorldState,
+    f: impl FnOnce(&[CircleCollider], &[RectCollider]) -> R,
+) -> R {
+    let mut cache = world.cached_colliders.borrow_mut();
+    let stale = cache
+        .as_ref()
+        .is_none_or(|(stage, _, _)| *stage != world.current_stage);
+    if stale {
+        log::debug!("Building colliders for stage {}", world.current_stage);
+        *cache = Some((
+            world.current_stage,
+            build_circle_colliders(world),
+            build_rect_colliders(world),
+        ));
+    }
+    let Some((_, circles, rects)) = cache.as_mut() else {
+        unreachable!("collider cache was just filled");
+    };
+    for collider in circles.iter_mut() {
+        refresh_circle_collider(collider, world);
     }
+    f(circles, rects)
+}
+
+fn active(circles: &[CircleCollider]) -> impl Iterator<Item = &CircleCollider> {
+    circles.iter().filter(|collider| collider.active)
+}
+
+/// Everyone who could stand in the stage. Whether they do right now is up
+/// to `refresh_circle_collider`, so rescues and deaths don't need a rebuild.
+fn build_circle_colliders(world: &WorldState) -> Vec<CircleCollider> {
+    let mut npcs: Vec<(NpcId, f32)> = vec![
+        (NpcId::Sans, 40.0),
+        (NpcId::MusicBox, 30.0),
+        (NpcId::Rarity, 40.0),
+        (NpcId::Gaster, 40.0),
+        (NpcId::Eilish, 40.0),
+    ];
+    npcs.extend((0..world.crowd.len()).map(|index| (NpcId::Crowd(index), CROWD_RADIUS)));
+    // Survivor, in the dead space or stage 3 depending on the rescue
+    npcs.push((NpcId::Survivor, 20.0));
 
-    colliders
-}
-
-fn get_rect_colliders(world: &WorldState) -> Vec<RectCollider> {
-    let mut colliders = Vec::new();
-
-    if world.current_stage == 3 {
-        // Ayasofya: Kapının dışındaki duvarlar
-        // Sol duvar
-        colliders.push(RectCollider {
-            rect: Rectangle::new(0.0, 0.0, 300.0, 600.0),
-        });
-        // Sağ duvar
-        colliders.push(RectCollider {
-            rect: Rectangle::new(500.0, 0.0, 300.0, 600.0),
-        });
-        // Üst duvar (kapının üstü)
-        colliders.push(RectCollider {
-            rect: Rectangle::new(300.0, 0.0, 200.0, 150.0),
-        });
+    npcs.into_iter()
+        .map(|(npc, radius)| CircleCollider {
+            npc,
+            pos: Vec2::zero(),
+            radius,
+            active: false,
+        })
+        .collect()
+}
+
+fn refresh_circle_collider(collider: &mut CircleCollider, world: &WorldState) {
+    let npc = collider.npc;
+    // Dead NPCs don't block anything
+    let state = world.npc_state(npc).filter(|state| state.alive);
+    if let Some(state) = state {
+        collider.pos = state.pos;
     }
+    // Only the ones in this stage, wherever the randomizer put them. Sans
+    // walks through the player while hunting them.
+    collider.active = state.is_some()
+        && world.in_current_stage(npc)
+        && (npc != NpcId::Sans || world.sans_chase == SansChase::Idle);
+}
 
-    colliders
+fn build_rect_colliders(world: &WorldState) -> Vec<RectCollider> {
+    world
+        .stage_def()
+        .walls
+        .iter()
+        .map(|&rect| RectCollider { rect })
+        .collect()
 }
@@ -1,16 +1,34 @@
-use crate::defs::Direction;
+use crate::defs::{Direction, SCREEN_WIDTH};
 use tetra::graphics::Texture;
 use tetra::math::Vec2;
 
+/// Seconds of dead space exposure before HP starts draining
+pub const EXPOSURE_FILL_SECONDS: f32 = 8.0;
+/// Seconds for a full meter to recover outside the dead space
+pub const EXPOSURE_DRAIN_SECONDS: f32 = 3.0;
+/// How close to a screen edge counts as leaving through it
+const BOUNDARY_MARGIN: f32 = 5.0;
+
 pub struct PlayerState {
     pub pos: Vec2<f32>,
     pub direction: Direction,
-    pub health: f32,
-    pub outfit: u8, // 0: None, 1: Fes, 2: Takke
+    pub player_hp: i32,
+    pub player_max_hp: i32,
+    pub damage_tick: u32, // Frames accumulated towards the next point of damage
+    pub pending_damage: f32, // Fractional damage carried over between frames
+    pub invuln_timer: f32, // Seconds of i-frames left after a hit
+    pub exposure: f32,    // 0.0 - 1.0, dead space meter
+    pub exposure_resistance: f32, // Fill rate multiplier, lowered by protective gear
+    pub outfit: u8,       // Worn right now, 0: None, 1: Fes, 2: Takke
+    pub owned_outfit: u8, // Picked at the Ayasofya wardrobe, worn everywhere
+    pub idle_time: f32,   // Seconds since the player last moved, drives the bob
+    pub blink_timer: f32, // Seconds until the next blink
+    pub blink_hold: f32,  // Seconds left of the current blink
+    pub auto_walk: Option<Vec2<f32>>, // Unit direction kept walking in, with the assist on
+    pub player_fade_alpha: f32, // Sprite opacity, back to 0 on entering a stage
 
     // Textures
     pub texture_front: Option<Texture>,
-    pub texture_left: Option<Texture>,
     pub texture_right: Option<Texture>,
     pub texture_fes: Option<Texture>,
     pub texture_takke: Option<Texture>,
@@ -21,13 +39,108 @@ impl PlayerState {
         Self {
             pos: Vec2::new(400.0, 300.0),
             direction: Direction::Front,
-            health: 100.0,
+            player_hp: 20,
+            player_max_hp: 20,
+            damage_tick: 0,
+            pending_damage: 0.0,
+            invuln_timer: 0.0,
+            exposure: 0.0,
+            exposure_resistance: 1.0,
             outfit: 0,
+            owned_outfit: 0,
+            idle_time: 0.0,
+            blink_timer: 5.0,
+            blink_hold: 0.0,
+            auto_walk: None,
+            player_fade_alpha: 1.0,
             texture_front: None,
-            texture_left: None,
             texture_right: None,
             texture_fes: None,
             texture_takke: None,
         }
     }
+
+    /// Ends tap-to-move. Anything that takes control away from the player
+    /// calls this so walking doesn't resume on its own afterwards.
+    pub fn stop_auto_walk(&mut self) {
+        self.auto_walk = None;
+    }
+
+    /// The screen edge `player_pos` is about to leave through, if any.
+    /// `stage` counts from 1 at the leftmost stage, which has nothing past
+    /// its left edge.
+    pub fn is_near_boundary(player_pos: Vec2<f32>, stage: u32) -> Option<Direction> {
+        if player_pos.x > SCREEN_WIDTH as f32 - BOUNDARY_MARGIN {
+            Some(Direction::Right)
+        } else if player_pos.x < BOUNDARY_MARGIN && stage > 1 {
+            Some(Direction::Left)
+        } else {
+            None
+        }
+    }
+
+    pub fn heal(&mut self, amount: i32) {
+        self.player_hp = (self.player_hp + amount).min(self.player_max_hp);
+    }
+
+    /// Fills the dead space meter while inside, at the rate the gear allows,
+    /// and drains it outside. True once it's full and still inside, which is
+    /// when HP starts to drain.
+    pub fn step_exposure(&mut self, in_dead_space: bool, dt: f32) -> bool {
+        if in_dead_space {
+            self.exposure += dt / EXPOSURE_FILL_SECONDS * self.exposure_resistance;
+            self.exposure = self.exposure.min(1.0);
+        } else {
+            self.exposure = (self.exposure - dt / EXPOSURE_DRAIN_SECONDS).max(0.0);
+        }
+        in_dead_space && self.exposure >= 1.0
+    }
+
+    /// Deals damage at `per_second` HP/s, keeping the fraction for the next frame.
+    pub fn damage_over_time(&mut self, per_second: f32, dt: f32) {
+        self.pending_damage += per_second * dt;
+        let whole = self.pending_damage.floor();
+        self.pending_damage -= whole;
+        self.player_hp -= whole as i32;
+    }
+
+    /// Deals 1 HP of damage every `frames` calls, for damage that's applied per frame.
+    pub fn tick_damage(&mut self, frames: u32) {
+        self.damage_tick += 1;
+        if self.damage_tick >= frames {
+            self.damage_tick = 0;
+            self.player_hp -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fill(player: &mut PlayerState, seconds: f32, inside: bool) -> bool {
+        let mut hurting = false;
+        for _ in 0..(seconds * 60.0).round() as u32 {
+            hurting = player.step_exposure(inside, 1.0 / 60.0);
+        }
+        hurting
+    }
+
+    #[test]
+    fn exposure_fills_then_hurts() {
+        let mut player = PlayerState::new();
+        assert!(!fill(&mut player, EXPOSURE_FILL_SECONDS - 1.0, true));
+        assert!(fill(&mut player, 1.5, true));
+        assert!(!fill(&mut player, EXPOSURE_DRAIN_SECONDS + 0.5, false));
+        assert_eq!(player.exposure, 0.0);
+    }
+
+    #[test]
+    fn protective_gear_slows_the_fill() {
+        let mut player = PlayerState::new();
+        player.exposure_resistance = 0.5; // The survivor's gas mask
+        assert!(!fill(&mut player, EXPOSURE_FILL_SECONDS, true));
+        assert!((player.exposure - 0.5).abs() < 0.01);
+        assert!(fill(&mut player, EXPOSURE_FILL_SECONDS + 0.5, true));
+    }
 }
@@ -0,0 +1,100 @@
+/// Pre-allocated slots with an alive flag. Spawning reuses a dead slot and
+/// removing only clears the flag, so combat bones and particle bursts never
+/// allocate or shift elements mid-frame.
+pub struct Pool<T> {
+    slots: Vec<T>,
+    alive: Vec<bool>,
+    free: Vec<usize>, // Dead slot indices, popped on spawn
+    overflowed: bool, // Warned about a full pool since the last clear
+}
+
+impl<T: Default> Pool<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            slots: (0..capacity).map(|_| T::default()).collect(),
+            alive: vec![false; capacity],
+            free: (0..capacity).rev().collect(),
+            overflowed: false,
+        }
+    }
+}
+
+impl<T> Pool<T> {
+    /// Puts `item` in a free slot. Returns false if the pool is full, which
+    /// is logged once until the next clear.
+    pub fn spawn(&mut self, item: T) -> bool {
+        let Some(index) = self.free.pop() else {
+            if !self.overflowed {
+                log::warn!("Pool of {} is full, dropping spawns", self.slots.len());
+                self.overflowed = true;
+            }
+            return false;
+        };
+        self.slots[index] = item;
+        self.alive[index] = true;
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.overflowed = false;
+        self.free.clear();
+        for index in (0..self.slots.len()).rev() {
+            self.alive[index] = false;
+            self.free.push(index);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.slots
+            .iter()
+            .zip(&self.alive)
+            .filter(|(_, alive)| **alive)
+            .map(|(item, _)| item)
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.slots
+            .iter_mut()
+            .zip(&self.alive)
+            .filter(|(_, alive)| **alive)
+            .map(|(item, _)| item)
+    }
+
+    /// Runs `keep` on every live item and frees the ones it returns false for.
+    pub fn retain_mut(&mut self, mut keep: impl FnMut(&mut T) -> bool) {
+        for index in 0..self.slots.len() {
+            if self.alive[index] && !keep(&mut self.slots[index]) {
+                self.alive[index] = false;
+                self.free.push(index);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freed_slots_are_reused_and_a_full_pool_refuses() {
+        let mut pool: Pool<u32> = Pool::new(3);
+        assert!(pool.spawn(1) && pool.spawn(2) && pool.spawn(3));
+        assert!(!pool.spawn(4));
+        assert_eq!(pool.len(), 3);
+
+        pool.retain_mut(|item| *item != 2);
+        assert_eq!(pool.len(), 2);
+        assert!(pool.spawn(5));
+        let mut items: Vec<u32> = pool.iter().copied().collect();
+        items.sort();
+        assert_eq!(items, [1, 3, 5]);
+
+        pool.clear();
+        assert_eq!(pool.len(), 0);
+        assert_eq!(pool.iter().count(), 0);
+    }
+}
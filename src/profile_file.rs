@@ -0,0 +1,313 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::value::RawValue;
+
+use crate::paths;
+use crate::randomizer::Layout;
+use crate::system::{DeathCause, User};
+
+/// Bump when the profile layout changes. Files from a newer version are
+/// refused instead of guessed at.
+pub const PROFILE_FORMAT_VERSION: u32 = 1;
+pub const PROFILE_EXTENSION: &str = "gorki";
+
+/// The shareable copy of a `User`, one JSON file per profile.
+#[derive(Serialize)]
+struct ProfileFile {
+    version: u32,
+    checksum: u32, // Over the compact JSON of `profile`
+    profile: ProfileData,
+}
+
+/// `ProfileFile` as read, with `profile` left as text. The checksum covers
+/// what the exporting version wrote, so fields added since then don't
+/// change it.
+#[derive(Deserialize)]
+struct RawProfileFile<'a> {
+    checksum: u32,
+    #[serde(borrow)]
+    profile: &'a RawValue,
+}
+
+// New fields need #[serde(default)], older files don't have them
+#[derive(Serialize, Deserialize)]
+struct ProfileData {
+    username: String,
+    teblig_count: u32,
+    cihad_count: u32,
+    tekfir_count: u32,
+    current_stage: u32,
+    exposure: f32,
+    sans_chase: f32,
+    flags: u32,
+    #[serde(default)] // Not in files exported before the music box was saved
+    music_on: bool,
+    #[serde(default)]
+    outfit: u8,
+    #[serde(default)]
+    playtime: f32,
+    #[serde(default)]
+    hardcore: bool,
+    #[serde(default)]
+    locked: bool,
+    #[serde(default)]
+    deaths: Vec<u32>, // By DeathCause::index, shorter in older files
+    #[serde(default)]
+    visited_stages: u8,
+    #[serde(default)]
+    randomizer: Option<String>, // Layout::encode, None for a standard profile
+}
+
+// Read first so a future file gets a version error, not a field error
+#[derive(Deserialize)]
+struct VersionHeader {
+    version: u32,
+}
+
+/// FNV-1a over compact JSON, only there to catch hand edits and truncated
+/// copies.
+fn checksum(json: &str) -> u32 {
+    json.bytes().fold(0x811c_9dc5, |hash: u32, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x0100_0193)
+    })
+}
+
+/// `json` without the whitespace between tokens, which is all the pretty
+/// printing adds to what `export` hashed.
+fn compact(json: &str) -> String {
+    let mut out = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for c in json.chars() {
+        if in_string {
+            out.push(c);
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+            out.push(c);
+        } else if !c.is_whitespace() {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Same characters the Create Save field accepts, anything else would break
+/// the users.db line.
+pub fn is_valid_username(name: &str) -> bool {
+    !name.trim().is_empty()
+        && name.len() <= 32
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == ' ' || c == '_' || c == '-')
+}
+
+/// Writes `<username>.gorki` to the data directory and returns its path.
+pub fn export(user: &User) -> Result<PathBuf, String> {
+    let profile = ProfileData {
+        username: user.username.clone(),
+        teblig_count: user.teblig_count,
+        cihad_count: user.cihad_count,
+        tekfir_count: user.tekfir_count,
+        current_stage: user.current_stage,
+        exposure: user.exposure,
+        sans_chase: user.sans_chase,
+        flags: user.flags,
+        music_on: user.music_on,
+        outfit: user.outfit,
+        playtime: user.playtime,
+        hardcore: user.hardcore,
+        locked: user.locked,
+        deaths: user.deaths.to_vec(),
+        visited_stages: user.visited_stages,
+        randomizer: user.randomizer.as_ref().map(Layout::encode),
+    };
+    let json = serde_json::to_string(&profile).map_err(|e| e.to_string())?;
+    let file = ProfileFile {
+        version: PROFILE_FORMAT_VERSION,
+        checksum: checksum(&json),
+        profile,
+    };
+    let content = serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?;
+    let path = paths::data_path(&format!("{}.{}", user.username, PROFILE_EXTENSION));
+    fs::write(&path, content).map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Every .gorki file in the data directory, sorted by name.
+pub fn list() -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(paths::data_dir()) else {
+        return Vec::new();
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == PROFILE_EXTENSION))
+        .collect();
+    files.sort();
+    files
+}
+
+/// Reads and checks a profile file. The error is shown on screen as is.
+pub fn import(path: &Path) -> Result<User, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Can't read file: {}", e))?;
+    let header: VersionHeader =
+        serde_json::from_str(&content).map_err(|_| "Not a profile file".to_string())?;
+    if header.version > PROFILE_FORMAT_VERSION {
+        return Err(format!(
+            "Made by a newer version (format {}), update the game",
+            header.version
+        ));
+    }
+    let damaged = |_| "Profile file is damaged".to_string();
+    let file: RawProfileFile = serde_json::from_str(&content).map_err(damaged)?;
+    if checksum(&compact(file.profile.get())) != file.checksum {
+        return Err("Checksum mismatch, the file was modified".to_string());
+    }
+
+    let profile: ProfileData = serde_json::from_str(file.profile.get()).map_err(damaged)?;
+    let mut deaths = [0; DeathCause::COUNT];
+    for (slot, count) in deaths.iter_mut().zip(&profile.deaths) {
+        *slot = *count;
+    }
+    if !is_valid_username(&profile.username) {
+        return Err("Profile has an invalid name".to_string());
+    }
+    let randomizer = match &profile.randomizer {
+        Some(value) => Some(
+            Layout::decode(value)
+                .ok_or_else(|| "Profile has a damaged world layout".to_string())?,
+        ),
+        None => None,
+    };
+    Ok(User {
+        username: profile.username,
+        teblig_count: profile.teblig_count,
+        cihad_count: profile.cihad_count,
+        tekfir_count: profile.tekfir_count,
+        current_stage: profile.current_stage.max(1),
+        exposure: profile.exposure.clamp(0.0, 1.0),
+        sans_chase: profile.sans_chase.max(0.0),
+        flags: profile.flags,
+        music_on: profile.music_on,
+        outfit: profile.outfit.min(2),
+        playtime: profile.playtime.max(0.0),
+        hardcore: profile.hardcore,
+        locked: profile.locked,
+        deaths,
+        visited_stages: profile.visited_stages,
+        randomizer,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::tests::temp_dir;
+
+    // Exported before music_on and the later fields existed
+    const OLD_FORMAT: &str = r#"{
+  "version": 1,
+  "checksum": 2446170136,
+  "profile": {
+    "username": "Ayse",
+    "teblig_count": 3,
+    "cihad_count": 1,
+    "tekfir_count": 0,
+    "current_stage": 2,
+    "exposure": 0.25,
+    "sans_chase": 0.0,
+    "flags": 5
+  }
+}"#;
+
+    fn write(name: &str, content: &str) -> PathBuf {
+        let path = temp_dir(name).join(format!("profile.{}", PROFILE_EXTENSION));
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn old_format_files_still_import() {
+        let user = import(&write("profile-old", OLD_FORMAT)).unwrap();
+        assert_eq!(user.username, "Ayse");
+        assert_eq!(user.teblig_count, 3);
+        assert_eq!(user.current_stage, 2);
+        assert_eq!(user.flags, 5);
+        assert!(!user.music_on && !user.hardcore && !user.locked);
+        assert_eq!(user.deaths, [0; DeathCause::COUNT]);
+        assert!(user.randomizer.is_none());
+    }
+
+    #[test]
+    fn edited_files_are_refused() {
+        let edited = OLD_FORMAT.replace("\"flags\": 5", "\"flags\": 7");
+        let error = import(&write("profile-edited", &edited)).err();
+        assert_eq!(
+            error.as_deref(),
+            Some("Checksum mismatch, the file was modified")
+        );
+    }
+
+    fn user(name: &str, randomizer: Option<Layout>) -> User {
+        User {
+            username: name.to_string(),
+            teblig_count: 4,
+            cihad_count: 2,
+            tekfir_count: 1,
+            current_stage: 3,
+            exposure: 0.5,
+            sans_chase: 0.0,
+            flags: 9,
+            music_on: true,
+            outfit: 2,
+            playtime: 125.5,
+            hardcore: true,
+            locked: false,
+            deaths: [1; DeathCause::COUNT],
+            visited_stages: 0b0111,
+            randomizer,
+        }
+    }
+
+    fn round_trip(original: &User) -> User {
+        let path = export(original).unwrap();
+        let imported = import(&path).unwrap();
+        assert_eq!(imported.username, original.username);
+        assert_eq!(imported.flags, original.flags);
+        assert_eq!(imported.outfit, original.outfit);
+        assert_eq!(imported.playtime, original.playtime);
+        assert_eq!(imported.hardcore, original.hardcore);
+        assert_eq!(imported.deaths, original.deaths);
+        assert_eq!(imported.visited_stages, original.visited_stages);
+        imported
+    }
+
+    #[test]
+    fn randomized_profiles_round_trip() {
+        let layout = Layout::generate(42);
+        let imported = round_trip(&user("Shuffled", Some(layout.clone())));
+        assert_eq!(imported.randomizer, Some(layout));
+    }
+
+    #[test]
+    fn standard_profiles_round_trip() {
+        let imported = round_trip(&user("Standard", None));
+        assert!(imported.randomizer.is_none());
+    }
+
+    #[test]
+    fn whitespace_inside_strings_is_kept() {
+        assert_eq!(
+            compact("{ \"a b\": \"c \\\" d\" }"),
+            "{\"a b\":\"c \\\" d\"}"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::collections::HashMap;
+
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use serde::Deserialize;
+
+use crate::defs::NpcId;
+use crate::world::STAGE_COUNT;
+
+/// Read when a randomizer profile is created, the copy built into the binary
+/// is used when it's missing or broken.
+const CONSTRAINTS_PATH: &str = "./assets/randomizer.toml";
+const BUNDLED_CONSTRAINTS: &str = include_str!("../assets/randomizer.toml");
+
+const MAX_TRIES: u32 = 10_000; // Shuffles rolled before giving up on the constraints
+
+/// Where the dead zone is in the standard game, its hazards are defined there.
+pub const DEAD_ZONE_HOME: u8 = 4;
+
+/// NPCs the randomizer moves, in `Layout::npc_stages` order. The crowd stays
+/// by the Ayasofya door and the survivor goes wherever the dead zone does.
+pub const SHUFFLED_NPCS: [NpcId; 5] = [
+    NpcId::Sans,
+    NpcId::MusicBox,
+    NpcId::Gaster,
+    NpcId::Rarity,
+    NpcId::Eilish,
+];
+
+/// Which stage holds what. Worked out once from the seed when a randomizer
+/// profile is created and saved with it, so every session of the profile
+/// sees the same world even if the constraints change later.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Layout {
+    pub seed: u64,
+    pub stage_order: [u8; STAGE_COUNT], // Left to right
+    pub npc_stages: [u8; SHUFFLED_NPCS.len()],
+    pub dead_zone_stage: u8,
+}
+
+impl Layout {
+    /// The world of a standard profile.
+    pub fn standard() -> Self {
+        Self {
+            seed: 0,
+            stage_order: [1, 2, 3, 4],
+            npc_stages: [1, 1, 2, 2, 4],
+            dead_zone_stage: DEAD_ZONE_HOME,
+        }
+    }
+
+    /// A shuffled world that follows the rules in randomizer.toml. The same
+    /// seed and rules always give the same world.
+    pub fn generate(seed: u64) -> Self {
+        let rules = Constraints::load();
+        let mut rng = StdRng::seed_from_u64(seed);
+        for _ in 0..MAX_TRIES {
+            let layout = Self::roll(seed, &rules, &mut rng);
+            if rules.allows(&layout) {
+                log::info!("Randomizer layout for seed {}: {}", seed, layout.encode());
+                return layout;
+            }
+        }
+        log::warn!(
+            "No randomizer layout for seed {} fits {}, using the standard one",
+            seed,
+            CONSTRAINTS_PATH
+        );
+        Self {
+            seed,
+            ..Self::standard()
+        }
+    }
+
+    fn roll(seed: u64, rules: &Constraints, rng: &mut StdRng) -> Self {
+        let mut stage_order = Self::standard().stage_order;
+        stage_order.shuffle(rng);
+        let npc_stages = SHUFFLED_NPCS.map(|npc| pick(rules.npc_stages(npc), rng));
+        Self {
+            seed,
+            stage_order,
+            npc_stages,
+            dead_zone_stage: pick(&rules.dead_zone.stages, rng),
+        }
+    }
+
+    pub fn first_stage(&self) -> u8 {
+        self.stage_order[0]
+    }
+
+    /// Where `stage` is counting from the left.
+    pub fn position(&self, stage: u8) -> usize {
+        self.stage_order
+            .iter()
+            .position(|s| *s == stage)
+            .unwrap_or(0)
+    }
+
+    /// The stage past the right edge, the last one leads back to the first.
+    pub fn next_stage(&self, stage: u8) -> u8 {
+        self.stage_order[(self.position(stage) + 1) % STAGE_COUNT]
+    }
+
+    /// The stage past the left edge, the first one has none.
+    pub fn previous_stage(&self, stage: u8) -> Option<u8> {
+        let position = self.position(stage);
+        (position > 0).then(|| self.stage_order[position - 1])
+    }
+
+    /// None for NPCs the randomizer doesn't move.
+    pub fn npc_stage(&self, npc: NpcId) -> Option<u8> {
+        let index = SHUFFLED_NPCS.iter().position(|n| *n == npc)?;
+        Some(self.npc_stages[index])
+    }
+
+    /// Whose hazards `stage` has. The dead zone trades places with whatever
+    /// was in its stage, which is nothing in the standard stages.
+    pub fn hazard_source(&self, stage: u8) -> u8 {
+        if stage == self.dead_zone_stage {
+            DEAD_ZONE_HOME
+        } else if stage == DEAD_ZONE_HOME {
+            self.dead_zone_stage
+        } else {
+            stage
+        }
+    }
+
+    /// The users.db field, `seed/order/npc stages/dead zone`, digits for stages.
+    pub fn encode(&self) -> String {
+        let digits = |stages: &[u8]| stages.iter().map(|s| s.to_string()).collect::<String>();
+        format!(
+            "{}/{}/{}/{}",
+            self.seed,
+            digits(&self.stage_order),
+            digits(&self.npc_stages),
+            self.dead_zone_stage
+        )
+    }
+
+    /// None if the field is damaged, including an order that skips a stage.
+    pub fn decode(value: &str) -> Option<Self> {
+        let parts: Vec<&str> = value.split('/').collect();
+        let [seed, order, npcs, dead_zone] = parts[..] else {
+            return None;
+        };
+        let stages = |digits: &str| -> Option<Vec<u8>> {
+            digits
+                .chars()
+                .map(|c| {
+                    let stage = c.to_digit(10)? as u8;
+                    (1..=STAGE_COUNT as u8).contains(&stage).then_some(stage)
+                })
+                .collect()
+        };
+        let stage_order: [u8; STAGE_COUNT] = stages(order)?.try_into().ok()?;
+        let mut sorted = stage_order;
+        sorted.sort();
+        if sorted != Self::standard().stage_order {
+            return None;
+        }
+        let [dead_zone_stage] = stages(dead_zone)?[..] else {
+            return None;
+        };
+        Some(Self {
+            seed: seed.parse().ok()?,
+            stage_order,
+            npc_stages: stages(npcs)?.try_into().ok()?,
+            dead_zone_stage,
+        })
+    }
+}
+
+fn pick(stages: &[u8], rng: &mut StdRng) -> u8 {
+    stages[rng.random_range(0..stages.len())]
+}
+
+/// What a randomized world has to keep true, see assets/randomizer.toml.
+/// NPCs are named as in `NpcId`.
+#[derive(Deserialize)]
+struct Constraints {
+    last_stage: Option<u8>,
+    #[serde(default)]
+    apart: Vec<Vec<String>>,
+    #[serde(default)]
+    npc_stages: HashMap<String, Vec<u8>>,
+    dead_zone: DeadZoneRules,
+}
+
+#[derive(Deserialize)]
+struct DeadZoneRules {
+    stages: Vec<u8>,
+    #[serde(default)]
+    keep_clear: Vec<String>,
+}
+
+impl Constraints {
+    fn load() -> Self {
+        match std::fs::read_to_string(CONSTRAINTS_PATH) {
+            Ok(content) => match Self::parse(&content) {
+                Ok(rules) => return rules,
+                Err(e) => log::warn!("Ignoring {}: {}", CONSTRAINTS_PATH, e),
+            },
+            Err(e) => log::warn!("Failed to read {}: {}", CONSTRAINTS_PATH, e),
+        }
+        Self::parse(BUNDLED_CONSTRAINTS).expect("bundled randomizer.toml is valid")
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let rules: Self = toml::from_str(content).map_err(|e| e.to_string())?;
+        let valid = |stages: &[u8]| {
+            !stages.is_empty() && stages.iter().all(|s| (1..=STAGE_COUNT as u8).contains(s))
+        };
+        if !valid(&rules.dead_zone.stages) || !rules.npc_stages.values().all(|s| valid(s)) {
+            return Err(format!("stages have to be 1 to {}", STAGE_COUNT));
+        }
+        Ok(rules)
+    }
+
+    /// Anywhere when the file doesn't say.
+    fn npc_stages(&self, npc: NpcId) -> &[u8] {
+        const ALL_STAGES: [u8; STAGE_COUNT] = [1, 2, 3, 4];
+        self.npc_stages
+            .get(&format!("{:?}", npc))
+            .map_or(&ALL_STAGES, |stages| stages.as_slice())
+    }
+
+    fn allows(&self, layout: &Layout) -> bool {
+        let stage_of = |name: &String| {
+            SHUFFLED_NPCS
+                .iter()
+                .position(|npc| format!("{:?}", npc) == *name)
+                .map(|index| layout.npc_stages[index])
+        };
+        let last = layout.stage_order[STAGE_COUNT - 1];
+        if self.last_stage.is_some_and(|stage| stage != last) {
+            return false;
+        }
+        let apart = self.apart.iter().all(|group| {
+            let stages: Vec<u8> = group.iter().filter_map(stage_of).collect();
+            stages
+                .iter()
+                .enumerate()
+                .all(|(i, stage)| !stages[..i].contains(stage))
+        });
+        let clear = self
+            .dead_zone
+            .keep_clear
+            .iter()
+            .filter_map(stage_of)
+            .all(|stage| stage != layout.dead_zone_stage);
+        apart && clear
+    }
+}
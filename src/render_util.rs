@@ -0,0 +1,122 @@
+use std::f32::consts::TAU;
+
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams};
+use tetra::math::Vec2;
+
+use crate::perf::{Render, RenderMut};
+
+const SHADOW_SEGMENTS: usize = 32;
+const SHADOW_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.4);
+const TEXT_SHADOW_OFFSET: Vec2<f32> = Vec2::new(2.0, 2.0);
+const BOLD_OFFSET: Vec2<f32> = Vec2::new(1.0, 0.0);
+const OUTLINE_DIAGONALS: [Vec2<f32>; 4] = [
+    Vec2::new(-1.0, -1.0),
+    Vec2::new(1.0, -1.0),
+    Vec2::new(-1.0, 1.0),
+    Vec2::new(1.0, 1.0),
+];
+
+/// Most draws one styled string may take, checked by `--check-text-draws`.
+pub const MAX_TEXT_DRAWS: usize = 5;
+
+/// How `draw_styled_text` decorates a string, one style per string.
+#[derive(Clone, Copy, Debug)]
+pub enum TextStyle {
+    Plain,
+    /// A copy behind the text, `offset` away from it.
+    Shadow {
+        offset: Vec2<f32>,
+        color: Color,
+    },
+    /// Copies on the four diagonals. Dark around light text, it reads over
+    /// the bright stages and the black combat screen alike.
+    Outline {
+        thickness: f32,
+        color: Color,
+    },
+    /// A second copy a pixel to the right, widening the strokes.
+    Bold,
+}
+
+impl TextStyle {
+    pub const SHADOW: Self = Self::Shadow {
+        offset: TEXT_SHADOW_OFFSET,
+        color: Color::BLACK,
+    };
+    pub const OUTLINE: Self = Self::Outline {
+        thickness: 1.0,
+        color: Color::BLACK,
+    };
+
+    /// Every draw the style takes, back to front, the text itself last.
+    /// Offsets grow with `text_scale` and the copies fade with the text.
+    pub fn passes(self, params: &DrawParams, text_scale: f32) -> Vec<DrawParams> {
+        let params = DrawParams {
+            scale: params.scale * text_scale,
+            ..params.clone()
+        };
+        let copy = |offset: Vec2<f32>, color: Color| DrawParams {
+            position: params.position + offset * text_scale,
+            color,
+            ..params.clone()
+        };
+        let fade = |color: Color| color.with_alpha(color.a * params.color.a);
+        let mut passes = match self {
+            TextStyle::Plain => Vec::new(),
+            TextStyle::Shadow { offset, color } => vec![copy(offset, fade(color))],
+            TextStyle::Outline { thickness, color } => OUTLINE_DIAGONALS
+                .iter()
+                .map(|diagonal| copy(*diagonal * thickness, fade(color)))
+                .collect(),
+            TextStyle::Bold => vec![copy(BOLD_OFFSET, params.color)],
+        };
+        passes.push(params);
+        passes
+    }
+}
+
+/// The unit circle every shadow is squashed from, built the first time
+/// it's asked for and kept in `cache`. Cloning a mesh only shares it.
+pub fn shadow_mesh(ctx: &mut Context, cache: &mut Option<Mesh>) -> tetra::Result<Mesh> {
+    if let Some(mesh) = cache {
+        return Ok(mesh.clone());
+    }
+    let points: Vec<Vec2<f32>> = (0..SHADOW_SEGMENTS)
+        .map(|i| {
+            let angle = i as f32 / SHADOW_SEGMENTS as f32 * TAU;
+            Vec2::new(angle.cos(), angle.sin())
+        })
+        .collect();
+    let mesh = Mesh::polygon(ctx, ShapeStyle::Fill, &points)?;
+    *cache = Some(mesh.clone());
+    Ok(mesh)
+}
+
+/// Soft ellipse on the ground centered on `pos`, `size` is its full width
+/// and height. `alpha` fades it along with the sprite casting it.
+pub fn draw_shadow(ctx: &mut Context, shadow: &Mesh, pos: Vec2<f32>, size: Vec2<f32>, alpha: f32) {
+    shadow.render(
+        ctx,
+        DrawParams::new()
+            .position(pos)
+            .scale(size / 2.0)
+            .color(Color::rgba(0.0, 0.0, 0.0, SHADOW_COLOR.a * alpha)),
+    );
+}
+
+/// `text` drawn in `style`, scaled by the text scale setting from its
+/// position. Every pass reuses the one `Text`, so its layout is built once.
+pub fn draw_styled_text(
+    ctx: &mut Context,
+    text: &mut Text,
+    params: DrawParams,
+    style: TextStyle,
+    text_scale: f32,
+) {
+    for pass in style.passes(&params, text_scale) {
+        text.render(ctx, pass);
+    }
+}
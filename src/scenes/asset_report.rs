@@ -0,0 +1,98 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::asset_check::{AssetReport, AssetStatus};
+use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+
+pub const VISIBLE_ROWS: usize = 17; // Also how far PageUp/PageDown jump
+const ROW_HEIGHT: f32 = 26.0;
+const TOP: f32 = 80.0;
+const LEFT: f32 = 40.0;
+
+/// F4 runs the check and opens the panel, or closes it. The game under it
+/// pauses, like under the dialogue log.
+pub fn toggle(state: &mut GameState) {
+    state.asset_report = match state.asset_report {
+        Some(_) => None,
+        None => Some(AssetReport::run()),
+    };
+}
+
+pub fn scroll_up(state: &mut GameState, rows: usize) {
+    if let Some(report) = &mut state.asset_report {
+        report.scroll_up(rows);
+    }
+}
+
+pub fn scroll_down(state: &mut GameState, rows: usize) {
+    if let Some(report) = &mut state.asset_report {
+        report.scroll_down(rows, VISIBLE_ROWS);
+    }
+}
+
+/// One row per asset, colored by how it did, with the totals underneath.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    let Some(report) = &state.asset_report else {
+        return Ok(());
+    };
+
+    let dim_rect = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Fill,
+        Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+    )?;
+    dim_rect.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+    );
+
+    let mut title = Text::new(
+        format!(
+            "Asset check: {}",
+            crate::asset_check::totals(&report.results)
+        ),
+        state.font(),
+    );
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(LEFT, 30.0))
+            .color(Color::WHITE),
+    );
+
+    let shown = report.results.iter().skip(report.scroll).take(VISIBLE_ROWS);
+    for (row, result) in shown.enumerate() {
+        let color = match result.status {
+            AssetStatus::Ok => Color::rgb(0.5, 0.9, 0.5),
+            AssetStatus::Missing => Color::rgb(1.0, 0.8, 0.2),
+            AssetStatus::NotInstalled => Color::rgb(0.6, 0.6, 0.6),
+            AssetStatus::Corrupt(_) => Color::rgb(1.0, 0.3, 0.3),
+        };
+        let mut line = Text::new(result.line(), state.font());
+        line.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(LEFT, TOP + row as f32 * ROW_HEIGHT))
+                .color(color),
+        );
+    }
+
+    let mut hint = Text::new("Up/Down: Scroll   F4/Esc: Close", state.font());
+    let width = hint.get_bounds(ctx).map(|b| b.width).unwrap_or(300.0);
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(
+                (SCREEN_WIDTH as f32 - width) / 2.0,
+                SCREEN_HEIGHT as f32 - 40.0,
+            ))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
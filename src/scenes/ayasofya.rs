@@ -1,64 +1,89 @@
+use rand::{Rng, RngCore};
 use tetra::Context;
-use tetra::graphics::{self, Color, DrawParams};
-use tetra::input::{self, Key};
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{self, Color, DrawParams, Rectangle};
+use tetra::input::Key;
 use tetra::math::Vec2;
 
 use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
 use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::render_util::shadow_mesh;
+use crate::scenes::overworld::{draw_player, move_player, spawn_player};
+use crate::sim::UpdateInput;
+use crate::texts::TextKey;
+use crate::world::{AYASOFYA_STAGE_INDEX, Entry, STAGE_DEFS};
 
-pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let speed = 2.0;
+// Just inside the entrance, a step up from where the player comes in
+const WARDROBE_POS: Vec2<f32> = Vec2::new(170.0, 240.0);
+const WARDROBE_SIZE: Vec2<f32> = Vec2::new(40.0, 70.0);
+const WARDROBE_RANGE: f32 = 70.0;
+const OUTFIT_COUNT: u8 = 3; // None, fes, takke
 
-    // Movement (Simple left/right/up/down)
-    if input::is_key_down(ctx, Key::W) || input::is_key_down(ctx, Key::Up) {
-        state.player.pos.y -= speed;
-    }
-    if input::is_key_down(ctx, Key::S) || input::is_key_down(ctx, Key::Down) {
-        state.player.pos.y += speed;
-    }
-    if input::is_key_down(ctx, Key::A) || input::is_key_down(ctx, Key::Left) {
-        state.player.pos.x -= speed;
-    }
-    if input::is_key_down(ctx, Key::D) || input::is_key_down(ctx, Key::Right) {
-        state.player.pos.x += speed;
-    }
+/// Puts the player inside the door and picks what they wear. Without a hat of their own they
+/// get a random loaner, which stays behind when they leave.
+pub fn enter(state: &mut GameState, rng: &mut dyn RngCore) {
+    let entrance = STAGE_DEFS[AYASOFYA_STAGE_INDEX].spawn(Entry::FromLeft);
+    state.player.pos = crate::physics::nearest_free_spot_in(
+        &STAGE_DEFS[AYASOFYA_STAGE_INDEX],
+        entrance.pos,
+        |_| true,
+    );
+    state.player.direction = entrance.facing;
+    state.world.bones.clear();
+    state.player.outfit = if state.player.owned_outfit == 0 {
+        rng.random_range(1..OUTFIT_COUNT)
+    } else {
+        state.player.owned_outfit
+    };
+}
 
-    // Boundaries
-    if state.player.pos.y < 150.0 {
-        state.player.pos.y = 150.0;
-    }
-    if state.player.pos.y > SCREEN_HEIGHT as f32 - 50.0 {
-        state.player.pos.y = SCREEN_HEIGHT as f32 - 50.0;
-    }
-    // Removed right boundary clamp to allow exit
+fn near_wardrobe(state: &GameState) -> bool {
+    state.player.pos.distance(WARDROBE_POS) < WARDROBE_RANGE
+}
 
-    // Exit Logic (Left side)
-    if state.player.pos.x < 0.0 {
-        state.scene = Scene::Desktop;
-        state.world.current_stage = 3;
-        state.player.pos.x = 400.0; // Center of stage 3 (entrance)
-        state.player.pos.y = 400.0; // Below the door
-        state.player.outfit = 0;
+/// Walking around inside, and back out to stage 3 through either side.
+pub fn step(input: &mut UpdateInput, state: &mut GameState) {
+    let start_pos = state.player.pos;
+    let room = &STAGE_DEFS[AYASOFYA_STAGE_INDEX];
+    let auto_walk = state.system.global_settings.auto_walk;
+    move_player(input, &mut state.player, room, auto_walk);
+
+    // The wardrobe cycles through the hats, and whatever is picked is kept
+    if near_wardrobe(state) && input.is_pressed(Key::F) {
+        let next = (state.player.owned_outfit + 1) % OUTFIT_COUNT;
+        state.player.owned_outfit = next;
+        state.player.outfit = next;
+        if let Some(user) = &mut state.system.current_user {
+            user.outfit = next;
+        }
+        match next {
+            1 => state.session_stats.collect("Fes"),
+            2 => state.session_stats.collect("Takke"),
+            _ => {}
+        }
+        crate::scenes::overworld::pickup_sparkle(state);
     }
 
-    // Exit Logic (Right side)
-    if state.player.pos.x > SCREEN_WIDTH as f32 {
+    // Exit Logic
+    let past_left = state.player.pos.x < 0.0 && room.left_exit;
+    let past_right = state.player.pos.x > SCREEN_WIDTH as f32 && room.right_exit;
+    if past_left || past_right {
         state.scene = Scene::Desktop;
         state.world.current_stage = 3;
-        state.player.pos.x = 400.0; // Center of stage 3 (entrance)
-        state.player.pos.y = 400.0; // Below the door
-        state.player.outfit = 0;
+        spawn_player(state, Entry::FromInterior);
+        state.player.outfit = state.player.owned_outfit; // Loaners stay here
+    } else {
+        state.player.pos.x = state.player.pos.x.clamp(0.0, SCREEN_WIDTH as f32);
     }
 
     // Ensure music is off
-    if state.world.music_playing {
-        if let Some(instance) = &mut state.world.music_instance {
-            instance.stop();
-        }
-        state.world.music_playing = false;
-    }
+    state.world.stop_music();
 
-    Ok(())
+    crate::scenes::bone::step_inside(input, state);
+
+    crate::scenes::idle::step(state, input.dt, state.player.pos != start_pos);
 }
 
 pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
@@ -71,7 +96,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         let scale_x = SCREEN_WIDTH as f32 / bg_width;
         let scale_y = SCREEN_HEIGHT as f32 / bg_height;
 
-        texture.draw(
+        texture.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(0.0, 0.0))
@@ -79,26 +104,57 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         );
     }
 
-    // Draw Player
-    let player_texture = match state.player.outfit {
-        1 => &state.player.texture_fes,
-        2 => &state.player.texture_takke,
-        _ => &state.player.texture_front,
-    };
+    draw_wardrobe(ctx, state)?;
 
-    if let Some(texture) = player_texture {
-        let width = texture.width() as f32;
-        let height = texture.height() as f32;
-        let origin = Vec2::new(width / 2.0, height / 2.0);
+    // Draw Player, a bit bigger in here
+    let shadow = shadow_mesh(ctx, &mut state.world.shadow_mesh)?;
+    draw_player(ctx, &state.player, state.player.outfit, 4.0, &shadow);
+    crate::particles::draw_batch(ctx, state.world.particles.rects())?;
 
-        texture.draw(
+    crate::scenes::bone::draw_toast(ctx, state)?;
+
+    if near_wardrobe(state) {
+        let names = &state.texts.ui.outfit_names;
+        let name = names
+            .get(state.player.owned_outfit as usize)
+            .map_or("", String::as_str);
+        let prompt = state
+            .texts
+            .fill(TextKey::WardrobePrompt, &[("outfit", &name)]);
+        let mut text = Text::new(prompt, state.font());
+        let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+        text.render(
             ctx,
             DrawParams::new()
-                .position(state.player.pos)
-                .origin(origin)
-                .scale(Vec2::new(4.0, 4.0)),
+                .position(Vec2::new(
+                    WARDROBE_POS.x - width / 2.0,
+                    WARDROBE_POS.y - WARDROBE_SIZE.y / 2.0 - 30.0,
+                ))
+                .color(Color::WHITE),
         );
     }
 
+    crate::scenes::objectives::draw_hud(ctx, state)?;
+
+    Ok(())
+}
+
+/// No art for it yet, a plain wooden cabinet.
+fn draw_wardrobe(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    let rect = Rectangle::new(
+        WARDROBE_POS.x - WARDROBE_SIZE.x / 2.0,
+        WARDROBE_POS.y - WARDROBE_SIZE.y / 2.0,
+        WARDROBE_SIZE.x,
+        WARDROBE_SIZE.y,
+    );
+    let body = Mesh::rectangle(ctx, ShapeStyle::Fill, rect)?;
+    body.render(ctx, DrawParams::new().color(Color::rgb(0.45, 0.28, 0.15)));
+    let border_color = if near_wardrobe(state) {
+        Color::rgb(1.0, 1.0, 0.0)
+    } else {
+        Color::rgb(0.25, 0.15, 0.08)
+    };
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), rect)?;
+    border.render(ctx, DrawParams::new().color(border_color));
     Ok(())
 }
@@ -0,0 +1,184 @@
+use tetra::Context;
+use tetra::graphics::DrawParams;
+use tetra::math::Vec2;
+
+use crate::actions::Action;
+use crate::defs::{Direction, NpcId, SCREEN_WIDTH};
+use crate::dialogue_log::Speaker;
+use crate::game_state::GameState;
+use crate::objectives::WorldFlag;
+use crate::perf::Render;
+use crate::sim::{Effects, UpdateInput};
+use crate::world::{BoneProjectile, StageDef};
+
+const BONE_SPEED: f32 = 360.0; // Pixels per second
+const BONE_LIFETIME: f32 = 1.5;
+const BONE_COOLDOWN: f32 = 0.5;
+const BONE_RADIUS: f32 = 8.0;
+const BONE_LENGTH: f32 = 40.0; // On screen, the texture is the tall combat bone
+const BONE_SPIN: f32 = 12.0; // Radians per second
+const MAX_BONES: usize = 3; // The cooldown and lifetime never allow more in the air
+const STAGGER_SECONDS: f32 = 2.0;
+const MISS_STREAK_ACHIEVEMENT: u32 = 10;
+const TOAST_SECONDS: f32 = 2.5;
+
+/// The reward for beating Sans.
+pub fn unlocked(state: &GameState) -> bool {
+    WorldFlag::DefeatedSans.is_set(state.world.flags)
+}
+
+/// Throwing, flight and hits. Called from the desktop step, after movement.
+pub fn step(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
+    let dt = input.dt;
+    tick_toast(state, dt);
+    state.world.bone_cooldown = (state.world.bone_cooldown - dt).max(0.0);
+
+    if unlocked(state)
+        && input.is_pressed(Action::Attack.key())
+        && state.world.bone_cooldown <= 0.0
+        && state.world.bones.len() < MAX_BONES
+    {
+        let direction = match state.player.direction {
+            Direction::Front => Vec2::new(0.0, 1.0),
+            Direction::Left => Vec2::new(-1.0, 0.0),
+            Direction::Right => Vec2::new(1.0, 0.0),
+        };
+        state.world.bones.push(BoneProjectile {
+            pos: state.player.pos,
+            velocity: direction * BONE_SPEED,
+            age: 0.0,
+            hit: false,
+            bounced: false,
+        });
+        state.world.bone_cooldown = BONE_COOLDOWN;
+    }
+
+    let stage_def = state.world.stage_def();
+    let mut bones = std::mem::take(&mut state.world.bones);
+    bones.retain_mut(|bone| {
+        bone.age += dt;
+        bone.pos += bone.velocity * dt;
+        if bone.age < BONE_LIFETIME && !out_of_bounds(bone.pos, stage_def) {
+            return match crate::physics::npc_at(bone.pos, BONE_RADIUS, &state.world) {
+                Some(npc) => hit_npc(state, bone, npc, effects),
+                None => true,
+            };
+        }
+        if !bone.hit {
+            record_miss(state);
+        }
+        false
+    });
+    state.world.bones = bones;
+}
+
+/// No throwing in the mosque, trying only earns a telling-off.
+pub fn step_inside(input: &mut UpdateInput, state: &mut GameState) {
+    tick_toast(state, input.dt);
+    if unlocked(state) && input.is_pressed(Action::Attack.key()) {
+        let scold = state.texts.ui.bone_scold.clone();
+        show_toast(state, scold);
+    }
+}
+
+fn out_of_bounds(pos: Vec2<f32>, stage_def: &StageDef) -> bool {
+    pos.x < 0.0
+        || pos.x > SCREEN_WIDTH as f32
+        || pos.y < stage_def.top_bound
+        || pos.y > stage_def.bottom_bound
+        || stage_def.walls.iter().any(|wall| wall.contains_point(pos))
+}
+
+/// Returns whether the bone keeps flying.
+fn hit_npc(
+    state: &mut GameState,
+    bone: &mut BoneProjectile,
+    npc: NpcId,
+    effects: &mut dyn Effects,
+) -> bool {
+    // Still inside him on the way back out
+    if npc == NpcId::Gaster && bone.bounced {
+        return true;
+    }
+    bone.hit = true;
+    state.world.bone_miss_streak = 0;
+
+    match npc {
+        NpcId::Crowd(index) => {
+            if let Some(pedestrian) = state.world.crowd.get_mut(index) {
+                pedestrian.stagger_timer = STAGGER_SECONDS;
+            }
+            false
+        }
+        NpcId::Rarity => {
+            // Unlike the knife, the bone doesn't care which side she's facing
+            crate::scenes::desktop::stab_rarity(state, effects);
+            false
+        }
+        NpcId::Gaster => {
+            bone.velocity = -bone.velocity;
+            bone.bounced = true;
+            if !state.world.gaster_talking && state.world.gaster_dialogue.is_none() {
+                state.world.gaster_talking = true;
+                state.world.current_gaster_dialogue = state.texts.gaster_bone_bounce.clone();
+                state
+                    .dialogue_log
+                    .push(Speaker::Gaster, &state.world.current_gaster_dialogue);
+            }
+            true
+        }
+        _ => false,
+    }
+}
+
+fn record_miss(state: &mut GameState) {
+    state.world.bone_miss_streak += 1;
+    if state.world.bone_miss_streak >= MISS_STREAK_ACHIEVEMENT
+        && !WorldFlag::MissedTenBones.is_set(state.world.flags)
+    {
+        crate::scenes::objectives::set_flag(state, WorldFlag::MissedTenBones);
+        state.session_stats.unlock("Bad to the Bone");
+        let achievement = state.texts.ui.bone_achievement.clone();
+        show_toast(state, achievement);
+    }
+}
+
+/// Note over the player's head for a couple of seconds.
+pub fn show_toast(state: &mut GameState, text: String) {
+    state.world.toast = Some(text);
+    state.world.toast_timer = TOAST_SECONDS;
+}
+
+fn tick_toast(state: &mut GameState, dt: f32) {
+    state.world.toast_timer = (state.world.toast_timer - dt).max(0.0);
+    if state.world.toast_timer <= 0.0 {
+        state.world.toast = None;
+    }
+}
+
+/// Bones in the air, spinning. Drawn over the sprites since they fly at
+/// chest height.
+pub fn draw(ctx: &mut Context, state: &GameState) {
+    let Some(texture) = &state.bone_texture else {
+        return;
+    };
+    let size = Vec2::new(texture.width() as f32, texture.height() as f32);
+    let scale = BONE_LENGTH / size.y;
+    for bone in &state.world.bones {
+        texture.render(
+            ctx,
+            DrawParams::new()
+                .position(bone.pos)
+                .origin(size / 2.0)
+                .scale(Vec2::new(scale, scale))
+                .rotation(bone.age * BONE_SPIN),
+        );
+    }
+}
+
+pub fn draw_toast(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    match &state.world.toast {
+        Some(toast) => crate::scenes::desktop::draw_bubble(ctx, state, toast),
+        None => Ok(()),
+    }
+}
@@ -1,8 +1,12 @@
 #![allow(clippy::collapsible_if)]
+use crate::actions::{Action, is_action_pressed};
 use crate::assets::{ASSET_LIST, AssetType};
-use crate::defs::{Scene, SCREEN_WIDTH, SCREEN_HEIGHT};
+use crate::defs::{Language, Scene, SCREEN_WIDTH, SCREEN_HEIGHT};
 use crate::game_state::GameState;
 use crate::global_db::GlobalSettings;
+use crate::graphics_quality::GraphicsQuality;
+use crate::perf::{Render, RenderMut};
+use crate::render_util::{TextStyle, draw_styled_text};
 use tetra::Context;
 use tetra::audio::{Sound, SoundInstance};
 use tetra::graphics::text::Text;
@@ -11,6 +15,11 @@ use tetra::input::{self, Key};
 use tetra::math::Vec2;
 
 const INTRO_FPS: f64 = 30.0;
+// Hints in the corner, a pixel-thin shadow keeps them off the intro frames
+const BOOT_TEXT_STYLE: TextStyle = TextStyle::Shadow {
+    offset: Vec2::new(1.0, 1.0),
+    color: Color::BLACK,
+};
 
 pub struct BootState {
     pub asset_index: usize,
@@ -26,6 +35,7 @@ pub struct BootState {
     
     intro_sound: Option<Sound>,
     intro_instance: Option<SoundInstance>,
+    intro_volume: f32, // Master * music volume from global.db
     initialized: bool,
     
     // Pulsing timer for "Press Enter" text
@@ -48,6 +58,7 @@ impl BootState {
             animation_ended: false,
             intro_sound: None,
             intro_instance: None,
+            intro_volume: 1.0,
             initialized: false,
             pulse_timer: 0.0,
             startup_frames: 0,
@@ -62,55 +73,68 @@ impl BootState {
         
         // Skip intro in debug/dev mode
         if cfg!(debug_assertions) {
-            println!("Debug mode detected: Skipping intro animation.");
+            log::info!("Debug mode detected: Skipping intro animation.");
             return;
         }
 
         let settings = GlobalSettings::load();
-        let (gif_path, audio_path) = if settings.language == "tr" {
+        self.intro_volume = settings.volume as f32 / 100.0 * settings.music_volume as f32 / 100.0;
+        let (gif_path, audio_path) = if settings.language == Language::Turkish {
             ("assets/intro_tr.gif", "assets/intro_tr.mp3")
         } else {
             ("assets/intro_en.gif", "assets/intro_en.mp3")
         };
 
-        // Load animation frames from GIF
-        self.load_gif(ctx, gif_path);
+        // Load animation frames from GIF, Performance mode keeps just a still
+        let frame_limit = if GraphicsQuality::from_settings(&settings).intro_animation {
+            usize::MAX
+        } else {
+            1
+        };
+        self.load_gif(ctx, gif_path, frame_limit);
 
         // Load intro audio
         match Sound::new(audio_path) {
             Ok(sound) => {
                 self.intro_sound = Some(sound);
-                println!("Loaded intro audio: {}", audio_path);
+                log::debug!("Loaded intro audio: {}", audio_path);
             }
             Err(e) => {
-                println!("Could not load intro audio '{}': {}", audio_path, e);
+                log::warn!("Could not load intro audio '{}': {}", audio_path, e);
             }
         }
 
-        // Play the audio
-        if !self.audio_muted {
-            if let Some(sound) = &self.intro_sound {
-                match sound.play_with(ctx, 1.0, 1.0) {
-                    Ok(instance) => {
-                        println!("Intro audio started.");
-                        self.intro_instance = Some(instance);
-                    }
-                    Err(e) => println!("Failed to play intro audio: {}", e),
+        // Play the audio, muted just means silent so unmuting can pick it up
+        if let Some(sound) = &self.intro_sound {
+            match sound.play_with(ctx, self.playback_volume(), 1.0) {
+                Ok(instance) => {
+                    log::debug!("Intro audio started.");
+                    self.intro_instance = Some(instance);
                 }
+                Err(e) => log::warn!("Failed to play intro audio: {}", e),
             }
         }
     }
 
-    fn load_gif(&mut self, ctx: &mut Context, path: &str) {
+    fn playback_volume(&self) -> f32 {
+        if self.audio_muted {
+            0.0
+        } else {
+            self.intro_volume
+        }
+    }
+
+    /// Decodes up to `frame_limit` frames of the GIF at `path`.
+    fn load_gif(&mut self, ctx: &mut Context, path: &str, frame_limit: usize) {
         use std::fs::File;
         use gif::{DecodeOptions, DisposalMethod};
         use tetra::graphics::TextureFormat;
 
-        println!("Loading GIF: {}", path);
+        log::debug!("Loading GIF: {}", path);
         let file = match File::open(path) {
             Ok(f) => f,
             Err(e) => {
-                println!("Failed to open GIF '{}': {}", path, e);
+                log::warn!("Failed to open GIF '{}': {}", path, e);
                 return;
             }
         };
@@ -121,7 +145,7 @@ impl BootState {
         let mut decoder = match options.read_info(file) {
             Ok(d) => d,
             Err(e) => {
-                println!("Failed to read GIF info: {}", e);
+                log::warn!("Failed to read GIF info: {}", e);
                 return;
             }
         };
@@ -163,7 +187,10 @@ impl BootState {
 
             match Texture::from_data(ctx, width as i32, height as i32, TextureFormat::Rgba8, &canvas) {
                  Ok(tex) => self.frames.push(tex),
-                 Err(e) => println!("Failed to create texture from GIF frame: {}", e),
+                 Err(e) => log::warn!("Failed to create texture from GIF frame: {}", e),
+             }
+             if self.frames.len() >= frame_limit {
+                 break;
              }
              
              // Handle disposal
@@ -186,7 +213,7 @@ impl BootState {
              }
         }
         
-        println!("Loaded {} GIF frames", self.frames.len());
+        log::debug!("Loaded {} GIF frames", self.frames.len());
     }
 }
 
@@ -203,19 +230,16 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     }
 
     // Handle Mute toggle
-    if input::is_key_pressed(ctx, Key::S) || input::is_key_pressed(ctx, Key::M) {
+    if is_action_pressed(ctx, state, Action::ToggleMute) {
         state.boot_state.audio_muted = !state.boot_state.audio_muted;
+        let volume = state.boot_state.playback_volume();
         if let Some(instance) = &mut state.boot_state.intro_instance {
-            if state.boot_state.audio_muted {
-                instance.set_volume(0.0);
-            } else {
-                instance.set_volume(1.0);
-            }
+            instance.set_volume(volume);
         }
     }
 
-    // Update animation
-    if !state.boot_state.frames.is_empty() && !state.boot_state.animation_ended {
+    // Update animation, a single still frame has nothing to loop
+    if state.boot_state.frames.len() > 1 && !state.boot_state.animation_ended {
         let dt = tetra::time::get_delta_time(ctx).as_secs_f64();
         state.boot_state.frame_timer += dt;
         
@@ -235,13 +259,11 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 }
                 
                 if let Some(sound) = &state.boot_state.intro_sound {
-                    if !state.boot_state.audio_muted {
-                        match sound.play_with(ctx, 1.0, 1.0) {
-                            Ok(instance) => {
-                                state.boot_state.intro_instance = Some(instance);
-                            }
-                            Err(e) => println!("Failed to restart intro audio: {}", e),
+                    match sound.play_with(ctx, state.boot_state.playback_volume(), 1.0) {
+                        Ok(instance) => {
+                            state.boot_state.intro_instance = Some(instance);
                         }
+                        Err(e) => log::warn!("Failed to restart intro audio: {}", e),
                     }
                 }
             }
@@ -251,35 +273,8 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     // Update pulse timer unconditionally
     state.boot_state.pulse_timer += tetra::time::get_delta_time(ctx).as_secs_f32();
 
-    // Asset Loading (one per frame for smooth animation)
-    if !state.boot_state.loading_complete {
-        if state.boot_state.asset_index < ASSET_LIST.len() {
-            let def = &ASSET_LIST[state.boot_state.asset_index];
-            
-            match def.asset_type {
-                AssetType::Texture => {
-                    if let Ok(tex) = Texture::new(ctx, def.path) {
-                        state.texture_cache.insert(def.name.to_string(), tex.clone());
-                        state.assign_texture(def.name, tex);
-                    } else {
-                        println!("Failed to load texture: {}", def.path);
-                    }
-                }
-                AssetType::Sound => {
-                    if let Ok(snd) = Sound::new(def.path) {
-                        state.sound_cache.insert(def.name.to_string(), snd.clone());
-                        state.assign_sound(def.name, snd);
-                    } else {
-                        println!("Failed to load sound: {}", def.path);
-                    }
-                }
-            }
-            
-            state.boot_state.asset_index += 1;
-        } else {
-            state.boot_state.loading_complete = true;
-        }
-    }
+    // Two assets per frame keeps the animation smooth without dragging out the boot
+    step_load_n(ctx, state, 2);
 
     // Transition Logic
     if state.boot_state.loading_complete {
@@ -290,13 +285,68 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             if let Some(instance) = &mut state.boot_state.intro_instance {
                 instance.stop();
             }
-            state.scene = Scene::Menu;
+            if std::mem::take(&mut state.session_stats.summary_after_boot) {
+                crate::scenes::session_summary::open(state);
+            } else {
+                state.scene = Scene::Menu;
+            }
         }
     }
 
     Ok(())
 }
 
+/// Loads the next asset in `ASSET_LIST` into the caches. Returns true once
+/// everything is loaded, further calls do nothing.
+fn step_load(ctx: &mut Context, state: &mut GameState) -> bool {
+    if state.boot_state.loading_complete {
+        return true;
+    }
+    let Some(def) = ASSET_LIST.get(state.boot_state.asset_index) else {
+        state.boot_state.loading_complete = true;
+        return true;
+    };
+
+    match def.asset_type {
+        AssetType::Texture => {
+            if let Ok(tex) = Texture::new(ctx, def.path) {
+                state.texture_cache.insert(def.name.to_string(), tex.clone());
+                state.assign_texture(def.name, tex);
+            } else if def.optional {
+                log::debug!("Optional texture not loaded: {}", def.path);
+            } else {
+                log::error!("Failed to load texture: {}", def.path);
+            }
+        }
+        AssetType::Sound => {
+            if let Ok(snd) = Sound::new(def.path) {
+                state.sound_cache.insert(def.name.to_string(), snd.clone());
+                state.assign_sound(def.name, snd);
+            } else if def.optional {
+                log::debug!("Optional sound not loaded: {}", def.path);
+            } else {
+                log::error!("Failed to load sound: {}", def.path);
+            }
+        }
+    }
+
+    state.boot_state.asset_index += 1;
+    if state.boot_state.asset_index >= ASSET_LIST.len() {
+        state.boot_state.loading_complete = true;
+    }
+    state.boot_state.loading_complete
+}
+
+/// Up to `n` assets this frame, for when one per frame makes the boot too slow.
+fn step_load_n(ctx: &mut Context, state: &mut GameState, n: usize) -> bool {
+    for _ in 0..n {
+        if step_load(ctx, state) {
+            return true;
+        }
+    }
+    state.boot_state.loading_complete
+}
+
 pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     graphics::clear(ctx, Color::BLACK);
 
@@ -311,12 +361,12 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         let scale_x = SCREEN_WIDTH as f32 / tex_width;
         let scale_y = SCREEN_HEIGHT as f32 / tex_height;
         
-        tex.draw(ctx, tetra::graphics::DrawParams::new()
+        tex.render(ctx, tetra::graphics::DrawParams::new()
             .scale(Vec2::new(scale_x, scale_y)));
     } else {
         // Fallback: show loading text if no frames
         let text = "GORKITALE";
-        let mut t = Text::new(text, state.font.clone());
+        let mut t = Text::new(text, state.font());
         let bounds = t.get_bounds(ctx).unwrap();
         let scale = 3.5;
         let pos = Vec2::new(
@@ -332,7 +382,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             1.0
         };
 
-        t.draw(ctx, tetra::graphics::DrawParams::new()
+        t.render(ctx, tetra::graphics::DrawParams::new()
             .position(pos)
             .scale(Vec2::new(scale, scale))
             .color(Color::rgba(1.0, 1.0, 1.0, alpha)));
@@ -349,43 +399,43 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         };
         
         let progress_text = format!("{} [{}/{}]", filename, current, total);
-        let mut text = Text::new(progress_text, state.font.clone());
+        let mut text = Text::new(progress_text, state.font());
+        let text_scale = state.text_scale();
         let bounds = text.get_bounds(ctx).unwrap();
         let pos = Vec2::new(
-            SCREEN_WIDTH as f32 - bounds.width - 10.0,
-            SCREEN_HEIGHT as f32 - bounds.height - 10.0,
+            SCREEN_WIDTH as f32 - bounds.width * text_scale - 10.0,
+            SCREEN_HEIGHT as f32 - bounds.height * text_scale - 10.0,
         );
-        // Draw with black outline for visibility
-        text.draw(ctx, tetra::graphics::DrawParams::new()
-            .position(pos + Vec2::new(1.0, 1.0))
-            .color(Color::BLACK));
-        text.draw(ctx, pos);
+        // Shadowed for visibility over the intro
+        draw_styled_text(ctx, &mut text, tetra::graphics::DrawParams::new().position(pos),
+            BOOT_TEXT_STYLE, text_scale);
     }
 
     // Draw "Press Enter" prompt
     if state.boot_state.waiting_for_input {
         let msg = "Press Enter to continue";
-        let mut text = Text::new(msg, state.font.clone());
+        let mut text = Text::new(msg, state.font());
+        let text_scale = state.text_scale();
         let bounds = text.get_bounds(ctx).unwrap();
         let pos = Vec2::new(
-            SCREEN_WIDTH as f32 - bounds.width - 20.0,
-            SCREEN_HEIGHT as f32 - bounds.height - 20.0,
+            SCREEN_WIDTH as f32 - bounds.width * text_scale - 20.0,
+            SCREEN_HEIGHT as f32 - bounds.height * text_scale - 20.0,
         );
         // Pulsing effect
         let alpha = (state.boot_state.pulse_timer * 3.0).sin() * 0.3 + 0.7;
         
-        text.draw(ctx, tetra::graphics::DrawParams::new()
-            .position(pos + Vec2::new(1.0, 1.0))
-            .color(Color::rgba(0.0, 0.0, 0.0, alpha)));
-        text.draw(ctx, tetra::graphics::DrawParams::new()
+        draw_styled_text(ctx, &mut text, tetra::graphics::DrawParams::new()
             .position(pos)
-            .color(Color::rgba(1.0, 1.0, 1.0, alpha)));
+            .color(Color::rgba(1.0, 1.0, 1.0, alpha)), BOOT_TEXT_STYLE, text_scale);
     }
 
     // Draw mute indicator
     if state.boot_state.audio_muted {
-        let mut text = Text::new("[MUTED - Press M to unmute]", state.font.clone());
-        text.draw(ctx, Vec2::new(10.0, 10.0));
+        let mut text = Text::new(
+            format!("[MUTED - Press {:?} to unmute]", Action::ToggleMute.key()),
+            state.font(),
+        );
+        text.render(ctx, Vec2::new(10.0, 10.0));
     }
 
     Ok(())
@@ -1,31 +1,66 @@
-use rand::Rng;
+use std::f32::consts::TAU;
+
+use rand::{Rng, RngCore};
 use tetra::Context;
 use tetra::graphics::mesh::{Mesh, ShapeStyle};
 use tetra::graphics::text::Text;
-use tetra::graphics::{self, Color, DrawParams, Rectangle};
-use tetra::input::{self, Key};
+use tetra::graphics::{self, Color, DrawParams, Rectangle, Texture};
+use tetra::input::Key;
 use tetra::math::Vec2;
 
-use crate::combat::{Bone, CombatTurn};
-use crate::defs::Scene;
+use crate::combat::{
+    AttackPattern, Bone, CombatData, CombatEnemy, CombatTurn, DEFAULT_COMBAT_BOX, PlayStyle,
+    TP_MAX, TpEffect,
+};
+use crate::defs::{SCREEN_WIDTH, Scene};
+use crate::dialogue_log::Speaker;
 use crate::game_state::GameState;
-
-pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+use crate::objectives::WorldFlag;
+use crate::perf::{Render, RenderMut};
+use crate::render_util::{TextStyle, draw_styled_text};
+use crate::scenes::overworld::spawn_player;
+use crate::sim::{Effect, Effects, UpdateInput};
+use crate::world::Entry;
+
+const HEART_IDLE_SPIN: f32 = 0.5; // Radians per second
+const HEART_HIT_SPIN: f32 = 25.0;
+const HEART_SPIN_SECONDS: f32 = 0.3; // Fast spin after a hit
+const ENEMY_DAMAGE_POS: Vec2<f32> = Vec2::new(460.0, 150.0); // Beside Sans's head
+pub const OUTCOME_BANNER_SECONDS: f32 = 3.0;
+const OUTCOME_BANNER_Y: f32 = 120.0; // Under the objective banner
+const BUTTON_LABELS: [&str; 5] = ["CİHAD", "TEKFİR", "ITEM", "TEBLİĞ", "TP"];
+const BUTTON_ORIGIN: Vec2<f32> = Vec2::new(100.0, 500.0);
+const BUTTON_SPACING: f32 = 140.0;
+const BUTTON_SIZE: Vec2<f32> = Vec2::new(120.0, 24.0);
+const BAR_Y: f32 = 25.0; // HP and TP bars along the top
+const BAR_HEIGHT: f32 = 20.0;
+
+/// Menus, the attack bar and Sans's turns.
+pub fn step(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
     if state.fade_alpha > 0.0 {
         state.fade_alpha -= 0.02;
     }
 
+    let dt = input.dt;
+    state.combat_data.animate_combat_box(dt);
+    state.combat_data.update_damage_numbers(dt);
+    state.combat_data.box_pulse_timer += dt;
+
     match state.combat_data.turn {
         CombatTurn::Menu => {
-            if input::is_key_pressed(ctx, Key::Left) && state.combat_data.menu_selection > 0 {
+            // The soul sits still between attacks
+            state.combat_data.heart_rotation = 0.0;
+            state.combat_data.heart_scale_pulse = 1.0;
+            state.combat_data.heart_spin_timer = 0.0;
+            state.combat_data.update_dialogue_queue(dt);
+
+            if input.is_pressed(Key::Left) && state.combat_data.menu_selection > 0 {
                 state.combat_data.menu_selection -= 1;
             }
-            if input::is_key_pressed(ctx, Key::Right) && state.combat_data.menu_selection < 3 {
+            if input.is_pressed(Key::Right) && state.combat_data.menu_selection < 4 {
                 state.combat_data.menu_selection += 1;
             }
-            if input::is_key_pressed(ctx, Key::Z)
-                || input::is_key_pressed(ctx, Key::Enter)
-                || input::is_key_pressed(ctx, Key::F)
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) || input.is_pressed(Key::F)
             {
                 match state.combat_data.menu_selection {
                     0 => {
@@ -50,31 +85,44 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         // Tebliğ (Mercy)
                         state.combat_data.turn = CombatTurn::TebligSubMenu;
                         state.combat_data.sub_menu_selection = 0;
+                        state.combat_data.mercy_opened = true;
+                    }
+                    4 => {
+                        // TP, only once the bar is full
+                        if state.combat_data.tp_full() {
+                            state.combat_data.turn = CombatTurn::TpSubMenu;
+                            state.combat_data.sub_menu_selection = 0;
+                        } else {
+                            state.combat_data.dialogue_text =
+                                "* TP barın henüz dolmadı.\n* Kemiklerin yanından sıyrıl!"
+                                    .to_string();
+                        }
                     }
                     _ => {}
                 }
             }
         }
         CombatTurn::TekfirSubMenu => {
-            if input::is_key_pressed(ctx, Key::Up) && state.combat_data.sub_menu_selection > 0 {
+            if input.is_pressed(Key::Up) && state.combat_data.sub_menu_selection > 0 {
                 state.combat_data.sub_menu_selection -= 1;
             }
-            if input::is_key_pressed(ctx, Key::Down) && state.combat_data.sub_menu_selection < 8 {
+            if input.is_pressed(Key::Down) && state.combat_data.sub_menu_selection < 8 {
                 state.combat_data.sub_menu_selection += 1;
             }
-            if input::is_key_pressed(ctx, Key::X) {
+            if input.is_pressed(Key::X) {
                 state.combat_data.turn = CombatTurn::Menu;
             }
-            if input::is_key_pressed(ctx, Key::Z) || input::is_key_pressed(ctx, Key::Enter) {
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) {
                 state.combat_data.turn = CombatTurn::ResultText;
 
                 // Update stats
                 if let Some(user) = &mut state.system.current_user {
                     user.tekfir_count += 1;
                 }
+                state.session_stats.tekfir += 1;
                 state.system.save_users();
 
-                let mut rng = rand::rng();
+                let rng = &mut *input.rng;
                 match state.combat_data.sub_menu_selection {
                     0 => {
                         // Müşrik
@@ -143,56 +191,91 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             }
         }
         CombatTurn::ItemSubMenu => {
-            if input::is_key_pressed(ctx, Key::Up) && state.combat_data.sub_menu_selection > 0 {
+            if input.is_pressed(Key::Up) && state.combat_data.sub_menu_selection > 0 {
                 state.combat_data.sub_menu_selection -= 1;
             }
-            if input::is_key_pressed(ctx, Key::Down) && state.combat_data.sub_menu_selection < 3 {
+            if input.is_pressed(Key::Down) && state.combat_data.sub_menu_selection < 3 {
                 state.combat_data.sub_menu_selection += 1;
             }
-            if input::is_key_pressed(ctx, Key::X) {
+            if input.is_pressed(Key::X) {
                 state.combat_data.turn = CombatTurn::Menu;
             }
-            if input::is_key_pressed(ctx, Key::Z) || input::is_key_pressed(ctx, Key::Enter) {
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) {
                 state.combat_data.turn = CombatTurn::ResultText;
                 match state.combat_data.sub_menu_selection {
                     0 => {
                         // Zemzem
                         state.combat_data.action_text =
-                            "Zemzem içtin.\nCanın 50 arttı!".to_string();
-                        state.player.health = (state.player.health + 50.0).min(100.0);
+                            "Zemzem içtin.\nCanın 10 arttı!".to_string();
+                        state.player.heal(10);
                     }
                     1 => {
                         // Hurma
-                        state.combat_data.action_text = "Hurma yedin.\nCanın 20 arttı!".to_string();
-                        state.player.health = (state.player.health + 20.0).min(100.0);
+                        state.combat_data.action_text = "Hurma yedin.\nCanın 4 arttı!".to_string();
+                        state.player.heal(4);
                     }
                     2 => {
                         // Zeytin
-                        state.combat_data.action_text =
-                            "Zeytin yedin.\nCanın 10 arttı!".to_string();
-                        state.player.health = (state.player.health + 10.0).min(100.0);
+                        state.combat_data.action_text = "Zeytin yedin.\nCanın 2 arttı!".to_string();
+                        state.player.heal(2);
                     }
                     3 => {
                         // Ayetel Kürsi
                         state.combat_data.action_text =
                             "Ayetel Kürsi okudun.\nCanın tamamen doldu!".to_string();
-                        state.player.health = 100.0;
+                        state.player.player_hp = state.player.player_max_hp;
                     }
                     _ => {}
                 }
             }
         }
+        CombatTurn::TpSubMenu => {
+            if input.is_pressed(Key::Up) && state.combat_data.sub_menu_selection > 0 {
+                state.combat_data.sub_menu_selection -= 1;
+            }
+            if input.is_pressed(Key::Down) && state.combat_data.sub_menu_selection < 1 {
+                state.combat_data.sub_menu_selection += 1;
+            }
+            if input.is_pressed(Key::X) {
+                state.combat_data.turn = CombatTurn::Menu;
+            }
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) {
+                let effect = if state.combat_data.sub_menu_selection == 0 {
+                    TpEffect::Heal
+                } else {
+                    TpEffect::DoubleDamage
+                };
+                if state.combat_data.spend_tp(effect) {
+                    state.combat_data.turn = CombatTurn::ResultText;
+                    match effect {
+                        TpEffect::Heal => {
+                            let amount = CombatData::tp_heal(state.player.player_max_hp);
+                            state.player.heal(amount);
+                            state.combat_data.action_text =
+                                "Gerginliğini boşalttın.\nCanın yenilendi!".to_string();
+                        }
+                        TpEffect::DoubleDamage => {
+                            state.combat_data.action_text =
+                                "Gerginliğini biledin.\nSonraki CİHAD iki kat vuracak!".to_string();
+                        }
+                    }
+                }
+            }
+        }
         CombatTurn::TebligSubMenu => {
-            if input::is_key_pressed(ctx, Key::Up) && state.combat_data.sub_menu_selection > 0 {
+            if input.is_pressed(Key::Up) && state.combat_data.sub_menu_selection > 0 {
                 state.combat_data.sub_menu_selection -= 1;
             }
-            if input::is_key_pressed(ctx, Key::Down) && state.combat_data.sub_menu_selection < 1 {
+            let option_count = teblig_options(state.combat_data.enemy).len();
+            if input.is_pressed(Key::Down)
+                && state.combat_data.sub_menu_selection + 1 < option_count
+            {
                 state.combat_data.sub_menu_selection += 1;
             }
-            if input::is_key_pressed(ctx, Key::X) {
+            if input.is_pressed(Key::X) {
                 state.combat_data.turn = CombatTurn::Menu;
             }
-            if input::is_key_pressed(ctx, Key::Z) || input::is_key_pressed(ctx, Key::Enter) {
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) {
                 match state.combat_data.sub_menu_selection {
                     0 => {
                         // Tebliğ Et
@@ -202,6 +285,7 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         if let Some(user) = &mut state.system.current_user {
                             user.teblig_count += 1;
                         }
+                        state.session_stats.teblig += 1;
                         state.system.save_users();
 
                         let acts = [
@@ -226,14 +310,43 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                             "Ona seccade serdin.\nÜstüne bastı.",
                             "Ona ezan okudun.\n'Sesin kötü' dedi.",
                         ];
-                        let mut rng = rand::rng();
+                        let rng = &mut *input.rng;
                         state.combat_data.action_text =
                             acts[rng.random_range(0..acts.len())].to_string();
+                        if state.combat_data.enemy == CombatEnemy::Sans {
+                            let line = state.texts.sans_combat_lines.sans_spare_line.clone();
+                            queue_line(state, line);
+                        }
                     }
-                    1 => {
+                    1 if !state.combat_data.enemy.is_boss() => {
                         // Kaç
-                        state.scene = Scene::Desktop;
-                        state.player.pos.x = 700.0;
+                        let cihad_count = state
+                            .system
+                            .current_user
+                            .as_ref()
+                            .map_or(0, |user| user.cihad_count);
+                        let stage = state.world.current_stage;
+                        if state
+                            .combat_data
+                            .flee_attempt(stage, cihad_count, &mut *input.rng)
+                        {
+                            log::info!(
+                                "Combat ended: fled on turn {}",
+                                state.combat_data.turn_number
+                            );
+                            state.combat_data.finish();
+                            state.combat_data.snap_combat_box(DEFAULT_COMBAT_BOX);
+                            state.scene = Scene::Desktop;
+                            match state.combat_data.return_pos {
+                                Some(pos) => state.player.pos = pos,
+                                None => spawn_player(state, Entry::FromInterior),
+                            }
+                        } else {
+                            // Costs the turn, straight on to the enemy's
+                            state.combat_data.action_text =
+                                state.texts.combat_actions.cant_escape.clone();
+                            state.combat_data.turn = CombatTurn::ResultText;
+                        }
                     }
                     _ => {}
                 }
@@ -250,9 +363,7 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     state.combat_data.action_text = "MISS".to_string();
                     state.combat_data.timer = 0.0;
                     state.combat_data.turn = CombatTurn::ResultText;
-                } else if input::is_key_pressed(ctx, Key::Z)
-                    || input::is_key_pressed(ctx, Key::Enter)
-                {
+                } else if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) {
                     state.combat_data.attack_bar_active = false;
                     let dist = (state.combat_data.attack_bar_pos - 400.0).abs();
                     let damage = if dist < 20.0 {
@@ -262,6 +373,7 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     } else {
                         0
                     };
+                    let damage = state.combat_data.fight_damage(damage);
 
                     if damage > 0 {
                         state.combat_data.action_text = format!("CİHAD! {} HASAR", damage);
@@ -270,15 +382,32 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         if let Some(user) = &mut state.system.current_user {
                             user.cihad_count += 1;
                         }
+                        state.session_stats.cihad += 1;
                         state.system.save_users();
 
                         state.combat_data.sans_shake = 10.0;
-                        state.combat_data.sans_hp -= damage;
-                        if state.combat_data.sans_hp <= 0 {
-                            state.combat_data.sans_hp = 0;
+                        state.combat_data.enemy_hp -= damage;
+                        state.combat_data.push_damage_number(
+                            damage,
+                            ENEMY_DAMAGE_POS,
+                            Color::WHITE,
+                        );
+                        let new_phase = state.combat_data.update_phase();
+                        if new_phase && state.combat_data.enemy_hp > 0 {
+                            queue_sans_phase_line(state, &mut *input.rng);
+                        }
+                        if state.combat_data.enemy_hp <= 0 {
+                            state.combat_data.enemy_hp = 0;
                             state.combat_data.dialogue_text =
-                                "welp... i'm going to grillby's.".to_string();
+                                state.combat_data.enemy.defeat_line().to_string();
                             state.combat_data.turn = CombatTurn::ResultText; // Or a win state
+                        } else if state.combat_data.mercy_opened
+                            && state.combat_data.enemy == CombatEnemy::Sans
+                        {
+                            // Offered mercy and then swung anyway, he takes it personally
+                            state.combat_data.sans_escaped = true;
+                            state.combat_data.action_text =
+                                format!("CİHAD! {} HASAR\nSans kaçtı...", damage);
                         }
                     } else {
                         state.combat_data.action_text = "MISS".to_string();
@@ -289,45 +418,84 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             }
         }
         CombatTurn::ResultText => {
-            if input::is_key_pressed(ctx, Key::Z)
-                || input::is_key_pressed(ctx, Key::Enter)
-                || input::is_key_pressed(ctx, Key::F)
+            if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) || input.is_pressed(Key::F)
             {
-                if state.combat_data.sans_hp <= 0 {
+                state
+                    .dialogue_log
+                    .push(Speaker::Narrator, &state.combat_data.action_text);
+
+                if state.combat_data.enemy_hp <= 0 {
                     // Victory transition
+                    state.dialogue_log.push(
+                        state.combat_data.enemy.speaker(),
+                        &state.combat_data.dialogue_text,
+                    );
+                    log::info!(
+                        "Combat ended: won on turn {}",
+                        state.combat_data.turn_number
+                    );
+                    state.combat_data.finish();
+                    state.combat_data.snap_combat_box(DEFAULT_COMBAT_BOX);
                     state.scene = Scene::Desktop;
-                    state.player.pos.x = 700.0;
+                    spawn_player(state, Entry::FromInterior);
+                    // Beating Sans unlocks the bone throw
+                    if state.combat_data.enemy == CombatEnemy::Sans {
+                        crate::scenes::objectives::set_flag(state, WorldFlag::DefeatedSans);
+                        state.session_stats.collect("Bone throw");
+                        crate::scenes::overworld::pickup_sparkle(state);
+                    }
+                } else if state.combat_data.sans_escaped {
+                    log::info!("Combat ended: Sans escaped, starting chase");
+                    state.combat_data.finish();
+                    state.combat_data.snap_combat_box(DEFAULT_COMBAT_BOX);
+                    state.scene = Scene::Desktop;
+                    spawn_player(state, Entry::FromInterior);
+                    crate::scenes::sans::start_chase(state, crate::scenes::sans::CHASE_SECONDS);
                 } else {
                     state.combat_data.turn = CombatTurn::SansTurn;
                     state.combat_data.timer = 0.0;
 
-                    let jokes = [
-                        "heh heh heh...",
-                        "you're gonna have a bad time.",
-                        "it's a beautiful day outside.",
-                        "birds are singing, flowers are blooming...",
-                        "on days like these, kids like you...",
-                        "should be burning in hell.",
-                        "take it easy, kid.",
-                        "don't you have anything better to do?",
-                        "i'm rooting for ya, kid.",
-                        "geeeeeet dunked on!",
-                    ];
-                    let mut rng = rand::rng();
+                    let taunts = state.combat_data.enemy.taunts();
+                    let rng = &mut *input.rng;
                     state.combat_data.dialogue_text =
-                        jokes[rng.random_range(0..jokes.len())].to_string();
+                        taunts[rng.random_range(0..taunts.len())].to_string();
+                    state.dialogue_log.push(
+                        state.combat_data.enemy.speaker(),
+                        &state.combat_data.dialogue_text,
+                    );
                 }
             }
         }
         CombatTurn::SansTurn => {
-            if state.combat_data.timer == 0.0 {
-                state.combat_data.heart_pos = Vec2::new(400.0, 395.0); // Center of box
+            let combat_box = state.combat_data.combat_box_current;
+            if state.combat_data.timer == 0.0 && !crate::scenes::combat_tutorial::is_open(state) {
+                state.combat_data.turn_number += 1;
+                state.combat_data.heart_pos = combat_box.center(); // Center of box
                 state.combat_data.heart_velocity = Vec2::zero();
                 state.combat_data.bones.clear();
 
                 // Randomize Attack Mode (0: Gravity, 1: Free Flight)
-                let mut rng = rand::rng();
+                let rng = &mut *input.rng;
                 state.combat_data.mode = rng.random_range(0..2);
+
+                // The bullet board only closes in during Sans's attacks, menus keep the full box
+                let phase_box = CombatData::combat_box_for_phase(state.combat_data.phase);
+                if rng.random_bool(0.3) {
+                    state.combat_data.pattern = AttackPattern::Corridor;
+                    state.combat_data.mode = 1; // Needs free flight to dodge falling bones
+                    state.combat_data.box_events = CombatData::corridor_events(phase_box);
+                } else {
+                    state.combat_data.pattern = AttackPattern::Standard;
+                    state.combat_data.tween_combat_box(phase_box, 0.4);
+                }
+
+                if crate::scenes::combat_tutorial::due(state) {
+                    crate::scenes::combat_tutorial::open(state);
+                }
+            }
+            // The turn holds at its first frame, before any bones, under the tutorial
+            if crate::scenes::combat_tutorial::step(input, state) {
+                return;
             }
             state.combat_data.timer += 1.0;
 
@@ -339,56 +507,78 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 state.combat_data.heart_velocity.y += 0.9;
 
                 // Jump (Snappier)
-                if input::is_key_pressed(ctx, Key::Up) && state.combat_data.can_jump {
+                if input.is_pressed(Key::Up) && state.combat_data.can_jump {
                     state.combat_data.heart_velocity.y = -13.0;
                     state.combat_data.can_jump = false;
                 }
 
                 // Fast Fall
-                if input::is_key_down(ctx, Key::Down) && !state.combat_data.can_jump {
+                if input.is_down(Key::Down) && !state.combat_data.can_jump {
                     state.combat_data.heart_velocity.y += 1.5;
                 }
 
                 // Horizontal movement
-                if input::is_key_down(ctx, Key::Left) {
+                if input.is_down(Key::Left) {
                     state.combat_data.heart_pos.x -= speed;
                 }
-                if input::is_key_down(ctx, Key::Right) {
+                if input.is_down(Key::Right) {
                     state.combat_data.heart_pos.x += speed;
                 }
 
                 // Apply velocity
                 state.combat_data.heart_pos += state.combat_data.heart_velocity;
 
-                // Floor collision (Box bottom is ~470)
-                if state.combat_data.heart_pos.y > 440.0 {
-                    state.combat_data.heart_pos.y = 440.0;
+                // Floor collision
+                let floor = combat_box.bottom() - 30.0;
+                if state.combat_data.heart_pos.y > floor {
+                    state.combat_data.heart_pos.y = floor;
                     state.combat_data.heart_velocity.y = 0.0;
                     state.combat_data.can_jump = true;
                 }
             } else {
                 // Free Flight Mode
-                if input::is_key_down(ctx, Key::Left) {
+                if input.is_down(Key::Left) {
                     state.combat_data.heart_pos.x -= speed;
                 }
-                if input::is_key_down(ctx, Key::Right) {
+                if input.is_down(Key::Right) {
                     state.combat_data.heart_pos.x += speed;
                 }
-                if input::is_key_down(ctx, Key::Up) {
+                if input.is_down(Key::Up) {
                     state.combat_data.heart_pos.y -= speed;
                 }
-                if input::is_key_down(ctx, Key::Down) {
+                if input.is_down(Key::Down) {
                     state.combat_data.heart_pos.y += speed;
                 }
             }
 
+            // Slow idle spin and a heartbeat as it moves, a fast spin right after a hit
+            let spin_speed = if state.combat_data.heart_spin_timer > 0.0 {
+                HEART_HIT_SPIN
+            } else {
+                HEART_IDLE_SPIN
+            };
+            state.combat_data.heart_spin_timer = (state.combat_data.heart_spin_timer - dt).max(0.0);
+            state.combat_data.heart_rotation += spin_speed * dt;
+            state.combat_data.heart_scale_pulse =
+                1.0 + 0.05 * (state.combat_data.heart_pos.x * 0.1).sin();
+
             // Clamp Heart to Box (Tighter bounds)
-            state.combat_data.heart_pos.x = state.combat_data.heart_pos.x.clamp(60.0, 730.0);
-            state.combat_data.heart_pos.y = state.combat_data.heart_pos.y.clamp(330.0, 460.0);
+            state.combat_data.heart_pos.x = state
+                .combat_data
+                .heart_pos
+                .x
+                .clamp(combat_box.x + 10.0, combat_box.right() - 20.0);
+            state.combat_data.heart_pos.y = state
+                .combat_data
+                .heart_pos
+                .y
+                .clamp(combat_box.y + 10.0, combat_box.bottom() - 10.0);
 
             // Spawn Bones (Complex Pattern)
-            if state.combat_data.timer % 40.0 == 0.0 {
-                let mut rng = rand::rng();
+            if state.combat_data.pattern == AttackPattern::Corridor {
+                spawn_corridor_bones(state, input.rng);
+            } else if state.combat_data.timer % 40.0 == 0.0 {
+                let rng = &mut *input.rng;
 
                 if state.combat_data.mode == 0 {
                     // Gravity Mode Patterns (Jump/Duck)
@@ -396,31 +586,39 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     match pattern {
                         0 => {
                             // Right to Left (Low)
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(800.0, 420.0),
                                 size: Vec2::new(20.0, 50.0),
                                 velocity: Vec2::new(-6.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         1 => {
                             // Left to Right (High) - Touching Top
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(-50.0, 320.0),
                                 size: Vec2::new(20.0, 90.0),
                                 velocity: Vec2::new(6.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         2 => {
                             // Both sides
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(800.0, 440.0),
                                 size: Vec2::new(20.0, 30.0),
                                 velocity: Vec2::new(-5.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(-50.0, 440.0),
                                 size: Vec2::new(20.0, 30.0),
                                 velocity: Vec2::new(5.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         _ => {}
@@ -433,54 +631,70 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     match direction {
                         0 => {
                             // Left -> Right
-                            let y_pos = rng.random_range(330.0..440.0);
-                            state.combat_data.bones.push(Bone {
+                            let y_pos =
+                                rng.random_range(combat_box.y + 10.0..combat_box.bottom() - 30.0);
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(-50.0, y_pos),
                                 size: Vec2::new(100.0, 10.0), // Thinner, longer
                                 velocity: Vec2::new(7.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         1 => {
                             // Right -> Left
-                            let y_pos = rng.random_range(330.0..440.0);
-                            state.combat_data.bones.push(Bone {
+                            let y_pos =
+                                rng.random_range(combat_box.y + 10.0..combat_box.bottom() - 30.0);
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(800.0, y_pos),
                                 size: Vec2::new(100.0, 10.0),
                                 velocity: Vec2::new(-7.0, 0.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         2 => {
                             // Top -> Bottom
-                            let x_pos = rng.random_range(60.0..730.0);
-                            state.combat_data.bones.push(Bone {
+                            let x_pos =
+                                rng.random_range(combat_box.x + 10.0..combat_box.right() - 20.0);
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(x_pos, 250.0), // Above box
                                 size: Vec2::new(10.0, 100.0), // Vertical
                                 velocity: Vec2::new(0.0, 5.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         3 => {
                             // Bottom -> Top
-                            let x_pos = rng.random_range(60.0..730.0);
-                            state.combat_data.bones.push(Bone {
+                            let x_pos =
+                                rng.random_range(combat_box.x + 10.0..combat_box.right() - 20.0);
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(x_pos, 500.0), // Below box
                                 size: Vec2::new(10.0, 100.0),
                                 velocity: Vec2::new(0.0, -5.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         4 => {
                             // Top-Left -> Diagonal Down-Right
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(0.0, 250.0),
                                 size: Vec2::new(15.0, 60.0),
                                 velocity: Vec2::new(4.0, 4.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         5 => {
                             // Bottom-Left -> Diagonal Up-Right
-                            state.combat_data.bones.push(Bone {
+                            state.combat_data.bones.spawn(Bone {
                                 pos: Vec2::new(0.0, 500.0),
                                 size: Vec2::new(15.0, 60.0),
                                 velocity: Vec2::new(4.0, -4.0),
+                                anchored: false,
+                                grazed: false,
                             });
                         }
                         _ => {}
@@ -489,61 +703,64 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             }
 
             // Update Bones & Collision
-            let heart_rect = Rectangle::new(
-                state.combat_data.heart_pos.x,
-                state.combat_data.heart_pos.y,
-                10.0,
-                10.0,
-            );
-
-            let bones = &mut state.combat_data.bones;
-            let mut hit = false;
-
-            let mut i = 0;
-            while i < bones.len() {
-                let velocity = bones[i].velocity;
-                bones[i].pos += velocity;
+            let (hit, grazes) = state.combat_data.update_bones();
 
-                let bone_rect = Rectangle::new(
-                    bones[i].pos.x,
-                    bones[i].pos.y,
-                    bones[i].size.x,
-                    bones[i].size.y,
-                );
-
-                if heart_rect.intersects(&bone_rect) {
-                    hit = true;
+            if hit {
+                // Contact only costs HP every few frames, pop a number when it does
+                if state.combat_data.heart_spin_timer <= 0.0 {
+                    state.combat_data.heart_rotation = input.rng.random_range(TAU..TAU * 3.0);
+                    state.combat_data.heart_spin_timer = HEART_SPIN_SECONDS;
                 }
 
-                // Remove if out of bounds
-                if bones[i].pos.x < -50.0
-                    || bones[i].pos.x > 850.0
-                    || bones[i].pos.y < 200.0
-                    || bones[i].pos.y > 600.0
-                {
-                    bones.remove(i);
-                } else {
-                    i += 1;
+                let hp_before = state.player.player_hp;
+                state.player.tick_damage(5);
+                if state.player.player_hp < hp_before {
+                    state.combat_data.damage_taken += hp_before - state.player.player_hp;
+                    state.combat_data.push_damage_number(
+                        hp_before - state.player.player_hp,
+                        state.combat_data.heart_pos + Vec2::new(5.0, -10.0),
+                        Color::RED,
+                    );
                 }
             }
 
-            if hit {
-                state.player.health -= 1.0;
+            if grazes > 0 {
+                for _ in 0..grazes {
+                    state.combat_data.add_graze();
+                    let quality = state.graphics_quality();
+                    state.combat_data.emit_graze_sparks(quality);
+                }
+                effects.apply(state, Effect::GrazeSound);
             }
 
-            if state.player.health <= 0.0 {
-                state.player.health = 0.0;
-                state.game_over_state = crate::game_state::GameOverState::new();
+            state.combat_data.graze_sparks.update(dt);
+
+            if state.player.player_hp <= 0 {
+                log::info!(
+                    "Combat ended: died on turn {}",
+                    state.combat_data.turn_number
+                );
+                let cause = state.combat_data.enemy.death_cause();
+                state.system.record_death(cause);
+                state
+                    .system
+                    .generate_kernel_panic(&state.system.current_user.clone(), cause);
+                state.player.player_hp = 0;
+                state.player.exposure = 0.0;
+                state.world.stop_music();
+                state.game_over_state = crate::game_state::GameOverState::after_death(cause);
                 state.scene = crate::defs::Scene::KernelPanic;
 
-                // Reset Game State on Death
-                state.world.current_stage = 1;
+                // Reset Game State on Death, back to the leftmost stage
+                let first_stage = state.world.layout.first_stage();
+                state.world.current_stage = first_stage;
                 state.player.pos = Vec2::new(400.0, 300.0);
                 state.player.direction = crate::defs::Direction::Front;
 
                 // Reset User Progress if logged in
                 if let Some(user) = &mut state.system.current_user {
-                    user.current_stage = 1;
+                    user.current_stage = first_stage as u32;
+                    user.music_on = false;
                     // We also need to update the user in the main list
                     if let Some(idx) = state
                         .system
@@ -551,7 +768,7 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         .iter()
                         .position(|u| u.username == user.username)
                     {
-                        state.system.users[idx].current_stage = 1;
+                        state.system.users[idx].current_stage = first_stage as u32;
                         state.system.save_users();
                     }
                 }
@@ -562,8 +779,14 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 state.combat_data.turn = CombatTurn::Menu;
                 state.combat_data.dialogue_text =
                     "You feel your sins crawling on your back.".to_string();
+                state
+                    .dialogue_log
+                    .push(Speaker::Narrator, &state.combat_data.dialogue_text);
                 state.combat_data.bones.clear();
                 state.combat_data.mode = 0; // Reset to default
+                state.combat_data.pattern = AttackPattern::Standard;
+                state.combat_data.box_events.clear();
+                state.combat_data.tween_combat_box(DEFAULT_COMBAT_BOX, 0.3);
             }
         }
     }
@@ -571,45 +794,193 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     if state.combat_data.sans_shake > 0.0 {
         state.combat_data.sans_shake -= 0.5;
     }
+}
+
+/// The Mercy submenu. Kaç is only offered against enemies that aren't bosses.
+fn teblig_options(enemy: CombatEnemy) -> &'static [&'static str] {
+    if enemy.is_boss() {
+        &["* Tebliğ Et"]
+    } else {
+        &["* Tebliğ Et", "* Kaç"]
+    }
+}
+
+/// Queues one of Sans's lines for the phase he's in. Shown in the menu
+/// once his turn is over.
+pub fn queue_sans_phase_line(state: &mut GameState, rng: &mut dyn RngCore) {
+    if state.combat_data.enemy != CombatEnemy::Sans {
+        return;
+    }
+    let lines = state
+        .texts
+        .sans_combat_lines
+        .for_phase(state.combat_data.phase);
+    if lines.is_empty() {
+        return;
+    }
+    let line = lines[rng.random_range(0..lines.len())].clone();
+    queue_line(state, line);
+}
+
+fn queue_line(state: &mut GameState, line: String) {
+    state
+        .dialogue_log
+        .push(state.combat_data.enemy.speaker(), &line);
+    if state.combat_data.combat_dialogue_queue.is_empty() {
+        state.combat_data.dialogue_display_timer = 0.0;
+    }
+    state.combat_data.combat_dialogue_queue.push_back(line);
+}
+
+/// "Spared!" in gold or "Defeated!" in red on the desktop after a fight,
+/// see GameState::apply_combat_outcome.
+pub fn draw_outcome_banner(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    let Some(path) = state.world.outcome_banner else {
+        return Ok(());
+    };
+    if state.world.outcome_banner_timer <= 0.0 {
+        return Ok(());
+    }
+    let (label, color) = match path {
+        PlayStyle::Pacifist => (&state.texts.ui.combat_spared, Color::rgb(1.0, 0.84, 0.0)),
+        PlayStyle::Genocide => (&state.texts.ui.combat_defeated, Color::rgb(1.0, 0.2, 0.2)),
+        PlayStyle::Neutral => return Ok(()),
+    };
 
+    // Fades out over the last second
+    let alpha = state.world.outcome_banner_timer.min(1.0);
+    let mut text = Text::new(label.as_str(), state.font());
+    let bounds = text
+        .get_bounds(ctx)
+        .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0));
+    let rect = Rectangle::new(
+        (SCREEN_WIDTH as f32 - bounds.width) / 2.0 - 12.0,
+        OUTCOME_BANNER_Y,
+        bounds.width + 24.0,
+        bounds.height + 16.0,
+    );
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, rect)?;
+    fill.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, alpha * 0.8)),
+    );
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), rect)?;
+    border.render(ctx, DrawParams::new().color(color.with_alpha(alpha)));
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(rect.x + 12.0, rect.y + 8.0))
+            .color(color.with_alpha(alpha)),
+    );
     Ok(())
 }
 
+/// Bones for the corridor pattern. Falling bones are anchored to the board
+/// so they slide with it, the ones from the right fly in world space.
+fn spawn_corridor_bones(state: &mut GameState, rng: &mut dyn RngCore) {
+    let combat_box = state.combat_data.combat_box_current;
+    let timer = state.combat_data.timer;
+
+    if (40.0..200.0).contains(&timer) && timer % 25.0 == 0.0 {
+        let x = rng.random_range(10.0..combat_box.width - 20.0);
+        state.combat_data.bones.spawn(Bone::in_box(
+            combat_box,
+            Vec2::new(x, -60.0),
+            Vec2::new(10.0, 60.0),
+            Vec2::new(0.0, 4.0),
+        ));
+    } else if (220.0..360.0).contains(&timer) && timer % 30.0 == 0.0 {
+        let y = rng.random_range(combat_box.y + 10.0..combat_box.bottom() - 20.0);
+        state.combat_data.bones.spawn(Bone {
+            pos: Vec2::new(800.0, y),
+            size: Vec2::new(60.0, 10.0),
+            velocity: Vec2::new(-6.0, 0.0),
+            anchored: false,
+            grazed: false,
+        });
+    }
+}
+
 pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     graphics::clear(ctx, Color::BLACK);
 
-    // Draw Sans
+    // Draw the enemy
     let shake_x = if state.combat_data.sans_shake > 0.0 {
         rand::rng().random_range(-5.0..5.0)
     } else {
         0.0
     };
 
-    if let Some(sans_texture) = &state.world.sans_combat_texture {
-        let s_width = sans_texture.width() as f32;
-        let s_height = sans_texture.height() as f32;
+    // Eilish only has her 1024x1536 overworld art
+    let (enemy_texture, enemy_scale) = match state.combat_data.enemy {
+        CombatEnemy::Sans => (&state.world.sans_combat_texture, 4.0),
+        CombatEnemy::Eilish => (&state.world.eilish_texture, 0.15),
+    };
+    if let Some(texture) = enemy_texture {
+        let s_width = texture.width() as f32;
+        let s_height = texture.height() as f32;
         let s_origin = Vec2::new(s_width / 2.0, s_height / 2.0);
 
-        sans_texture.draw(
+        texture.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(400.0 + shake_x, 200.0))
                 .origin(s_origin)
-                .scale(Vec2::new(4.0, 4.0)),
+                .scale(Vec2::new(enemy_scale, enemy_scale)),
         );
     }
 
-    // Draw UI Box
-    let box_rect = Rectangle::new(50.0, 320.0, 700.0, 150.0);
-    let box_mesh = Mesh::rectangle(ctx, ShapeStyle::Stroke(4.0), box_rect)?;
-    box_mesh.draw(ctx, DrawParams::new().color(Color::WHITE));
+    // Draw UI Box, only rebuilding the border when the board changed
+    let box_rect = state.combat_data.combat_box_current;
+    let cached =
+        matches!(&state.combat_data.box_border_mesh, Some((rect, _, _)) if *rect == box_rect);
+    if !cached {
+        let mesh = Mesh::rectangle(ctx, ShapeStyle::Stroke(4.0), box_rect)?;
+        let halo_rect = Rectangle::new(
+            box_rect.x - 4.0,
+            box_rect.y - 4.0,
+            box_rect.width + 8.0,
+            box_rect.height + 8.0,
+        );
+        let halo = Mesh::rectangle(ctx, ShapeStyle::Stroke(6.0), halo_rect)?;
+        state.combat_data.box_border_mesh = Some((box_rect, mesh, halo));
+    }
+
+    // The border breathes, and runs hotter as Sans's phases go by
+    let pulse = crate::easing::ease_in_out_cubic(
+        (state.combat_data.box_pulse_timer * 3.0).sin() * 0.5 + 0.5,
+    );
+    let phase = state.combat_data.phase;
+    let (green, blue) = if phase >= 2 {
+        (
+            0.3 * (state.combat_data.phase_count + 1 - phase) as f32,
+            0.0,
+        )
+    } else {
+        (1.0, 1.0)
+    };
+    if let Some((_, box_mesh, halo)) = &state.combat_data.box_border_mesh {
+        halo.render(
+            ctx,
+            DrawParams::new().color(Color::rgba(1.0, green, blue, pulse * 0.2)),
+        );
+        box_mesh.render(
+            ctx,
+            DrawParams::new().color(Color::rgba(1.0, green, blue, 0.5 + pulse * 0.5)),
+        );
+    }
 
     // Draw Text inside box
     let text_pos = Vec2::new(70.0, 340.0);
     match state.combat_data.turn {
         CombatTurn::Menu => {
-            let mut t = Text::new(&state.combat_data.dialogue_text, state.font.clone());
-            t.draw(
+            // A queued line from Sans takes the box until it runs out
+            let text = state
+                .combat_data
+                .queued_dialogue()
+                .unwrap_or(&state.combat_data.dialogue_text);
+            let mut t = Text::new(text, state.font());
+            t.render(
                 ctx,
                 DrawParams::new().position(text_pos).color(Color::WHITE),
             );
@@ -633,8 +1004,8 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 let x = 100.0 + col as f32 * 220.0;
                 let y = 340.0 + row as f32 * 30.0;
 
-                let mut t = Text::new(*opt, state.font.clone());
-                t.draw(
+                let mut t = Text::new(*opt, state.font());
+                t.render(
                     ctx,
                     DrawParams::new()
                         .position(Vec2::new(x, y))
@@ -643,7 +1014,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 
                 if state.combat_data.sub_menu_selection == i {
                     if let Some(heart_tex) = &state.heart_texture {
-                        heart_tex.draw(
+                        heart_tex.render(
                             ctx,
                             DrawParams::new()
                                 .position(Vec2::new(x - 30.0, y + 5.0))
@@ -654,7 +1025,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         let heart_rect = Rectangle::new(x - 30.0, y + 5.0, 10.0, 10.0);
                         let heart_mesh =
                             Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
-                        heart_mesh.draw(ctx, DrawParams::new().color(Color::RED));
+                        heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
                     }
                 }
             }
@@ -662,8 +1033,37 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         CombatTurn::ItemSubMenu => {
             let options = ["* Zemzem", "* Hurma", "* Zeytin", "* Ayetel Kürsi"];
             for (i, opt) in options.iter().enumerate() {
-                let mut t = Text::new(*opt, state.font.clone());
-                t.draw(
+                let mut t = Text::new(*opt, state.font());
+                t.render(
+                    ctx,
+                    DrawParams::new()
+                        .position(Vec2::new(100.0, 340.0 + i as f32 * 30.0))
+                        .color(Color::WHITE),
+                );
+
+                if state.combat_data.sub_menu_selection == i {
+                    if let Some(heart_tex) = &state.heart_texture {
+                        heart_tex.render(
+                            ctx,
+                            DrawParams::new()
+                                .position(Vec2::new(70.0, 345.0 + i as f32 * 30.0))
+                                .scale(Vec2::new(0.08, 0.08))
+                                .color(Color::RED),
+                        );
+                    } else {
+                        let heart_rect = Rectangle::new(70.0, 345.0 + i as f32 * 30.0, 10.0, 10.0);
+                        let heart_mesh =
+                            Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
+                        heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
+                    }
+                }
+            }
+        }
+        CombatTurn::TpSubMenu => {
+            let options = ["* Şifa", "* Çift Hasar"];
+            for (i, opt) in options.iter().enumerate() {
+                let mut t = Text::new(*opt, state.font());
+                t.render(
                     ctx,
                     DrawParams::new()
                         .position(Vec2::new(100.0, 340.0 + i as f32 * 30.0))
@@ -672,7 +1072,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 
                 if state.combat_data.sub_menu_selection == i {
                     if let Some(heart_tex) = &state.heart_texture {
-                        heart_tex.draw(
+                        heart_tex.render(
                             ctx,
                             DrawParams::new()
                                 .position(Vec2::new(70.0, 345.0 + i as f32 * 30.0))
@@ -683,16 +1083,16 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         let heart_rect = Rectangle::new(70.0, 345.0 + i as f32 * 30.0, 10.0, 10.0);
                         let heart_mesh =
                             Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
-                        heart_mesh.draw(ctx, DrawParams::new().color(Color::RED));
+                        heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
                     }
                 }
             }
         }
         CombatTurn::TebligSubMenu => {
-            let options = ["* Tebliğ Et", "* Kaç"];
+            let options = teblig_options(state.combat_data.enemy);
             for (i, opt) in options.iter().enumerate() {
-                let mut t = Text::new(*opt, state.font.clone());
-                t.draw(
+                let mut t = Text::new(*opt, state.font());
+                t.render(
                     ctx,
                     DrawParams::new()
                         .position(Vec2::new(100.0, 340.0 + i as f32 * 30.0))
@@ -701,7 +1101,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 
                 if state.combat_data.sub_menu_selection == i {
                     if let Some(heart_tex) = &state.heart_texture {
-                        heart_tex.draw(
+                        heart_tex.render(
                             ctx,
                             DrawParams::new()
                                 .position(Vec2::new(70.0, 345.0 + i as f32 * 30.0))
@@ -712,7 +1112,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                         let heart_rect = Rectangle::new(70.0, 345.0 + i as f32 * 30.0, 10.0, 10.0);
                         let heart_mesh =
                             Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
-                        heart_mesh.draw(ctx, DrawParams::new().color(Color::RED));
+                        heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
                     }
                 }
             }
@@ -740,7 +1140,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     glow_height,
                 );
                 let glow_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, glow_rect).unwrap();
-                glow_mesh.draw(
+                glow_mesh.render(
                     ctx,
                     DrawParams::new().color(Color::rgba(0.5, 0.5, 0.5, 0.3)),
                 );
@@ -760,7 +1160,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 let pulse = (state.combat_data.timer * 0.1).sin().abs();
                 let target_color =
                     Color::rgb(0.2 + 0.3 * pulse, 0.2 + 0.3 * pulse, 0.2 + 0.3 * pulse);
-                target_mesh.draw(ctx, DrawParams::new().color(target_color));
+                target_mesh.render(ctx, DrawParams::new().color(target_color));
 
                 // Center "Perfect" line (Double line)
                 let line_h = 140.0;
@@ -770,7 +1170,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     Rectangle::new(center_x - 2.0, center_y - line_h / 2.0, 4.0, line_h),
                 )
                 .unwrap();
-                c_line1.draw(ctx, DrawParams::new().color(Color::WHITE));
+                c_line1.render(ctx, DrawParams::new().color(Color::WHITE));
 
                 // Moving Bar
                 let bar_x = state.combat_data.attack_bar_pos;
@@ -785,7 +1185,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                             Rectangle::new(bar_x - offset, center_y - bar_h / 2.0, bar_w, bar_h);
                         let trail_mesh =
                             Mesh::rectangle(ctx, ShapeStyle::Fill, trail_rect).unwrap();
-                        trail_mesh.draw(
+                        trail_mesh.render(
                             ctx,
                             DrawParams::new().color(Color::rgba(
                                 1.0,
@@ -815,22 +1215,22 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     Color::WHITE
                 };
 
-                bar_mesh.draw(ctx, DrawParams::new().color(bar_color));
+                bar_mesh.render(ctx, DrawParams::new().color(bar_color));
 
                 // Outline
                 let bar_outline = Mesh::rectangle(ctx, ShapeStyle::Stroke(3.0), bar_rect).unwrap();
-                bar_outline.draw(ctx, DrawParams::new().color(Color::BLACK));
+                bar_outline.render(ctx, DrawParams::new().color(Color::BLACK));
             } else {
-                let mut t = Text::new(&state.combat_data.action_text, state.font.clone());
-                t.draw(
+                let mut t = Text::new(&state.combat_data.action_text, state.font());
+                t.render(
                     ctx,
                     DrawParams::new().position(text_pos).color(Color::WHITE),
                 );
             }
         }
         CombatTurn::ResultText => {
-            let mut t = Text::new(&state.combat_data.action_text, state.font.clone());
-            t.draw(
+            let mut t = Text::new(&state.combat_data.action_text, state.font());
+            t.render(
                 ctx,
                 DrawParams::new().position(text_pos).color(Color::WHITE),
             );
@@ -839,13 +1239,13 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             // Draw Dialogue Bubble
             let bubble_rect = Rectangle::new(450.0, 100.0, 200.0, 80.0);
             let bubble_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, bubble_rect).unwrap();
-            bubble_mesh.draw(ctx, DrawParams::new().color(Color::WHITE));
+            bubble_mesh.render(ctx, DrawParams::new().color(Color::WHITE));
 
             let bubble_border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), bubble_rect).unwrap();
-            bubble_border.draw(ctx, DrawParams::new().color(Color::BLACK));
+            bubble_border.render(ctx, DrawParams::new().color(Color::BLACK));
 
-            let mut t = Text::new("You're gonna\nhave a bad time.", state.font.clone());
-            t.draw(
+            let mut t = Text::new("You're gonna\nhave a bad time.", state.font());
+            t.render(
                 ctx,
                 DrawParams::new()
                     .position(Vec2::new(460.0, 110.0))
@@ -854,14 +1254,27 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 
             // Draw Heart
             // Clip to box
-            graphics::set_scissor(ctx, Rectangle::new(50, 320, 700, 150));
+            graphics::set_scissor(
+                ctx,
+                Rectangle::new(
+                    box_rect.x as i32,
+                    box_rect.y as i32,
+                    box_rect.width as i32,
+                    box_rect.height as i32,
+                ),
+            );
 
             if let Some(heart_tex) = &state.heart_texture {
-                heart_tex.draw(
+                // Spun around its middle, so offset the top-left position by half its size
+                let size = Vec2::new(heart_tex.width() as f32, heart_tex.height() as f32);
+                let scale = 0.1 * state.combat_data.heart_scale_pulse; // Scaled down further
+                heart_tex.render(
                     ctx,
                     DrawParams::new()
-                        .position(state.combat_data.heart_pos)
-                        .scale(Vec2::new(0.1, 0.1)) // Scaled down further
+                        .position(state.combat_data.heart_pos + size * 0.05)
+                        .origin(size / 2.0)
+                        .rotation(state.combat_data.heart_rotation)
+                        .scale(Vec2::new(scale, scale))
                         .color(Color::RED),
                 );
             } else {
@@ -873,132 +1286,273 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                     10.0,
                 );
                 let heart_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
-                heart_mesh.draw(ctx, DrawParams::new().color(Color::RED));
-            }
-
-            // Draw Bones
-            for bone in &state.combat_data.bones {
-                if let Some(bone_tex) = &state.bone_texture {
-                    // Stretch bone texture to fit size
-                    // Assuming bone texture is vertical
-                    let scale_x = bone.size.x / bone_tex.width() as f32;
-                    let scale_y = bone.size.y / bone_tex.height() as f32;
-
-                    bone_tex.draw(
-                        ctx,
-                        DrawParams::new()
-                            .position(bone.pos)
-                            .scale(Vec2::new(scale_x, scale_y))
-                            .color(Color::WHITE),
-                    );
-                } else {
-                    let bone_rect =
-                        Rectangle::new(bone.pos.x, bone.pos.y, bone.size.x, bone.size.y);
-                    let bone_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, bone_rect).unwrap();
-                    bone_mesh.draw(ctx, DrawParams::new().color(Color::WHITE));
-                }
+                heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
             }
 
+            draw_projectiles(ctx, &state.combat_data, state.bone_texture.as_ref());
+
+            // Graze sparks
+            crate::particles::draw_batch(ctx, state.combat_data.graze_sparks.rects())?;
+
             graphics::reset_scissor(ctx);
         }
     }
 
-    // Draw Buttons (Fight, Act, Item, Mercy)
-    let buttons = ["CİHAD", "TEKFİR", "ITEM", "TEBLİĞ"];
-    for (i, btn) in buttons.iter().enumerate() {
-        let x = 100.0 + i as f32 * 160.0;
-        let y = 500.0;
-        let color = if state.combat_data.turn == CombatTurn::Menu
-            && state.combat_data.menu_selection == i
-        {
+    // Outside the scissor so numbers can rise past the board
+    for number in &state.combat_data.active_damage_numbers {
+        let remaining = 1.0 - number.age / number.lifetime;
+        let mut text = Text::new(number.value.to_string(), state.font());
+        let bounds = text
+            .get_bounds(ctx)
+            .unwrap_or(Rectangle::new(0.0, 0.0, 10.0, 16.0));
+        draw_styled_text(
+            ctx,
+            &mut text,
+            DrawParams::new()
+                .position(number.pos)
+                .origin(Vec2::new(bounds.width / 2.0, bounds.height / 2.0))
+                .scale(Vec2::broadcast(1.0 + 0.5 * remaining))
+                .color(number.color.with_alpha(remaining)),
+            TextStyle::SHADOW,
+            state.text_scale(),
+        );
+    }
+
+    draw_hud(ctx, state)?;
+
+    crate::scenes::combat_tutorial::draw(ctx, state);
+
+    Ok(())
+}
+
+/// The bones, clipped to the board by the caller. Drawn as white boxes
+/// when the bone texture is missing.
+fn draw_projectiles(ctx: &mut Context, combat_data: &CombatData, bone_texture: Option<&Texture>) {
+    for bone in combat_data.bones.iter() {
+        if let Some(bone_tex) = bone_texture {
+            let texture_size = Vec2::new(bone_tex.width() as f32, bone_tex.height() as f32);
+            bone_tex.render(ctx, bone_params(bone, texture_size));
+        } else {
+            let bone_rect = Rectangle::new(bone.pos.x, bone.pos.y, bone.size.x, bone.size.y);
+            let bone_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, bone_rect).unwrap();
+            bone_mesh.render(ctx, DrawParams::new().color(Color::WHITE));
+        }
+    }
+}
+
+/// The bone texture stretched over the bone's box, whatever its size.
+fn bone_params(bone: &Bone, texture_size: Vec2<f32>) -> DrawParams {
+    DrawParams::new()
+        .position(bone.pos)
+        .scale(bone.size / texture_size)
+        .color(Color::WHITE)
+}
+
+/// Where the `index`th action button sits, its label at the top left.
+fn button_rect(index: usize) -> Rectangle {
+    Rectangle::new(
+        BUTTON_ORIGIN.x + index as f32 * BUTTON_SPACING,
+        BUTTON_ORIGIN.y,
+        BUTTON_SIZE.x,
+        BUTTON_SIZE.y,
+    )
+}
+
+/// Background and filled part of a bar along the top, the fill None when
+/// it's empty. `value` is clamped, overkill doesn't draw backwards.
+fn bar_rects(x: f32, width: f32, value: f32, max: f32) -> (Rectangle, Option<Rectangle>) {
+    let back = Rectangle::new(x, BAR_Y, width, BAR_HEIGHT);
+    let share = if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let fill = (share > 0.0).then(|| Rectangle::new(x, BAR_Y, width * share, BAR_HEIGHT));
+    (back, fill)
+}
+
+fn draw_bar(
+    ctx: &mut Context,
+    (back, fill): (Rectangle, Option<Rectangle>),
+    back_color: Color,
+    fill_color: Color,
+) -> tetra::Result {
+    let back_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, back)?;
+    back_mesh.render(ctx, DrawParams::new().color(back_color));
+    if let Some(fill) = fill {
+        let fill_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, fill)?;
+        fill_mesh.render(ctx, DrawParams::new().color(fill_color));
+    }
+    Ok(())
+}
+
+/// The action buttons along the bottom and the HP and TP bars along the top.
+fn draw_hud(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    // Draw Buttons (Fight, Act, Item, Mercy, TP)
+    for (i, btn) in BUTTON_LABELS.iter().enumerate() {
+        let rect = button_rect(i);
+        let selected =
+            state.combat_data.turn == CombatTurn::Menu && state.combat_data.menu_selection == i;
+        let color = if selected {
             Color::rgb(1.0, 1.0, 0.0) // Yellow
         } else {
             Color::rgb(1.0, 0.5, 0.0) // Orange
         };
 
-        let mut t = Text::new(*btn, state.font.clone());
-        t.draw(
+        let mut t = Text::new(*btn, state.font());
+        t.render(
             ctx,
-            DrawParams::new().position(Vec2::new(x, y)).color(color),
+            DrawParams::new()
+                .position(Vec2::new(rect.x, rect.y))
+                .color(color),
         );
 
         // Draw Heart Cursor
-        if state.combat_data.turn == CombatTurn::Menu && state.combat_data.menu_selection == i {
+        if selected {
             if let Some(heart_tex) = &state.heart_texture {
-                heart_tex.draw(
+                heart_tex.render(
                     ctx,
                     DrawParams::new()
-                        .position(Vec2::new(x - 25.0, y + 2.0))
+                        .position(Vec2::new(rect.x - 25.0, rect.y + 2.0))
                         .scale(Vec2::new(0.08, 0.08))
                         .color(Color::RED),
                 );
             } else {
-                let heart_rect = Rectangle::new(x - 20.0, y + 5.0, 10.0, 10.0);
+                let heart_rect = Rectangle::new(rect.x - 20.0, rect.y + 5.0, 10.0, 10.0);
                 let heart_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, heart_rect).unwrap();
-                heart_mesh.draw(ctx, DrawParams::new().color(Color::RED));
+                heart_mesh.render(ctx, DrawParams::new().color(Color::RED));
             }
         }
     }
 
     // Draw Sans Health (Top Left)
     // HP Text
-    let mut sans_hp_label = Text::new("SANS HP", state.font.clone());
-    sans_hp_label.draw(
+    let mut sans_hp_label = Text::new(
+        format!("{} HP", state.combat_data.enemy.name()),
+        state.font(),
+    );
+    draw_styled_text(
         ctx,
+        &mut sans_hp_label,
         DrawParams::new()
             .position(Vec2::new(20.0, 20.0))
             .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
     );
 
-    // HP Bar Background (Dark Gray)
-    let sans_max_bar_width = 200.0;
-    let sans_bar_bg_rect = Rectangle::new(120.0, 25.0, sans_max_bar_width, 20.0);
-    let sans_bar_bg_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, sans_bar_bg_rect)?;
-    sans_bar_bg_mesh.draw(ctx, DrawParams::new().color(Color::rgb(0.2, 0.2, 0.2)));
-
-    // HP Bar Foreground (Blue)
-    let sans_current_bar_width = (state.combat_data.sans_hp as f32
-        / state.combat_data.sans_max_hp as f32)
-        * sans_max_bar_width;
-    if sans_current_bar_width > 0.0 {
-        let sans_bar_fg_rect = Rectangle::new(120.0, 25.0, sans_current_bar_width, 20.0);
-        let sans_bar_fg_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, sans_bar_fg_rect)?;
-        sans_bar_fg_mesh.draw(ctx, DrawParams::new().color(Color::rgb(0.0, 0.5, 1.0))); // Blue
-    }
+    // Dark gray behind, blue for what's left
+    let enemy_bar = bar_rects(
+        120.0,
+        200.0,
+        state.combat_data.enemy_hp as f32,
+        state.combat_data.enemy_max_hp as f32,
+    );
+    draw_bar(
+        ctx,
+        enemy_bar,
+        Color::rgb(0.2, 0.2, 0.2),
+        Color::rgb(0.0, 0.5, 1.0),
+    )?;
+
+    // TP Bar (next to Sans HP)
+    let mut tp_label = Text::new("TP", state.font());
+    draw_styled_text(
+        ctx,
+        &mut tp_label,
+        DrawParams::new()
+            .position(Vec2::new(340.0, 20.0))
+            .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
+    );
+
+    let tp_color = if state.combat_data.tp_full() {
+        Color::rgb(1.0, 1.0, 0.0) // Ready to spend
+    } else {
+        Color::rgb(1.0, 0.5, 0.0)
+    };
+    let tp_bar = bar_rects(370.0, 100.0, state.combat_data.tp, TP_MAX);
+    draw_bar(ctx, tp_bar, Color::rgb(0.2, 0.2, 0.2), tp_color)?;
 
     // Draw Player Health (Native Bar Style - Top Right)
     // HP Text
-    let mut hp_label = Text::new("HP", state.font.clone());
-    hp_label.draw(
+    let mut hp_label = Text::new("HP", state.font());
+    draw_styled_text(
         ctx,
+        &mut hp_label,
         DrawParams::new()
             .position(Vec2::new(550.0, 20.0))
             .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
     );
 
-    // HP Bar Background (Red)
-    let max_bar_width = 100.0;
-    let bar_bg_rect = Rectangle::new(590.0, 25.0, max_bar_width, 20.0);
-    let bar_bg_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, bar_bg_rect)?;
-    bar_bg_mesh.draw(ctx, DrawParams::new().color(Color::RED));
-
-    // HP Bar Foreground (Yellow)
-    let current_bar_width = (state.player.health / 100.0) * max_bar_width;
-    if current_bar_width > 0.0 {
-        let bar_fg_rect = Rectangle::new(590.0, 25.0, current_bar_width, 20.0);
-        let bar_fg_mesh = Mesh::rectangle(ctx, ShapeStyle::Fill, bar_fg_rect)?;
-        bar_fg_mesh.draw(ctx, DrawParams::new().color(Color::rgb(1.0, 1.0, 0.0)));
-    }
+    // Red behind, yellow for what's left
+    let hp_bar = bar_rects(
+        590.0,
+        100.0,
+        state.player.player_hp as f32,
+        state.player.player_max_hp as f32,
+    );
+    draw_bar(ctx, hp_bar, Color::RED, Color::rgb(1.0, 1.0, 0.0))?;
 
     // HP Numbers
-    let hp_text = format!("{}/100", state.player.health as i32);
-    let mut t = Text::new(hp_text, state.font.clone());
-    t.draw(
+    let hp_text = format!("{}/{}", state.player.player_hp, state.player.player_max_hp);
+    let mut t = Text::new(hp_text, state.font());
+    draw_styled_text(
         ctx,
+        &mut t,
         DrawParams::new()
             .position(Vec2::new(700.0, 20.0))
             .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
     );
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buttons_sit_in_a_row_without_overlapping() {
+        for i in 1..BUTTON_LABELS.len() {
+            let previous = button_rect(i - 1);
+            let rect = button_rect(i);
+            assert_eq!(rect.y, previous.y);
+            assert!(rect.x >= previous.right());
+        }
+        assert!(button_rect(BUTTON_LABELS.len() - 1).right() <= SCREEN_WIDTH as f32);
+    }
+
+    #[test]
+    fn bars_fill_by_share_and_clamp() {
+        let (back, fill) = bar_rects(120.0, 200.0, 30.0, 60.0);
+        assert_eq!(back.width, 200.0);
+        assert_eq!(fill.map(|fill| fill.width), Some(100.0));
+
+        assert_eq!(bar_rects(120.0, 200.0, 0.0, 60.0).1, None);
+        assert_eq!(bar_rects(120.0, 200.0, -5.0, 60.0).1, None); // Overkill
+        assert_eq!(
+            bar_rects(120.0, 200.0, 90.0, 60.0).1.map(|fill| fill.width),
+            Some(200.0)
+        );
+        assert_eq!(bar_rects(120.0, 200.0, 5.0, 0.0).1, None);
+    }
+
+    #[test]
+    fn bone_texture_stretches_over_the_bone() {
+        let bone = Bone {
+            pos: Vec2::new(50.0, 80.0),
+            size: Vec2::new(10.0, 60.0),
+            ..Bone::default()
+        };
+        let texture_size = Vec2::new(20.0, 120.0);
+        let params = bone_params(&bone, texture_size);
+        assert_eq!(params.position, bone.pos);
+        assert_eq!(params.scale * texture_size, bone.size);
+    }
+}
@@ -0,0 +1,83 @@
+use tetra::Context;
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams};
+use tetra::input::Key;
+use tetra::math::Vec2;
+
+use crate::game_state::GameState;
+use crate::objectives::WorldFlag;
+use crate::perf::RenderMut;
+use crate::sim::UpdateInput;
+
+/// A profile's first fight, or the next one after Settings asked for a
+/// replay.
+pub fn due(state: &GameState) -> bool {
+    !WorldFlag::CombatTutorialDone.is_set(state.world.flags)
+        || state.system.global_settings.replay_combat_tutorial
+}
+
+/// Lines up the cards for the turn that's about to start. The gravity card
+/// only comes up when this turn's soul actually falls.
+pub fn open(state: &mut GameState) {
+    let ui = &state.texts.ui;
+    let mut cards = vec![ui.tutorial_move.clone(), ui.tutorial_bones.clone()];
+    if state.combat_data.mode == 0 {
+        cards.push(ui.tutorial_gravity.clone());
+    }
+    cards.push(ui.tutorial_turn.clone());
+    log::info!("Showing the combat tutorial");
+    state.combat_data.tutorial_cards = cards.into();
+}
+
+pub fn is_open(state: &GameState) -> bool {
+    !state.combat_data.tutorial_cards.is_empty()
+}
+
+/// Confirm moves to the next card. Returns whether the cards are still up,
+/// the turn underneath holds until they're gone. Sans's turn doesn't read
+/// Confirm, so the press that closes the last card goes nowhere.
+pub fn step(input: &UpdateInput, state: &mut GameState) -> bool {
+    if !is_open(state) {
+        return false;
+    }
+    if input.is_pressed(Key::Z) || input.is_pressed(Key::Enter) || input.is_pressed(Key::F) {
+        state.combat_data.tutorial_cards.pop_front();
+        if !is_open(state) {
+            finish(state);
+            return false;
+        }
+    }
+    true
+}
+
+fn finish(state: &mut GameState) {
+    state.system.global_settings.replay_combat_tutorial = false;
+    state.system.save_global_settings();
+    // Saves the profile along with the flag
+    crate::scenes::objectives::set_flag(state, WorldFlag::CombatTutorialDone);
+}
+
+/// The front card in the dialogue box, drawn over the fight.
+pub fn draw(ctx: &mut Context, state: &GameState) {
+    let Some(card) = state.combat_data.tutorial_cards.front() else {
+        return;
+    };
+    crate::scenes::sans::draw_dialogue_box(ctx);
+
+    let mut text = Text::new(card, state.font());
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(70.0, 465.0))
+            .color(Color::WHITE),
+    );
+
+    let mut hint = Text::new(&state.texts.ui.tutorial_hint, state.font());
+    let width = hint.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(735.0 - width, 550.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+}
@@ -0,0 +1,171 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{self, Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+
+const SCROLL_SPEED: f32 = 60.0; // Pixels per second
+const GRADIENT_STRIPS: usize = 10;
+const GRADIENT_TOP: (f32, f32, f32) = (0.05, 0.05, 0.25); // Dark blue, fading to black
+
+const WHITE: Color = Color::WHITE;
+const GOLD: Color = Color::rgb(1.0, 0.85, 0.3);
+const GREY: Color = Color::rgb(0.6, 0.6, 0.6);
+
+/// One row of the roll. `gap` is the space between this row's top and the
+/// next one's, `offset` is worked out from the gaps above it.
+pub struct CreditLine {
+    pub text: &'static str,
+    pub size: f32,
+    pub color: Color,
+    pub gap: f32,
+    offset: f32,
+}
+
+impl CreditLine {
+    const fn new(text: &'static str, size: f32, color: Color, gap: f32) -> Self {
+        Self {
+            text,
+            size,
+            color,
+            gap,
+            offset: 0.0,
+        }
+    }
+}
+
+fn credit_lines() -> Vec<CreditLine> {
+    vec![
+        CreditLine::new("Credits", 32.0, GOLD, 80.0),
+        CreditLine::new("Developed by", 20.0, GOLD, 30.0),
+        CreditLine::new(
+            "lazypwny751, Bumbleshot, GitHub Copilot and <You>!",
+            16.0,
+            WHITE,
+            60.0,
+        ),
+        CreditLine::new("Engine", 20.0, GOLD, 30.0),
+        CreditLine::new("Tetra (Rust)", 16.0, WHITE, 60.0),
+        CreditLine::new("Code", 20.0, GOLD, 30.0),
+        CreditLine::new("GitHub Copilot, lazypwny751", 16.0, WHITE, 60.0),
+        CreditLine::new("Lore", 20.0, GOLD, 30.0),
+        CreditLine::new("Bumbleshot, lazypwny751", 16.0, WHITE, 60.0),
+        CreditLine::new("Assets", 20.0, GOLD, 30.0),
+        CreditLine::new("Bumbleshot, lazypwny751", 16.0, WHITE, 60.0),
+        CreditLine::new("Art", 20.0, GOLD, 30.0),
+        CreditLine::new("<you could be a developer of this game>", 16.0, GREY, 60.0),
+        CreditLine::new("Music", 20.0, GOLD, 30.0),
+        CreditLine::new("<you could be a developer of this game>", 16.0, GREY, 80.0),
+        CreditLine::new("Special Thanks to", 20.0, GOLD, 30.0),
+        CreditLine::new("The entire Tetra community!", 16.0, WHITE, 80.0),
+        CreditLine::new(
+            "https://github.com/ByCh4n-Group/gorkitale",
+            16.0,
+            GREY,
+            30.0,
+        ),
+    ]
+}
+
+pub struct CreditsState {
+    pub scroll_y: f32, // Y of the first line, starts below the screen
+    pub lines: Vec<CreditLine>,
+}
+
+impl CreditsState {
+    pub fn new() -> Self {
+        let mut lines = credit_lines();
+        let mut offset = 0.0;
+        for line in &mut lines {
+            line.offset = offset;
+            offset += line.gap;
+        }
+        Self {
+            scroll_y: SCREEN_HEIGHT as f32,
+            lines,
+        }
+    }
+
+    /// The last line has scrolled off the top.
+    pub fn finished(&self) -> bool {
+        self.lines
+            .last()
+            .is_none_or(|line| self.scroll_y + line.offset + line.gap < 0.0)
+    }
+}
+
+pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    // Stops once it's done, any key goes back to the menu from there
+    if !state.credits_state.finished() {
+        let dt = tetra::time::get_delta_time(ctx).as_secs_f32();
+        state.credits_state.scroll_y -= SCROLL_SPEED * dt;
+    }
+    Ok(())
+}
+
+pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    graphics::clear(ctx, Color::BLACK);
+    draw_gradient(ctx)?;
+
+    for i in 0..state.credits_state.lines.len() {
+        let line = &state.credits_state.lines[i];
+        let y = state.credits_state.scroll_y + line.offset;
+        if !(-line.gap..SCREEN_HEIGHT as f32).contains(&y) {
+            continue;
+        }
+        let (text, size, color) = (line.text, line.size, line.color);
+
+        let font = state.get_font(ctx, size).clone();
+        let mut text = Text::new(text, font);
+        let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(0.0);
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new((SCREEN_WIDTH as f32 - width) / 2.0, y))
+                .color(color),
+        );
+    }
+
+    let hint = if state.credits_state.finished() {
+        &state.texts.ui.credits_done_hint
+    } else {
+        &state.texts.ui.credits_hint
+    };
+    let mut hint = Text::new(hint.as_str(), state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(20.0, SCREEN_HEIGHT as f32 - 40.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
+
+/// Dark blue at the top fading to black, in flat strips.
+fn draw_gradient(ctx: &mut Context) -> tetra::Result {
+    let strip_height = SCREEN_HEIGHT as f32 / GRADIENT_STRIPS as f32;
+    let (r, g, b) = GRADIENT_TOP;
+    for i in 0..GRADIENT_STRIPS {
+        let fade = 1.0 - i as f32 / (GRADIENT_STRIPS - 1) as f32;
+        let strip = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(
+                0.0,
+                i as f32 * strip_height,
+                SCREEN_WIDTH as f32,
+                strip_height + 1.0,
+            ),
+        )?;
+        strip.render(
+            ctx,
+            DrawParams::new().color(Color::rgb(r * fade, g * fade, b * fade)),
+        );
+    }
+    Ok(())
+}
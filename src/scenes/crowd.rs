@@ -0,0 +1,226 @@
+use rand::Rng;
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{NpcId, SCREEN_WIDTH};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::scenes::desktop::{PLAYER_SHADOW, Sprite};
+use crate::sim::UpdateInput;
+use crate::world::{CrowdNpc, StageDef};
+
+pub const CROWD_STAGE: u8 = 3;
+pub const CROWD_RADIUS: f32 = 20.0; // Their colliders, see physics
+const WANDER_SPEED: f32 = 0.5; // Pixels per frame, a slow stroll
+const PLAYER_CLEARANCE: f32 = 40.0; // Never step into the player
+const GIVE_WAY_SPEED: f32 = 1.0; // Pixels per frame, backing off from the player
+const LEASH: f32 = 2.0; // Times the wander radius they can be pushed from home
+const BARK_DISTANCE: f32 = 100.0;
+const BARK_SECONDS: f32 = 2.0;
+const BARK_COOLDOWN_SECONDS: f32 = 8.0;
+const STAGGER_SWAY_SPEED: f32 = 20.0; // Radians per second
+
+/// Ambient pedestrians in the Ayasofya courtyard. They stroll around their
+/// spawn point and bark a line when the player walks past.
+pub fn step(input: &mut UpdateInput, state: &mut GameState) {
+    if state.world.current_stage != CROWD_STAGE {
+        return;
+    }
+
+    let dt = input.dt;
+    let player_pos = state.player.pos;
+    let room = state.world.stage_def();
+    let rng = &mut *input.rng;
+
+    for npc in &mut state.world.crowd {
+        // Reeling from a bone, no strolling until it wears off
+        npc.stagger_timer = (npc.stagger_timer - dt).max(0.0);
+
+        // Wander
+        npc.wander_timer -= dt;
+        if npc.wander_timer <= 0.0 {
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let reach = rng.random_range(0.0..=npc.wander_radius);
+            npc.target = npc.home + Vec2::new(angle.cos(), angle.sin()) * reach;
+            npc.wander_timer = rng.random_range(2.0..5.0);
+        }
+
+        let to_target = npc.target - npc.pos;
+        if npc.stagger_timer <= 0.0 {
+            if npc.pos.distance(player_pos) < PLAYER_CLEARANCE {
+                // Too close, back off instead of standing in the player's way
+                npc.pos = give_way(npc, player_pos, room);
+            } else if to_target.magnitude() > WANDER_SPEED {
+                let next_pos = npc.pos + to_target.normalized() * WANDER_SPEED;
+                if next_pos.distance(player_pos) > PLAYER_CLEARANCE {
+                    npc.pos = next_pos;
+                }
+            }
+        }
+
+        // Bark
+        npc.bark_timer = (npc.bark_timer - dt).max(0.0);
+        npc.bark_cooldown = (npc.bark_cooldown - dt).max(0.0);
+        if npc.bark_timer <= 0.0 {
+            npc.bark = None;
+        }
+
+        let barks = &state.texts.crowd_barks;
+        if npc.bark_cooldown <= 0.0
+            && !barks.is_empty()
+            && npc.pos.distance(player_pos) < BARK_DISTANCE
+        {
+            npc.bark = Some(barks[rng.random_range(0..barks.len())].clone());
+            npc.bark_timer = BARK_SECONDS;
+            npc.bark_cooldown = BARK_COOLDOWN_SECONDS;
+        }
+    }
+}
+
+/// One step straight away from the player, as long as it stays in the
+/// walkable band and on its leash around home.
+fn give_way(npc: &CrowdNpc, player_pos: Vec2<f32>, room: &StageDef) -> Vec2<f32> {
+    let away = npc.pos - player_pos;
+    // Standing exactly on the player: step down the band
+    let normal = if away.magnitude() > 0.001 {
+        away.normalized()
+    } else {
+        Vec2::new(0.0, 1.0)
+    };
+    let next = npc.pos + normal * GIVE_WAY_SPEED;
+    let in_band = (room.top_bound..=room.bottom_bound).contains(&next.y)
+        && (0.0..=SCREEN_WIDTH as f32).contains(&next.x);
+    if in_band && next.distance(npc.home) <= npc.wander_radius * LEASH {
+        next
+    } else {
+        npc.pos
+    }
+}
+
+/// Pedestrians for the desktop's depth-sorted sprite pass.
+pub fn collect_sprites<'a>(state: &'a GameState, sprites: &mut Vec<Sprite<'a>>) {
+    if state.world.current_stage != CROWD_STAGE {
+        return;
+    }
+
+    for (index, npc) in state.world.crowd.iter().enumerate() {
+        if let Some(texture) = &state.world.pedestrian_textures[npc.sprite] {
+            // Staggered ones sway on the spot
+            let sway = Vec2::new((npc.stagger_timer * STAGGER_SWAY_SPEED).sin() * 4.0, 0.0);
+            sprites.push(
+                Sprite::centered(texture, npc.pos + sway, Vec2::new(3.0, 3.0))
+                    .shadow(PLAYER_SHADOW)
+                    .npc(NpcId::Crowd(index)),
+            );
+        }
+    }
+}
+
+/// Speech bubbles, drawn after the sprites so they go on top of every pedestrian.
+pub fn draw_barks(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if state.world.current_stage != CROWD_STAGE {
+        return Ok(());
+    }
+
+    for npc in &state.world.crowd {
+        let Some(bark) = &npc.bark else {
+            continue;
+        };
+
+        let mut text = Text::new(bark.as_str(), state.font());
+        let bounds = text
+            .get_bounds(ctx)
+            .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0));
+        let bubble = Rectangle::new(
+            npc.pos.x - bounds.width / 2.0 - 6.0,
+            npc.pos.y - 80.0,
+            bounds.width + 12.0,
+            bounds.height + 10.0,
+        );
+
+        let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, bubble)?;
+        fill.render(ctx, DrawParams::new().color(Color::WHITE));
+        let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), bubble)?;
+        border.render(ctx, DrawParams::new().color(Color::BLACK));
+
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(bubble.x + 6.0, bubble.y + 5.0))
+                .color(Color::BLACK),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physics::wall_separation;
+    use crate::world::STAGE_DEFS;
+
+    /// Standing at `pos`, with the first stage 3 pedestrian's home.
+    fn pedestrian(pos: Vec2<f32>) -> CrowdNpc {
+        let spawn = &STAGE_DEFS[CROWD_STAGE as usize - 1].crowd[0];
+        CrowdNpc {
+            pos,
+            home: spawn.home,
+            target: pos,
+            sprite: spawn.sprite,
+            wander_radius: spawn.wander_radius,
+            wander_timer: 0.0,
+            bark: None,
+            bark_timer: 0.0,
+            bark_cooldown: 0.0,
+            stagger_timer: 0.0,
+        }
+    }
+
+    #[test]
+    fn backs_away_from_the_player() {
+        let room = &STAGE_DEFS[CROWD_STAGE as usize - 1];
+        let mut npc = pedestrian(room.crowd[0].home);
+        let player = npc.home + Vec2::new(20.0, 0.0);
+        for _ in 0..10 {
+            npc.pos = give_way(&npc, player, room);
+        }
+        assert!(npc.pos.distance(player) > 25.0);
+        assert!(npc.pos.x < npc.home.x);
+    }
+
+    #[test]
+    fn stays_on_its_leash_and_in_the_band() {
+        let room = &STAGE_DEFS[CROWD_STAGE as usize - 1];
+        let mut npc = pedestrian(room.crowd[0].home);
+        let player = npc.home + Vec2::new(10.0, 0.0);
+        for _ in 0..200 {
+            npc.pos = give_way(&npc, player, room);
+        }
+        assert!(npc.pos.distance(npc.home) <= npc.wander_radius * LEASH);
+
+        let mut npc = pedestrian(Vec2::new(400.0, room.top_bound));
+        npc.home = npc.pos;
+        let player = Vec2::new(400.0, room.top_bound + 10.0);
+        assert_eq!(give_way(&npc, player, room), npc.pos);
+    }
+
+    #[test]
+    fn homes_keep_clear_of_the_walls() {
+        for room in &STAGE_DEFS {
+            for spawn in room.crowd {
+                // Wherever wandering or giving way takes them
+                let reach = CROWD_RADIUS + spawn.wander_radius * LEASH;
+                assert_eq!(
+                    wall_separation(spawn.home, reach, room.walls),
+                    Vec2::zero(),
+                    "Crowd home {} is in a wall",
+                    spawn.home
+                );
+            }
+        }
+    }
+}
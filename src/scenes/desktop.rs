@@ -1,78 +1,196 @@
 use rand::Rng;
 use tetra::Context;
-use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::mesh::{GeometryBuilder, Mesh, ShapeStyle};
 use tetra::graphics::text::Text;
-use tetra::graphics::{self, Color, DrawParams, Rectangle};
+use tetra::graphics::{self, Color, DrawParams, Rectangle, Texture};
 use tetra::input::{self, Key};
-use tetra::math::Vec2;
+use tetra::math::{Mat4, Vec2};
 
-use crate::defs::{Direction, SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::defs::{Direction, NpcId, SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::dialogue_log::Speaker;
 use crate::game_state::GameState;
+use crate::particles::ParticleStyle;
+use crate::perf::{Render, RenderMut};
+use crate::player::PlayerState;
+use crate::render_util::{TextStyle, draw_shadow, draw_styled_text, shadow_mesh};
+use crate::scenes::highlight::Target;
+use crate::scenes::overworld::{PLAYER_RADIUS, move_player, player_sprite, spawn_player};
+use crate::sim::{Effect, Effects, UpdateInput};
+use crate::system::DeathCause;
+use crate::texts::TextKey;
+use crate::world::{Entry, HudElement, STAGE_COUNT, SansChase, WorldState};
+
+// Dead space damage ramps up the longer the player stays in it
+const DEAD_ZONE_BASE_DPS: f32 = 1.0;
+const DEAD_ZONE_DPS_RAMP: f32 = 0.5; // Extra HP/s per second spent inside
+const DEAD_ZONE_MAX_DPS: f32 = 6.0; // Reached after 10 seconds
+
+// Stage exits need a deliberate push so brushing the edge doesn't count
+const EDGE_PUSH_SECONDS: f32 = 0.3;
+
+const STAB_PARTICLES: usize = 8;
+const EDGE_OVERSHOOT: f32 = 16.0; // How far past an exit the player can lean
+const EDGE_BARK_SECONDS: f32 = 2.0;
+const EDGE_GLOW_WIDTH: f32 = 24.0;
+
+const RARITY_STAB_FRAMES: u8 = 3;
+const RARITY_STAB_FRAME_SECONDS: f32 = 0.15;
+
+// Sprites fade in behind the stage, starting once it's half visible
+const SPRITE_FADE_START: f32 = 0.5; // fade_alpha, the black over the stage
+const SPRITE_FADE_RATE: f32 = 2.0; // Alpha per second
+
+const INTERACT_RANGE: f32 = 120.0; // How close F reaches, NPC center to player center
+
+const TOOLTIP_DELAY: f32 = 0.5; // Seconds of hovering before a HUD tooltip shows
+
+// Stage 3's door into Ayasofya, part of the background art
+pub const AYASOFYA_DOOR: Rectangle = Rectangle {
+    x: 300.0,
+    y: 150.0,
+    width: 200.0,
+    height: 350.0,
+};
+
+pub const PLAYER_SHADOW: Vec2<f32> = Vec2::new(30.0, 8.0);
+pub const NPC_SHADOW: Vec2<f32> = Vec2::new(34.0, 9.0);
+
+pub fn update(ctx: &mut Context, state: &mut GameState) {
+    update_tooltip(ctx, state);
+    crate::sim::update(ctx, state);
+}
 
-pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let speed = 2.0;
-    let mut next_pos = state.player.pos;
-
-    if input::is_key_down(ctx, Key::W) || input::is_key_down(ctx, Key::Up) {
-        next_pos.y -= speed;
-        state.player.direction = Direction::Front;
-    }
-    if input::is_key_down(ctx, Key::S) || input::is_key_down(ctx, Key::Down) {
-        next_pos.y += speed;
-        state.player.direction = Direction::Front;
+/// Movement, stage exits, dead space and the NPCs of the current stage.
+pub fn step(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
+    // Listening to Eilish locks movement and pauses the dead space
+    if state.world.eilish_listening {
+        state.player.stop_auto_walk();
+        crate::scenes::eilish::step(input, state, effects);
+        return;
     }
-    if input::is_key_down(ctx, Key::A) || input::is_key_down(ctx, Key::Left) {
-        next_pos.x -= speed;
-        state.player.direction = Direction::Left;
+
+    // Talking to Sans locks movement until a choice is made
+    if state.world.sans_dialogue.is_some() {
+        state.player.stop_auto_walk();
+        crate::scenes::sans::step(input, state);
+        return;
     }
-    if input::is_key_down(ctx, Key::D) || input::is_key_down(ctx, Key::Right) {
-        next_pos.x += speed;
-        state.player.direction = Direction::Right;
+    if state.world.gaster_dialogue.is_some() {
+        state.player.stop_auto_walk();
+        crate::scenes::gaster::step_dialogue(input, state);
+        return;
     }
 
-    // Collision Check
-    let mut collided = false;
-    let player_radius = 20.0;
-
-    // Screen Boundaries (Top/Bottom)
-    if next_pos.y < 150.0 || next_pos.y > SCREEN_HEIGHT as f32 - 50.0 {
-        collided = true;
+    let dt = input.dt;
+    let start_pos = state.player.pos;
+    let stage_def = state.world.stage_def();
+    let auto_walk = state.system.global_settings.auto_walk;
+    let moved_x = move_player(input, &mut state.player, stage_def, auto_walk).x;
+
+    // NPC Collision, push out of whoever we walked into (or spawned in)
+    if crate::physics::check_collision(state.player.pos, PLAYER_RADIUS, &state.world) {
+        state.player.stop_auto_walk();
+        state.player.pos +=
+            crate::physics::separation_vector(state.player.pos, PLAYER_RADIUS, &state.world);
+        state.player.pos.y = state
+            .player
+            .pos
+            .y
+            .clamp(stage_def.top_bound, stage_def.bottom_bound);
     }
 
-    // Object Collision
-    if crate::physics::check_collision(next_pos, player_radius, &state.world) {
-        collided = true;
+    // Nudge the player and NPCs apart if they ended up overlapping
+    if !state.fade_out {
+        crate::physics::separate_from_npcs(
+            &mut state.player.pos,
+            PLAYER_RADIUS,
+            &mut state.world,
+            stage_def.top_bound,
+            stage_def.bottom_bound,
+        );
     }
 
-    if !collided {
-        state.player.pos = next_pos;
+    // Only the closest NPC gets to react to F, so overlapping ranges don't
+    // trigger several interactions at once
+    state.world.nearest_npc =
+        crate::physics::query_nearest_npc(state.player.pos, &state.world).map(|(npc, _)| npc);
+
+    // No walking off the edge while Sans is after you
+    if crate::scenes::sans::is_chasing(state) {
+        state.player.pos.x = state.player.pos.x.clamp(0.0, SCREEN_WIDTH as f32);
     }
 
     // Stage Transition Logic
-    if state.player.pos.x > SCREEN_WIDTH as f32
-        || (state.player.pos.x < 0.0 && state.world.current_stage > 1)
-    {
-        if !state.fade_out {
+    let stage_position = state.world.layout.position(state.world.current_stage) as u32 + 1;
+    let leaving =
+        PlayerState::is_near_boundary(state.player.pos, stage_position).filter(|direction| {
+            match direction {
+                Direction::Right => state.world.right_exit(),
+                _ => state.world.left_exit(),
+            }
+        });
+    if let Some(direction) = leaving {
+        state.player.pos.x = state
+            .player
+            .pos
+            .x
+            .clamp(-EDGE_OVERSHOOT, SCREEN_WIDTH as f32 + EDGE_OVERSHOOT);
+
+        // Letting go or backing off starts the count over
+        let pushing = if direction == Direction::Right {
+            moved_x > 0.0
+        } else {
+            moved_x < 0.0
+        };
+        if pushing {
+            state.world.edge_push_timer += dt;
+        } else {
+            state.world.edge_push_timer = 0.0;
+        }
+        if state.world.edge_push_timer >= EDGE_PUSH_SECONDS && !state.fade_out {
             state.fade_out = true;
+            state.world.transition_direction = Some(direction);
+        }
+    } else {
+        state.world.edge_push_timer = 0.0;
+        if state.player.pos.x < 0.0 || state.player.pos.x > SCREEN_WIDTH as f32 {
+            state.player.pos.x = state.player.pos.x.clamp(0.0, SCREEN_WIDTH as f32);
+            state.player.stop_auto_walk();
+            if !state.world.edge_bark_shown {
+                state.world.edge_bark_shown = true;
+                state.world.edge_bark_timer = EDGE_BARK_SECONDS;
+            }
         }
-    } else if state.player.pos.x < 0.0 {
-        state.player.pos.x = 0.0;
     }
+    state.world.edge_bark_timer = (state.world.edge_bark_timer - dt).max(0.0);
+    state.world.outcome_banner_timer = (state.world.outcome_banner_timer - dt).max(0.0);
 
     if state.fade_out {
         state.fade_alpha += 0.05;
         if state.fade_alpha >= 1.0 {
             state.fade_alpha = 1.0;
-            if state.player.pos.x > SCREEN_WIDTH as f32 {
-                state.world.current_stage += 1;
-                if state.world.current_stage > 4 {
-                    state.world.current_stage = 1;
-                }
-                state.player.pos.x = 10.0;
+            if let Some(stage) = state.world.travel_target.take() {
+                state.world.reset_for_stage(stage);
+                spawn_player(state, Entry::FastTravel);
+            } else if state.world.transition_direction.take() == Some(Direction::Right) {
+                let next = state.world.layout.next_stage(state.world.current_stage);
+                state.world.reset_for_stage(next);
+                spawn_player(state, Entry::FromLeft);
             } else {
-                state.world.current_stage -= 1;
-                state.player.pos.x = SCREEN_WIDTH as f32 - 10.0;
+                let previous = state
+                    .world
+                    .layout
+                    .previous_stage(state.world.current_stage)
+                    .unwrap_or(state.world.current_stage);
+                state.world.reset_for_stage(previous);
+                spawn_player(state, Entry::FromRight);
             }
+            state.player.exposure = 0.0;
+            state.player.player_fade_alpha = 0.0;
+            state.player.stop_auto_walk();
+            log::info!("Entered stage {}", state.world.current_stage);
+            crate::scenes::travel_map::mark_visited(state);
+            state.update_presence();
 
             // Update user profile and save
             if let Some(user) = &mut state.system.current_user {
@@ -94,51 +212,85 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     } else if state.fade_alpha > 0.0 {
         state.fade_alpha -= 0.05;
     }
+    fade_in_sprites(state, dt);
+
+    // Dead Space Logic, whatever hazards the stage has at this point in its clock
+    state.world.stage_clock += dt;
+    let intensity = state.world.hazard_intensity(state.player.pos);
+    let in_dead_space = intensity > 0.0;
+    let hurting = if state.system.hard_mode {
+        // Hard: damage starts as soon as you step in
+        in_dead_space
+    } else {
+        // Exposure fills while inside, HP only drains once it's full
+        state.player.step_exposure(in_dead_space, dt)
+    };
 
-    // Dead Space Logic (Stage 4, Right Side)
-    if state.world.current_stage == 4 && state.player.pos.x > 500.0 {
-        state.player.health -= 0.5; // Damage multiplier
+    if hurting {
+        state.world.dead_zone_time += dt;
+        let damage_per_second = (DEAD_ZONE_BASE_DPS
+            + state.world.dead_zone_time * DEAD_ZONE_DPS_RAMP)
+            .min(DEAD_ZONE_MAX_DPS);
+        state
+            .player
+            .damage_over_time(damage_per_second * intensity, dt);
+    } else {
+        state.world.dead_zone_time = 0.0;
+    }
 
-        if state.player.health <= 0.0 {
-            // Game Over -> Kernel Panic
-            // state.generate_kernel_panic(); // No longer needed
-            state.game_over_state = crate::game_state::GameOverState::new();
-            state.scene = Scene::KernelPanic;
-            state.session_started = false;
+    state.player.invuln_timer = (state.player.invuln_timer - dt).max(0.0);
+    state.world.screen_shake = (state.world.screen_shake - dt).max(0.0);
+
+    if state.player.player_hp <= 0 {
+        // Game Over -> Kernel Panic
+        let cause = if crate::scenes::sans::is_chasing(state) {
+            DeathCause::SansChase
+        } else {
+            DeathCause::DeadZone
+        };
+        state.system.record_death(cause);
+        state
+            .system
+            .generate_kernel_panic(&state.system.current_user.clone(), cause);
+        state.player.exposure = 0.0;
+        state.world.sans_chase = SansChase::Idle;
+        if let Some(pos) = state.world.npc_home(NpcId::Sans) {
+            state.world.sans_pos = pos;
         }
+        if let Some(user) = &mut state.system.current_user {
+            user.sans_chase = 0.0;
+            user.music_on = false;
+        }
+        state.world.stop_music();
+        state.game_over_state = crate::game_state::GameOverState::after_death(cause);
+        state.scene = Scene::KernelPanic;
+        state.session_started = false;
     }
 
+    let nearest = state
+        .world
+        .interactable_at(state.player.pos, INTERACT_RANGE);
+
     // MusicBox Interaction (Stage 1)
-    if state.world.current_stage == 1 {
-        let dx = state.player.pos.x - state.world.musicbox_pos.x;
-        let dy = state.player.pos.y - state.world.musicbox_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < 120.0 && input::is_key_pressed(ctx, Key::F) {
-            if state.world.music_playing {
-                if let Some(instance) = &mut state.world.music_instance {
-                    instance.stop();
-                }
-                state.world.music_playing = false;
-            } else {
-                #[allow(clippy::collapsible_if)]
-                if let Some(track) = &state.world.music_track {
-                    if let Ok(instance) = track.play(ctx) {
-                        instance.set_repeating(true);
-                        instance.set_volume(state.system.volume);
-                        state.world.music_instance = Some(instance);
-                        state.world.music_playing = true;
-                    }
-                }
-            }
+    if nearest == Some(NpcId::MusicBox) && input.is_pressed(Key::F) {
+        if state.world.music_playing {
+            state.world.stop_music();
+        } else {
+            effects.apply(state, Effect::StartMusicBox);
         }
     }
 
+    // Tracks are all loaded during boot, the check only matters if that failed
+    if state.world.music_resume && state.world.music_track.is_some() {
+        state.world.music_resume = false;
+        effects.apply(state, Effect::StartMusicBox);
+    }
+
     if state.world.music_playing {
         state.world.disco_timer += 1.0;
         if state.world.disco_timer > 10.0 {
             state.world.disco_timer = 0.0;
-            let mut rng = rand::rng();
+            let rng = &mut *input.rng;
             state.world.disco_color = Color::rgb(
                 rng.random_range(0.0..1.0),
                 rng.random_range(0.0..1.0),
@@ -148,85 +300,163 @@ pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     }
 
     // Sans Interaction (Stage 1)
-    if state.world.current_stage == 1 {
-        let dx = state.player.pos.x - state.world.sans_pos.x;
-        let dy = state.player.pos.y - state.world.sans_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < 120.0 && input::is_key_pressed(ctx, Key::F) {
-            state.scene = Scene::CombatTransition;
-            state.fade_out = true;
-            state.fade_alpha = 0.0;
-        }
-    }
+    crate::scenes::sans::step(input, state);
 
     // Gaster Interaction (Stage 2)
-    if state.world.current_stage == 2 {
-        // Simple distance check
-        let dx = state.player.pos.x - state.world.gaster_pos.x;
-        let dy = state.player.pos.y - state.world.gaster_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < 120.0 {
-            if input::is_key_pressed(ctx, Key::F) {
+    if state.world.in_current_stage(NpcId::Gaster) {
+        crate::scenes::gaster::step(input, state, effects);
+
+        if crate::scenes::gaster::is_vanishing(state) {
+            // Can't talk to static
+        } else if nearest == Some(NpcId::Gaster) && !crate::scenes::disco::gaster_sulking(state) {
+            if input.is_pressed(Key::F) {
                 state.world.gaster_talking = !state.world.gaster_talking;
                 if state.world.gaster_talking {
-                    let mut rng = rand::rng();
-                    let idx = rng.random_range(0..state.world.gaster_dialogues.len());
-                    state.world.current_gaster_dialogue = state.world.gaster_dialogues[idx].clone();
+                    // After a tekfir he drops the tongue twisters
+                    if crate::scenes::gaster::knows_tekfir(state)
+                        && !state.texts.gaster_dialogues_post_tekfir.is_empty()
+                    {
+                        let tree = crate::scenes::gaster::build_dialogue(state, input.rng);
+                        state.world.current_gaster_dialogue = tree.current_node().text.clone();
+                        state.world.gaster_dialogue = Some(tree);
+                    } else if state.player.outfit != 0 && !state.world.gaster_hat_remarked {
+                        // He only brings the hat up once
+                        state.world.gaster_hat_remarked = true;
+                        state.world.current_gaster_dialogue = state.texts.gaster_hat_remark.clone();
+                    } else {
+                        let idx = input
+                            .rng
+                            .random_range(0..state.world.gaster_dialogues.len());
+                        state.world.current_gaster_dialogue =
+                            state.world.gaster_dialogues[idx].clone();
+                    }
+                    state
+                        .dialogue_log
+                        .push(Speaker::Gaster, &state.world.current_gaster_dialogue);
                 }
             }
         } else if state.world.gaster_talking {
             state.world.gaster_talking = false;
         }
 
-        // Rarity Interaction (Stage 2)
-        if state.world.rarity_alive {
-            let dx = state.player.pos.x - state.world.rarity_pos.x;
-            let dy = state.player.pos.y - state.world.rarity_pos.y;
-            let distance = (dx * dx + dy * dy).sqrt();
+        crate::scenes::gaster::update_talk_blend(state, dt);
+    }
 
+    // Rarity Interaction (Stage 2)
+    if state.world.in_current_stage(NpcId::Rarity) {
+        if state.world.rarity_stab_frame > 0 {
+            // She only goes away once the stab animation has played out
+            state.world.rarity_stab_frame_timer -= dt;
+            if state.world.rarity_stab_frame_timer <= 0.0 {
+                if state.world.rarity_stab_frame < RARITY_STAB_FRAMES {
+                    state.world.rarity_stab_frame += 1;
+                    state.world.rarity_stab_frame_timer = RARITY_STAB_FRAME_SECONDS;
+                } else {
+                    state.world.rarity_stab_frame = 0;
+                    state.world.rarity_alive = false;
+                    state.world.rarity_stabbed_timer = 180.0; // 3 seconds
+                }
+            }
+        } else if state.world.rarity_alive {
             // Only interact if behind (Player X < Rarity X) and close
-            if distance < 120.0
+            if nearest == Some(NpcId::Rarity)
                 && state.player.pos.x < state.world.rarity_pos.x
-                && input::is_key_pressed(ctx, Key::F)
+                && input.is_pressed(Key::F)
             {
-                state.world.rarity_alive = false;
-                state.world.rarity_stabbed_timer = 180.0; // 3 seconds
+                stab_rarity(state, effects);
             }
         } else if state.world.rarity_stabbed_timer > 0.0 {
             state.world.rarity_stabbed_timer -= 1.0;
         }
     }
 
-    // Ayasofya Interaction (Stage 3)
-    if state.world.current_stage == 3 {
-        // Door area: Kapı ortadan aşağıya doğru uzanıyor
-        // X: 300-500 (orta), Y: 150-500 (yukarıdan aşağıya)
-        let door_rect = Rectangle::new(300.0, 150.0, 200.0, 350.0);
-        if state.player.pos.x >= door_rect.x
-            && state.player.pos.x <= door_rect.x + door_rect.width
-            && state.player.pos.y >= door_rect.y
-            && state.player.pos.y <= door_rect.y + door_rect.height
-            && input::is_key_pressed(ctx, Key::F)
-        {
-            state.scene = Scene::AyasofyaInside;
-            state.player.pos = Vec2::new(100.0, 300.0); // Entrance inside
+    // Crowd (Stage 3)
+    crate::scenes::crowd::step(input, state);
 
-            // Randomly select outfit
-            let mut rng = rand::rng();
-            state.player.outfit = rng.random_range(1..3); // 1 or 2
-        }
+    // NPC reactions to the music box (Stages 1 and 2)
+    crate::scenes::disco::step(input, state);
+
+    // Ayasofya Interaction (Stage 3)
+    if state.world.current_stage == 3
+        && in_ayasofya_door(state.player.pos)
+        && input.is_pressed(Key::F)
+    {
+        state.scene = Scene::AyasofyaInside;
+        state.player.exposure = 0.0;
+        crate::scenes::ayasofya::enter(state, input.rng);
     }
 
     // Eilish Interaction (Stage 4)
-    crate::scenes::eilish::update(ctx, state);
-    Ok(())
+    crate::scenes::eilish::step(input, state, effects);
+
+    // Survivor (Stage 4 until rescued, then Stage 3)
+    crate::scenes::rescue::step(input, state);
+
+    crate::scenes::bone::step(input, state, effects);
+
+    let walked = state.player.pos.distance(start_pos);
+    crate::scenes::overworld::step_footsteps(state, walked);
+
+    crate::scenes::idle::step(state, dt, walked > 0.0);
+}
+
+/// Whether `pos` is in the door area, edges included. Kapı ortadan aşağıya
+/// doğru uzanıyor, X: 300-500 (orta), Y: 150-500 (yukarıdan aşağıya).
+pub fn in_ayasofya_door(pos: Vec2<f32>) -> bool {
+    let door = AYASOFYA_DOOR;
+    pos.x >= door.x
+        && pos.x <= door.x + door.width
+        && pos.y >= door.y
+        && pos.y <= door.y + door.height
+}
+
+/// Starts Rarity's death animation, she disappears once it has played out.
+pub fn stab_rarity(state: &mut GameState, effects: &mut dyn Effects) {
+    if !state.world.rarity_alive || state.world.rarity_stab_frame > 0 {
+        return;
+    }
+    state.world.rarity_stab_frame = 1;
+    state.world.rarity_stab_frame_timer = RARITY_STAB_FRAME_SECONDS;
+    let quality = state.graphics_quality();
+    state.world.particles.spawn_burst(
+        state.world.rarity_pos,
+        STAB_PARTICLES,
+        ParticleStyle::Stab,
+        quality,
+    );
+    effects.apply(state, Effect::StabSound);
+}
+
+/// Starts the music box track, the audio half of pressing F next to it.
+pub fn start_music_box(ctx: &mut Context, state: &mut GameState) {
+    // A second start before the first one registered would stack two instances
+    if state.world.music_playing {
+        return;
+    }
+    state.world.stop_music();
+    #[allow(clippy::collapsible_if)]
+    if let Some(track) = &state.world.music_track {
+        if let Ok(instance) = track.play(ctx) {
+            instance.set_repeating(true);
+            instance.set_volume(state.system.volume);
+            state.world.music_instance = Some(instance);
+            state.world.music_playing = true;
+        }
+    }
 }
 
 pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     graphics::clear(ctx, Color::BLACK);
 
+    let quality = state.graphics_quality();
+
+    // Shake the whole scene after a hit
+    if state.world.screen_shake > 0.0 && quality.screen_shake {
+        let mut rng = rand::rng();
+        let offset = Vec2::new(rng.random_range(-4.0..4.0), rng.random_range(-4.0..4.0));
+        graphics::set_transform_matrix(ctx, Mat4::translation_2d(offset));
+    }
+
     if state.world.current_stage == 3 {
         if let Some(texture) = &state.world.ayasofya_giris_texture {
             let bg_width = texture.width() as f32;
@@ -234,88 +464,207 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             let scale_x = SCREEN_WIDTH as f32 / bg_width;
             let scale_y = SCREEN_HEIGHT as f32 / bg_height;
 
-            texture.draw(
+            texture.render(
                 ctx,
                 DrawParams::new()
                     .position(Vec2::new(0.0, 0.0))
                     .scale(Vec2::new(scale_x, scale_y)),
             );
         }
-    } else if let Some(bg_texture) = &state.world.bg_texture {
-        let bg_width = bg_texture.width() as f32;
-        let bg_height = bg_texture.height() as f32;
-        let scale_x = SCREEN_WIDTH as f32 / bg_width;
-        let scale_y = SCREEN_HEIGHT as f32 / bg_height;
+    } else {
+        let mut tint = WorldState::stage_tint(state.world.current_stage);
+        // Without the overlay the disco lights only color the background
+        if state.world.music_playing && !quality.fullscreen_overlays {
+            tint *= state.world.disco_color;
+        }
+        draw_background_layers(ctx, state, tint);
+    }
 
-        bg_texture.draw(
+    // Draw Dead Space, hazards where the stage clock has them right now
+    let hazard_def = state.world.hazard_def();
+    for zone in hazard_def.hazards {
+        let zone_rect =
+            Mesh::rectangle(ctx, ShapeStyle::Fill, zone.rect_at(state.world.stage_clock))?;
+        zone_rect.render(
             ctx,
-            DrawParams::new()
-                .position(Vec2::new(0.0, 0.0))
-                .scale(Vec2::new(scale_x, scale_y))
-                .color(if state.world.current_stage == 1 {
-                    Color::WHITE
-                } else if state.world.current_stage == 2 {
-                    Color::rgb(0.8, 0.8, 1.0)
-                }
-                // Blueish tint
-                else {
-                    Color::rgb(1.0, 0.8, 0.8)
-                }), // Reddish tint
+            DrawParams::new().color(Color::rgba(
+                1.0,
+                0.0,
+                0.0,
+                (0.3 + (state.world.dead_zone_time / 10.0).min(0.4)) * zone.intensity.min(1.0),
+            )),
+        );
+    }
+    for pocket in hazard_def.safe_pockets {
+        let pocket_rect = Mesh::rectangle(ctx, ShapeStyle::Fill, *pocket)?;
+        pocket_rect.render(
+            ctx,
+            DrawParams::new().color(Color::rgba(0.3, 0.6, 1.0, 0.25)),
         );
     }
 
-    // Draw Gaster (Stage 2)
-    if state.world.current_stage == 2 {
-        let gaster_texture = if state.world.gaster_talking {
-            &state.world.npc_gaster_talking
-        } else {
-            &state.world.npc_gaster_standing
-        };
+    draw_edge_indicators(ctx, state)?;
+
+    // World sprites, whoever stands lower on screen is drawn in front
+    let shadow = shadow_mesh(ctx, &mut state.world.shadow_mesh)?;
+    let mut sprites = Vec::new();
+    collect_sprites(state, &mut sprites);
+    crate::scenes::eilish::collect_sprites(state, &mut sprites);
+    crate::scenes::crowd::collect_sprites(state, &mut sprites);
+    crate::scenes::rescue::collect_sprites(state, &mut sprites);
+    sprites.sort_by(|a, b| a.feet_y.total_cmp(&b.feet_y));
+    // NPCs fade in on a new stage, the player fades itself in player_sprite
+    for sprite in &mut sprites {
+        if sprite.npc.is_some() {
+            sprite.params.color.a *= state.world.npc_fade_alpha;
+        }
+    }
+    // Shadows first, so nobody's shadow lands on someone standing in front
+    for sprite in &sprites {
+        if let Some(size) = sprite.shadow {
+            let alpha = sprite.params.color.a;
+            draw_shadow(
+                ctx,
+                &shadow,
+                Vec2::new(sprite.pos.x, sprite.feet_y),
+                size,
+                alpha,
+            );
+        }
+    }
+    // The interaction target glows from behind its sprite
+    let highlight = crate::scenes::highlight::target(state);
+    for sprite in sprites {
+        if sprite
+            .npc
+            .is_some_and(|npc| highlight == Some(Target::Npc(npc)))
+        {
+            crate::scenes::highlight::draw_behind(ctx, state, &sprite);
+        }
+        sprite.texture.render(ctx, sprite.params);
+    }
+    crate::scenes::highlight::draw_door(ctx, state)?;
+    crate::scenes::bone::draw(ctx, state);
+    crate::particles::draw_batch(ctx, state.world.particles.rects())?;
+    draw_spawn_points(ctx, state)?;
 
-        if let Some(tex) = gaster_texture {
-            let g_width = tex.width() as f32;
-            let g_height = tex.height() as f32;
-            let g_origin = Vec2::new(g_width / 2.0, g_height / 2.0);
+    crate::scenes::gaster::draw_static(ctx, state);
 
-            tex.draw(
+    // Disco Lights Overlay
+    if state.world.music_playing && quality.fullscreen_overlays {
+        let light_rect = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+        )
+        .unwrap();
+        light_rect.render(
+            ctx,
+            DrawParams::new().color(state.world.disco_color.with_alpha(0.2)),
+        );
+    }
+
+    // Prompts and bubbles stay on top of every sprite
+    let nearest = state
+        .world
+        .interactable_at(state.player.pos, INTERACT_RANGE);
+    if state.world.in_current_stage(NpcId::Gaster) {
+        crate::scenes::gaster::draw_idle_line(ctx, state)?;
+
+        // Gaster Interaction Prompt
+        if nearest == Some(NpcId::Gaster)
+            && !state.world.gaster_talking
+            && !crate::scenes::gaster::is_vanishing(state)
+            && !crate::scenes::disco::gaster_sulking(state)
+        {
+            let anchor = state.world.gaster_pos - Vec2::new(0.0, 80.0);
+            draw_prompt(
                 ctx,
-                DrawParams::new()
-                    .position(state.world.gaster_pos)
-                    .origin(g_origin)
-                    .scale(Vec2::new(3.0, 3.0)),
+                state,
+                "Press F to interact",
+                anchor,
+                Color::rgb(1.0, 1.0, 0.0),
             );
         }
+    }
 
-        // Interaction Prompt
-        let dx = state.player.pos.x - state.world.gaster_pos.x;
-        let dy = state.player.pos.y - state.world.gaster_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
+    // Ayasofya Door Prompt (Stage 3)
+    if state.world.current_stage == 3 {
+        // Kapı alanı: ortadan aşağıya
+        if in_ayasofya_door(state.player.pos) {
+            draw_prompt(
+                ctx,
+                state,
+                "Press F to enter Ayasofya",
+                Vec2::new(400.0, 130.0),
+                Color::WHITE,
+            );
+        }
+    }
 
-        if distance < 100.0 && !state.world.gaster_talking {
-            let prompt = "Press F to interact";
-            let mut text = Text::new(prompt, state.font.clone());
-            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+    crate::scenes::eilish::draw(ctx, state)?;
+    crate::scenes::rescue::draw(ctx, state)?;
+    crate::scenes::disco::draw(ctx, state)?;
+    crate::scenes::crowd::draw_barks(ctx, state)?;
+    draw_edge_bark(ctx, state)?;
+    crate::scenes::bone::draw_toast(ctx, state)?;
+
+    if state.world.in_current_stage(NpcId::MusicBox) {
+        // MusicBox Interaction Prompt
+        if nearest == Some(NpcId::MusicBox) {
+            let prompt = if state.world.music_playing {
+                "Press F to Stop Music"
+            } else {
+                "Press F to Play Music"
+            };
+            let anchor = state.world.musicbox_pos - Vec2::new(0.0, 60.0);
+            draw_prompt(ctx, state, prompt, anchor, Color::rgb(0.0, 1.0, 1.0));
+        }
+    }
 
-            text.draw(
+    if state.world.in_current_stage(NpcId::Sans) {
+        // Sans Interaction Prompt
+        if nearest == Some(NpcId::Sans)
+            && state.world.sans_dialogue.is_none()
+            && state.world.sans_chase == SansChase::Idle
+        {
+            let anchor = state.world.sans_pos - Vec2::new(0.0, 80.0);
+            draw_prompt(
                 ctx,
-                DrawParams::new()
-                    .position(Vec2::new(
-                        state.world.gaster_pos.x - width / 2.0,
-                        state.world.gaster_pos.y - 80.0,
-                    ))
-                    .color(Color::rgb(1.0, 1.0, 0.0)),
+                state,
+                "Press F to interact",
+                anchor,
+                Color::rgb(1.0, 1.0, 0.0),
             );
         }
+    }
+
+    if state.world.in_current_stage(NpcId::Rarity) {
+        if state.world.rarity_alive && state.world.rarity_stab_frame == 0 {
+            // Rarity Interaction Prompt (Only from behind)
+            if nearest == Some(NpcId::Rarity) && state.player.pos.x < state.world.rarity_pos.x {
+                let anchor = state.world.rarity_pos - Vec2::new(0.0, 80.0);
+                draw_prompt(ctx, state, "Press F to Stab", anchor, Color::RED);
+            }
+        } else if state.world.rarity_stabbed_timer > 0.0 {
+            // Draw stabbed message
+            let msg = "You stabbed Rarity from behind!";
+            draw_prompt(ctx, state, msg, state.world.rarity_pos, Color::RED);
+        }
+    }
 
-        // Dialogue Box
-        if state.world.gaster_talking {
+    if state.world.in_current_stage(NpcId::Gaster) {
+        // Gaster Dialogue Box
+        if let Some(tree) = &state.world.gaster_dialogue {
+            crate::scenes::sans::draw_tree(ctx, state, tree);
+        } else if state.world.gaster_talking {
             // Draw a box at the bottom
             if let Ok(box_rect) = Mesh::rectangle(
                 ctx,
                 ShapeStyle::Fill,
                 Rectangle::new(50.0, 450.0, 700.0, 130.0),
             ) {
-                box_rect.draw(
+                box_rect.render(
                     ctx,
                     DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.8)),
                 );
@@ -326,11 +675,11 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
                 ShapeStyle::Stroke(2.0),
                 Rectangle::new(50.0, 450.0, 700.0, 130.0),
             ) {
-                border_rect.draw(ctx, DrawParams::new().color(Color::WHITE));
+                border_rect.render(ctx, DrawParams::new().color(Color::WHITE));
             }
 
-            let mut text = Text::new(&state.world.current_gaster_dialogue, state.font.clone());
-            text.draw(
+            let mut text = Text::new(&state.world.current_gaster_dialogue, state.font());
+            text.render(
                 ctx,
                 DrawParams::new()
                     .position(Vec2::new(70.0, 470.0))
@@ -339,130 +688,27 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         }
     }
 
-    // Ayasofya Door Prompt (Stage 3)
-    if state.world.current_stage == 3 {
-        // Kapı alanı: ortadan aşağıya
-        let door_rect = Rectangle::new(300.0, 150.0, 200.0, 350.0);
-        if state.player.pos.x >= door_rect.x
-            && state.player.pos.x <= door_rect.x + door_rect.width
-            && state.player.pos.y >= door_rect.y
-            && state.player.pos.y <= door_rect.y + door_rect.height
-        {
-            let prompt = "Press F to enter Ayasofya";
-            let mut text = Text::new(prompt, state.font.clone());
-            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
-
-            text.draw(
-                ctx,
-                DrawParams::new()
-                    .position(Vec2::new(400.0 - width / 2.0, 130.0))
-                    .color(Color::WHITE),
-            );
-        }
-    }
-
-    // Draw Dead Space (Stage 4)
-    if state.world.current_stage == 4 {
-        let dead_space_rect = Mesh::rectangle(
-            ctx,
-            ShapeStyle::Fill,
-            Rectangle::new(500.0, 0.0, 300.0, SCREEN_HEIGHT as f32),
-        )?;
-        dead_space_rect.draw(
-            ctx,
-            DrawParams::new().color(Color::rgba(1.0, 0.0, 0.0, 0.3)),
-        );
-    }
-
-    crate::scenes::eilish::draw(ctx, state)?;
-
-    // Draw player
-    let texture_opt = match state.player.direction {
-        Direction::Front => &state.player.texture_front,
-        Direction::Left => &state.player.texture_left,
-        Direction::Right => &state.player.texture_right,
-    };
-
-    if let Some(texture) = texture_opt {
-        // Center the sprite on player_pos
-        let width = texture.width() as f32;
-        let height = texture.height() as f32;
-        let origin = Vec2::new(width / 2.0, height / 2.0);
-
-        // Scale up the character (e.g. 3x)
-        texture.draw(
-            ctx,
-            DrawParams::new()
-                .position(state.player.pos)
-                .origin(origin)
-                .scale(Vec2::new(3.0, 3.0)),
-        );
-    }
-
-    // Draw MusicBox (Stage 1)
-    if state.world.current_stage == 1 {
-        if let Some(musicbox_texture) = &state.world.musicbox_texture {
-            let m_width = musicbox_texture.width() as f32;
-            let m_height = musicbox_texture.height() as f32;
-            let m_origin = Vec2::new(m_width / 2.0, m_height / 2.0);
-
-            musicbox_texture.draw(
-                ctx,
-                DrawParams::new()
-                    .position(state.world.musicbox_pos)
-                    .origin(m_origin)
-                    .scale(Vec2::new(0.3, 0.3)),
-            );
-        }
-
-        // Interaction Prompt
-        let dx = state.player.pos.x - state.world.musicbox_pos.x;
-        let dy = state.player.pos.y - state.world.musicbox_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < 120.0 {
-            let prompt = if state.world.music_playing {
-                "Press F to Stop Music"
-            } else {
-                "Press F to Play Music"
-            };
-            let mut text = Text::new(prompt, state.font.clone());
-            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
-
-            text.draw(
-                ctx,
-                DrawParams::new()
-                    .position(Vec2::new(
-                        state.world.musicbox_pos.x - width / 2.0,
-                        state.world.musicbox_pos.y - 60.0,
-                    ))
-                    .color(Color::rgb(0.0, 1.0, 1.0)),
-            );
-        }
-    }
-
-    // Disco Lights Overlay
-    if state.world.music_playing {
-        let light_rect = Mesh::rectangle(
-            ctx,
-            ShapeStyle::Fill,
-            Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
-        )
-        .unwrap();
-        light_rect.draw(
-            ctx,
-            DrawParams::new().color(state.world.disco_color.with_alpha(0.2)),
-        );
+    if state.world.in_current_stage(NpcId::Sans) {
+        crate::scenes::sans::draw_dialogue(ctx, state);
     }
 
     // Draw Stage Indicator
-    let stage_text = format!("Stage: {}/4", state.world.current_stage);
-    let mut text = Text::new(stage_text, state.font.clone());
-    text.draw(
+    let stage_text = state.texts.fill(
+        TextKey::StageIndicator,
+        &[
+            ("stage_current", &state.world.current_stage),
+            ("stage_total", &STAGE_COUNT),
+        ],
+    );
+    let mut text = Text::new(stage_text, state.font());
+    draw_styled_text(
         ctx,
+        &mut text,
         DrawParams::new()
             .position(Vec2::new(10.0, 10.0))
             .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
     );
 
     // Draw Health Bar (Top Right)
@@ -477,41 +723,86 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         ShapeStyle::Fill,
         Rectangle::new(bar_x, bar_y, bar_width, bar_height),
     )?;
-    health_bar_bg.draw(ctx, DrawParams::new().color(Color::rgb(0.2, 0.2, 0.2)));
+    health_bar_bg.render(ctx, DrawParams::new().color(Color::rgb(0.2, 0.2, 0.2)));
 
-    let health_fill_width = (state.player.health / 100.0) * bar_width;
+    let health_fill_width =
+        (state.player.player_hp as f32 / state.player.player_max_hp as f32) * bar_width;
     if health_fill_width > 0.0 {
         let health_bar_fg = Mesh::rectangle(
             ctx,
             ShapeStyle::Fill,
             Rectangle::new(bar_x, bar_y, health_fill_width, bar_height),
         )?;
-        health_bar_fg.draw(ctx, DrawParams::new().color(Color::RED));
+        health_bar_fg.render(ctx, DrawParams::new().color(Color::RED));
     }
 
-    let hp_text = format!("HP: {:.0}%", state.player.health);
-    let mut hp_display = Text::new(hp_text, state.font.clone());
+    // Exposure Meter (below health, only while it matters)
+    if state.player.exposure > 0.0 {
+        let exposure_y = bar_y + bar_height + 5.0;
+        let exposure_height = 6.0;
+        let exposure_bg = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(bar_x, exposure_y, bar_width, exposure_height),
+        )?;
+        exposure_bg.render(ctx, DrawParams::new().color(Color::rgb(0.2, 0.2, 0.2)));
+
+        let exposure_fg = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(
+                bar_x,
+                exposure_y,
+                state.player.exposure * bar_width,
+                exposure_height,
+            ),
+        )?;
+        let exposure_color = if state.player.exposure >= 1.0 {
+            Color::rgb(1.0, 0.0, 1.0)
+        } else {
+            Color::rgb(0.6, 0.2, 1.0)
+        };
+        exposure_fg.render(ctx, DrawParams::new().color(exposure_color));
+    }
+
+    let hp_text = state.texts.fill(
+        TextKey::HpLabel,
+        &[
+            ("hp", &state.player.player_hp),
+            ("max_hp", &state.player.player_max_hp),
+        ],
+    );
+    let mut hp_display = Text::new(hp_text, state.font());
     // Position text to the left of the bar or below? Let's put it inside/below
     // Or just to the left
-    let hp_bounds = hp_display.get_bounds(ctx).unwrap();
-    hp_display.draw(
+    let hp_width = hp_display.get_bounds(ctx).unwrap().width * state.text_scale();
+    draw_styled_text(
         ctx,
+        &mut hp_display,
         DrawParams::new()
-            .position(Vec2::new(bar_x - hp_bounds.width - 10.0, bar_y))
+            .position(Vec2::new(bar_x - hp_width - 10.0, bar_y))
             .color(Color::WHITE),
+        TextStyle::OUTLINE,
+        state.text_scale(),
     );
 
     // Draw FPS
     let fps = tetra::time::get_fps(ctx);
     let fps_text = format!("FPS: {:.0}", fps);
-    let mut fps_display = Text::new(fps_text, state.font.clone());
-    fps_display.draw(
+    let mut fps_display = Text::new(fps_text, state.font());
+    fps_display.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(10.0, 30.0))
             .color(Color::rgb(1.0, 1.0, 0.0)),
     );
 
+    crate::scenes::objectives::draw_hud(ctx, state)?;
+    crate::scenes::combat::draw_outcome_banner(ctx, state)?;
+    draw_tooltip(ctx, state)?;
+
+    crate::scenes::eilish::draw_song_overlay(ctx, state)?;
+
     // Fade Overlay
     if state.fade_alpha > 0.0 {
         let fade_rect = Mesh::rectangle(
@@ -520,118 +811,535 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
         )
         .unwrap();
-        fade_rect.draw(
+        fade_rect.render(
+            ctx,
+            DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, state.fade_alpha)),
+        );
+    }
+
+    // Fade Transition Overlay
+    if state.fade_out {
+        let fade_rect = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+        )
+        .unwrap();
+        fade_rect.render(
             ctx,
             DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, state.fade_alpha)),
         );
     }
 
-    // Draw Sans in Stage 1
-    if state.world.current_stage == 1 {
-        if let Some(sans_handshake_texture) = &state.world.sans_handshake_texture {
-            let s_width = sans_handshake_texture.width() as f32;
-            let s_height = sans_handshake_texture.height() as f32;
-            let s_origin = Vec2::new(s_width / 2.0, s_height / 2.0);
+    graphics::reset_transform_matrix(ctx);
+
+    Ok(())
+}
 
-            sans_handshake_texture.draw(
+/// The stage's background layers, back to front, each multiplied by
+/// `modulate`. Layers whose texture didn't load are left out.
+fn draw_background_layers(ctx: &mut Context, state: &GameState, modulate: Color) {
+    for layer in state.world.stage_def().bg_layers {
+        let Some(texture) = state.world.layer_texture(layer.texture) else {
+            continue;
+        };
+        let width = texture.width() as f32;
+        let height = texture.height() as f32;
+        let tint = layer.tint * modulate;
+
+        if !state.world.bg_tile {
+            texture.render(
                 ctx,
                 DrawParams::new()
-                    .position(state.world.sans_pos)
-                    .origin(s_origin)
-                    .scale(Vec2::new(3.0, 3.0)), // Increased scale from 2.0 to 3.0
+                    .position(Vec2::new(0.0, 0.0))
+                    .scale(Vec2::new(
+                        SCREEN_WIDTH as f32 / width,
+                        SCREEN_HEIGHT as f32 / height,
+                    ))
+                    .color(tint),
             );
+            continue;
         }
 
-        // Interaction Prompt
-        let dx = state.player.pos.x - state.world.sans_pos.x;
-        let dy = state.player.pos.y - state.world.sans_pos.y;
-        let distance = (dx * dx + dy * dy).sqrt();
-
-        if distance < 120.0 {
-            let prompt = "Press F to interact";
-            let mut text = Text::new(prompt, state.font.clone());
-            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
-
-            text.draw(
+        // Keep the aspect ratio, fit the height and scroll horizontally.
+        // rem_euclid keeps the first tile at or left of the screen edge, also
+        // while the player leans past the left exit.
+        let scale = SCREEN_HEIGHT as f32 / height;
+        let tile_width = width * scale;
+        let scroll = state.player.pos.x * layer.parallax + state.world.stage_clock * layer.drift;
+        let mut x = -scroll.rem_euclid(tile_width);
+        while x < SCREEN_WIDTH as f32 {
+            texture.render(
                 ctx,
                 DrawParams::new()
-                    .position(Vec2::new(
-                        state.world.sans_pos.x - width / 2.0,
-                        state.world.sans_pos.y - 80.0,
-                    ))
-                    .color(Color::rgb(1.0, 1.0, 0.0)),
+                    .position(Vec2::new(x, 0.0))
+                    .scale(Vec2::new(scale, scale))
+                    .color(tint),
             );
+            x += tile_width;
         }
     }
+}
 
-    // Draw Rarity in Stage 2
-    if state.world.current_stage == 2 {
-        if state.world.rarity_alive {
-            if let Some(rarity_texture) = &state.world.rarity_texture {
-                let r_width = rarity_texture.width() as f32;
-                let r_height = rarity_texture.height() as f32;
-                let r_origin = Vec2::new(r_width / 2.0, r_height / 2.0);
+/// A world sprite waiting for its turn in the depth-sorted pass.
+pub struct Sprite<'a> {
+    pub texture: &'a Texture,
+    pub params: DrawParams,
+    pub pos: Vec2<f32>,
+    pub feet_y: f32,               // Sort key, the bottom edge of the scaled sprite
+    pub shadow: Option<Vec2<f32>>, // Ground shadow size, drawn under every sprite
+    pub npc: Option<NpcId>,        // Who this is, for the interaction highlight
+}
 
-                rarity_texture.draw(
-                    ctx,
-                    DrawParams::new()
-                        .position(state.world.rarity_pos)
-                        .origin(r_origin)
-                        .scale(Vec2::new(1.3, 1.3)),
-                );
-            }
+impl<'a> Sprite<'a> {
+    /// Centered on `pos` like every overworld sprite. Tall sprites still
+    /// sort by where they stand rather than by their middle.
+    pub fn centered(texture: &'a Texture, pos: Vec2<f32>, scale: Vec2<f32>) -> Self {
+        let size = Vec2::new(texture.width() as f32, texture.height() as f32);
+        Self {
+            texture,
+            params: DrawParams::new()
+                .position(pos)
+                .origin(size / 2.0)
+                .scale(scale),
+            pos,
+            feet_y: pos.y + size.y * scale.y.abs() / 2.0,
+            shadow: None,
+            npc: None,
+        }
+    }
 
-            // Interaction Prompt (Only from behind)
-            let dx = state.player.pos.x - state.world.rarity_pos.x;
-            let dy = state.player.pos.y - state.world.rarity_pos.y;
-            let distance = (dx * dx + dy * dy).sqrt();
+    pub fn color(mut self, color: Color) -> Self {
+        self.params = self.params.color(color);
+        self
+    }
 
-            if distance < 120.0 && state.player.pos.x < state.world.rarity_pos.x {
-                let prompt = "Press F to Stab";
-                let mut text = Text::new(prompt, state.font.clone());
-                let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+    pub fn shadow(mut self, size: Vec2<f32>) -> Self {
+        self.shadow = Some(size);
+        self
+    }
 
-                text.draw(
-                    ctx,
-                    DrawParams::new()
-                        .position(Vec2::new(
-                            state.world.rarity_pos.x - width / 2.0,
-                            state.world.rarity_pos.y - 80.0,
-                        ))
-                        .color(Color::RED),
+    pub fn npc(mut self, npc: NpcId) -> Self {
+        self.npc = Some(npc);
+        self
+    }
+}
+
+/// Brings the player and NPCs in once the background is half revealed, a
+/// beat behind the stage fade.
+fn fade_in_sprites(state: &mut GameState, dt: f32) {
+    if state.fade_out || state.fade_alpha > SPRITE_FADE_START {
+        return;
+    }
+    let step = SPRITE_FADE_RATE * dt;
+    state.world.npc_fade_alpha = (state.world.npc_fade_alpha + step).min(1.0);
+    state.player.player_fade_alpha = (state.player.player_fade_alpha + step).min(1.0);
+}
+
+/// The player and this scene's own NPCs and props. Eilish and the crowd add
+/// themselves from their modules.
+fn collect_sprites<'a>(state: &'a GameState, sprites: &mut Vec<Sprite<'a>>) {
+    sprites.extend(player_sprite(&state.player, state.player.outfit, 3.0));
+
+    if state.world.in_current_stage(NpcId::Sans) {
+        let sans_texture = if state.world.sans_chase == SansChase::Shrugging
+            || crate::scenes::idle::sans_shrugging(state)
+        {
+            &state.world.sans_shrug_texture
+        } else {
+            &state.world.sans_handshake_texture
+        };
+        if let Some(texture) = sans_texture {
+            sprites.push(
+                Sprite::centered(
+                    texture,
+                    state.world.sans_pos + state.world.dance_offset(NpcId::Sans),
+                    Vec2::new(3.0, 3.0),
+                )
+                .shadow(NPC_SHADOW)
+                .npc(NpcId::Sans),
+            );
+        }
+    }
+
+    #[allow(clippy::collapsible_if)]
+    if state.world.in_current_stage(NpcId::MusicBox) {
+        if let Some(texture) = &state.world.musicbox_texture {
+            sprites.push(
+                Sprite::centered(texture, state.world.musicbox_pos, Vec2::new(0.3, 0.3))
+                    .npc(NpcId::MusicBox),
+            );
+        }
+    }
+
+    #[allow(clippy::collapsible_if)]
+    if state.world.in_current_stage(NpcId::Gaster) {
+        // Both of Gaster's layers share a base, the stable sort keeps them in order
+        if crate::scenes::gaster::is_visible(state) {
+            // Now and then the talking sprite flashes with nobody talking to him
+            let blend = if crate::scenes::idle::gaster_flickering(state) {
+                1.0
+            } else {
+                state.world.gaster_talk_blend
+            };
+            let gaster_layers = [
+                (&state.world.npc_gaster_standing, 1.0 - blend),
+                (&state.world.npc_gaster_talking, blend),
+            ];
+            // One shadow for the pair, on whichever layer comes first
+            let mut shadow = Some(NPC_SHADOW);
+            for (texture, alpha) in gaster_layers {
+                if let Some(texture) = texture.as_ref().filter(|_| alpha > 0.0) {
+                    let mut sprite =
+                        Sprite::centered(texture, state.world.gaster_pos, Vec2::new(3.0, 3.0))
+                            .color(Color::rgba(1.0, 1.0, 1.0, alpha));
+                    // The highlight goes behind the bottom layer only
+                    if let Some(size) = shadow.take() {
+                        sprite = sprite.shadow(size).npc(NpcId::Gaster);
+                    }
+                    sprites.push(sprite);
+                }
+            }
+        }
+    }
+
+    if state.world.in_current_stage(NpcId::Rarity) {
+        let rarity_texture = match state.world.rarity_stab_frame {
+            0 => &state.world.rarity_texture,
+            frame => &state.world.rarity_stab_textures[frame as usize - 1],
+        };
+        #[allow(clippy::collapsible_if)]
+        if state.world.rarity_alive {
+            if let Some(texture) = rarity_texture {
+                sprites.push(
+                    Sprite::centered(
+                        texture,
+                        state.world.rarity_pos + state.world.dance_offset(NpcId::Rarity),
+                        Vec2::new(1.3, 1.3),
+                    )
+                    .shadow(NPC_SHADOW)
+                    .npc(NpcId::Rarity),
                 );
             }
-        } else if state.world.rarity_stabbed_timer > 0.0 {
-            // Draw stabbed message
-            let msg = "You stabbed Rarity from behind!";
-            let mut text = Text::new(msg, state.font.clone());
-            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(200.0);
+        }
+    }
+}
+
+/// The F3 spawn point markers for one stage, built the first time the
+/// overlay shows there. The landing spots are worked out then too, so they
+/// show where the player lands on entering, not where the crowd has since
+/// wandered.
+pub struct SpawnOverlay {
+    stage: u8,
+    mesh: Mesh,
+    labels: Vec<(Text, Vec2<f32>, Color)>,
+}
+
+/// A ring per spawn point with a tick the way the player faces, orange with
+/// a second ring where the player really lands if the point is blocked.
+fn build_spawn_overlay(ctx: &mut Context, state: &GameState) -> tetra::Result<SpawnOverlay> {
+    let stage_def = state.world.stage_def();
+    let mut builder = GeometryBuilder::new();
+    let mut labels = Vec::new();
+    for entry in Entry::ALL {
+        let spawn = stage_def.spawn(entry);
+        let landed = crate::physics::nearest_free_spot(spawn.pos, PLAYER_RADIUS, &state.world);
+        let color = if landed == spawn.pos {
+            Color::GREEN
+        } else {
+            Color::rgb(1.0, 0.5, 0.0)
+        };
+        let facing = match spawn.facing {
+            Direction::Left => Vec2::new(-1.0, 0.0),
+            Direction::Right => Vec2::new(1.0, 0.0),
+            Direction::Front => Vec2::new(0.0, 1.0),
+        };
+
+        builder.set_color(color);
+        builder.circle(ShapeStyle::Stroke(1.0), spawn.pos, PLAYER_RADIUS)?;
+        builder.polyline(2.0, &[spawn.pos, spawn.pos + facing * PLAYER_RADIUS])?;
+        if landed != spawn.pos {
+            builder.set_color(Color::GREEN);
+            builder.circle(ShapeStyle::Stroke(1.0), landed, PLAYER_RADIUS)?;
+        }
 
-            text.draw(
+        labels.push((
+            Text::new(format!("{:?}", entry), state.font()),
+            spawn.pos + Vec2::new(-PLAYER_RADIUS, PLAYER_RADIUS + 2.0),
+            color,
+        ));
+    }
+    Ok(SpawnOverlay {
+        stage: state.world.current_stage,
+        mesh: builder.build_mesh(ctx)?,
+        labels,
+    })
+}
+
+/// The stage's spawn points while the F3 overlay is up.
+fn draw_spawn_points(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.perf.overlay_visible {
+        return Ok(());
+    }
+
+    let mut cache = state.world.spawn_overlay.borrow_mut();
+    if cache
+        .as_ref()
+        .is_none_or(|overlay| overlay.stage != state.world.current_stage)
+    {
+        *cache = Some(build_spawn_overlay(ctx, state)?);
+    }
+    let Some(overlay) = cache.as_mut() else {
+        unreachable!("spawn overlay was just built");
+    };
+    overlay.mesh.render(ctx, DrawParams::new());
+    for (label, pos, color) in &mut overlay.labels {
+        label.render(ctx, DrawParams::new().position(*pos).color(*color));
+    }
+    Ok(())
+}
+
+/// Soft glow and arrow on edges that lead to another stage. Brightens while
+/// the player is pushing through.
+fn draw_edge_indicators(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    // Edges are locked during a chase, and stage 3's exits are behind its walls
+    if state.world.current_stage == 3 || crate::scenes::sans::is_chasing(state) {
+        return Ok(());
+    }
+
+    let stage_def = state.world.stage_def();
+    let push = (state.world.edge_push_timer / EDGE_PUSH_SECONDS).min(1.0);
+    let mid_y = (stage_def.top_bound + stage_def.bottom_bound) / 2.0;
+    let height = stage_def.bottom_bound - stage_def.top_bound;
+
+    for (exit, right) in [
+        (state.world.left_exit(), false),
+        (state.world.right_exit(), true),
+    ] {
+        if !exit {
+            continue;
+        }
+
+        // Three stacked bands, brightest right at the edge
+        for band in 0..3 {
+            let width = EDGE_GLOW_WIDTH * (3 - band) as f32 / 3.0;
+            let x = if right {
+                SCREEN_WIDTH as f32 - width
+            } else {
+                0.0
+            };
+            let glow = Mesh::rectangle(
                 ctx,
-                DrawParams::new()
-                    .position(Vec2::new(
-                        state.world.rarity_pos.x - width / 2.0,
-                        state.world.rarity_pos.y,
-                    ))
-                    .color(Color::RED),
+                ShapeStyle::Fill,
+                Rectangle::new(x, stage_def.top_bound, width, height),
+            )?;
+            glow.render(
+                ctx,
+                DrawParams::new().color(Color::rgba(1.0, 1.0, 0.8, 0.06 + push * 0.08)),
             );
         }
-    }
 
-    // Fade Transition Overlay
-    if state.fade_out {
-        let fade_rect = Mesh::rectangle(
+        let (tip, back) = if right {
+            (SCREEN_WIDTH as f32 - 6.0, SCREEN_WIDTH as f32 - 18.0)
+        } else {
+            (6.0, 18.0)
+        };
+        let arrow = Mesh::polygon(
             ctx,
             ShapeStyle::Fill,
-            Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
-        )
-        .unwrap();
-        fade_rect.draw(
+            &[
+                Vec2::new(back, mid_y - 12.0),
+                Vec2::new(tip, mid_y),
+                Vec2::new(back, mid_y + 12.0),
+            ],
+        )?;
+        arrow.render(
             ctx,
-            DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, state.fade_alpha)),
+            DrawParams::new().color(Color::rgba(1.0, 1.0, 0.8, 0.4 + push * 0.6)),
         );
     }
 
     Ok(())
 }
+
+/// "There's nothing that way" over the player's head after bumping an edge
+/// that doesn't lead anywhere.
+/// An interaction prompt centered on `anchor`, outlined so it reads over the
+/// bright stages.
+fn draw_prompt(
+    ctx: &mut Context,
+    state: &GameState,
+    prompt: &str,
+    anchor: Vec2<f32>,
+    color: Color,
+) {
+    let text_scale = state.text_scale();
+    let mut text = Text::new(prompt, state.font());
+    let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0) * text_scale;
+    draw_styled_text(
+        ctx,
+        &mut text,
+        DrawParams::new()
+            .position(Vec2::new(anchor.x - width / 2.0, anchor.y))
+            .color(color),
+        TextStyle::OUTLINE,
+        text_scale,
+    );
+}
+
+fn draw_edge_bark(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if state.world.edge_bark_timer <= 0.0 {
+        return Ok(());
+    }
+    draw_bubble(ctx, state, &state.texts.ui.nothing_that_way)
+}
+
+/// Speech bubble over the player's head.
+pub fn draw_bubble(ctx: &mut Context, state: &GameState, content: &str) -> tetra::Result {
+    draw_bubble_at(ctx, state, content, state.player.pos)
+}
+
+/// Speech bubble over whoever stands at `pos`.
+pub fn draw_bubble_at(
+    ctx: &mut Context,
+    state: &GameState,
+    content: &str,
+    pos: Vec2<f32>,
+) -> tetra::Result {
+    let mut text = Text::new(content, state.font());
+    let bounds = text
+        .get_bounds(ctx)
+        .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0));
+    // They might be right at the edge, keep the bubble on screen
+    let x =
+        (pos.x - bounds.width / 2.0 - 6.0).clamp(4.0, SCREEN_WIDTH as f32 - bounds.width - 16.0);
+    let bubble = Rectangle::new(x, pos.y - 90.0, bounds.width + 12.0, bounds.height + 10.0);
+
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, bubble)?;
+    fill.render(ctx, DrawParams::new().color(Color::WHITE));
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), bubble)?;
+    border.render(ctx, DrawParams::new().color(Color::BLACK));
+
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(bubble.x + 6.0, bubble.y + 5.0))
+            .color(Color::BLACK),
+    );
+
+    Ok(())
+}
+
+/// Bounding boxes of the hoverable HUD elements, matching the layout in `draw`.
+fn hud_bounds(ctx: &mut Context, state: &GameState) -> [(HudElement, Rectangle); 3] {
+    let text_bounds = |ctx: &mut Context, content: String| {
+        Text::new(content, state.font())
+            .get_bounds(ctx)
+            .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0))
+    };
+
+    let stage = text_bounds(
+        ctx,
+        state.texts.fill(
+            TextKey::StageIndicator,
+            &[
+                ("stage_current", &state.world.current_stage),
+                ("stage_total", &STAGE_COUNT),
+            ],
+        ),
+    );
+    let fps = text_bounds(ctx, format!("FPS: {:.0}", tetra::time::get_fps(ctx)));
+    let hp = text_bounds(
+        ctx,
+        state.texts.fill(
+            TextKey::HpLabel,
+            &[
+                ("hp", &state.player.player_hp),
+                ("max_hp", &state.player.player_max_hp),
+            ],
+        ),
+    );
+
+    // The bar and its label count as one element
+    let bar_x = SCREEN_WIDTH as f32 - 150.0 - 10.0;
+    let health_x = bar_x - hp.width - 10.0;
+    [
+        (
+            HudElement::Health,
+            Rectangle::new(
+                health_x,
+                10.0,
+                SCREEN_WIDTH as f32 - 10.0 - health_x,
+                hp.height.max(15.0),
+            ),
+        ),
+        (
+            HudElement::Stage,
+            Rectangle::new(10.0, 10.0, stage.width, stage.height),
+        ),
+        (
+            HudElement::Fps,
+            Rectangle::new(10.0, 30.0, fps.width, fps.height),
+        ),
+    ]
+}
+
+/// Tracks which HUD element the mouse rests on, and for how long.
+fn update_tooltip(ctx: &mut Context, state: &mut GameState) {
+    let mouse = input::get_mouse_position(ctx);
+    let hovered = hud_bounds(ctx, state)
+        .into_iter()
+        .find(|(_, rect)| rect.contains_point(mouse))
+        .map(|(element, _)| element);
+
+    if hovered != state.world.tooltip_target {
+        state.world.tooltip_target = hovered;
+        state.world.tooltip_hover_timer = 0.0;
+    } else if hovered.is_some() {
+        state.world.tooltip_hover_timer += tetra::time::get_delta_time(ctx).as_secs_f32();
+    }
+}
+
+/// Small dark box under the cursor describing the hovered HUD element.
+fn draw_tooltip(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    let Some(target) = state.world.tooltip_target else {
+        return Ok(());
+    };
+    if state.world.tooltip_hover_timer < TOOLTIP_DELAY {
+        return Ok(());
+    }
+
+    let label = match target {
+        HudElement::Health => &state.texts.ui.tooltip_health,
+        HudElement::Stage => &state.texts.ui.tooltip_stage,
+        HudElement::Fps => &state.texts.ui.tooltip_fps,
+    };
+    let mut text = Text::new(label.as_str(), state.font());
+    let bounds = text
+        .get_bounds(ctx)
+        .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0));
+
+    // Below the heart cursor, kept on screen for the elements on the right
+    let mouse = input::get_mouse_position(ctx);
+    let width = bounds.width + 12.0;
+    let height = bounds.height + 10.0;
+    let x = mouse.x.min(SCREEN_WIDTH as f32 - width - 4.0);
+    let tooltip = Rectangle::new(x, mouse.y + 14.0, width, height);
+
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, tooltip)?;
+    fill.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.85)),
+    );
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(1.0), tooltip)?;
+    border.render(ctx, DrawParams::new().color(Color::rgb(0.5, 0.5, 0.5)));
+
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(tooltip.x + 6.0, tooltip.y + 5.0))
+            .color(Color::WHITE),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,99 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+
+pub const VISIBLE_ROWS: usize = 18; // Also how far PageUp/PageDown jump
+const ROW_HEIGHT: f32 = 26.0;
+const TOP: f32 = 80.0;
+const LEFT: f32 = 40.0;
+const NAME_WIDTH: f32 = 150.0; // Timestamp and speaker column
+
+/// Scenes the log can be opened over. Both pause while it's open.
+pub fn can_open(state: &GameState) -> bool {
+    matches!(state.scene, Scene::Desktop | Scene::AyasofyaInside)
+}
+
+/// Dimmed list of the session's dialogue, newest at the bottom.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.dialogue_log.open {
+        return Ok(());
+    }
+
+    let dim_rect = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Fill,
+        Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+    )?;
+    dim_rect.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+    );
+
+    let mut title = Text::new(&state.texts.ui.dialogue_log_title, state.font());
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(LEFT, 30.0))
+            .color(Color::WHITE),
+    );
+
+    let entries = state.dialogue_log.entries();
+    if entries.is_empty() {
+        let mut empty = Text::new(&state.texts.ui.dialogue_log_empty, state.font());
+        empty.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(LEFT, TOP))
+                .color(Color::rgb(0.5, 0.5, 0.5)),
+        );
+    }
+
+    // The bottom row is `scroll` entries back from the newest
+    let end = entries.len() - state.dialogue_log.scroll.min(entries.len());
+    let start = end.saturating_sub(VISIBLE_ROWS);
+    for (row, entry) in entries.range(start..end).enumerate() {
+        let y = TOP + row as f32 * ROW_HEIGHT;
+        let minutes = (entry.time / 60.0) as u32;
+        let seconds = (entry.time % 60.0) as u32;
+
+        let mut name = Text::new(
+            format!("{:02}:{:02} {}", minutes, seconds, entry.speaker.name()),
+            state.font(),
+        );
+        name.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(LEFT, y))
+                .color(entry.speaker.color()),
+        );
+
+        // One row per entry, multi-line pages are joined up
+        let mut line = Text::new(entry.text.replace('\n', " "), state.font());
+        line.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(LEFT + NAME_WIDTH, y))
+                .color(Color::WHITE),
+        );
+    }
+
+    let mut hint = Text::new(&state.texts.ui.dialogue_log_hint, state.font());
+    let width = hint.get_bounds(ctx).map(|b| b.width).unwrap_or(300.0);
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(
+                (SCREEN_WIDTH as f32 - width) / 2.0,
+                SCREEN_HEIGHT as f32 - 40.0,
+            ))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,133 @@
+use tetra::Context;
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams};
+use tetra::math::Vec2;
+
+use crate::defs::{NpcId, SCREEN_WIDTH};
+use crate::game_state::GameState;
+use crate::perf::RenderMut;
+use crate::sim::UpdateInput;
+use crate::world::WorldState;
+
+const DANCE_DISTANCE: f32 = 150.0; // Rarity dances once she's this close to the music
+const RARITY_SPEED: f32 = 1.5; // Pixels per tick
+const RARITY_RADIUS: f32 = 20.0;
+const RARITY_MIN_X: f32 = 10.0; // Keeps her off the left edge
+
+/// How the NPCs take the music box. Sans taps his foot, Gaster sulks and
+/// Rarity goes to dance next to it. The box stands in stage 1, from the
+/// stage right of it (2) it's heard through the left edge.
+pub fn step(input: &mut UpdateInput, state: &mut GameState) {
+    let world = &mut state.world;
+    if !world.music_playing {
+        stop(world);
+        if let Some(home) = world.npc_home(NpcId::Rarity) {
+            walk_rarity(world, home);
+        }
+        return;
+    }
+
+    world.dance_time += input.dt;
+    if !music_heard(world) {
+        return;
+    }
+
+    // He won't talk over the music
+    if world.in_current_stage(NpcId::Gaster) {
+        world.gaster_talking = false;
+        world.gaster_dialogue = None;
+    }
+
+    if world.in_current_stage(NpcId::Rarity) && !world.rarity_dancing {
+        let source = music_source(world);
+        let before = world.rarity_pos;
+        walk_rarity(world, source);
+        // Up against the edge counts as close enough
+        let moved = world.rarity_pos.distance(before);
+        if world.rarity_pos.distance(source) <= DANCE_DISTANCE || moved < RARITY_SPEED / 2.0 {
+            world.rarity_dancing = true;
+        }
+    }
+}
+
+/// Ends every reaction at once, for the music stopping or a fight starting.
+/// Rarity walks home on her own afterwards.
+pub fn stop(world: &mut WorldState) {
+    world.dance_time = 0.0;
+    world.rarity_dancing = false;
+}
+
+pub fn gaster_sulking(state: &GameState) -> bool {
+    state.scene == crate::defs::Scene::Desktop
+        && state.world.music_playing
+        && music_heard(&state.world)
+        && state.world.in_current_stage(NpcId::Gaster)
+}
+
+/// In the music box's stage and the one right of it.
+fn music_heard(world: &WorldState) -> bool {
+    let box_stage = world.npc_stage(NpcId::MusicBox);
+    world.current_stage == box_stage
+        || world.layout.previous_stage(world.current_stage) == Some(box_stage)
+}
+
+/// The music box in the current stage's coordinates, one screen to the
+/// left when it's heard through the edge.
+fn music_source(world: &WorldState) -> Vec2<f32> {
+    if world.in_current_stage(NpcId::MusicBox) {
+        world.musicbox_pos
+    } else {
+        world.musicbox_pos - Vec2::new(SCREEN_WIDTH as f32, 0.0)
+    }
+}
+
+fn walk_rarity(world: &mut WorldState, target: Vec2<f32>) {
+    if !world.rarity_alive || world.rarity_stab_frame > 0 {
+        return;
+    }
+    let to_target = target - world.rarity_pos;
+    let distance = to_target.magnitude();
+    if distance < 1.0 {
+        return;
+    }
+    let delta = to_target / distance * RARITY_SPEED.min(distance);
+
+    let stage_def = world.stage_def();
+    let mut pos = crate::physics::move_and_collide(
+        world.rarity_pos,
+        delta,
+        RARITY_RADIUS,
+        world,
+        NpcId::Rarity,
+    );
+    pos.x = pos.x.clamp(RARITY_MIN_X, SCREEN_WIDTH as f32);
+    pos.y = pos.y.clamp(stage_def.top_bound, stage_def.bottom_bound);
+    world.rarity_pos = pos;
+}
+
+/// Gaster's "..." while the music plays, drawn over the sprites in place
+/// of his idle line.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !gaster_sulking(state) || !crate::scenes::gaster::is_visible(state) {
+        return Ok(());
+    }
+    crate::scenes::desktop::draw_bubble_at(ctx, state, "...", state.world.gaster_pos)?;
+
+    if state.player.pos.distance(state.world.gaster_pos) < 100.0
+        && state.world.is_nearest(NpcId::Gaster)
+    {
+        let mut text = Text::new("He's not talking over this", state.font());
+        let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(
+                    state.world.gaster_pos.x - width / 2.0,
+                    state.world.gaster_pos.y - 55.0,
+                ))
+                .color(Color::rgb(0.6, 0.6, 0.6)),
+        );
+    }
+
+    Ok(())
+}
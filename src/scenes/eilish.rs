@@ -3,13 +3,39 @@ use tetra::Context;
 use tetra::graphics::mesh::{Mesh, ShapeStyle};
 use tetra::graphics::text::Text;
 use tetra::graphics::{Color, DrawParams, Rectangle};
-use tetra::input::{self, Key};
+use tetra::input::Key;
 use tetra::math::Vec2;
 
+use crate::combat::CombatEnemy;
+use crate::defs::{Direction, NpcId, SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::dialogue_log::Speaker;
 use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::scenes::desktop::{PLAYER_SHADOW, Sprite};
+use crate::sim::{Effect, Effects, UpdateInput};
 
-pub fn update(ctx: &mut Context, state: &mut GameState) {
-    if state.world.current_stage != 4 {
+const SONG_CUES_PATH: &str = "./assets/eilish_song.cues";
+
+// She comes for players who linger in the dead space
+const APPROACH_DELAY: f32 = 5.0; // Seconds of dead_zone_time before she moves
+const APPROACH_BASE_SPEED: f32 = 0.5; // Pixels per tick
+const APPROACH_RAMP: f32 = 0.2; // Extra pixels per tick for every further second
+const APPROACH_MAX_SPEED: f32 = 2.6; // Player walks at 2.0
+const RETURN_SPEED: f32 = 1.5;
+const CATCH_DISTANCE: f32 = 50.0;
+
+pub fn step(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
+    if !state.world.in_current_stage(NpcId::Eilish) {
+        return;
+    }
+
+    if state.world.eilish_listening {
+        update_song(input.dt, state);
+        return;
+    }
+
+    if update_approach(state) {
+        start_combat(state);
         return;
     }
 
@@ -17,10 +43,35 @@ pub fn update(ctx: &mut Context, state: &mut GameState) {
     let dy = state.player.pos.y - state.world.eilish_pos.y;
     let distance = (dx * dx + dy * dy).sqrt();
 
-    if distance < 120.0 {
-        if input::is_key_pressed(ctx, Key::F) {
-            state.world.eilish_talking = true;
-            state.world.eilish_dialogue_timer = 300.0; // 5 seconds
+    // Face the player
+    state.world.eilish_facing = if dx < -30.0 {
+        Direction::Left
+    } else if dx > 30.0 {
+        Direction::Right
+    } else {
+        Direction::Front
+    };
+
+    if distance < 120.0 && state.world.is_nearest(NpcId::Eilish) {
+        if input.is_pressed(Key::L) {
+            start_song(state, effects);
+        } else if input.is_pressed(Key::F) {
+            if state.world.eilish_song_skipped {
+                // She remembers being cut off, once
+                state.world.eilish_song_skipped = false;
+                say(state, "You left in the middle of my song. Rude.");
+                return;
+            }
+
+            let friendly = [
+                "Thanks for listening earlier.",
+                "I wrote that one about this place.",
+                "Stay away from the red side, okay?",
+            ];
+            if state.world.eilish_song_heard && input.rng.random_bool(0.5) {
+                say(state, friendly[input.rng.random_range(0..friendly.len())]);
+                return;
+            }
 
             let dialogues = [
                 "Don't go to the dead space!",
@@ -32,9 +83,7 @@ pub fn update(ctx: &mut Context, state: &mut GameState) {
                 "Have you tried turning it off and on again?",
                 "sudo rm -rf / ... just kidding!",
             ];
-            let mut rng = rand::rng();
-            state.world.eilish_current_dialogue =
-                dialogues[rng.random_range(0..dialogues.len())].to_string();
+            say(state, dialogues[input.rng.random_range(0..dialogues.len())]);
         }
     } else {
         // Close textbox when out of range
@@ -51,37 +100,229 @@ pub fn update(ctx: &mut Context, state: &mut GameState) {
     }
 }
 
-pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
-    if state.world.current_stage != 4 {
+/// Walks her towards the player once the dead space has had them for a
+/// while, faster the longer they stay, and back home once they're out.
+/// Returns true when she reaches the player.
+fn update_approach(state: &mut GameState) -> bool {
+    let home = state
+        .world
+        .npc_home(NpcId::Eilish)
+        .unwrap_or(state.world.eilish_pos);
+    let lingering = state.world.dead_zone_time - APPROACH_DELAY;
+
+    let delta = if lingering > 0.0 {
+        let to_player = state.player.pos - state.world.eilish_pos;
+        if to_player.magnitude() < CATCH_DISTANCE {
+            return true;
+        }
+        let speed = (APPROACH_BASE_SPEED + lingering * APPROACH_RAMP).min(APPROACH_MAX_SPEED);
+        to_player.normalized() * speed
+    } else if state.world.dead_zone_time <= 0.0 && state.world.eilish_pos != home {
+        let to_home = home - state.world.eilish_pos;
+        if to_home.magnitude() <= RETURN_SPEED {
+            state.world.eilish_pos = home;
+            return false;
+        }
+        to_home.normalized() * RETURN_SPEED
+    } else {
+        return false;
+    };
+
+    let stage_def = state.world.stage_def();
+    let mut pos = crate::physics::move_and_collide(
+        state.world.eilish_pos,
+        delta,
+        40.0,
+        &state.world,
+        NpcId::Eilish,
+    );
+    pos.x = pos.x.clamp(0.0, SCREEN_WIDTH as f32);
+    pos.y = pos.y.clamp(stage_def.top_bound, stage_def.bottom_bound);
+    state.world.eilish_pos = pos;
+    false
+}
+
+/// She caught the player. She's back home and the dead space timer is
+/// cleared by the time they come out of the fight.
+fn start_combat(state: &mut GameState) {
+    if let Some(home) = state.world.npc_home(NpcId::Eilish) {
+        state.world.eilish_pos = home;
+    }
+    state.world.dead_zone_time = 0.0;
+    state.world.eilish_talking = false;
+    state.world.pending_combat_enemy = Some(CombatEnemy::Eilish);
+    state.scene = Scene::CombatTransition;
+    state.fade_out = true;
+    state.fade_alpha = 0.0;
+}
+
+/// Opens her textbox with `line` and records it in the dialogue log.
+fn say(state: &mut GameState, line: &str) {
+    state.world.eilish_talking = true;
+    state.world.eilish_dialogue_timer = 300.0; // 5 seconds
+    state.world.eilish_current_dialogue = line.to_string();
+    state.dialogue_log.push(Speaker::Eilish, line);
+}
+
+/// Whether L would start her song right now, so the dialogue log leaves it alone.
+pub fn can_listen(state: &GameState) -> bool {
+    state.scene == Scene::Desktop
+        && state.world.in_current_stage(NpcId::Eilish)
+        && !state.world.eilish_listening
+        && state.world.is_nearest(NpcId::Eilish)
+        && state.player.pos.distance(state.world.eilish_pos) < 120.0
+}
+
+fn start_song(state: &mut GameState, effects: &mut dyn Effects) {
+    if state.world.eilish_song_cues.is_empty() {
+        state.world.eilish_song_cues = crate::subtitles::load_cues(SONG_CUES_PATH);
+    }
+    if state.world.eilish_song_cues.is_empty() {
+        return;
+    }
+
+    // Only one song at a time
+    state.world.stop_music();
+
+    effects.apply(state, Effect::StartEilishSong);
+    state.world.eilish_talking = false;
+    state.world.eilish_listening = true;
+    state.world.eilish_song_timer = 0.0;
+}
+
+/// Plays the song `start_song` queued up.
+pub fn play_song(ctx: &mut Context, state: &mut GameState) {
+    if let Some(song) = &state.world.eilish_song {
+        match song.play_with(ctx, state.system.volume, 1.0) {
+            Ok(instance) => state.world.eilish_song_instance = Some(instance),
+            Err(e) => log::warn!("Failed to play Eilish song: {}", e),
+        }
+    }
+}
+
+fn update_song(dt: f32, state: &mut GameState) {
+    let previous = state.world.eilish_song_timer;
+    state.world.eilish_song_timer += dt;
+
+    // Lyrics go in the log as they come up
+    for cue in &state.world.eilish_song_cues {
+        if cue.start >= previous && cue.start < state.world.eilish_song_timer {
+            state.dialogue_log.push(Speaker::Eilish, &cue.text);
+        }
+    }
+
+    if state.world.eilish_song_timer
+        >= crate::subtitles::cues_duration(&state.world.eilish_song_cues)
+    {
+        stop_song(state);
+        state.world.eilish_song_heard = true;
+        state.player.heal(4);
+
+        say(state, "Thanks for listening. Feel a bit better?");
+    }
+}
+
+/// Called when the player presses Esc during the song.
+pub fn skip_song(state: &mut GameState) {
+    stop_song(state);
+    state.world.eilish_song_skipped = true;
+}
+
+fn stop_song(state: &mut GameState) {
+    if let Some(instance) = &mut state.world.eilish_song_instance {
+        instance.stop();
+    }
+    state.world.eilish_song_instance = None;
+    state.world.eilish_listening = false;
+}
+
+/// Dims the screen and shows the current lyric line. Drawn on top of the stage.
+pub fn draw_song_overlay(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.world.eilish_listening {
         return Ok(());
     }
 
-    // Draw Eilish
-    if let Some(eilish_texture) = &state.world.eilish_texture {
-        let e_width = eilish_texture.width() as f32;
-        let e_height = eilish_texture.height() as f32;
-        let e_origin = Vec2::new(e_width / 2.0, e_height / 2.0);
+    let dim_rect = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Fill,
+        Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+    )?;
+    dim_rect.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.6)),
+    );
 
-        eilish_texture.draw(
-            ctx,
-            DrawParams::new()
-                .position(state.world.eilish_pos)
-                .origin(e_origin)
-                .scale(Vec2::new(0.1, 0.1)),
+    if let Some(cue) =
+        crate::subtitles::active_cue(&state.world.eilish_song_cues, state.world.eilish_song_timer)
+    {
+        crate::subtitles::draw_subtitle(ctx, &state.font(), &cue.text)?;
+    }
+
+    let mut hint = Text::new("Esc to stop listening", state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(10.0, SCREEN_HEIGHT as f32 - 30.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
+
+/// Eilish for the desktop's depth-sorted sprite pass.
+pub fn collect_sprites<'a>(state: &'a GameState, sprites: &mut Vec<Sprite<'a>>) {
+    if !state.world.in_current_stage(NpcId::Eilish) {
+        return;
+    }
+
+    // Without the side views, the front sprite mirrored stands in for the left one
+    let world = &state.world;
+    let (texture, flip_x) = match world.eilish_facing {
+        Direction::Front => (world.eilish_texture.as_ref(), false),
+        Direction::Left => match &world.eilish_texture_left {
+            Some(texture) => (Some(texture), false),
+            None => (world.eilish_texture.as_ref(), true),
+        },
+        Direction::Right => (
+            world
+                .eilish_texture_right
+                .as_ref()
+                .or(world.eilish_texture.as_ref()),
+            false,
+        ),
+    };
+
+    if let Some(eilish_texture) = texture {
+        let scale_x = if flip_x { -0.1 } else { 0.1 };
+        sprites.push(
+            Sprite::centered(
+                eilish_texture,
+                state.world.eilish_pos,
+                Vec2::new(scale_x, 0.1),
+            )
+            .shadow(PLAYER_SHADOW)
+            .npc(NpcId::Eilish),
         );
     }
+}
+
+/// Her prompt and textbox, drawn over the sprites.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.world.in_current_stage(NpcId::Eilish) {
+        return Ok(());
+    }
 
     // Interaction Prompt
     let dx = state.player.pos.x - state.world.eilish_pos.x;
     let dy = state.player.pos.y - state.world.eilish_pos.y;
     let distance = (dx * dx + dy * dy).sqrt();
 
-    if distance < 120.0 {
-        let prompt = "Press F to Talk";
-        let mut text = Text::new(prompt, state.font.clone());
+    if distance < 120.0 && state.world.is_nearest(NpcId::Eilish) && !state.world.eilish_listening {
+        let prompt = "Press F to Talk, L to Listen";
+        let mut text = Text::new(prompt, state.font());
         let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
 
-        text.draw(
+        text.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(
@@ -100,7 +341,7 @@ pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
             ShapeStyle::Fill,
             Rectangle::new(50.0, 450.0, 700.0, 130.0),
         ) {
-            box_rect.draw(
+            box_rect.render(
                 ctx,
                 DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.8)),
             );
@@ -111,11 +352,11 @@ pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
             ShapeStyle::Stroke(2.0),
             Rectangle::new(50.0, 450.0, 700.0, 130.0),
         ) {
-            border_rect.draw(ctx, DrawParams::new().color(Color::WHITE));
+            border_rect.render(ctx, DrawParams::new().color(Color::WHITE));
         }
 
-        let mut text = Text::new(&state.world.eilish_current_dialogue, state.font.clone());
-        text.draw(
+        let mut text = Text::new(&state.world.eilish_current_dialogue, state.font());
+        text.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(70.0, 470.0))
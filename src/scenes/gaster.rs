@@ -0,0 +1,304 @@
+use rand::{Rng, RngCore};
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle, Texture, TextureFormat};
+use tetra::input::Key;
+use tetra::math::Vec2;
+
+use crate::defs::{Direction, NpcId};
+use crate::dialogue::{DialogueAction, DialogueChoice, DialogueNode, DialogueTree};
+use crate::dialogue_log::Speaker;
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::sim::{Effect, Effects, UpdateInput};
+use crate::world::STAGE_DEFS;
+
+const VANISH_DURATION: f32 = 0.6; // Seconds of static, he moves halfway through
+const STATIC_SIZE: i32 = 32;
+const STATIC_JITTER: f32 = 6.0; // Pixels
+const PLAYER_CLEARANCE: f32 = 65.0; // Gaster + player collider radius, plus a little room
+const IDLE_LINE_SECONDS: f32 = 3.0; // Fades out over this long
+const IDLE_HEAR_DISTANCE: f32 = 200.0;
+
+/// Teleport timer and static effect. Only runs in his stage, 2 unless the
+/// randomizer moved him.
+pub fn step(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
+    if !state.world.in_current_stage(NpcId::Gaster) {
+        return;
+    }
+
+    let dt = input.dt;
+    update_idle_line(state, dt, input.rng);
+
+    if state.world.gaster_vanish_timer > 0.0 {
+        let before = state.world.gaster_vanish_timer;
+        state.world.gaster_vanish_timer -= dt;
+
+        // Halfway through the static, jump to the new spot
+        if before > VANISH_DURATION / 2.0
+            && state.world.gaster_vanish_timer <= VANISH_DURATION / 2.0
+        {
+            state.world.gaster_pos = pick_destination(state);
+        }
+
+        effects.apply(state, Effect::GasterStatic { play_sound: false });
+        return;
+    }
+
+    state.world.gaster_teleport_timer -= dt;
+
+    // Never vanish mid-conversation, wait for the dialogue to close
+    if state.world.gaster_teleport_timer <= 0.0 && !state.world.gaster_talking {
+        state.world.gaster_vanish_timer = VANISH_DURATION;
+        state.world.gaster_teleport_timer = state.world.gaster_rng.random_range(20.0..60.0);
+        effects.apply(state, Effect::GasterStatic { play_sound: true });
+    }
+}
+
+/// Every 5-15 seconds he mutters a random idle line.
+fn update_idle_line(state: &mut GameState, dt: f32, rng: &mut dyn RngCore) {
+    state.world.gaster_idle_fade = (state.world.gaster_idle_fade - dt).max(0.0);
+    state.world.gaster_idle_timer -= dt;
+    if state.world.gaster_idle_timer > 0.0 {
+        return;
+    }
+
+    state.world.gaster_idle_timer = rng.random_range(5.0..15.0);
+    let lines = &state.texts.gaster_idle_lines;
+    if !lines.is_empty() {
+        state.world.gaster_idle_line = lines[rng.random_range(0..lines.len())].clone();
+        state.world.gaster_idle_fade = IDLE_LINE_SECONDS;
+    }
+}
+
+/// Whether he's heard about a tekfir, which puts him in the darker mood.
+pub fn knows_tekfir(state: &GameState) -> bool {
+    state
+        .system
+        .current_user
+        .as_ref()
+        .map_or(0, |u| u.tekfir_count)
+        > 0
+}
+
+/// One of his darker lines, with the chance to apologize.
+pub fn build_dialogue(state: &GameState, rng: &mut dyn RngCore) -> DialogueTree {
+    let texts = &state.texts;
+    let lines = &texts.gaster_dialogues_post_tekfir;
+    let line = lines[rng.random_range(0..lines.len())].clone();
+
+    DialogueTree::new(vec![
+        DialogueNode {
+            text: line,
+            choices: vec![
+                DialogueChoice {
+                    label: texts.gaster_apologize.clone(),
+                    action: DialogueAction::Close,
+                    next: Some(1),
+                },
+                DialogueChoice {
+                    label: texts.gaster_leave.clone(),
+                    action: DialogueAction::Close,
+                    next: None,
+                },
+            ],
+        },
+        DialogueNode {
+            text: texts.gaster_apology_reply.clone(),
+            choices: vec![DialogueChoice {
+                label: texts.gaster_leave.clone(),
+                action: DialogueAction::Close,
+                next: None,
+            }],
+        },
+    ])
+}
+
+/// Choices for the darker dialogue. Movement stays locked until it closes.
+pub fn step_dialogue(input: &mut UpdateInput, state: &mut GameState) {
+    update_talk_blend(state, input.dt);
+
+    let Some(tree) = &mut state.world.gaster_dialogue else {
+        return;
+    };
+    if input.is_pressed(Key::Up) || input.is_pressed(Key::W) {
+        tree.select_prev();
+    }
+    if input.is_pressed(Key::Down) || input.is_pressed(Key::S) {
+        tree.select_next();
+    }
+    if input.is_pressed(Key::Enter) || input.is_pressed(Key::F) {
+        if let Some(choice) = tree.current_node().choices.get(tree.selected) {
+            state.dialogue_log.push(Speaker::Player, &choice.label);
+        }
+        let (_, still_open) = tree.confirm();
+        if still_open {
+            state.world.current_gaster_dialogue = tree.current_node().text.clone();
+            state
+                .dialogue_log
+                .push(Speaker::Gaster, &state.world.current_gaster_dialogue);
+        } else {
+            close_dialogue(state);
+        }
+    }
+}
+
+pub fn close_dialogue(state: &mut GameState) {
+    state.world.gaster_dialogue = None;
+    state.world.gaster_talking = false;
+}
+
+/// Cross-fade between standing and talking sprites
+pub fn update_talk_blend(state: &mut GameState, dt: f32) {
+    let step = state.world.gaster_talk_blend_speed * dt;
+    state.world.gaster_talk_blend = if state.world.gaster_talking {
+        (state.world.gaster_talk_blend + step).min(1.0)
+    } else {
+        (state.world.gaster_talk_blend - step).max(0.0)
+    };
+}
+
+pub fn is_vanishing(state: &GameState) -> bool {
+    state.world.gaster_vanish_timer > 0.0
+}
+
+/// Whether Gaster's sprite should be drawn (hidden during the first half of the static).
+pub fn is_visible(state: &GameState) -> bool {
+    state.world.gaster_vanish_timer <= VANISH_DURATION / 2.0
+}
+
+fn pick_destination(state: &mut GameState) -> Vec2<f32> {
+    // Small chance to show up right behind the player
+    if state.world.gaster_rng.random_bool(0.15) {
+        let offset = match state.player.direction {
+            Direction::Left => 70.0,
+            Direction::Right => -70.0,
+            Direction::Front => 0.0,
+        };
+        let behind = if offset == 0.0 {
+            Vec2::new(state.player.pos.x, state.player.pos.y - 70.0)
+        } else {
+            Vec2::new(state.player.pos.x + offset, state.player.pos.y)
+        };
+        let stage_def = state.world.stage_def();
+        if behind.y >= stage_def.top_bound && behind.y <= stage_def.bottom_bound {
+            return behind;
+        }
+    }
+
+    // Stage 2's, he keeps them wherever the randomizer puts him
+    let points = STAGE_DEFS[1].gaster_teleport_points;
+    if points.is_empty() {
+        return state.world.gaster_pos;
+    }
+
+    // Don't "teleport" to where he already is, or on top of the player
+    let player_pos = state.player.pos;
+    let start = state.world.gaster_rng.random_range(0..points.len());
+    for i in 0..points.len() {
+        let point = points[(start + i) % points.len()];
+        if point != state.world.gaster_pos && point.distance(player_pos) > PLAYER_CLEARANCE {
+            return point;
+        }
+    }
+    state.world.gaster_pos
+}
+
+/// The static effect, with the hiss when it starts. The noise texture is
+/// built the first time and only moved around after that, see `draw_static`.
+pub fn show_static(ctx: &mut Context, state: &mut GameState, play_sound: bool) {
+    if play_sound {
+        state.world.sound_queue.enqueue("Gaster Static");
+    }
+    if state.world.gaster_static_texture.is_none() {
+        build_static(ctx, state);
+    }
+}
+
+fn build_static(ctx: &mut Context, state: &mut GameState) {
+    let mut rng = rand::rng();
+    let mut data = Vec::with_capacity((STATIC_SIZE * STATIC_SIZE * 4) as usize);
+    for _ in 0..STATIC_SIZE * STATIC_SIZE {
+        let value: u8 = rng.random();
+        let alpha: u8 = if rng.random_bool(0.7) { 220 } else { 0 };
+        data.extend_from_slice(&[value, value, value, alpha]);
+    }
+
+    match Texture::from_data(ctx, STATIC_SIZE, STATIC_SIZE, TextureFormat::Rgba8, &data) {
+        Ok(tex) => state.world.gaster_static_texture = Some(tex),
+        Err(e) => log::warn!("Failed to create static texture: {}", e),
+    }
+}
+
+pub fn draw_static(ctx: &mut Context, state: &GameState) {
+    if !state.world.in_current_stage(NpcId::Gaster) || !is_vanishing(state) {
+        return;
+    }
+
+    if let Some(tex) = &state.world.gaster_static_texture {
+        // A random flip and a few pixels of jitter each frame read as fresh noise
+        let mut rng = rand::rng();
+        let flip = |flipped: bool| if flipped { -1.0 } else { 1.0 };
+        let scale = Vec2::new(3.0 * flip(rng.random()), 4.0 * flip(rng.random()));
+        let jitter = Vec2::new(
+            rng.random_range(-STATIC_JITTER..=STATIC_JITTER),
+            rng.random_range(-STATIC_JITTER..=STATIC_JITTER),
+        );
+        let origin = Vec2::new(STATIC_SIZE as f32 / 2.0, STATIC_SIZE as f32 / 2.0);
+        tex.render(
+            ctx,
+            DrawParams::new()
+                .position(state.world.gaster_pos + jitter)
+                .origin(origin)
+                .scale(scale)
+                .color(Color::rgba(1.0, 1.0, 1.0, 0.8)),
+        );
+    }
+}
+
+/// Small bubble over his head with the current idle line, only heard up close
+/// and never over a real conversation.
+pub fn draw_idle_line(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.world.in_current_stage(NpcId::Gaster)
+        || state.world.gaster_talking
+        || state.world.gaster_idle_fade <= 0.0
+        || crate::scenes::disco::gaster_sulking(state)
+        || !is_visible(state)
+        || state.player.pos.distance(state.world.gaster_pos) > IDLE_HEAR_DISTANCE
+    {
+        return Ok(());
+    }
+
+    let alpha = state.world.gaster_idle_fade / IDLE_LINE_SECONDS;
+    let mut text = Text::new(state.world.gaster_idle_line.as_str(), state.font());
+    let bounds = text
+        .get_bounds(ctx)
+        .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 20.0));
+    let bubble = Rectangle::new(
+        state.world.gaster_pos.x - bounds.width / 2.0 - 6.0,
+        state.world.gaster_pos.y - 120.0, // Clear of the "Press F" prompt
+        bounds.width + 12.0,
+        bounds.height + 10.0,
+    );
+
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, bubble)?;
+    fill.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, alpha * 0.8)),
+    );
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), bubble)?;
+    border.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(1.0, 1.0, 1.0, alpha)),
+    );
+
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(bubble.x + 6.0, bubble.y + 5.0))
+            .color(Color::rgba(1.0, 1.0, 1.0, alpha)),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,92 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::{Color, DrawParams, Rectangle};
+
+use crate::defs::NpcId;
+use crate::game_state::GameState;
+use crate::perf::Render;
+use crate::scenes::desktop::{AYASOFYA_DOOR, Sprite};
+
+const HIGHLIGHT_RANGE: f32 = 120.0; // Same reach as the F interactions
+const HIGHLIGHT_SCALE: f32 = 1.12;
+const PULSE_SPEED: f32 = 4.0; // Radians per second
+const DOOR_INSET: f32 = 4.0;
+
+/// What pressing F would act on right now.
+#[derive(PartialEq, Clone, Copy)]
+pub enum Target {
+    Npc(NpcId),
+    Door,
+}
+
+/// The one thing to outline, if any. The door wins over NPCs since F opens
+/// it wherever the player stands inside it, so only one is ever outlined.
+pub fn target(state: &GameState) -> Option<Target> {
+    if !state.graphics_quality().interact_highlight {
+        return None;
+    }
+    if state.world.current_stage == 3 && crate::scenes::desktop::in_ayasofya_door(state.player.pos)
+    {
+        return Some(Target::Door);
+    }
+
+    let npc = state.world.nearest_npc?;
+    let pos = state.world.npc_state(npc)?.pos;
+    if state.player.pos.distance(pos) > HIGHLIGHT_RANGE || !interactable(state, npc) {
+        return None;
+    }
+    Some(Target::Npc(npc))
+}
+
+/// Matches when each NPC's own prompt shows.
+fn interactable(state: &GameState, npc: NpcId) -> bool {
+    let world = &state.world;
+    match npc {
+        NpcId::Sans => world.sans_dialogue.is_none() && !crate::scenes::sans::is_chasing(state),
+        NpcId::MusicBox => true,
+        NpcId::Gaster => {
+            !world.gaster_talking
+                && crate::scenes::gaster::is_visible(state)
+                && !crate::scenes::gaster::is_vanishing(state)
+                && !crate::scenes::disco::gaster_sulking(state)
+        }
+        // Only from behind
+        NpcId::Rarity => world.rarity_stab_frame == 0 && state.player.pos.x < world.rarity_pos.x,
+        NpcId::Eilish => !world.eilish_listening,
+        NpcId::Survivor => !world.survivor_escort,
+        // Pedestrians bark on their own, there's nothing to press
+        NpcId::Crowd(_) => false,
+    }
+}
+
+/// Pulses between a faint and a solid glow.
+fn pulse(state: &GameState) -> f32 {
+    0.6 + 0.4 * (state.world.stage_clock * PULSE_SPEED).sin()
+}
+
+/// The sprite again, a little larger and flat colored, to go behind it.
+pub fn draw_behind(ctx: &mut Context, state: &GameState, sprite: &Sprite) {
+    let mut params = sprite.params.clone();
+    params.scale *= HIGHLIGHT_SCALE;
+    params.color = Color::rgba(1.0, 0.95, 0.4, pulse(state) * sprite.params.color.a);
+    sprite.texture.render(ctx, params);
+}
+
+/// The door has no sprite of its own, it's part of the background.
+pub fn draw_door(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if target(state) != Some(Target::Door) {
+        return Ok(());
+    }
+    let door = Rectangle::new(
+        AYASOFYA_DOOR.x + DOOR_INSET,
+        AYASOFYA_DOOR.y + DOOR_INSET,
+        AYASOFYA_DOOR.width - DOOR_INSET * 2.0,
+        AYASOFYA_DOOR.height - DOOR_INSET * 2.0,
+    );
+    let outline = Mesh::rectangle(ctx, ShapeStyle::Stroke(3.0), door)?;
+    outline.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(1.0, 0.95, 0.4, pulse(state))),
+    );
+    Ok(())
+}
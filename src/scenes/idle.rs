@@ -0,0 +1,73 @@
+use rand::Rng;
+use std::f32::consts::TAU;
+use tetra::math::Vec2;
+
+use crate::defs::NpcId;
+use crate::game_state::GameState;
+use crate::player::PlayerState;
+use crate::world::SansChase;
+
+const BOB_AMPLITUDE: f32 = 1.5; // Pixels
+const BOB_HZ: f32 = 0.5;
+const BOB_DELAY: f32 = 0.5; // Seconds standing still before the bob starts
+const BLINK_SECONDS: f32 = 0.12;
+const BLINK_SQUASH: f32 = 0.94; // The sprites have no eyes to close, a blink is a quick squash
+const SANS_SHRUG_SECONDS: f32 = 0.5;
+const GASTER_FLICKER_SECONDS: f32 = 0.08;
+
+/// Idle fidgets for the player, Sans and Gaster. Called from the desktop
+/// step after the dialogue and cutscene early-outs, so those pause it.
+pub fn step(state: &mut GameState, dt: f32, moved: bool) {
+    let player = &mut state.player;
+    player.idle_time = if moved { 0.0 } else { player.idle_time + dt };
+
+    let world = &mut state.world;
+    player.blink_hold = (player.blink_hold - dt).max(0.0);
+    player.blink_timer -= dt;
+    if player.blink_timer <= 0.0 {
+        player.blink_timer = world.idle_rng.random_range(4.0..7.0);
+        player.blink_hold = BLINK_SECONDS;
+    }
+
+    // Only while he's standing around, not mid-chase or already shrugging
+    world.sans_idle_shrug = (world.sans_idle_shrug - dt).max(0.0);
+    if world.in_current_stage(NpcId::Sans) && world.sans_chase == SansChase::Idle {
+        world.sans_idle_timer -= dt;
+        if world.sans_idle_timer <= 0.0 {
+            world.sans_idle_timer = world.idle_rng.random_range(8.0..16.0);
+            world.sans_idle_shrug = SANS_SHRUG_SECONDS;
+        }
+    }
+
+    world.gaster_flicker = (world.gaster_flicker - dt).max(0.0);
+    if world.in_current_stage(NpcId::Gaster) && !world.gaster_talking {
+        world.gaster_flicker_timer -= dt;
+        if world.gaster_flicker_timer <= 0.0 {
+            world.gaster_flicker_timer = world.idle_rng.random_range(6.0..14.0);
+            world.gaster_flicker = GASTER_FLICKER_SECONDS;
+        }
+    }
+}
+
+/// Offset and vertical scale for the player sprite this frame.
+pub fn player_pose(player: &PlayerState) -> (Vec2<f32>, f32) {
+    let bob = if player.idle_time > BOB_DELAY {
+        ((player.idle_time - BOB_DELAY) * BOB_HZ * TAU).sin() * BOB_AMPLITUDE
+    } else {
+        0.0
+    };
+    let squash = if player.blink_hold > 0.0 {
+        BLINK_SQUASH
+    } else {
+        1.0
+    };
+    (Vec2::new(0.0, bob), squash)
+}
+
+pub fn sans_shrugging(state: &GameState) -> bool {
+    state.world.sans_idle_shrug > 0.0
+}
+
+pub fn gaster_flickering(state: &GameState) -> bool {
+    state.world.gaster_flicker > 0.0
+}
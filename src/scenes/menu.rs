@@ -1,9 +1,16 @@
 use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
 use crate::game_state::GameState;
+use crate::global_db::GlobalSettings;
+use crate::graphics_quality::GraphicsQuality;
+use crate::highscores::{Highscores, RunRecord};
+use crate::perf::{Render, RenderMut};
+use crate::render_util::{TextStyle, draw_styled_text};
+use crate::system::User;
 use rand::Rng;
+use std::path::PathBuf;
 use tetra::Context;
 use tetra::graphics::text::Text;
-use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::graphics::{Color, DrawParams, Rectangle, Texture};
 use tetra::math::Vec2;
 
 #[derive(PartialEq, Clone, Copy)]
@@ -12,9 +19,52 @@ pub enum MenuSubState {
     SaveSelect,
     CreateSave,
     Settings,
-    Credits,
+    ImportProfile,
+    RenameProfile,
+    Leaderboard,
 }
 
+/// Order of the Select Save list. users.db itself stays in last played
+/// order, this only changes what the list shows.
+#[derive(PartialEq, Clone, Copy)]
+pub enum ProfileSort {
+    LastPlayed,
+    Name,
+    Playtime,
+}
+
+impl ProfileSort {
+    pub fn next(self) -> Self {
+        match self {
+            ProfileSort::LastPlayed => ProfileSort::Name,
+            ProfileSort::Name => ProfileSort::Playtime,
+            ProfileSort::Playtime => ProfileSort::LastPlayed,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ProfileSort::LastPlayed => "Last played",
+            ProfileSort::Name => "Name",
+            ProfileSort::Playtime => "Playtime",
+        }
+    }
+}
+
+/// Shown after the name wherever profiles are listed.
+pub fn profile_marker(user: &User) -> &'static str {
+    if user.locked {
+        " \u{2620}" // Skull
+    } else if user.hardcore {
+        " (Hardcore)"
+    } else {
+        ""
+    }
+}
+
+pub const PROFILES_PER_PAGE: usize = 8;
+pub const MAX_PROFILES: usize = 50; // Only checked when creating, imports can go past it
+
 pub struct SnowParticle {
     pub pos: Vec2<f32>,
     pub speed: f32,
@@ -24,11 +74,22 @@ pub struct SnowParticle {
 pub struct MenuState {
     pub sub_state: MenuSubState,
     pub options: Vec<String>,
+    pub menu_icons: Vec<Option<Texture>>, // 16x16, one per option
     pub selected_index: usize,
     pub snow_particles: Vec<SnowParticle>,
     pub title_blink_timers: Vec<f32>,
+    pub glitch_timer: f32,
+    pub glitch_chars: [Option<char>; 9], // One slot per letter of "Gorkitale"
     pub input_buffer: String,
     pub error_message: Option<String>,
+    pub import_files: Vec<PathBuf>, // .gorki files listed on the import screen
+    pub notice: Option<String>,     // Export result under the profile list
+    pub notice_timer: f32,
+    pub profile_sort: ProfileSort,
+    pub new_profile_hardcore: bool,   // Create Save toggle
+    pub new_profile_randomizer: bool, // Create Save toggle
+    pub skip_rename_key: bool,        // Drops the "r" typed by opening Rename
+    pub leaderboard: Vec<RunRecord>,  // Read from highscores.json when opened
 
     // Chase Animation
     pub chasers: Vec<ChaseEntity>,
@@ -42,20 +103,12 @@ pub struct ChaseEntity {
     pub is_sans: bool,
 }
 
+/// Settings presets for the menu snow, (label, particle count).
+pub const PARTICLE_LEVELS: [(&str, u32); 3] = [("Low", 30), ("Medium", 100), ("High", 300)];
+
 impl MenuState {
-    pub fn new() -> Self {
+    pub fn new(snow_particle_count: u32) -> Self {
         let mut rng = rand::rng();
-        let mut snow_particles = Vec::new();
-        for _ in 0..100 {
-            snow_particles.push(SnowParticle {
-                pos: Vec2::new(
-                    rng.random_range(0.0..SCREEN_WIDTH as f32),
-                    rng.random_range(0.0..SCREEN_HEIGHT as f32),
-                ),
-                speed: rng.random_range(0.5..2.0),
-                size: rng.random_range(1.0..3.0),
-            });
-        }
 
         let title_len = "Gorkitale".len();
         let mut title_blink_timers = Vec::new();
@@ -63,7 +116,7 @@ impl MenuState {
             title_blink_timers.push(rng.random_range(0.0..1.0));
         }
 
-        Self {
+        let mut menu_state = Self {
             sub_state: MenuSubState::Main,
             options: vec![
                 "Start Game".to_string(),
@@ -71,20 +124,245 @@ impl MenuState {
                 "Select Save".to_string(),
                 "Settings".to_string(),
                 "Credits".to_string(),
+                "Statistics".to_string(),
+                "Leaderboard".to_string(),
                 "Quit Game".to_string(),
             ],
+            menu_icons: vec![None; 8],
             selected_index: 0,
-            snow_particles,
+            snow_particles: Vec::new(),
             title_blink_timers,
+            glitch_timer: 0.0,
+            glitch_chars: [None; 9],
             input_buffer: String::new(),
             error_message: None,
+            import_files: Vec::new(),
+            notice: None,
+            notice_timer: 0.0,
+            profile_sort: ProfileSort::LastPlayed,
+            new_profile_hardcore: false,
+            new_profile_randomizer: false,
+            skip_rename_key: false,
+            leaderboard: Vec::new(),
 
             chasers: Vec::new(),
+        };
+        menu_state.rebuild_particles(snow_particle_count);
+        menu_state
+    }
+
+    pub fn rebuild_particles(&mut self, count: u32) {
+        let mut rng = rand::rng();
+        self.snow_particles.clear();
+        for _ in 0..count {
+            self.snow_particles.push(SnowParticle {
+                pos: Vec2::new(
+                    rng.random_range(0.0..SCREEN_WIDTH as f32),
+                    rng.random_range(0.0..SCREEN_HEIGHT as f32),
+                ),
+                speed: rng.random_range(0.5..2.0),
+                size: rng.random_range(1.0..3.0),
+            });
         }
     }
 }
 
-pub fn update(_ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+/// Steps the snow to the next/previous preset, rebuilds it and saves.
+pub fn cycle_particle_level(state: &mut GameState, forward: bool) {
+    let count = state.system.global_settings.snow_particle_count;
+    // Custom counts from global.db snap to the nearest preset above/below
+    let next = if forward {
+        PARTICLE_LEVELS
+            .iter()
+            .find(|(_, c)| *c > count)
+            .unwrap_or(&PARTICLE_LEVELS[0])
+    } else {
+        PARTICLE_LEVELS
+            .iter()
+            .rev()
+            .find(|(_, c)| *c < count)
+            .unwrap_or(&PARTICLE_LEVELS[PARTICLE_LEVELS.len() - 1])
+    };
+
+    state.system.global_settings.snow_particle_count = next.1;
+    state
+        .menu_state
+        .rebuild_particles(snow_particle_count(&state.system.global_settings));
+    state.system.save_global_settings();
+}
+
+/// The snow setting after Performance mode's cut.
+pub fn snow_particle_count(settings: &GlobalSettings) -> u32 {
+    GraphicsQuality::from_settings(settings).particle_count(settings.snow_particle_count as usize)
+        as u32
+}
+
+pub fn toggle_performance_mode(state: &mut GameState) {
+    let settings = &mut state.system.global_settings;
+    settings.performance_mode = !settings.performance_mode;
+    state
+        .menu_state
+        .rebuild_particles(snow_particle_count(&state.system.global_settings));
+    state.system.save_global_settings();
+}
+
+const NOTICE_SECONDS: f32 = 4.0;
+
+/// Indices into `users` in the order the Select Save list shows them.
+pub fn profile_order(state: &GameState) -> Vec<usize> {
+    let users = &state.system.users;
+    let mut order: Vec<usize> = (0..users.len()).collect();
+    match state.menu_state.profile_sort {
+        // set_user_as_top keeps the list itself in this order
+        ProfileSort::LastPlayed => {}
+        ProfileSort::Name => order.sort_by_key(|&i| users[i].username.to_lowercase()),
+        ProfileSort::Playtime => {
+            order.sort_by(|&a, &b| users[b].playtime.total_cmp(&users[a].playtime))
+        }
+    }
+    order
+}
+
+/// The `users` index of the highlighted row, `None` on Back.
+pub fn selected_profile(state: &GameState) -> Option<usize> {
+    profile_order(state)
+        .get(state.menu_state.selected_index)
+        .copied()
+}
+
+pub fn profile_page_count(state: &GameState) -> usize {
+    state.system.users.len().div_ceil(PROFILES_PER_PAGE).max(1)
+}
+
+/// The page the highlight is on. Clamped, so an index past the end (Back,
+/// or a profile that's gone) shows the last page rather than an empty one.
+pub fn profile_page(state: &GameState) -> usize {
+    (state.menu_state.selected_index / PROFILES_PER_PAGE).min(profile_page_count(state) - 1)
+}
+
+/// Jumps a page back or forward, wrapping, keeping the row within the page.
+pub fn change_profile_page(state: &mut GameState, forward: bool) {
+    let count = profile_page_count(state);
+    let page = profile_page(state);
+    let page = if forward {
+        (page + 1) % count
+    } else {
+        (page + count - 1) % count
+    };
+    let row = state.menu_state.selected_index % PROFILES_PER_PAGE;
+    let last = state.system.users.len().saturating_sub(1);
+    state.menu_state.selected_index = (page * PROFILES_PER_PAGE + row).min(last);
+}
+
+/// Steps to the next sort order, keeping the same profile highlighted.
+pub fn cycle_profile_sort(state: &mut GameState) {
+    let selected = selected_profile(state);
+    state.menu_state.profile_sort = state.menu_state.profile_sort.next();
+    if let Some(user) = selected {
+        state.menu_state.selected_index = profile_order(state)
+            .iter()
+            .position(|&i| i == user)
+            .unwrap_or(0);
+    }
+}
+
+/// Writes the highlighted profile to a .gorki file and shows where it went.
+pub fn export_selected_profile(state: &mut GameState) {
+    let Some(user) = selected_profile(state).map(|i| &state.system.users[i]) else {
+        return;
+    };
+    let (notice, error) = match crate::profile_file::export(user) {
+        Ok(path) => {
+            log::info!("Exported profile '{}' to {}", user.username, path.display());
+            (Some(format!("Exported to {}", path.display())), None)
+        }
+        Err(e) => {
+            log::error!("Failed to export profile '{}': {}", user.username, e);
+            (None, Some(format!("Export failed: {}", e)))
+        }
+    };
+    state.menu_state.notice = notice;
+    state.menu_state.notice_timer = NOTICE_SECONDS;
+    state.menu_state.error_message = error;
+}
+
+/// Opens the name field for the highlighted profile, filled with its name.
+/// The highlight stays put so confirming knows which one it was.
+pub fn open_rename(state: &mut GameState) {
+    let Some(user) = selected_profile(state).map(|i| &state.system.users[i]) else {
+        return;
+    };
+    state.menu_state.input_buffer = user.username.clone();
+    state.menu_state.sub_state = MenuSubState::RenameProfile;
+    state.menu_state.skip_rename_key = true;
+    state.menu_state.error_message = None;
+}
+
+/// Renames the highlighted profile to what's typed. A rejected name keeps
+/// the field open with the reason shown.
+pub fn confirm_rename(state: &mut GameState) {
+    let Some(index) = selected_profile(state) else {
+        state.menu_state.sub_state = MenuSubState::SaveSelect;
+        return;
+    };
+    let name = state.menu_state.input_buffer.clone();
+    match state.system.rename_user(index, &name) {
+        Ok(()) => {
+            state.menu_state.sub_state = MenuSubState::SaveSelect;
+            state.menu_state.input_buffer.clear();
+            state.menu_state.error_message = None;
+            state.update_presence();
+        }
+        Err(e) => state.menu_state.error_message = Some(e),
+    }
+}
+
+/// Lists the .gorki files in the data directory to pick one from.
+pub fn open_import(state: &mut GameState) {
+    state.menu_state.import_files = crate::profile_file::list();
+    state.menu_state.sub_state = MenuSubState::ImportProfile;
+    state.menu_state.selected_index = 0;
+    state.menu_state.error_message = None;
+}
+
+/// Reads highscores.json fresh, runs may have ended since the last look.
+pub fn open_leaderboard(state: &mut GameState) {
+    state.menu_state.leaderboard = Highscores::load().top10_by_teblig();
+    state.menu_state.sub_state = MenuSubState::Leaderboard;
+}
+
+/// Imports the highlighted file. A bad file keeps the list open with the
+/// reason shown, a good one lands at the top of Select Profile.
+pub fn import_selected_profile(state: &mut GameState) {
+    let Some(path) = state
+        .menu_state
+        .import_files
+        .get(state.menu_state.selected_index)
+    else {
+        return;
+    };
+    match crate::profile_file::import(path) {
+        Ok(user) => {
+            let username = state.system.import_user(user);
+            log::info!("Imported profile '{}' from {}", username, path.display());
+            state.menu_state.sub_state = MenuSubState::SaveSelect;
+            state.menu_state.selected_index = 0;
+            state.menu_state.error_message = None;
+            state.menu_state.notice = Some(format!("Imported as {}", username));
+            state.menu_state.notice_timer = NOTICE_SECONDS;
+        }
+        Err(e) => {
+            log::warn!("Rejected profile file {}: {}", path.display(), e);
+            state.menu_state.error_message = Some(e);
+        }
+    }
+}
+
+const GLITCH_SYMBOLS: [char; 8] = ['!', '@', '#', '$', '?', '*', '█', '░'];
+const GLITCH_INTERVAL: f32 = 0.5;
+const GLITCH_DURATION: f32 = 0.1;
+
+pub fn update(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     // Update Snow
     let mut rng = rand::rng();
     for particle in &mut state.menu_state.snow_particles {
@@ -103,6 +381,27 @@ pub fn update(_ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         }
     }
 
+    // Update Title Glitch
+    let dt = tetra::time::get_delta_time(ctx).as_secs_f32();
+    let menu = &mut state.menu_state;
+    if menu.notice_timer > 0.0 {
+        menu.notice_timer -= dt;
+        if menu.notice_timer <= 0.0 {
+            menu.notice = None;
+        }
+    }
+    menu.glitch_timer += dt;
+    if menu.glitch_timer >= GLITCH_INTERVAL {
+        menu.glitch_timer = 0.0;
+        for _ in 0..rng.random_range(1..=2) {
+            let idx = rng.random_range(0..menu.glitch_chars.len());
+            menu.glitch_chars[idx] =
+                Some(GLITCH_SYMBOLS[rng.random_range(0..GLITCH_SYMBOLS.len())]);
+        }
+    } else if menu.glitch_timer >= GLITCH_DURATION {
+        menu.glitch_chars = [None; 9];
+    }
+
     // Update Chase Animation
     // Spawn new chasers
     if state.menu_state.chasers.len() < 5 && rng.random_bool(0.02) {
@@ -157,38 +456,40 @@ pub fn update(_ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 
 pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     // Draw Snow
-    for particle in &state.menu_state.snow_particles {
-        let rect = Rectangle::new(particle.pos.x, particle.pos.y, particle.size, particle.size);
-        let mesh = tetra::graphics::mesh::Mesh::rectangle(
-            ctx,
-            tetra::graphics::mesh::ShapeStyle::Fill,
-            rect,
-        )?;
-        mesh.draw(ctx, DrawParams::new().color(Color::WHITE));
-    }
+    crate::particles::draw_batch(
+        ctx,
+        state.menu_state.snow_particles.iter().map(|particle| {
+            let rect = Rectangle::new(particle.pos.x, particle.pos.y, particle.size, particle.size);
+            (rect, Color::WHITE)
+        }),
+    )?;
 
     // Draw Chase Animation (Background)
     for chaser in &state.menu_state.chasers {
         let texture = if chaser.is_sans {
             state.world.sans_texture.clone()
-        } else if chaser.velocity.x < 0.0 {
-            state.player.texture_left.clone()
         } else {
             state.player.texture_right.clone()
         };
+        // The player sprite only faces right, mirror it when running left
+        let flip = if !chaser.is_sans && chaser.velocity.x < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
 
         if let Some(tex) = texture {
             let width = tex.width() as f32;
             let height = tex.height() as f32;
             let origin = Vec2::new(width / 2.0, height / 2.0);
 
-            tex.draw(
+            tex.render(
                 ctx,
                 DrawParams::new()
                     .position(chaser.pos)
                     .origin(origin)
                     .rotation(chaser.rotation)
-                    .scale(Vec2::new(chaser.scale, chaser.scale))
+                    .scale(Vec2::new(chaser.scale * flip, chaser.scale))
                     .color(Color::rgba(1.0, 1.0, 1.0, 0.5)),
             );
         }
@@ -199,7 +500,9 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         MenuSubState::SaveSelect => draw_save_select(ctx, state),
         MenuSubState::CreateSave => draw_create_save(ctx, state),
         MenuSubState::Settings => draw_settings(ctx, state),
-        MenuSubState::Credits => draw_credits(ctx, state),
+        MenuSubState::ImportProfile => draw_import_profile(ctx, state),
+        MenuSubState::RenameProfile => draw_rename_profile(ctx, state),
+        MenuSubState::Leaderboard => draw_leaderboard(ctx, state),
     }?;
 
     // Draw Transition Fade
@@ -210,7 +513,7 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
             tetra::graphics::mesh::ShapeStyle::Fill,
             Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
         )?;
-        fade_rect.draw(
+        fade_rect.render(
             ctx,
             DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, alpha)),
         );
@@ -222,44 +525,47 @@ pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
 fn draw_main_menu(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     // Draw Title "Gorkitale"
     let title = "Gorkitale";
-    let start_x = (SCREEN_WIDTH as f32 / 2.0) - (title.len() as f32 * 20.0); // Approx centering
+    let text_scale = state.text_scale();
+    let spacing = 40.0 * text_scale;
+    let start_x = (SCREEN_WIDTH as f32 / 2.0) - (title.len() as f32 * spacing / 2.0); // Approx centering
     let start_y = 100.0;
+    let title_font = state.get_font(ctx, 32.0).clone();
 
     for (i, char) in title.chars().enumerate() {
         let timer = state.menu_state.title_blink_timers[i];
-        let alpha = if timer > 0.2 { 1.0 } else { 0.3 }; // Blink effect
+        let glitch = state.menu_state.glitch_chars.get(i).copied().flatten();
+        // Blink effect, a glitched letter always shows at full alpha
+        let alpha = if timer > 0.2 || glitch.is_some() {
+            1.0
+        } else {
+            0.3
+        };
 
-        let mut text = Text::new(char.to_string(), state.font.clone());
-        let pos = Vec2::new(start_x + (i as f32 * 40.0), start_y);
+        let mut text = Text::new(glitch.unwrap_or(char).to_string(), title_font.clone());
+        let pos = Vec2::new(start_x + (i as f32 * spacing), start_y);
         let color = Color::rgba(1.0, 1.0, 1.0, alpha);
-
-        // Simulate Bold by drawing multiple times with slight offsets
-        for offset_x in 0..=1 {
-            for offset_y in 0..=1 {
-                text.draw(
-                    ctx,
-                    DrawParams::new()
-                        .position(pos + Vec2::new(offset_x as f32, offset_y as f32))
-                        .scale(Vec2::new(2.0, 2.0))
-                        .color(color),
-                );
-            }
-        }
+        draw_styled_text(
+            ctx,
+            &mut text,
+            DrawParams::new().position(pos).color(color),
+            TextStyle::Bold,
+            text_scale,
+        );
     }
 
     // Draw Current User Info
     if let Some(user) = state.system.users.first() {
-        let user_text = format!("Current Profile: {}", user.username);
-        let mut text = Text::new(user_text, state.font.clone());
-        text.draw(
+        let user_text = format!("Current Profile: {}{}", user.username, profile_marker(user));
+        let mut text = Text::new(user_text, state.font());
+        text.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(20.0, 20.0))
                 .color(Color::rgb(0.7, 0.7, 0.7)),
         );
     } else {
-        let mut text = Text::new("No Profile Selected", state.font.clone());
-        text.draw(
+        let mut text = Text::new("No Profile Selected", state.font());
+        text.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(20.0, 20.0))
@@ -267,16 +573,28 @@ fn draw_main_menu(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         );
     }
 
+    // Backup Restore Notice
+    if let Some(notice) = state.system.restore_notice {
+        let mut text = Text::new(state.texts.restore_notice(notice), state.font());
+        let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(400.0);
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new((SCREEN_WIDTH as f32 - width) / 2.0, 540.0))
+                .color(Color::rgb(1.0, 0.6, 0.0)),
+        );
+    }
+
     // Draw Options
     let menu_start_y =
         (SCREEN_HEIGHT as f32 / 2.0) - (state.menu_state.options.len() as f32 * 20.0); // Center vertically
     let menu_start_x = (SCREEN_WIDTH as f32 / 2.0) - 100.0; // Fixed X position for left alignment, slightly offset from center
 
-    let users_exist = !state.system.users.is_empty();
+    let can_start = state.system.can_start();
 
     for (i, option) in state.menu_state.options.iter().enumerate() {
         let is_start_game = i == 0;
-        let disabled = is_start_game && !users_exist;
+        let disabled = is_start_game && !can_start;
 
         let color = if disabled {
             Color::rgb(0.3, 0.3, 0.3) // Dark Gray
@@ -291,23 +609,43 @@ fn draw_main_menu(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         } else {
             "  "
         };
-        let mut text = Text::new(format!("{}{}", prefix, option), state.font.clone());
+        let mut text = Text::new(format!("{}{}", prefix, option), state.font());
+        let option_y = menu_start_y + (i as f32 * 40.0);
+
+        if let Some(Some(icon)) = state.menu_state.menu_icons.get(i) {
+            let alpha = if i == state.menu_state.selected_index {
+                1.0
+            } else {
+                0.5
+            };
+            icon.render(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(menu_start_x - 30.0, option_y + 4.0))
+                    .color(Color::WHITE.with_alpha(alpha)),
+            );
+        }
 
         // Left aligned at fixed X
-        text.draw(
+        text.render(
             ctx,
             DrawParams::new()
-                .position(Vec2::new(menu_start_x, menu_start_y + (i as f32 * 40.0)))
+                .position(Vec2::new(menu_start_x, option_y))
                 .color(color),
         );
     }
 
+    // Paused mid-session, the full objective list sits beside the options
+    if state.session_started {
+        crate::scenes::objectives::draw_list(ctx, state, Vec2::new(540.0, menu_start_y))?;
+    }
+
     Ok(())
 }
 
 fn draw_save_select(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let mut title = Text::new("Select Profile", state.font.clone());
-    title.draw(
+    let mut title = Text::new("Select Profile", state.font());
+    title.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(300.0, 50.0))
@@ -315,9 +653,13 @@ fn draw_save_select(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     );
 
     let start_y = 150.0;
+    let page = profile_page(state);
+    let first = page * PROFILES_PER_PAGE;
 
-    // List users
-    for (i, user) in state.system.users.iter().enumerate() {
+    // List this page's users
+    let order = profile_order(state);
+    for (row, &user_index) in order.iter().skip(first).take(PROFILES_PER_PAGE).enumerate() {
+        let i = first + row;
         let color = if i == state.menu_state.selected_index {
             Color::rgb(1.0, 1.0, 0.0)
         } else {
@@ -329,49 +671,190 @@ fn draw_save_select(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         } else {
             "  "
         };
-        let mut text = Text::new(format!("{}{}", prefix, user.username), state.font.clone());
-        text.draw(
+        let user = &state.system.users[user_index];
+        let mut text = Text::new(
+            format!("{}{}{}", prefix, user.username, profile_marker(user)),
+            state.font(),
+        );
+        text.render(
             ctx,
             DrawParams::new()
-                .position(Vec2::new(200.0, start_y + (i as f32 * 30.0)))
+                .position(Vec2::new(200.0, start_y + (row as f32 * 30.0)))
                 .color(color),
         );
     }
 
-    // "Press Esc to go back"
-    let mut hint = Text::new("Press Esc to go back", state.font.clone());
-    hint.draw(
+    let page_count = profile_page_count(state);
+    let footer_y = start_y + PROFILES_PER_PAGE as f32 * 30.0 + 10.0;
+    if page_count > 1 {
+        let mut page_text = Text::new(format!("Page {}/{}", page + 1, page_count), state.font());
+        page_text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, footer_y))
+                .color(Color::rgb(0.7, 0.7, 0.7)),
+        );
+    }
+
+    if let Some(err) = &state.menu_state.error_message {
+        let mut err_text = Text::new(err, state.font());
+        err_text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, 440.0))
+                .color(Color::RED),
+        );
+    } else if let Some(notice) = &state.menu_state.notice {
+        let mut notice_text = Text::new(notice, state.font());
+        notice_text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, 440.0))
+                .color(Color::rgb(0.6, 1.0, 0.6)),
+        );
+    }
+
+    // A locked profile can't be played, but its last run can still be looked at
+    #[allow(clippy::collapsible_if)]
+    if let Some(index) = selected_profile(state) {
+        if state.system.users[index].locked {
+            let user = &state.system.users[index];
+            let mut stats = Text::new(
+                format!(
+                    "RIP  Teblig: {}  Cihad: {}  Tekfir: {}  Stage {}",
+                    user.teblig_count, user.cihad_count, user.tekfir_count, user.current_stage
+                ),
+                state.font(),
+            );
+            stats.render(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(200.0, 470.0))
+                    .color(Color::rgb(0.8, 0.2, 0.2)),
+            );
+        }
+    }
+
+    let mut hint = Text::new(
+        "R: Rename   X: Export   I: Import   Esc: Back",
+        state.font(),
+    );
+    hint.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(200.0, 500.0))
             .color(Color::rgb(0.5, 0.5, 0.5)),
     );
+    let mut sort_hint = Text::new(
+        format!(
+            "S: Sort ({})   Left/Right: Page",
+            state.menu_state.profile_sort.label()
+        ),
+        state.font(),
+    );
+    sort_hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(200.0, 530.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
 
     Ok(())
 }
 
-fn draw_create_save(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let mut title = Text::new("Create New Profile", state.font.clone());
-    title.draw(
+fn draw_import_profile(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    let mut title = Text::new("Import Profile", state.font());
+    title.render(
         ctx,
         DrawParams::new()
-            .position(Vec2::new(250.0, 100.0))
+            .position(Vec2::new(300.0, 50.0))
             .scale(Vec2::new(1.5, 1.5)),
     );
 
-    let mut prompt = Text::new("Enter Name:", state.font.clone());
-    prompt.draw(ctx, DrawParams::new().position(Vec2::new(250.0, 200.0)));
+    let start_y = 150.0;
+    if state.menu_state.import_files.is_empty() {
+        let mut empty = Text::new(
+            format!(
+                "No .{} files in {}",
+                crate::profile_file::PROFILE_EXTENSION,
+                crate::paths::data_dir().display()
+            ),
+            state.font(),
+        );
+        empty.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, start_y))
+                .color(Color::rgb(0.7, 0.7, 0.7)),
+        );
+    }
+
+    for (i, path) in state.menu_state.import_files.iter().enumerate() {
+        let selected = i == state.menu_state.selected_index;
+        let color = if selected {
+            Color::rgb(1.0, 1.0, 0.0)
+        } else {
+            Color::WHITE
+        };
+        let prefix = if selected { "> " } else { "  " };
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let mut text = Text::new(format!("{}{}", prefix, name), state.font());
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, start_y + (i as f32 * 30.0)))
+                .color(color),
+        );
+    }
+
+    if let Some(err) = &state.menu_state.error_message {
+        let mut err_text = Text::new(err, state.font());
+        err_text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, 440.0))
+                .color(Color::RED),
+        );
+    }
+
+    let mut hint = Text::new("Press Enter to Import, Esc to Cancel", state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(200.0, 500.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
 
-    // Handle text wrapping/scrolling
-    let display_text = if state.menu_state.input_buffer.len() > 20 {
-        let start = state.menu_state.input_buffer.len() - 20;
-        format!("...{}_", &state.menu_state.input_buffer[start..])
+    Ok(())
+}
+
+/// The typed name with a cursor, long names scrolled to their end.
+fn input_line(buffer: &str) -> String {
+    if buffer.len() > 20 {
+        let start = buffer.len() - 20;
+        format!("...{}_", &buffer[start..])
     } else {
-        format!("{}_", state.menu_state.input_buffer)
-    };
+        format!("{}_", buffer)
+    }
+}
+
+fn draw_rename_profile(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    let mut title = Text::new("Rename Profile", state.font());
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(250.0, 100.0))
+            .scale(Vec2::new(1.5, 1.5)),
+    );
 
-    let mut input = Text::new(display_text, state.font.clone());
-    input.draw(
+    let old_name = selected_profile(state)
+        .map(|i| state.system.users[i].username.clone())
+        .unwrap_or_default();
+    let mut prompt = Text::new(format!("New name for {}:", old_name), state.font());
+    prompt.render(ctx, DrawParams::new().position(Vec2::new(250.0, 200.0)));
+
+    let mut input = Text::new(input_line(&state.menu_state.input_buffer), state.font());
+    input.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(250.0, 240.0))
@@ -379,8 +862,8 @@ fn draw_create_save(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     );
 
     if let Some(err) = &state.menu_state.error_message {
-        let mut err_text = Text::new(err, state.font.clone());
-        err_text.draw(
+        let mut err_text = Text::new(err, state.font());
+        err_text.render(
             ctx,
             DrawParams::new()
                 .position(Vec2::new(250.0, 300.0))
@@ -388,8 +871,8 @@ fn draw_create_save(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
         );
     }
 
-    let mut hint = Text::new("Press Enter to Confirm, Esc to Cancel", state.font.clone());
-    hint.draw(
+    let mut hint = Text::new("Press Enter to Confirm, Esc to Cancel", state.font());
+    hint.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(200.0, 500.0))
@@ -399,48 +882,157 @@ fn draw_create_save(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     Ok(())
 }
 
-fn draw_settings(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let mut title = Text::new("Settings", state.font.clone());
-    title.draw(
+/// (header, x) for each leaderboard column.
+const LEADERBOARD_COLUMNS: [(&str, f32); 7] = [
+    ("#", 60.0),
+    ("Name", 110.0),
+    ("Teblig", 320.0),
+    ("Tekfir", 410.0),
+    ("LOVE", 500.0),
+    ("Stage", 580.0),
+    ("Time", 670.0),
+];
+
+fn draw_leaderboard(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    let mut title = Text::new("Leaderboard", state.font());
+    title.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(300.0, 50.0))
             .scale(Vec2::new(1.5, 1.5)),
     );
 
-    let options = ["Language", "Volume"];
-    let start_y = 200.0;
+    let start_y = 130.0;
+    for (header, x) in LEADERBOARD_COLUMNS {
+        let mut text = Text::new(header, state.font());
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(x, start_y))
+                .color(Color::rgb(0.7, 0.7, 0.7)),
+        );
+    }
+
+    if state.menu_state.leaderboard.is_empty() {
+        let mut text = Text::new("No runs yet", state.font());
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(110.0, start_y + 40.0))
+                .color(Color::rgb(0.5, 0.5, 0.5)),
+        );
+    }
 
-    for (i, opt) in options.iter().enumerate() {
-        let color = if i == state.menu_state.selected_index {
+    // The list is sorted, so the profile's first row is its best run
+    let current = state.system.users.first().map(|u| u.username.as_str());
+    let best = state
+        .menu_state
+        .leaderboard
+        .iter()
+        .position(|record| Some(record.username.as_str()) == current);
+
+    for (i, record) in state.menu_state.leaderboard.iter().enumerate() {
+        let color = if Some(i) == best {
             Color::rgb(1.0, 1.0, 0.0)
         } else {
             Color::WHITE
         };
+        let minutes = (record.run_time_secs / 60.0) as u32;
+        let seconds = (record.run_time_secs % 60.0) as u32;
+        let cells = [
+            (i + 1).to_string(),
+            record.username.clone(),
+            record.teblig.to_string(),
+            record.tekfir.to_string(),
+            record.love.to_string(),
+            record.stage_reached.to_string(),
+            format!("{:02}:{:02}", minutes, seconds),
+        ];
+        let y = start_y + 40.0 + i as f32 * 30.0;
+        for (cell, (_, x)) in cells.into_iter().zip(LEADERBOARD_COLUMNS) {
+            let mut text = Text::new(cell, state.font());
+            text.render(
+                ctx,
+                DrawParams::new().position(Vec2::new(x, y)).color(color),
+            );
+        }
+    }
 
-        let prefix = if i == state.menu_state.selected_index {
-            "> "
-        } else {
-            "  "
-        };
+    let mut hint = Text::new("Esc: Back", state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(200.0, 530.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
 
-        let value = match i {
-            0 => format!("{:?}", state.system.language),
-            1 => format!("{:.0}%", state.system.volume * 100.0),
-            _ => "".to_string(),
-        };
+    Ok(())
+}
+
+fn draw_create_save(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    let mut title = Text::new("Create New Profile", state.font());
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(250.0, 100.0))
+            .scale(Vec2::new(1.5, 1.5)),
+    );
+
+    let mut prompt = Text::new("Enter Name:", state.font());
+    prompt.render(ctx, DrawParams::new().position(Vec2::new(250.0, 200.0)));
+
+    let mut input = Text::new(input_line(&state.menu_state.input_buffer), state.font());
+    input.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(250.0, 240.0))
+            .color(Color::rgb(1.0, 1.0, 0.0)),
+    );
+
+    let (checkbox, hardcore_color) = if state.menu_state.new_profile_hardcore {
+        ("[x]", Color::rgb(1.0, 0.3, 0.3))
+    } else {
+        ("[ ]", Color::rgb(0.7, 0.7, 0.7))
+    };
+    let mut hardcore = Text::new(
+        format!("{} Hardcore: one death locks the profile (Tab)", checkbox),
+        state.font(),
+    );
+    hardcore.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(250.0, 270.0))
+            .color(hardcore_color),
+    );
+
+    let (checkbox, randomizer_color) = if state.menu_state.new_profile_randomizer {
+        ("[x]", Color::rgb(0.6, 1.0, 0.6))
+    } else {
+        ("[ ]", Color::rgb(0.7, 0.7, 0.7))
+    };
+    let mut randomizer = Text::new(
+        format!("{} Randomizer: shuffled NPCs and stages (F2)", checkbox),
+        state.font(),
+    );
+    randomizer.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(250.0, 300.0))
+            .color(randomizer_color),
+    );
 
-        let mut text = Text::new(format!("{}{}: < {} >", prefix, opt, value), state.font.clone());
-        text.draw(
+    if let Some(err) = &state.menu_state.error_message {
+        let mut err_text = Text::new(err, state.font());
+        err_text.render(
             ctx,
             DrawParams::new()
-                .position(Vec2::new(200.0, start_y + (i as f32 * 40.0)))
-                .color(color),
+                .position(Vec2::new(250.0, 340.0))
+                .color(Color::RED),
         );
     }
 
-    let mut hint = Text::new("Press Esc to go back", state.font.clone());
-    hint.draw(
+    let mut hint = Text::new("Press Enter to Confirm, Esc to Cancel", state.font());
+    hint.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(200.0, 500.0))
@@ -450,41 +1042,112 @@ fn draw_settings(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
     Ok(())
 }
 
-fn draw_credits(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
-    let mut title = Text::new("Credits", state.font.clone());
-    title.draw(
+fn draw_settings(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    let mut title = Text::new("Settings", state.font());
+    title.render(
         ctx,
         DrawParams::new()
             .position(Vec2::new(300.0, 50.0))
             .scale(Vec2::new(1.5, 1.5)),
     );
 
-    let credits = [
-        "Developed by: lazypwny751, Bumbleshot, GitHub Copilot and <You>!",
-        "Engine:       Tetra (Rust)",
-        "Code:         GitHub Copilot, lazypwny751",
-        "Lore:         Bumbleshot, lazypwny751",
-        "Assets:       Bumbleshot, lazypwny751",
-        "Art:          <you could be a developer of this game>",
-        "Music:        <you could be a developer of this game>",
-        "",
-        "Special Thanks to: The entire Tetra community!",
-        "repository: https://github.com/ByCh4n-Group/gorkitale",
+    let options = [
+        "Language",
+        "Volume",
+        "Difficulty",
+        "Particles",
+        "Objectives",
+        "Performance mode",
+        "Auto-walk",
+        "VSync",
+        "FPS cap",
+        "Replay combat tutorial",
     ];
+    let start_y = 170.0;
+
+    for (i, opt) in options.iter().enumerate() {
+        let color = if i == state.menu_state.selected_index {
+            Color::rgb(1.0, 1.0, 0.0)
+        } else {
+            Color::WHITE
+        };
+
+        let prefix = if i == state.menu_state.selected_index {
+            "> "
+        } else {
+            "  "
+        };
+
+        let value = match i {
+            0 => format!("{:?}", state.system.language),
+            1 => format!("{:.0}%", state.system.volume * 100.0),
+            2 => {
+                if state.system.hard_mode {
+                    "Hard".to_string()
+                } else {
+                    "Normal".to_string()
+                }
+            }
+            3 => {
+                let count = state.system.global_settings.snow_particle_count;
+                match PARTICLE_LEVELS.iter().find(|(_, c)| *c == count) {
+                    Some((label, _)) => label.to_string(),
+                    None => format!("Custom ({})", count),
+                }
+            }
+            4 => {
+                if state.system.global_settings.show_objectives {
+                    "Shown".to_string()
+                } else {
+                    "Hidden".to_string()
+                }
+            }
+            5 => {
+                if state.system.global_settings.performance_mode {
+                    "On".to_string()
+                } else {
+                    "Off".to_string()
+                }
+            }
+            6 => {
+                if state.system.global_settings.auto_walk {
+                    "On".to_string()
+                } else {
+                    "Off".to_string()
+                }
+            }
+            7 => {
+                if state.system.global_settings.vsync {
+                    "On".to_string()
+                } else {
+                    "Off".to_string()
+                }
+            }
+            8 => crate::frame_pacing::cap_label(state.system.global_settings.fps_cap),
+            9 => {
+                if state.system.global_settings.replay_combat_tutorial {
+                    "Next fight".to_string()
+                } else {
+                    "Off".to_string()
+                }
+            }
+            _ => "".to_string(),
+        };
 
-    for (i, line) in credits.iter().enumerate() {
-        let mut text = Text::new(*line, state.font.clone());
-        text.draw(
+        let mut text = Text::new(format!("{}{}: < {} >", prefix, opt, value), state.font());
+        text.render(
             ctx,
-            DrawParams::new().position(Vec2::new(200.0, 150.0 + (i as f32 * 30.0))),
+            DrawParams::new()
+                .position(Vec2::new(200.0, start_y + (i as f32 * 35.0)))
+                .color(color),
         );
     }
 
-    let mut hint = Text::new("Press Esc to go back", state.font.clone());
-    hint.draw(
+    let mut hint = Text::new("Press Esc to go back", state.font());
+    hint.render(
         ctx,
         DrawParams::new()
-            .position(Vec2::new(200.0, 500.0))
+            .position(Vec2::new(200.0, 530.0))
             .color(Color::rgb(0.5, 0.5, 0.5)),
     );
 
@@ -1,6 +1,24 @@
+pub mod asset_report;
 pub mod ayasofya;
+pub mod bone;
 pub mod boot;
 pub mod combat;
+pub mod combat_tutorial;
+pub mod credits;
+pub mod crowd;
 pub mod desktop;
+pub mod dialogue_log;
+pub mod disco;
 pub mod eilish;
+pub mod gaster;
+pub mod highlight;
+pub mod idle;
 pub mod menu;
+pub mod objectives;
+pub mod overworld;
+pub mod rescue;
+pub mod sans;
+pub mod session_summary;
+pub mod statistics;
+pub mod travel_map;
+pub mod whats_new;
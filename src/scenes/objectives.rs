@@ -0,0 +1,199 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{NpcId, SCREEN_WIDTH, Scene};
+use crate::game_state::GameState;
+use crate::objectives::{OBJECTIVES, WorldFlag};
+use crate::perf::{Render, RenderMut};
+use crate::texts::TextKey;
+
+const BANNER_SECONDS: f32 = 3.0;
+const LIST_ROW_HEIGHT: f32 = 28.0;
+
+/// Sets the world flags the objectives are checked against, and announces
+/// any objective that just got completed.
+pub fn update(state: &mut GameState, dt: f32) {
+    state.world.objective_banner_timer = (state.world.objective_banner_timer - dt).max(0.0);
+
+    let stage = state.world.current_stage;
+    let mut reached = Vec::new();
+    if state.world.in_current_stage(NpcId::MusicBox)
+        && state.player.pos.distance(state.world.musicbox_pos) < 120.0
+    {
+        reached.push(WorldFlag::FoundMusicBox);
+    }
+    if state.world.sans_dialogue.is_some() {
+        reached.push(WorldFlag::MetSans);
+    }
+    // Anything past stage 1 is past stage 2, unless the randomizer moved it
+    let cold_district = if state.world.is_randomized() {
+        stage == 2
+    } else {
+        stage >= 2
+    };
+    if cold_district {
+        reached.push(WorldFlag::ReachedColdDistrict);
+    }
+    if state.scene == Scene::AyasofyaInside {
+        reached.push(WorldFlag::EnteredAyasofya);
+    }
+    // Standing in any of the dead zone's hazards counts
+    if state.scene == Scene::Desktop
+        && stage == state.world.layout.dead_zone_stage
+        && state.world.hazard_intensity(state.player.pos) > 0.0
+    {
+        reached.push(WorldFlag::FacedRedZone);
+    }
+
+    let old_flags = state.world.flags;
+    let flags = reached
+        .iter()
+        .fold(old_flags, |flags, flag| flags | flag.bit());
+    if flags == old_flags {
+        return;
+    }
+    state.world.flags = flags;
+
+    for def in &OBJECTIVES {
+        if def.is_complete(flags) && !def.is_complete(old_flags) {
+            log::info!("Objective complete: {:?}", def.id);
+            state.world.objective_banner = Some(def.id);
+            state.world.objective_banner_timer = BANNER_SECONDS;
+        }
+    }
+
+    // Milestones are worth a save right away
+    if let Some(user) = &mut state.system.current_user {
+        user.flags = flags;
+    }
+    state.system.save_users();
+}
+
+/// Sets a flag that isn't tied to an objective, saved right away like the rest.
+pub fn set_flag(state: &mut GameState, flag: WorldFlag) {
+    state.world.flags |= flag.bit();
+    if let Some(user) = &mut state.system.current_user {
+        user.flags = state.world.flags;
+    }
+    state.system.save_users();
+}
+
+/// Current objective under the FPS counter, and the banner when one
+/// completes. Both hidden when the tracker is turned off in Settings.
+pub fn draw_hud(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.system.global_settings.show_objectives {
+        return Ok(());
+    }
+
+    let current = match crate::objectives::current(state.world.flags) {
+        Some(def) => state
+            .texts
+            .objectives
+            .title(def.id, state.world.is_randomized()),
+        None => &state.texts.objectives.all_done,
+    };
+    let mut text = Text::new(format!("> {}", current), state.font());
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(10.0, 50.0))
+            .color(Color::rgb(0.7, 0.9, 1.0)),
+    );
+
+    let Some(id) = state.world.objective_banner else {
+        return Ok(());
+    };
+    if state.world.objective_banner_timer <= 0.0 {
+        return Ok(());
+    }
+
+    // Fades out over the last second
+    let alpha = state.world.objective_banner_timer.min(1.0);
+    let banner = state.texts.fill(
+        TextKey::ObjectiveComplete,
+        &[(
+            "objective",
+            &state
+                .texts
+                .objectives
+                .title(id, state.world.is_randomized()),
+        )],
+    );
+    let mut banner_text = Text::new(banner, state.font());
+    let bounds = banner_text
+        .get_bounds(ctx)
+        .unwrap_or(Rectangle::new(0.0, 0.0, 200.0, 20.0));
+    let rect = Rectangle::new(
+        (SCREEN_WIDTH as f32 - bounds.width) / 2.0 - 12.0,
+        70.0,
+        bounds.width + 24.0,
+        bounds.height + 16.0,
+    );
+
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, rect)?;
+    fill.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, alpha * 0.8)),
+    );
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), rect)?;
+    border.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(1.0, 1.0, 0.0, alpha)),
+    );
+    banner_text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(rect.x + 12.0, rect.y + 8.0))
+            .color(Color::rgba(1.0, 1.0, 1.0, alpha)),
+    );
+
+    Ok(())
+}
+
+/// Every objective for the pause menu, completed ones struck through.
+pub fn draw_list(ctx: &mut Context, state: &GameState, pos: Vec2<f32>) -> tetra::Result {
+    let mut header = Text::new(state.texts.objectives.header.as_str(), state.font());
+    header.render(
+        ctx,
+        DrawParams::new()
+            .position(pos)
+            .color(Color::rgb(0.7, 0.7, 0.7)),
+    );
+
+    for (i, def) in OBJECTIVES.iter().enumerate() {
+        let y = pos.y + (i + 1) as f32 * LIST_ROW_HEIGHT;
+        let done = def.is_complete(state.world.flags);
+        let color = if done {
+            Color::rgb(0.5, 0.5, 0.5)
+        } else {
+            Color::WHITE
+        };
+
+        let title = state
+            .texts
+            .objectives
+            .title(def.id, state.world.is_randomized());
+        let mut text = Text::new(title, state.font());
+        text.render(
+            ctx,
+            DrawParams::new().position(Vec2::new(pos.x, y)).color(color),
+        );
+
+        if done {
+            let bounds = text
+                .get_bounds(ctx)
+                .unwrap_or(Rectangle::new(0.0, 0.0, 100.0, 16.0));
+            let strike = Mesh::rectangle(
+                ctx,
+                ShapeStyle::Fill,
+                Rectangle::new(pos.x, y + bounds.height / 2.0 + 2.0, bounds.width, 2.0),
+            )?;
+            strike.render(ctx, DrawParams::new().color(color));
+        }
+    }
+
+    Ok(())
+}
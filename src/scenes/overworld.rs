@@ -0,0 +1,200 @@
+use tetra::Context;
+use tetra::graphics::Color;
+use tetra::graphics::mesh::Mesh;
+use tetra::input::Key;
+use tetra::math::Vec2;
+
+use crate::defs::Direction;
+use crate::game_state::GameState;
+use crate::particles::ParticleStyle;
+use crate::perf::Render;
+use crate::player::PlayerState;
+use crate::render_util::draw_shadow;
+use crate::scenes::desktop::{PLAYER_SHADOW, Sprite};
+use crate::sim::UpdateInput;
+use crate::world::{Entry, StageDef};
+
+const PLAYER_SPEED: f32 = 2.0; // Pixels per frame
+pub const PLAYER_RADIUS: f32 = 20.0;
+const FEET_OFFSET: Vec2<f32> = Vec2::new(0.0, 24.0); // From the sprite's center at 3x
+const FOOTSTEP_SPACING: f32 = 24.0; // Pixels walked between puffs
+const PUFFS_PER_STEP: usize = 3;
+const SPARKLES_PER_PICKUP: usize = 12;
+
+const WALK_KEYS: [([Key; 2], Vec2<f32>); 4] = [
+    ([Key::W, Key::Up], Vec2::new(0.0, -1.0)),
+    ([Key::S, Key::Down], Vec2::new(0.0, 1.0)),
+    ([Key::A, Key::Left], Vec2::new(-1.0, 0.0)),
+    ([Key::D, Key::Right], Vec2::new(1.0, 0.0)),
+];
+
+/// One frame of walking around `room`: facing, the vertical bounds and the
+/// room's walls. NPCs aren't part of it, the desktop pushes out of them on
+/// its own. Returns the step the keys asked for, before anything blocked it.
+/// With `auto_walk` the keys are taps instead, see `auto_walk_step`.
+pub fn move_player(
+    input: &UpdateInput,
+    player: &mut PlayerState,
+    room: &StageDef,
+    auto_walk: bool,
+) -> Vec2<f32> {
+    let mut step = Vec2::zero();
+    if auto_walk {
+        step = auto_walk_step(input, player);
+    } else {
+        player.stop_auto_walk();
+        if input.is_down(Key::W) || input.is_down(Key::Up) {
+            step.y -= PLAYER_SPEED;
+            player.direction = Direction::Front;
+        }
+        if input.is_down(Key::S) || input.is_down(Key::Down) {
+            step.y += PLAYER_SPEED;
+            player.direction = Direction::Front;
+        }
+        if input.is_down(Key::A) || input.is_down(Key::Left) {
+            step.x -= PLAYER_SPEED;
+            player.direction = Direction::Left;
+        }
+        if input.is_down(Key::D) || input.is_down(Key::Right) {
+            step.x += PLAYER_SPEED;
+            player.direction = Direction::Right;
+        }
+    }
+
+    // Clamped per axis so walking along a bound still slides sideways
+    let mut pos = player.pos + step;
+    pos.y = pos.y.clamp(room.top_bound, room.bottom_bound);
+    pos += crate::physics::wall_separation(pos, PLAYER_RADIUS, room.walls);
+    pos.y = pos.y.clamp(room.top_bound, room.bottom_bound);
+
+    // Ran into a wall or the top or bottom of the room
+    if player.auto_walk.is_some() && pos.distance(player.pos) < PLAYER_SPEED / 2.0 {
+        player.stop_auto_walk();
+    }
+    player.pos = pos;
+    step
+}
+
+/// Tap-to-move. A direction key starts walking that way and keeps going,
+/// another one turns, the same one again stops. F always stops first.
+fn auto_walk_step(input: &UpdateInput, player: &mut PlayerState) -> Vec2<f32> {
+    if input.is_pressed(Key::F) {
+        player.stop_auto_walk();
+        return Vec2::zero();
+    }
+    for (keys, direction) in WALK_KEYS {
+        if keys.iter().any(|&key| input.is_pressed(key)) {
+            player.auto_walk = if player.auto_walk == Some(direction) {
+                None
+            } else {
+                Some(direction)
+            };
+        }
+    }
+
+    let Some(direction) = player.auto_walk else {
+        return Vec2::zero();
+    };
+    player.direction = if direction.x < 0.0 {
+        Direction::Left
+    } else if direction.x > 0.0 {
+        Direction::Right
+    } else {
+        Direction::Front
+    };
+    direction * PLAYER_SPEED
+}
+
+/// The player's sprite at `scale`, with the idle pose and the i-frame blink.
+/// Outfit 1 and 2 are the fes and takke. They only have front art, walking
+/// sideways falls back to the plain sprite.
+pub fn player_sprite(player: &PlayerState, outfit: u8, scale: f32) -> Option<Sprite<'_>> {
+    let blink_hidden = player.invuln_timer > 0.0 && (player.invuln_timer * 10.0) as i32 % 2 == 0;
+    if blink_hidden {
+        return None;
+    }
+
+    let texture = match (player.direction, outfit) {
+        (Direction::Front, 1) => player.texture_fes.as_ref(),
+        (Direction::Front, 2) => player.texture_takke.as_ref(),
+        _ => None,
+    }
+    .or(match player.direction {
+        Direction::Front => player.texture_front.as_ref(),
+        Direction::Left | Direction::Right => player.texture_right.as_ref(),
+    })?;
+    let scale_x = if player.direction == Direction::Left {
+        -scale
+    } else {
+        scale
+    };
+
+    // Squash from the feet so the idle blink doesn't lift them
+    let (mut offset, squash) = crate::scenes::idle::player_pose(player);
+    offset.y += texture.height() as f32 * scale * (1.0 - squash) / 2.0;
+    // The centered origin keeps the flip in place
+    Some(
+        Sprite::centered(
+            texture,
+            player.pos + offset,
+            Vec2::new(scale_x, scale * squash),
+        )
+        .shadow(PLAYER_SHADOW)
+        .color(Color::rgba(1.0, 1.0, 1.0, player.player_fade_alpha)),
+    )
+}
+
+/// Puts the player at the current stage's spawn point for `entry`, or the
+/// nearest free spot if someone is standing on it.
+pub fn spawn_player(state: &mut GameState, entry: Entry) {
+    let spawn = state.world.stage_def().spawn(entry);
+    state.player.pos = crate::physics::nearest_free_spot(spawn.pos, PLAYER_RADIUS, &state.world);
+    state.player.direction = spawn.facing;
+}
+
+/// Puffs of snow from the player's feet on stages with snow, one burst
+/// every few steps. `walked` is this frame's distance.
+pub fn step_footsteps(state: &mut GameState, walked: f32) {
+    if !state.world.stage_def().has_snow() {
+        return;
+    }
+    state.world.footstep_distance += walked;
+    if state.world.footstep_distance >= FOOTSTEP_SPACING {
+        state.world.footstep_distance = 0.0;
+        let quality = state.graphics_quality();
+        state.world.particles.spawn_burst(
+            state.player.pos + FEET_OFFSET,
+            PUFFS_PER_STEP,
+            ParticleStyle::SnowPuff,
+            quality,
+        );
+    }
+}
+
+/// Golden sparkle over the player for anything they pick up.
+pub fn pickup_sparkle(state: &mut GameState) {
+    let quality = state.graphics_quality();
+    state.world.particles.spawn_burst(
+        state.player.pos,
+        SPARKLES_PER_PICKUP,
+        ParticleStyle::Sparkle,
+        quality,
+    );
+}
+
+/// For scenes where the player is the only sprite, so nothing needs sorting.
+pub fn draw_player(ctx: &mut Context, player: &PlayerState, outfit: u8, scale: f32, shadow: &Mesh) {
+    if let Some(sprite) = player_sprite(player, outfit, scale) {
+        if let Some(size) = sprite.shadow {
+            let alpha = sprite.params.color.a;
+            draw_shadow(
+                ctx,
+                shadow,
+                Vec2::new(sprite.pos.x, sprite.feet_y),
+                size,
+                alpha,
+            );
+        }
+        sprite.texture.render(ctx, sprite.params);
+    }
+}
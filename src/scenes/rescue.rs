@@ -0,0 +1,227 @@
+use rand::Rng;
+use std::f32::consts::FRAC_PI_2;
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::input::Key;
+use tetra::math::Vec2;
+
+use crate::defs::{NpcId, SCREEN_WIDTH, Scene};
+use crate::game_state::GameState;
+use crate::objectives::WorldFlag;
+use crate::perf::{Render, RenderMut};
+use crate::player::{EXPOSURE_DRAIN_SECONDS, EXPOSURE_FILL_SECONDS};
+use crate::scenes::desktop::{PLAYER_SHADOW, Sprite};
+use crate::sim::UpdateInput;
+
+const TALK_DISTANCE: f32 = 120.0;
+const FOLLOW_DISTANCE: f32 = 50.0; // Stays this far behind the player
+const FOLLOW_SPEED: f32 = 2.2; // Pixels per tick, a little faster than the player
+const SURVIVOR_RADIUS: f32 = 20.0;
+const SURVIVOR_FILL_SECONDS: f32 = EXPOSURE_FILL_SECONDS / 2.0; // Already weak from waiting
+const RESCUE_LINE_X: f32 = 320.0; // Left of everything stage 4's hazards ever cover
+const LINE_SECONDS: f32 = 3.0;
+const GAS_MASK_RESISTANCE: f32 = 0.5;
+
+/// The survivor collapsed deep in the stage 4 dead space. F starts an
+/// escort out of it, and once rescued they wait in stage 3 to say thanks.
+pub fn step(input: &mut UpdateInput, state: &mut GameState) {
+    if state.scene != Scene::Desktop || state.world.current_stage != state.world.survivor_stage() {
+        return;
+    }
+
+    let dt = input.dt;
+    state.world.survivor_line_timer = (state.world.survivor_line_timer - dt).max(0.0);
+    if state.world.survivor_line_timer <= 0.0 {
+        state.world.survivor_line = None;
+    }
+
+    let in_reach = state.player.pos.distance(state.world.survivor_pos) < TALK_DISTANCE
+        && state.world.is_nearest(NpcId::Survivor);
+
+    if rescued(state) {
+        let thanks = &state.texts.survivor_thanks;
+        if in_reach && input.is_pressed(Key::F) && !thanks.is_empty() {
+            let line = thanks[input.rng.random_range(0..thanks.len())].clone();
+            say(state, line);
+        }
+        return;
+    }
+
+    if !state.world.survivor_escort {
+        if in_reach && input.is_pressed(Key::F) {
+            state.world.survivor_escort = true;
+            state.world.survivor_exposure = 0.0;
+            let line = state.texts.survivor_help.clone();
+            say(state, line);
+        }
+        return;
+    }
+
+    follow_player(state);
+
+    // Same meter as the player's, without anything to slow it down
+    if state.world.hazard_intensity(state.world.survivor_pos) > 0.0 {
+        state.world.survivor_exposure += dt / SURVIVOR_FILL_SECONDS;
+    } else {
+        state.world.survivor_exposure =
+            (state.world.survivor_exposure - dt / EXPOSURE_DRAIN_SECONDS).max(0.0);
+    }
+    if state.world.survivor_exposure >= 1.0 {
+        // They collapse and crawl back to where they were found
+        state.world.survivor_escort = false;
+        state.world.survivor_exposure = 0.0;
+        if let Some(home) = state.world.npc_home(NpcId::Survivor) {
+            state.world.survivor_pos = home;
+        }
+        let line = state.texts.survivor_collapsed.clone();
+        say(state, line);
+        return;
+    }
+
+    let out = |pos: Vec2<f32>| pos.x < RESCUE_LINE_X && state.world.hazard_intensity(pos) <= 0.0;
+    if out(state.world.survivor_pos) && out(state.player.pos) {
+        complete(state);
+    }
+}
+
+/// Walks towards the player, stopping short so they trail behind.
+fn follow_player(state: &mut GameState) {
+    let to_player = state.player.pos - state.world.survivor_pos;
+    let distance = to_player.magnitude();
+    if distance <= FOLLOW_DISTANCE {
+        return;
+    }
+    let delta = to_player / distance * FOLLOW_SPEED.min(distance - FOLLOW_DISTANCE);
+
+    let stage_def = state.world.stage_def();
+    let mut pos = crate::physics::move_and_collide(
+        state.world.survivor_pos,
+        delta,
+        SURVIVOR_RADIUS,
+        &state.world,
+        NpcId::Survivor,
+    );
+    pos.x = pos.x.clamp(0.0, SCREEN_WIDTH as f32);
+    pos.y = pos.y.clamp(stage_def.top_bound, stage_def.bottom_bound);
+    state.world.survivor_pos = pos;
+}
+
+/// Both made it out. The gas mask is the reward, and the survivor moves
+/// on to stage 3.
+fn complete(state: &mut GameState) {
+    log::info!("Survivor rescued from the dead space");
+    state.world.survivor_escort = false;
+    state.world.survivor_line = None;
+    if let Some(user) = &mut state.system.current_user {
+        user.teblig_count += 1;
+    }
+    state.session_stats.teblig += 1;
+    state.session_stats.collect("Gas mask");
+    crate::scenes::overworld::pickup_sparkle(state);
+    // Saves the teblig along with the flag
+    crate::scenes::objectives::set_flag(state, WorldFlag::RescuedSurvivor);
+    apply_gas_mask(state);
+    let notice = state.texts.ui.gas_mask_received.clone();
+    crate::scenes::bone::show_toast(state, notice);
+}
+
+pub fn rescued(state: &GameState) -> bool {
+    WorldFlag::RescuedSurvivor.is_set(state.world.flags)
+}
+
+/// The gas mask is kept as the rescue flag, so it comes back with the
+/// profile. Call after the world flags are loaded.
+pub fn apply_gas_mask(state: &mut GameState) {
+    state.player.exposure_resistance = if rescued(state) {
+        GAS_MASK_RESISTANCE
+    } else {
+        1.0
+    };
+}
+
+fn say(state: &mut GameState, line: String) {
+    state
+        .dialogue_log
+        .push(crate::dialogue_log::Speaker::Survivor, &line);
+    state.world.survivor_line = Some(line);
+    state.world.survivor_line_timer = LINE_SECONDS;
+}
+
+/// The survivor for the desktop's depth-sorted sprite pass. Lying down
+/// until the escort starts.
+pub fn collect_sprites<'a>(state: &'a GameState, sprites: &mut Vec<Sprite<'a>>) {
+    if state.world.current_stage != state.world.survivor_stage() {
+        return;
+    }
+    let Some(texture) = &state.world.survivor_texture else {
+        return;
+    };
+
+    let mut sprite = Sprite::centered(texture, state.world.survivor_pos, Vec2::new(3.0, 3.0))
+        .shadow(PLAYER_SHADOW)
+        .npc(NpcId::Survivor);
+    if !rescued(state) && !state.world.survivor_escort {
+        sprite.params = sprite.params.rotation(FRAC_PI_2);
+        sprite.feet_y = state.world.survivor_pos.y + texture.width() as f32 * 1.5;
+    }
+    sprites.push(sprite);
+}
+
+/// Prompt, the survivor's exposure meter during the escort and their
+/// speech bubble. Drawn over the sprites.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if state.world.current_stage != state.world.survivor_stage() {
+        return Ok(());
+    }
+    let pos = state.world.survivor_pos;
+
+    let prompt = if rescued(state) {
+        Some("Press F to talk")
+    } else if !state.world.survivor_escort {
+        Some("Press F to help")
+    } else {
+        None
+    };
+    #[allow(clippy::collapsible_if)]
+    if let Some(prompt) = prompt {
+        if state.player.pos.distance(pos) < TALK_DISTANCE
+            && state.world.is_nearest(NpcId::Survivor)
+            && state.world.survivor_line.is_none()
+        {
+            let mut text = Text::new(prompt, state.font());
+            let width = text.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+            text.render(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(pos.x - width / 2.0, pos.y - 80.0))
+                    .color(Color::rgb(1.0, 1.0, 0.0)),
+            );
+        }
+    }
+
+    if state.world.survivor_escort && state.world.survivor_exposure > 0.0 {
+        let width = 40.0;
+        let meter = Rectangle::new(pos.x - width / 2.0, pos.y - 55.0, width, 5.0);
+        let background = Mesh::rectangle(ctx, ShapeStyle::Fill, meter)?;
+        background.render(ctx, DrawParams::new().color(Color::rgb(0.2, 0.2, 0.2)));
+        let fill = Mesh::rectangle(
+            ctx,
+            ShapeStyle::Fill,
+            Rectangle::new(
+                meter.x,
+                meter.y,
+                state.world.survivor_exposure.min(1.0) * width,
+                meter.height,
+            ),
+        )?;
+        fill.render(ctx, DrawParams::new().color(Color::rgb(0.6, 0.2, 1.0)));
+    }
+
+    if let Some(line) = &state.world.survivor_line {
+        crate::scenes::desktop::draw_bubble_at(ctx, state, line, pos)?;
+    }
+
+    Ok(())
+}
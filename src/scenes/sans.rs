@@ -0,0 +1,288 @@
+use rand::{Rng, RngCore};
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::input::Key;
+use tetra::math::Vec2;
+
+use crate::combat::CombatEnemy;
+use crate::defs::{NpcId, SCREEN_WIDTH, Scene};
+use crate::dialogue::{DialogueAction, DialogueChoice, DialogueNode, DialogueTree};
+use crate::dialogue_log::Speaker;
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::sim::UpdateInput;
+use crate::world::SansChase;
+
+const HANDSHAKE_TRUST: f32 = 20.0;
+
+pub const CHASE_SECONDS: f32 = 20.0;
+const CHASE_SPEED: f32 = 2.4; // Player walks at 2.0
+const CHASE_STEERING: f32 = 0.08; // How quickly he turns towards the player
+const CHASE_CONTACT_DISTANCE: f32 = 50.0;
+const CHASE_CONTACT_DAMAGE: i32 = 15;
+const CHASE_IFRAMES: f32 = 1.5;
+const SHRUG_SECONDS: f32 = 2.0;
+const RETURN_SPEED: f32 = 1.5;
+
+/// Sans interaction in stage 1. While the dialogue is open it takes over input.
+pub fn step(input: &mut UpdateInput, state: &mut GameState) {
+    if !state.world.in_current_stage(NpcId::Sans) {
+        return;
+    }
+
+    // No talking while he's hunting you or walking back
+    if state.world.sans_chase != SansChase::Idle {
+        update_chase(input.dt, state);
+        return;
+    }
+
+    if let Some(tree) = &mut state.world.sans_dialogue {
+        if input.is_pressed(Key::Up) || input.is_pressed(Key::W) {
+            tree.select_prev();
+        }
+        if input.is_pressed(Key::Down) || input.is_pressed(Key::S) {
+            tree.select_next();
+        }
+        if input.is_pressed(Key::Enter) || input.is_pressed(Key::F) {
+            let node = tree.current_node();
+            state.dialogue_log.push(Speaker::Sans, &node.text);
+            if let Some(choice) = node.choices.get(tree.selected) {
+                state.dialogue_log.push(Speaker::Player, &choice.label);
+            }
+            let (action, still_open) = tree.confirm();
+            if !still_open {
+                state.world.sans_dialogue = None;
+            }
+            apply_action(state, action);
+        }
+        return;
+    }
+
+    let dx = state.player.pos.x - state.world.sans_pos.x;
+    let dy = state.player.pos.y - state.world.sans_pos.y;
+    let distance = (dx * dx + dy * dy).sqrt();
+
+    if distance < 120.0 && state.world.is_nearest(NpcId::Sans) && input.is_pressed(Key::F) {
+        // He only talks to people he trusts, or to strangers once
+        if state.world.trust(NpcId::Sans) > 0.0 || !state.world.sans_met {
+            state.world.sans_dialogue = Some(build_dialogue(state, input.rng));
+        } else {
+            start_combat(state);
+        }
+        state.world.sans_met = true;
+    }
+}
+
+pub fn is_chasing(state: &GameState) -> bool {
+    state.world.sans_chase == SansChase::Chasing
+}
+
+pub fn start_chase(state: &mut GameState, seconds: f32) {
+    state.world.sans_dialogue = None;
+    state.world.sans_chase = SansChase::Chasing;
+    state.world.sans_chase_timer = seconds;
+    state.world.sans_velocity = Vec2::zero();
+}
+
+fn update_chase(dt: f32, state: &mut GameState) {
+    state.world.sans_chase_timer -= dt;
+
+    match state.world.sans_chase {
+        SansChase::Chasing => {
+            // Simple seek steering, ease the velocity towards the player
+            let to_player = state.player.pos - state.world.sans_pos;
+            let desired = if to_player.magnitude() > 0.001 {
+                to_player.normalized() * CHASE_SPEED
+            } else {
+                Vec2::zero()
+            };
+            state.world.sans_velocity += (desired - state.world.sans_velocity) * CHASE_STEERING;
+            move_sans(state, state.world.sans_velocity);
+
+            let touching = state.player.pos.distance(state.world.sans_pos) < CHASE_CONTACT_DISTANCE;
+            if touching && state.player.invuln_timer <= 0.0 {
+                state.player.player_hp -= CHASE_CONTACT_DAMAGE;
+                state.player.invuln_timer = CHASE_IFRAMES;
+                state.world.screen_shake = 0.3;
+            }
+
+            if state.world.sans_chase_timer <= 0.0 {
+                state.world.sans_chase = SansChase::Shrugging;
+                state.world.sans_chase_timer = SHRUG_SECONDS;
+                state.world.sans_velocity = Vec2::zero();
+                if let Some(user) = &mut state.system.current_user {
+                    user.sans_chase = 0.0;
+                }
+            }
+        }
+        SansChase::Shrugging => {
+            if state.world.sans_chase_timer <= 0.0 {
+                state.world.sans_chase = SansChase::Returning;
+            }
+        }
+        SansChase::Returning => {
+            let home = state
+                .world
+                .npc_home(NpcId::Sans)
+                .unwrap_or(state.world.sans_pos);
+            let to_home = home - state.world.sans_pos;
+            if to_home.magnitude() <= RETURN_SPEED {
+                state.world.sans_pos = home;
+                state.world.sans_chase = SansChase::Idle;
+            } else {
+                move_sans(state, to_home.normalized() * RETURN_SPEED);
+            }
+        }
+        SansChase::Idle => {}
+    }
+}
+
+fn move_sans(state: &mut GameState, delta: Vec2<f32>) {
+    let stage_def = state.world.stage_def();
+    let mut pos = crate::physics::move_and_collide(
+        state.world.sans_pos,
+        delta,
+        40.0,
+        &state.world,
+        NpcId::Sans,
+    );
+    pos.x = pos.x.clamp(0.0, SCREEN_WIDTH as f32);
+    pos.y = pos.y.clamp(stage_def.top_bound, stage_def.bottom_bound);
+    state.world.sans_pos = pos;
+}
+
+pub fn close_dialogue(state: &mut GameState) {
+    state.world.sans_dialogue = None;
+}
+
+fn build_dialogue(state: &GameState, rng: &mut dyn RngCore) -> DialogueTree {
+    let texts = &state.texts.sans_dialogues;
+    let shook_before = state
+        .system
+        .current_user
+        .as_ref()
+        .is_some_and(|u| u.teblig_count > 0);
+
+    let greeting = if shook_before && !texts.after_handshake.is_empty() {
+        texts.after_handshake[rng.random_range(0..texts.after_handshake.len())].clone()
+    } else {
+        texts.first_meeting.clone()
+    };
+
+    // He sizes you up first
+    let lines = &state.texts.sans_greeting_lines;
+    let tier = match state.player.player_hp {
+        hp if hp > 15 => &lines.confident,
+        10..=15 => &lines.hurt,
+        _ => &lines.critical,
+    };
+    let greeting = if tier.is_empty() {
+        greeting
+    } else {
+        format!("{}\n{}", tier[rng.random_range(0..tier.len())], greeting)
+    };
+
+    DialogueTree::new(vec![DialogueNode {
+        text: greeting,
+        choices: vec![
+            DialogueChoice {
+                label: texts.shake_hands.clone(),
+                action: DialogueAction::ShakeHands,
+                next: None,
+            },
+            DialogueChoice {
+                label: texts.fight.clone(),
+                action: DialogueAction::Fight,
+                next: None,
+            },
+        ],
+    }])
+}
+
+fn apply_action(state: &mut GameState, action: DialogueAction) {
+    match action {
+        DialogueAction::ShakeHands => {
+            if let Some(user) = &mut state.system.current_user {
+                user.teblig_count += 1;
+            }
+            state.session_stats.teblig += 1;
+            state.system.save_users();
+            *state.world.trust.entry(NpcId::Sans).or_insert(0.0) += HANDSHAKE_TRUST;
+        }
+        DialogueAction::Fight => start_combat(state),
+        DialogueAction::Close => {}
+    }
+}
+
+fn start_combat(state: &mut GameState) {
+    state.world.pending_combat_enemy = Some(CombatEnemy::Sans);
+    state.scene = Scene::CombatTransition;
+    state.fade_out = true;
+    state.fade_alpha = 0.0;
+}
+
+pub fn draw_dialogue(ctx: &mut Context, state: &GameState) {
+    if let Some(tree) = &state.world.sans_dialogue {
+        draw_tree(ctx, state, tree);
+    }
+}
+
+/// The black box along the bottom that Sans's and Gaster's dialogue and the
+/// combat tutorial are drawn in.
+pub fn draw_dialogue_box(ctx: &mut Context) {
+    if let Ok(box_rect) = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Fill,
+        Rectangle::new(50.0, 450.0, 700.0, 130.0),
+    ) {
+        box_rect.render(
+            ctx,
+            DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+        );
+    }
+
+    if let Ok(border_rect) = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Stroke(2.0),
+        Rectangle::new(50.0, 450.0, 700.0, 130.0),
+    ) {
+        border_rect.render(ctx, DrawParams::new().color(Color::WHITE));
+    }
+}
+
+/// Text and choices of `tree`, Gaster's darker dialogue uses it too.
+pub fn draw_tree(ctx: &mut Context, state: &GameState, tree: &DialogueTree) {
+    draw_dialogue_box(ctx);
+
+    let node = tree.current_node();
+    let mut text = Text::new(&node.text, state.font());
+    text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(70.0, 465.0))
+            .color(Color::WHITE),
+    );
+
+    for (i, choice) in node.choices.iter().enumerate() {
+        let selected = i == tree.selected;
+        let label = if selected {
+            format!("> {}", choice.label)
+        } else {
+            format!("  {}", choice.label)
+        };
+        let color = if selected {
+            Color::rgb(1.0, 1.0, 0.0)
+        } else {
+            Color::WHITE
+        };
+        let mut choice_text = Text::new(label, state.font());
+        choice_text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(90.0 + i as f32 * 250.0, 540.0))
+                .color(color),
+        );
+    }
+}
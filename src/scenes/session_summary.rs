@@ -0,0 +1,171 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{self, Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{SCREEN_HEIGHT, Scene};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::scenes::menu::MenuSubState;
+use crate::world::STAGE_COUNT;
+
+const CARD: Rectangle = Rectangle {
+    x: 100.0,
+    y: 70.0,
+    width: 600.0,
+    height: 440.0,
+};
+const PADDING: f32 = 30.0;
+const AVATAR_HEIGHT: f32 = 100.0;
+const COLUMNS_Y: f32 = 220.0; // Under the avatar
+const RIGHT_COLUMN_X: f32 = 420.0;
+const ROW_HEIGHT: f32 = 30.0;
+
+const LABEL: Color = Color::rgb(0.6, 0.6, 0.6);
+const GAIN: Color = Color::rgb(1.0, 1.0, 0.0); // Same yellow as the teblig highlights
+
+/// Shows the card on the way back to the main menu.
+pub fn open(state: &mut GameState) {
+    state.scene = Scene::SessionSummary;
+}
+
+/// Enter and Esc both leave straight for the main menu. The counts stay
+/// until the next Start Game, so resuming a paused session keeps adding up.
+pub fn close(state: &mut GameState) {
+    state.scene = Scene::Menu;
+    state.menu_state.sub_state = MenuSubState::Main;
+}
+
+fn duration(seconds: f32) -> String {
+    let minutes = (seconds / 60.0) as u32;
+    format!("{}:{:02}", minutes, (seconds % 60.0) as u32)
+}
+
+fn list_or_none(items: &[&str]) -> String {
+    if items.is_empty() {
+        "None".to_string()
+    } else {
+        items.join(", ")
+    }
+}
+
+fn draw_row(
+    ctx: &mut Context,
+    state: &GameState,
+    pos: Vec2<f32>,
+    label: &str,
+    value: &str,
+    color: Color,
+) {
+    let mut label = Text::new(label, state.font());
+    label.render(ctx, DrawParams::new().position(pos).color(LABEL));
+    let mut value = Text::new(value, state.font());
+    value.render(
+        ctx,
+        DrawParams::new()
+            .position(pos + Vec2::new(150.0, 0.0))
+            .color(color),
+    );
+}
+
+/// The player as they left, wearing the hat they own.
+fn draw_avatar(ctx: &mut Context, state: &GameState) {
+    let player = &state.player;
+    let texture = match player.owned_outfit {
+        1 => player.texture_fes.as_ref(),
+        2 => player.texture_takke.as_ref(),
+        _ => None,
+    }
+    .or(player.texture_front.as_ref());
+    if let Some(texture) = texture {
+        let scale = AVATAR_HEIGHT / texture.height() as f32;
+        texture.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(CARD.x + PADDING, CARD.y + PADDING))
+                .scale(Vec2::new(scale, scale)),
+        );
+    }
+}
+
+/// Zeros for a short session read the same as anything else, every row
+/// is always there.
+pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    graphics::clear(ctx, Color::BLACK);
+
+    let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, CARD)?;
+    fill.render(ctx, DrawParams::new().color(Color::rgb(0.08, 0.08, 0.12)));
+    let border = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), CARD)?;
+    border.render(ctx, DrawParams::new().color(Color::WHITE));
+
+    draw_avatar(ctx, state);
+
+    let header_x = CARD.x + PADDING + AVATAR_HEIGHT + 20.0;
+    let title_font = state.get_font(ctx, 24.0).clone();
+    let mut title = Text::new("Session Summary", title_font);
+    title.render(
+        ctx,
+        DrawParams::new().position(Vec2::new(header_x, CARD.y + PADDING)),
+    );
+    if let Some(user) = &state.system.current_user {
+        let mut name = Text::new(user.username.as_str(), state.font());
+        name.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(header_x, CARD.y + PADDING + 40.0))
+                .color(LABEL),
+        );
+    }
+
+    let stats = &state.session_stats;
+    let left = [
+        ("Time played", duration(stats.time_played)),
+        ("Stages", format!("{}/{}", stats.stage_count(), STAGE_COUNT)),
+        ("Damage taken", stats.damage_taken.to_string()),
+    ];
+    let right = [
+        ("Teblig", format!("+{}", stats.teblig)),
+        ("Cihad", format!("+{}", stats.cihad)),
+        ("Tekfir", format!("+{}", stats.tekfir)),
+    ];
+    let items = list_or_none(&stats.items);
+    let achievements = list_or_none(&stats.achievements);
+
+    for (i, (label, value)) in left.iter().enumerate() {
+        let pos = Vec2::new(CARD.x + PADDING, COLUMNS_Y + i as f32 * ROW_HEIGHT);
+        draw_row(ctx, state, pos, label, value, Color::WHITE);
+    }
+    for (i, (label, value)) in right.iter().enumerate() {
+        let pos = Vec2::new(RIGHT_COLUMN_X, COLUMNS_Y + i as f32 * ROW_HEIGHT);
+        draw_row(ctx, state, pos, label, value, GAIN);
+    }
+
+    let lists_y = COLUMNS_Y + 4.0 * ROW_HEIGHT;
+    draw_row(
+        ctx,
+        state,
+        Vec2::new(CARD.x + PADDING, lists_y),
+        "Items",
+        &items,
+        Color::WHITE,
+    );
+    draw_row(
+        ctx,
+        state,
+        Vec2::new(CARD.x + PADDING, lists_y + ROW_HEIGHT),
+        "Achievements",
+        &achievements,
+        Color::WHITE,
+    );
+
+    let mut hint = Text::new("Enter to continue, Esc to skip", state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(20.0, SCREEN_HEIGHT as f32 - 40.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
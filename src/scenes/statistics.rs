@@ -0,0 +1,97 @@
+use tetra::Context;
+use tetra::graphics::text::Text;
+use tetra::graphics::{self, Color, DrawParams};
+use tetra::math::Vec2;
+
+use crate::defs::SCREEN_HEIGHT;
+use crate::game_state::GameState;
+use crate::perf::RenderMut;
+use crate::system::DeathCause;
+
+pub fn update(_ctx: &mut Context, _state: &mut GameState) -> tetra::Result {
+    Ok(())
+}
+
+/// All-time totals across every profile in users.db.
+pub fn draw(ctx: &mut Context, state: &mut GameState) -> tetra::Result {
+    graphics::clear(ctx, Color::BLACK);
+
+    let mut title = Text::new("Statistics", state.font());
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(300.0, 50.0))
+            .scale(Vec2::new(1.5, 1.5)),
+    );
+
+    let users = &state.system.users;
+    let total_teblig: u32 = users.iter().map(|u| u.teblig_count).sum();
+    let total_cihad: u32 = users.iter().map(|u| u.cihad_count).sum();
+    let total_tekfir: u32 = users.iter().map(|u| u.tekfir_count).sum();
+    let furthest_stage = users.iter().map(|u| u.current_stage).max().unwrap_or(0);
+    let deaths: Vec<String> = DeathCause::ALL
+        .iter()
+        .filter_map(|cause| {
+            let count: u32 = users.iter().map(|u| u.deaths[cause.index()]).sum();
+            (count > 0).then(|| format!("{} {}", cause.label(), count))
+        })
+        .collect();
+    let deaths = if deaths.is_empty() {
+        "None".to_string()
+    } else {
+        deaths.join(", ")
+    };
+
+    let totals = [
+        format!("Profiles:       {}", users.len()),
+        format!("Teblig:         {}", total_teblig),
+        format!("Cihad:          {}", total_cihad),
+        format!("Tekfir:         {}", total_tekfir),
+        format!("Furthest Stage: {}", furthest_stage),
+        format!("Deaths:         {}", deaths),
+    ];
+
+    for (i, line) in totals.iter().enumerate() {
+        let mut text = Text::new(line.as_str(), state.font());
+        text.render(
+            ctx,
+            DrawParams::new().position(Vec2::new(200.0, 130.0 + (i as f32 * 30.0))),
+        );
+    }
+
+    // Per-profile breakdown, top of the list first. Locked hardcore
+    // profiles get a headstone.
+    for (i, user) in users.iter().take(5).enumerate() {
+        let line = format!(
+            "{:<16} T:{} C:{} K:{} Stage {}{}",
+            user.username,
+            user.teblig_count,
+            user.cihad_count,
+            user.tekfir_count,
+            user.current_stage,
+            if user.locked { "  RIP \u{2620}" } else { "" }
+        );
+        let color = if user.locked {
+            Color::rgb(0.8, 0.2, 0.2)
+        } else {
+            Color::rgb(0.7, 0.7, 0.7)
+        };
+        let mut text = Text::new(line, state.font());
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(200.0, 310.0 + (i as f32 * 30.0)))
+                .color(color),
+        );
+    }
+
+    let mut hint = Text::new("Press Esc to go back", state.font());
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(20.0, SCREEN_HEIGHT as f32 - 40.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH, Scene};
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::world::STAGE_COUNT;
+
+const ALL_STAGES: u8 = (1 << STAGE_COUNT) - 1;
+const BOX_SIZE: f32 = 100.0;
+const BOX_GAP: f32 = 30.0;
+
+fn stage_bit(stage: u8) -> u8 {
+    1 << (stage - 1)
+}
+
+fn visited(state: &GameState) -> u8 {
+    state
+        .system
+        .current_user
+        .as_ref()
+        .map_or(0, |user| user.visited_stages)
+}
+
+/// Fast travel opens up once the profile has been to every stage.
+pub fn unlocked(state: &GameState) -> bool {
+    visited(state) & ALL_STAGES == ALL_STAGES
+}
+
+/// Records the current stage on the profile, saving is left to the caller.
+/// The last new stage unlocks fast travel, with a note saying so.
+pub fn mark_visited(state: &mut GameState) {
+    let stage = state.world.current_stage;
+    if stage == 0 || stage as usize > STAGE_COUNT {
+        return;
+    }
+    state.session_stats.visit(stage);
+    let Some(user) = &mut state.system.current_user else {
+        return;
+    };
+    if user.visited_stages & stage_bit(stage) != 0 {
+        return;
+    }
+    user.visited_stages |= stage_bit(stage);
+    if user.visited_stages & ALL_STAGES == ALL_STAGES {
+        log::info!("Every stage visited, fast travel unlocked");
+        state.session_stats.unlock("Fast travel");
+        let note = state.texts.ui.travel_unlocked.clone();
+        crate::scenes::bone::show_toast(state, note);
+    }
+}
+
+pub fn can_open(state: &GameState) -> bool {
+    matches!(state.scene, Scene::Desktop | Scene::AyasofyaInside)
+}
+
+/// Why the map won't open right now, if it won't.
+fn refusal(state: &GameState) -> Option<&str> {
+    let ui = &state.texts.ui;
+    if !unlocked(state) {
+        Some(&ui.travel_locked)
+    } else if state.scene == Scene::AyasofyaInside {
+        Some(&ui.travel_blocked_ayasofya)
+    } else if crate::scenes::sans::is_chasing(state) {
+        Some(&ui.travel_blocked_chase)
+    } else if state.player.exposure > 0.0 {
+        Some(&ui.travel_blocked_exposure)
+    } else {
+        None
+    }
+}
+
+/// M opens the map, or says over the player's head why it can't.
+pub fn toggle(state: &mut GameState) {
+    if state.world.map_open {
+        state.world.map_open = false;
+        return;
+    }
+    // Already on the way somewhere
+    if state.fade_out {
+        return;
+    }
+    if let Some(reason) = refusal(state) {
+        let reason = reason.to_string();
+        crate::scenes::bone::show_toast(state, reason);
+        return;
+    }
+    state.player.stop_auto_walk();
+    state.world.map_open = true;
+    state.world.map_selection = state.world.current_stage;
+}
+
+/// Next visited stage along the strip, wrapping.
+pub fn move_selection(state: &mut GameState, forward: bool) {
+    let visited = visited(state);
+    let count = STAGE_COUNT as u8;
+    let mut stage = state.world.map_selection;
+    for _ in 0..count {
+        stage = if forward {
+            stage % count + 1
+        } else {
+            (stage + count - 2) % count + 1
+        };
+        if visited & stage_bit(stage) != 0 {
+            state.world.map_selection = stage;
+            return;
+        }
+    }
+}
+
+/// Closes the map and fades out towards the picked stage. Desktop's stage
+/// transition finishes the trip.
+pub fn confirm(state: &mut GameState) {
+    state.world.map_open = false;
+    let stage = state.world.map_selection;
+    if stage == state.world.current_stage {
+        return;
+    }
+    log::info!("Fast travel to stage {}", stage);
+    state.world.travel_target = Some(stage);
+    state.fade_out = true;
+}
+
+/// The stages as a strip of boxes, visited ones lit, over a dimmed screen.
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.world.map_open {
+        return Ok(());
+    }
+
+    let dim_rect = Mesh::rectangle(
+        ctx,
+        ShapeStyle::Fill,
+        Rectangle::new(0.0, 0.0, SCREEN_WIDTH as f32, SCREEN_HEIGHT as f32),
+    )?;
+    dim_rect.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.8)),
+    );
+
+    let mut title = Text::new(&state.texts.ui.travel_map_title, state.font());
+    let width = title.get_bounds(ctx).map(|b| b.width).unwrap_or(100.0);
+    title.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new((SCREEN_WIDTH as f32 - width) / 2.0, 150.0))
+            .color(Color::WHITE),
+    );
+
+    let visited = visited(state);
+    let strip_width = STAGE_COUNT as f32 * BOX_SIZE + (STAGE_COUNT - 1) as f32 * BOX_GAP;
+    let left = (SCREEN_WIDTH as f32 - strip_width) / 2.0;
+    let top = (SCREEN_HEIGHT as f32 - BOX_SIZE) / 2.0;
+    for stage in 1..=STAGE_COUNT as u8 {
+        let x = left + (stage - 1) as f32 * (BOX_SIZE + BOX_GAP);
+        let rect = Rectangle::new(x, top, BOX_SIZE, BOX_SIZE);
+        let lit = visited & stage_bit(stage) != 0;
+
+        let fill = Mesh::rectangle(ctx, ShapeStyle::Fill, rect)?;
+        fill.render(
+            ctx,
+            DrawParams::new().color(if lit {
+                Color::rgb(0.25, 0.25, 0.3)
+            } else {
+                Color::rgb(0.08, 0.08, 0.08)
+            }),
+        );
+        let (stroke, outline_color) = if stage == state.world.map_selection {
+            (3.0, Color::rgb(1.0, 1.0, 0.0))
+        } else {
+            (1.0, Color::rgb(0.5, 0.5, 0.5))
+        };
+        let outline = Mesh::rectangle(ctx, ShapeStyle::Stroke(stroke), rect)?;
+        outline.render(ctx, DrawParams::new().color(outline_color));
+
+        let mut number = Text::new(stage.to_string(), state.font());
+        let bounds = number
+            .get_bounds(ctx)
+            .unwrap_or(Rectangle::new(0.0, 0.0, 10.0, 16.0));
+        number.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(
+                    x + (BOX_SIZE - bounds.width * 2.0) / 2.0,
+                    top + (BOX_SIZE - bounds.height * 2.0) / 2.0,
+                ))
+                .scale(Vec2::new(2.0, 2.0))
+                .color(if lit {
+                    Color::WHITE
+                } else {
+                    Color::rgb(0.3, 0.3, 0.3)
+                }),
+        );
+
+        if stage == state.world.current_stage {
+            let mut here = Text::new(&state.texts.ui.travel_here, state.font());
+            let width = here.get_bounds(ctx).map(|b| b.width).unwrap_or(80.0);
+            here.render(
+                ctx,
+                DrawParams::new()
+                    .position(Vec2::new(
+                        x + (BOX_SIZE - width) / 2.0,
+                        top + BOX_SIZE + 10.0,
+                    ))
+                    .color(Color::rgb(0.7, 0.7, 0.7)),
+            );
+        }
+    }
+
+    let mut hint = Text::new(&state.texts.ui.travel_map_hint, state.font());
+    let width = hint.get_bounds(ctx).map(|b| b.width).unwrap_or(300.0);
+    hint.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new((SCREEN_WIDTH as f32 - width) / 2.0, 450.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
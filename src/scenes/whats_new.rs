@@ -0,0 +1,144 @@
+use tetra::Context;
+use tetra::graphics::mesh::{Mesh, ShapeStyle};
+use tetra::graphics::text::Text;
+use tetra::graphics::{Color, DrawParams, Rectangle};
+use tetra::math::Vec2;
+
+use crate::changelog::{CHANGELOG, current_version};
+use crate::defs::Scene;
+use crate::game_state::GameState;
+use crate::perf::{Render, RenderMut};
+use crate::texts::TextKey;
+
+pub const VISIBLE_ROWS: usize = 14; // Also how far PageUp/PageDown jump
+const ROW_HEIGHT: f32 = 24.0;
+const PANEL: Rectangle = Rectangle {
+    x: 50.0,
+    y: 60.0,
+    width: 700.0,
+    height: 470.0,
+};
+
+pub struct WhatsNewState {
+    pub open: bool,
+    pub scroll: usize, // First visible row
+}
+
+impl WhatsNewState {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            scroll: 0,
+        }
+    }
+}
+
+pub fn open(state: &mut GameState) {
+    state.whats_new.open = true;
+    state.whats_new.scroll = 0;
+}
+
+/// Closes the panel and remembers the version, so it won't open again until
+/// the next update.
+pub fn close(state: &mut GameState) {
+    state.whats_new.open = false;
+    if state.system.global_settings.last_seen_version != current_version() {
+        state.system.global_settings.last_seen_version = current_version().to_string();
+        state.system.save_global_settings();
+    }
+}
+
+/// Scenes the panel can be shown over.
+pub fn can_open(state: &GameState) -> bool {
+    matches!(state.scene, Scene::Menu | Scene::Credits)
+}
+
+/// Version headings and bullets, one row each.
+fn rows() -> Vec<(bool, String)> {
+    let mut rows = Vec::new();
+    for entry in CHANGELOG {
+        if !rows.is_empty() {
+            rows.push((false, String::new()));
+        }
+        rows.push((true, entry.version.to_string()));
+        for change in entry.changes {
+            rows.push((false, format!("- {}", change)));
+        }
+    }
+    rows
+}
+
+/// Towards older releases.
+pub fn scroll_down(state: &mut GameState, rows_count: usize) {
+    let max = rows().len().saturating_sub(VISIBLE_ROWS);
+    state.whats_new.scroll = (state.whats_new.scroll + rows_count).min(max);
+}
+
+/// Towards the newest release.
+pub fn scroll_up(state: &mut GameState, rows_count: usize) {
+    state.whats_new.scroll = state.whats_new.scroll.saturating_sub(rows_count);
+}
+
+pub fn draw(ctx: &mut Context, state: &GameState) -> tetra::Result {
+    if !state.whats_new.open || !can_open(state) {
+        return Ok(());
+    }
+
+    // Same box as the dialogue textboxes
+    let box_rect = Mesh::rectangle(ctx, ShapeStyle::Fill, PANEL)?;
+    box_rect.render(
+        ctx,
+        DrawParams::new().color(Color::rgba(0.0, 0.0, 0.0, 0.9)),
+    );
+    let border_rect = Mesh::rectangle(ctx, ShapeStyle::Stroke(2.0), PANEL)?;
+    border_rect.render(ctx, DrawParams::new().color(Color::WHITE));
+
+    let title = state
+        .texts
+        .fill(TextKey::WhatsNewTitle, &[("version", &current_version())]);
+    let mut title_text = Text::new(title, state.font());
+    title_text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(PANEL.x + 20.0, PANEL.y + 15.0))
+            .color(Color::rgb(1.0, 1.0, 0.0)),
+    );
+
+    let rows = rows();
+    let top = PANEL.y + 55.0;
+    for (i, (heading, line)) in rows
+        .iter()
+        .skip(state.whats_new.scroll)
+        .take(VISIBLE_ROWS)
+        .enumerate()
+    {
+        let color = if *heading {
+            Color::rgb(0.4, 0.7, 1.0)
+        } else {
+            Color::WHITE
+        };
+        let mut text = Text::new(line.as_str(), state.font());
+        text.render(
+            ctx,
+            DrawParams::new()
+                .position(Vec2::new(PANEL.x + 20.0, top + i as f32 * ROW_HEIGHT))
+                .color(color),
+        );
+    }
+
+    // Only mention scrolling when there's something to scroll to
+    let hint = if rows.len() > VISIBLE_ROWS {
+        &state.texts.ui.whats_new_hint_scroll
+    } else {
+        &state.texts.ui.whats_new_hint
+    };
+    let mut hint_text = Text::new(hint.as_str(), state.font());
+    hint_text.render(
+        ctx,
+        DrawParams::new()
+            .position(Vec2::new(PANEL.x + 20.0, PANEL.bottom() - 30.0))
+            .color(Color::rgb(0.5, 0.5, 0.5)),
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+/// What happened since the player last pressed Start Game, for the card
+/// shown on the way back to the menu. Counted at the same places as the
+/// profile totals on the statistics screen, and cleared by `start`.
+#[derive(Default)]
+pub struct SessionStats {
+    pub time_played: f32,   // Seconds, counted like User::playtime
+    pub stages_visited: u8, // Bit n - 1 for stage n, like User::visited_stages
+    pub damage_taken: u32,
+    pub teblig: u32,
+    pub cihad: u32,
+    pub tekfir: u32,
+    pub items: Vec<&'static str>,
+    pub achievements: Vec<&'static str>,
+    // Game over brings the menu back through the boot screen
+    pub summary_after_boot: bool,
+}
+
+impl SessionStats {
+    pub fn start(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn visit(&mut self, stage: u8) {
+        self.stages_visited |= 1 << (stage - 1);
+    }
+
+    pub fn stage_count(&self) -> u32 {
+        self.stages_visited.count_ones()
+    }
+
+    /// Noted once, picking the same hat twice doesn't list it twice.
+    pub fn collect(&mut self, item: &'static str) {
+        if !self.items.contains(&item) {
+            self.items.push(item);
+        }
+    }
+
+    pub fn unlock(&mut self, achievement: &'static str) {
+        if !self.achievements.contains(&achievement) {
+            self.achievements.push(achievement);
+        }
+    }
+}
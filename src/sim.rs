@@ -0,0 +1,237 @@
+use rand::RngCore;
+use tetra::Context;
+use tetra::input::{self, Key};
+
+use crate::defs::Scene;
+use crate::game_state::GameState;
+
+/// Every key the gameplay steps look at. Menus and the dialogue log still
+/// read their keys through input_handler.
+const TRACKED_KEYS: [Key; 14] = [
+    Key::W,
+    Key::A,
+    Key::S,
+    Key::D,
+    Key::Up,
+    Key::Down,
+    Key::Left,
+    Key::Right,
+    Key::F,
+    Key::Q, // Action::Attack
+    Key::L,
+    Key::X,
+    Key::Z,
+    Key::Enter,
+];
+
+/// Everything a gameplay step needs from outside GameState for one tick, so
+/// the overworld and combat can be driven without a Context.
+pub struct UpdateInput<'a> {
+    pub dt: f32,
+    pub down: Vec<Key>,
+    pub pressed: Vec<Key>, // Only on the tick the key went down
+    pub rng: &'a mut dyn RngCore,
+}
+
+impl<'a> UpdateInput<'a> {
+    pub fn new(dt: f32, rng: &'a mut dyn RngCore) -> Self {
+        Self {
+            dt,
+            down: Vec::new(),
+            pressed: Vec::new(),
+            rng,
+        }
+    }
+
+    pub fn from_context(ctx: &Context, rng: &'a mut dyn RngCore) -> Self {
+        let mut update_input = Self::new(tetra::time::get_delta_time(ctx).as_secs_f32(), rng);
+        for key in TRACKED_KEYS {
+            if input::is_key_down(ctx, key) {
+                update_input.down.push(key);
+            }
+            if input::is_key_pressed(ctx, key) {
+                update_input.pressed.push(key);
+            }
+        }
+        update_input
+    }
+
+    pub fn is_down(&self, key: Key) -> bool {
+        self.down.contains(&key)
+    }
+
+    pub fn is_pressed(&self, key: Key) -> bool {
+        self.pressed.contains(&key)
+    }
+}
+
+/// Work a step needs the Context for, audio and generated textures.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Effect {
+    StartMusicBox,
+    StartEilishSong,
+    GasterStatic { play_sound: bool },
+    GrazeSound,
+    StabSound,
+}
+
+/// Where a step sends its effects. They're applied on the spot, so the rest
+/// of the step already sees a started track as playing.
+pub trait Effects {
+    fn apply(&mut self, state: &mut GameState, effect: Effect);
+}
+
+/// Headless: only records what was asked for.
+impl Effects for Vec<Effect> {
+    fn apply(&mut self, _state: &mut GameState, effect: Effect) {
+        self.push(effect);
+    }
+}
+
+/// Plays the audio and builds the textures with the frame's Context.
+struct ContextEffects<'a> {
+    ctx: &'a mut Context,
+}
+
+impl Effects for ContextEffects<'_> {
+    fn apply(&mut self, state: &mut GameState, effect: Effect) {
+        match effect {
+            Effect::StartMusicBox => crate::scenes::desktop::start_music_box(self.ctx, state),
+            Effect::StartEilishSong => crate::scenes::eilish::play_song(self.ctx, state),
+            Effect::GasterStatic { play_sound } => {
+                crate::scenes::gaster::show_static(self.ctx, state, play_sound)
+            }
+            Effect::GrazeSound => state.world.sound_queue.enqueue("Graze Tick"),
+            Effect::StabSound => state.world.sound_queue.enqueue("Stab SFX"),
+        }
+    }
+}
+
+/// One tick of whichever gameplay scene is active, without a Context. Other
+/// scenes are left untouched.
+pub fn tick(input: &mut UpdateInput, state: &mut GameState, effects: &mut dyn Effects) {
+    let scene = state.scene;
+    let hp_before = state.player.player_hp;
+    match state.scene {
+        Scene::Desktop => {
+            crate::scenes::desktop::step(input, state, effects);
+            crate::scenes::objectives::update(state, input.dt);
+            state.world.particles.update(input.dt);
+        }
+        Scene::AyasofyaInside => {
+            crate::scenes::ayasofya::step(input, state);
+            crate::scenes::objectives::update(state, input.dt);
+            state.world.particles.update(input.dt);
+        }
+        Scene::Combat => crate::scenes::combat::step(input, state, effects),
+        _ => {}
+    }
+    // Fights, cutscenes and the room change all start with a new scene
+    if state.scene != scene {
+        state.player.stop_auto_walk();
+        crate::scenes::disco::stop(&mut state.world);
+    }
+    // Every hit, hazard and chase goes through a tick, heals don't count
+    let damage = hp_before - state.player.player_hp;
+    state.session_stats.damage_taken += damage.max(0) as u32;
+}
+
+/// The tetra side of `tick`, reads this frame's input and plays whatever
+/// the step asks for as it goes.
+pub fn update(ctx: &mut Context, state: &mut GameState) {
+    let mut rng = rand::rng();
+    let mut update_input = UpdateInput::from_context(ctx, &mut rng);
+    let dt = update_input.dt;
+    tick(&mut update_input, state, &mut ContextEffects { ctx });
+    crate::audio::update(ctx, state, dt);
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use tetra::math::Vec2;
+
+    use super::*;
+    use crate::defs::{Direction, SCREEN_WIDTH};
+    use crate::objectives::WorldFlag;
+    use crate::world::Entry;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn on_stage(stage: u8) -> GameState {
+        let mut state = GameState::headless();
+        state.scene = Scene::Desktop;
+        state.world.reset_for_stage(stage);
+        crate::scenes::overworld::spawn_player(&mut state, Entry::FromLeft);
+        state
+    }
+
+    /// Runs `ticks` ticks holding `down`, with `pressed` only on the first.
+    fn run(state: &mut GameState, ticks: u32, down: &[Key], pressed: &[Key]) -> Vec<Effect> {
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut effects = Vec::new();
+        for i in 0..ticks {
+            let mut input = UpdateInput::new(DT, &mut rng);
+            input.down = down.to_vec();
+            if i == 0 {
+                input.pressed = pressed.to_vec();
+            }
+            tick(&mut input, state, &mut effects);
+        }
+        effects
+    }
+
+    #[test]
+    fn holding_right_walks_right() {
+        let mut state = on_stage(1);
+        let start = state.player.pos;
+        run(&mut state, 30, &[Key::D], &[]);
+        assert!(state.player.pos.x > start.x + 10.0);
+        assert_eq!(state.player.pos.y, start.y);
+        assert!(state.player.direction == Direction::Right);
+    }
+
+    #[test]
+    fn pushing_through_the_right_edge_enters_the_next_stage() {
+        let mut state = on_stage(1);
+        state.player.pos.x = SCREEN_WIDTH as f32 - 10.0;
+        run(&mut state, 90, &[Key::D], &[]);
+        assert_eq!(state.world.current_stage, 2);
+        assert!(state.player.pos.x < SCREEN_WIDTH as f32 / 2.0);
+        assert!(!state.fade_out);
+        assert!(WorldFlag::ReachedColdDistrict.is_set(state.world.flags));
+    }
+
+    #[test]
+    fn hard_mode_dead_space_costs_hp() {
+        let mut state = on_stage(1);
+        let stage = state.world.layout.dead_zone_stage;
+        state.world.reset_for_stage(stage);
+        state.system.hard_mode = true;
+        let hazard = state.world.hazard_def().hazards[0].rect_at(0.0);
+        state.player.pos = Vec2::new(hazard.center().x, hazard.center().y);
+        assert!(state.world.hazard_intensity(state.player.pos) > 0.0);
+
+        let hp = state.player.player_hp;
+        run(&mut state, 60, &[], &[]);
+        assert!(state.player.player_hp < hp);
+        assert_eq!(
+            state.session_stats.damage_taken,
+            (hp - state.player.player_hp) as u32
+        );
+        assert!(WorldFlag::FacedRedZone.is_set(state.world.flags));
+    }
+
+    #[test]
+    fn f_at_the_music_box_starts_it() {
+        let mut state = on_stage(1);
+        state.player.pos = state.world.musicbox_pos + Vec2::new(0.0, 60.0);
+        let effects = run(&mut state, 1, &[], &[Key::F]);
+        assert_eq!(effects, [Effect::StartMusicBox]);
+        assert!(WorldFlag::FoundMusicBox.is_set(state.world.flags));
+
+        // Headless nothing plays, so nothing else is asked for
+        assert!(run(&mut state, 30, &[], &[]).is_empty());
+    }
+}
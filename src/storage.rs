@@ -0,0 +1,114 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Writes `content` to `path` without ever leaving a half-written file behind.
+/// The previous version is kept as `<path>.bak`, the new one goes to `<path>.tmp`
+/// and is then renamed over the original.
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    if path.exists() {
+        fs::copy(path, with_suffix(path, ".bak"))?;
+    }
+    let tmp_path = with_suffix(path, ".tmp");
+    fs::write(&tmp_path, content)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Reads and parses `path`, falling back to `<path>.bak` if the primary file
+/// is missing, empty or fails to parse. The returned flag is `true` when a
+/// damaged primary file was replaced by the backup.
+pub fn read_with_backup<T>(path: &Path, parse: impl Fn(&str) -> Option<T>) -> Option<(T, bool)> {
+    let primary = fs::read_to_string(path).ok();
+    #[allow(clippy::collapsible_if)]
+    if let Some(content) = &primary {
+        if let Some(value) = parse(content) {
+            return Some((value, false));
+        }
+    }
+
+    let backup_path = with_suffix(path, ".bak");
+    let value = parse(&fs::read_to_string(&backup_path).ok()?)?;
+    if primary.is_some() {
+        log::warn!(
+            "'{}' is corrupted, restored from '{}'",
+            path.display(),
+            backup_path.display()
+        );
+        // Put the good copy back so the next save doesn't rotate the damaged file into .bak
+        let _ = fs::copy(&backup_path, path);
+    }
+    Some((value, primary.is_some()))
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+
+    /// A fresh directory under the system temp dir, unique per test.
+    pub fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("gorkitale-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn complete(content: &str) -> Option<String> {
+        content.ends_with('\n').then(|| content.to_string())
+    }
+
+    #[test]
+    fn write_keeps_the_previous_version_as_backup() {
+        let path = temp_dir("write").join("global.db");
+        write_atomic(&path, "first\n").unwrap();
+        write_atomic(&path, "second\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "second\n");
+        assert_eq!(
+            fs::read_to_string(with_suffix(&path, ".bak")).unwrap(),
+            "first\n"
+        );
+        assert!(!with_suffix(&path, ".tmp").exists());
+    }
+
+    #[test]
+    fn truncated_file_falls_back_to_the_backup() {
+        let path = temp_dir("truncated").join("global.db");
+        write_atomic(&path, "first\n").unwrap();
+        write_atomic(&path, "second\n").unwrap();
+        fs::write(&path, "sec").unwrap();
+
+        assert_eq!(
+            read_with_backup(&path, complete),
+            Some(("first\n".to_string(), true))
+        );
+        // The good copy is put back in place
+        assert_eq!(fs::read_to_string(&path).unwrap(), "first\n");
+    }
+
+    #[test]
+    fn leftover_tmp_from_an_interrupted_write_is_ignored() {
+        let path = temp_dir("leftover").join("global.db");
+        write_atomic(&path, "saved\n").unwrap();
+        fs::write(with_suffix(&path, ".tmp"), "half writ").unwrap();
+
+        assert_eq!(
+            read_with_backup(&path, complete),
+            Some(("saved\n".to_string(), false))
+        );
+        write_atomic(&path, "next\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "next\n");
+    }
+
+    #[test]
+    fn nothing_usable_returns_none() {
+        let path = temp_dir("none").join("global.db");
+        assert_eq!(read_with_backup(&path, complete), None);
+        fs::write(&path, "").unwrap();
+        assert_eq!(read_with_backup(&path, complete), None);
+    }
+}
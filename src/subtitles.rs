@@ -0,0 +1,81 @@
+use tetra::Context;
+use tetra::graphics::text::{Font, Text};
+use tetra::graphics::{Color, DrawParams};
+use tetra::math::Vec2;
+
+use crate::defs::{SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::perf::RenderMut;
+
+pub struct SubtitleCue {
+    pub start: f32,
+    pub end: f32,
+    pub text: String,
+}
+
+/// Parses a cue file: one `start,end,text` line per cue (in seconds).
+/// Empty lines and lines starting with `#` are ignored.
+pub fn parse_cues(content: &str) -> Vec<SubtitleCue> {
+    let mut cues = Vec::new();
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let parts: Vec<&str> = line.splitn(3, ',').collect();
+        if parts.len() < 3 {
+            continue;
+        }
+        if let (Ok(start), Ok(end)) = (parts[0].trim().parse(), parts[1].trim().parse()) {
+            cues.push(SubtitleCue {
+                start,
+                end,
+                text: parts[2].trim().replace("\\n", "\n"),
+            });
+        }
+    }
+    cues
+}
+
+pub fn load_cues(path: &str) -> Vec<SubtitleCue> {
+    match std::fs::read_to_string(path) {
+        Ok(content) => parse_cues(&content),
+        Err(e) => {
+            log::warn!("Could not load cues '{}': {}", path, e);
+            Vec::new()
+        }
+    }
+}
+
+pub fn active_cue(cues: &[SubtitleCue], time: f32) -> Option<&SubtitleCue> {
+    cues.iter().find(|c| time >= c.start && time < c.end)
+}
+
+/// End time of the last cue.
+pub fn cues_duration(cues: &[SubtitleCue]) -> f32 {
+    cues.iter().map(|c| c.end).fold(0.0, f32::max)
+}
+
+/// Draws a caption centered near the bottom of the screen with a black outline.
+pub fn draw_subtitle(ctx: &mut Context, font: &Font, text: &str) -> tetra::Result {
+    let mut t = Text::new(text, font.clone());
+    let bounds = t.get_bounds(ctx).unwrap_or_default();
+    let pos = Vec2::new(
+        (SCREEN_WIDTH as f32 - bounds.width) / 2.0,
+        SCREEN_HEIGHT as f32 - bounds.height - 60.0,
+    );
+
+    for offset in [
+        Vec2::new(-1.0, 0.0),
+        Vec2::new(1.0, 0.0),
+        Vec2::new(0.0, -1.0),
+        Vec2::new(0.0, 1.0),
+    ] {
+        t.render(
+            ctx,
+            DrawParams::new().position(pos + offset).color(Color::BLACK),
+        );
+    }
+    t.render(ctx, DrawParams::new().position(pos).color(Color::WHITE));
+
+    Ok(())
+}
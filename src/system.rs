@@ -1,7 +1,80 @@
+use crate::crash;
 use crate::defs::Language;
 use crate::global_db::GlobalSettings;
+use crate::paths;
+use crate::randomizer::Layout;
+use crate::storage;
+use rand::Rng;
 use tetra::Context;
 
+pub const DEATH_PANIC_CAUSE: &str = "dead_space: soul integrity lost";
+
+/// What killed the player. Picks the kernel panic report and the game over
+/// lines, and is counted per profile. Unknown gets the generic report.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeathCause {
+    DeadZone,
+    SansBone,
+    SansChase,
+    Eilish,
+    Unknown,
+}
+
+impl DeathCause {
+    pub const COUNT: usize = 5;
+    pub const ALL: [DeathCause; Self::COUNT] = [
+        DeathCause::DeadZone,
+        DeathCause::SansBone,
+        DeathCause::SansChase,
+        DeathCause::Eilish,
+        DeathCause::Unknown,
+    ];
+
+    /// Slot in `User::deaths`.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// For the statistics screen and the panic screen's floating stats.
+    pub fn label(self) -> &'static str {
+        match self {
+            DeathCause::DeadZone => "Dead space",
+            DeathCause::SansBone => "Sans",
+            DeathCause::SansChase => "Sans chase",
+            DeathCause::Eilish => "Eilish",
+            DeathCause::Unknown => "Unknown",
+        }
+    }
+
+    /// The panicking module, the oops line naming it and the symbols of
+    /// its fake call trace, innermost first. None for the generic report.
+    fn panic_report(self) -> Option<(&'static str, &'static str, [&'static str; 3])> {
+        match self {
+            DeathCause::DeadZone => Some((
+                "red_zone.ko",
+                "Oops in red_zone.ko: soul integrity lost",
+                ["soul_integrity_check", "dead_space_damage", "exposure_tick"],
+            )),
+            DeathCause::SansBone => Some((
+                "bone_handler.ko",
+                "bone_handler: divide by zero",
+                ["hp_underflow", "bone_collide", "bone_pattern_step"],
+            )),
+            DeathCause::SansChase => Some((
+                "sans_chase.ko",
+                "sans_chase: caught by a lazy skeleton",
+                ["hp_underflow", "contact_damage", "chase_steer"],
+            )),
+            DeathCause::Eilish => Some((
+                "eilish_audio.ko",
+                "eilish_audio: buffer overrun in bad_guy()",
+                ["hp_underflow", "audio_attack", "song_pattern_step"],
+            )),
+            DeathCause::Unknown => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct User {
     pub username: String,
@@ -9,60 +82,178 @@ pub struct User {
     pub cihad_count: u32,
     pub tekfir_count: u32,
     pub current_stage: u32,
+    pub exposure: f32,
+    pub sans_chase: f32, // Seconds left if saved mid-chase, 0 otherwise
+    pub flags: u32,      // objectives::WorldFlag bits
+    pub music_on: bool,  // Music box was playing when the save was made
+    pub outfit: u8,      // PlayerState::owned_outfit
+    pub playtime: f32,   // Seconds in a started session, for sorting the profile list
+    pub hardcore: bool,  // Permadeath, chosen when the profile is created
+    pub locked: bool,    // A hardcore profile that died, it can't be played again
+    pub deaths: [u32; DeathCause::COUNT], // Indexed by DeathCause::index
+    pub visited_stages: u8, // Bit n - 1 for stage n, all of them unlocks fast travel
+    pub randomizer: Option<Layout>, // Shuffled world, chosen when the profile is created
+}
+
+/// Shown on the main menu after something had to be recovered at startup,
+/// see `TextResources::restore_notice`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RestoreNotice {
+    UsersBackup,
+    SettingsBackup,
+    Crash { saved: bool }, // Whether the panic hook managed to write the snapshot
 }
 
 pub struct SystemState {
     pub language: Language,
     pub volume: f32,
+    pub hard_mode: bool,
+    pub global_settings: GlobalSettings,
     pub users: Vec<User>,
     pub current_user: Option<User>,
+    pub restore_notice: Option<RestoreNotice>,
+    pub kernel_panic_lines: Vec<String>, // Log shown on the Kernel Panic screen
+}
+
+/// Parses users.db. Returns `None` for an empty file or a malformed line,
+/// which is what a write cut short by a crash looks like.
+fn parse_users(content: &str) -> Option<Vec<User>> {
+    let mut users = Vec::new();
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let parts: Vec<&str> = line.split(',').collect();
+        if parts.len() < 4 {
+            return None;
+        }
+        // Older saves don't have the stage, exposure, chase, flag, music, outfit,
+        // playtime or hardcore fields
+        let current_stage = match parts.get(4) {
+            Some(value) => value.parse().ok()?,
+            None => 1,
+        };
+        let exposure = match parts.get(5) {
+            Some(value) => value.parse().ok()?,
+            None => 0.0,
+        };
+        let sans_chase = match parts.get(6) {
+            Some(value) => value.parse().ok()?,
+            None => 0.0,
+        };
+        let flags = match parts.get(7) {
+            Some(value) => value.parse().ok()?,
+            None => 0,
+        };
+        let music_on = match parts.get(8) {
+            Some(value) => value.parse().ok()?,
+            None => false,
+        };
+        let outfit = match parts.get(9) {
+            Some(value) => value.parse().ok()?,
+            None => 0,
+        };
+        let playtime = match parts.get(10) {
+            Some(value) => value.parse().ok()?,
+            None => 0.0,
+        };
+        let hardcore = match parts.get(11) {
+            Some(value) => value.parse().ok()?,
+            None => false,
+        };
+        let locked = match parts.get(12) {
+            Some(value) => value.parse().ok()?,
+            None => false,
+        };
+        // Counts per DeathCause, separated by semicolons
+        let mut deaths = [0; DeathCause::COUNT];
+        if let Some(value) = parts.get(13) {
+            for (slot, count) in deaths.iter_mut().zip(value.split(';')) {
+                *slot = count.parse().ok()?;
+            }
+        }
+        let visited_stages = match parts.get(14) {
+            Some(value) => value.parse().ok()?,
+            None => 0,
+        };
+        // Empty for a standard profile
+        let randomizer = match parts.get(15) {
+            Some(value) if !value.is_empty() => Some(Layout::decode(value)?),
+            _ => None,
+        };
+        users.push(User {
+            username: parts[0].to_string(),
+            teblig_count: parts[1].parse().ok()?,
+            cihad_count: parts[2].parse().ok()?,
+            tekfir_count: parts[3].parse().ok()?,
+            current_stage,
+            exposure,
+            sans_chase,
+            flags,
+            music_on,
+            outfit,
+            playtime,
+            hardcore,
+            locked,
+            deaths,
+            visited_stages,
+            randomizer,
+        });
+    }
+    if users.is_empty() {
+        return None;
+    }
+    Some(users)
 }
 
 impl SystemState {
     pub fn new(_ctx: &mut Context) -> tetra::Result<Self> {
-        let mut users = Vec::new();
-        if let Ok(content) = std::fs::read_to_string("users.db") {
-            for line in content.lines() {
-                let parts: Vec<&str> = line.split(',').collect();
-                if parts.len() >= 5 {
-                    users.push(User {
-                        username: parts[0].to_string(),
-                        teblig_count: parts[1].parse().unwrap_or(0),
-                        cihad_count: parts[2].parse().unwrap_or(0),
-                        tekfir_count: parts[3].parse().unwrap_or(0),
-                        current_stage: parts[4].parse().unwrap_or(1),
-                    });
-                } else if parts.len() >= 4 {
-                    // Backwards compatibility
-                    users.push(User {
-                        username: parts[0].to_string(),
-                        teblig_count: parts[1].parse().unwrap_or(0),
-                        cihad_count: parts[2].parse().unwrap_or(0),
-                        tekfir_count: parts[3].parse().unwrap_or(0),
-                        current_stage: 1,
-                    });
+        let mut restore_notice = None;
+
+        let users = match storage::read_with_backup(&paths::data_path("users.db"), parse_users) {
+            Some((users, restored)) => {
+                if restored {
+                    restore_notice = Some(RestoreNotice::UsersBackup);
                 }
+                users
             }
-        }
+            None => Vec::new(),
+        };
 
         let global_settings = GlobalSettings::new();
-        let language = if global_settings.language == "tr" {
-            Language::Turkish
-        } else {
-            Language::English
-        };
-        let volume = global_settings.volume as f32 / 100.0;
+        if global_settings.restored_from_backup && restore_notice.is_none() {
+            restore_notice = Some(RestoreNotice::SettingsBackup);
+        }
+        #[allow(clippy::collapsible_if)]
+        if let Some(saved) = crash::take_marker() {
+            if restore_notice.is_none() {
+                restore_notice = Some(RestoreNotice::Crash { saved });
+            }
+        }
+        Ok(Self::with_data(global_settings, users, restore_notice))
+    }
 
-        Ok(Self {
-            language,
-            volume,
+    /// Default settings and no profiles, without touching the disk.
+    pub fn in_memory() -> Self {
+        Self::with_data(GlobalSettings::default(), Vec::new(), None)
+    }
+
+    fn with_data(
+        global_settings: GlobalSettings,
+        users: Vec<User>,
+        restore_notice: Option<RestoreNotice>,
+    ) -> Self {
+        Self {
+            language: global_settings.language,
+            volume: global_settings.volume as f32 / 100.0,
+            hard_mode: global_settings.hard_mode,
+            global_settings,
             users,
             current_user: None,
-        })
+            restore_notice,
+            kernel_panic_lines: Vec::new(),
+        }
     }
 
-    pub fn save_users(&mut self) {
-        // Sync current_user back to users list
+    /// Syncs current_user back to the users list and builds users.db from it.
+    fn users_content(&mut self) -> String {
         #[allow(clippy::collapsible_if)]
         if let Some(curr) = &self.current_user {
             if let Some(u) = self.users.iter_mut().find(|u| u.username == curr.username) {
@@ -70,17 +261,76 @@ impl SystemState {
                 u.cihad_count = curr.cihad_count;
                 u.tekfir_count = curr.tekfir_count;
                 u.current_stage = curr.current_stage;
+                u.exposure = curr.exposure;
+                u.sans_chase = curr.sans_chase;
+                u.flags = curr.flags;
+                u.music_on = curr.music_on;
+                u.outfit = curr.outfit;
+                u.playtime = curr.playtime;
+                u.locked = curr.locked;
+                u.deaths = curr.deaths;
+                u.visited_stages = curr.visited_stages;
             }
         }
 
         let mut content = String::new();
         for u in &self.users {
             content.push_str(&format!(
-                "{},{},{},{},{}\n",
-                u.username, u.teblig_count, u.cihad_count, u.tekfir_count, u.current_stage
+                "{},{},{},{},{},{:.3},{:.1},{},{},{},{:.0},{},{},{},{},{}\n",
+                u.username,
+                u.teblig_count,
+                u.cihad_count,
+                u.tekfir_count,
+                u.current_stage,
+                u.exposure,
+                u.sans_chase,
+                u.flags,
+                u.music_on,
+                u.outfit,
+                u.playtime,
+                u.hardcore,
+                u.locked,
+                u.deaths.map(|count| count.to_string()).join(";"),
+                u.visited_stages,
+                u.randomizer.as_ref().map_or(String::new(), Layout::encode)
             ));
         }
-        std::fs::write("users.db", content).ok();
+        content
+    }
+
+    pub fn save_users(&mut self) {
+        let content = self.users_content();
+        crash::store_snapshot(content.clone());
+        match storage::write_atomic(&paths::data_path("users.db"), &content) {
+            Ok(()) => log::info!("Saved {} profiles to users.db", self.users.len()),
+            Err(e) => log::error!("Failed to save users.db: {}", e),
+        }
+    }
+
+    /// Refreshes the copy the panic hook saves, without touching the disk.
+    pub fn snapshot_users(&mut self) {
+        let content = self.users_content();
+        crash::store_snapshot(content);
+    }
+
+    /// Start Game plays the top profile, unless there is none or it's locked.
+    pub fn can_start(&self) -> bool {
+        self.users.first().is_some_and(|u| !u.locked)
+    }
+
+    /// Locks the current profile for good if it's a hardcore one, and saves.
+    /// Call once a death has been confirmed. Returns whether it locked.
+    pub fn lock_current_user(&mut self) -> bool {
+        let Some(user) = &mut self.current_user else {
+            return false;
+        };
+        if !user.hardcore || user.locked {
+            return false;
+        }
+        user.locked = true;
+        log::info!("Hardcore profile '{}' died, locking it", user.username);
+        self.save_users();
+        true
     }
 
     pub fn set_user_as_top(&mut self, index: usize) {
@@ -91,17 +341,200 @@ impl SystemState {
         }
     }
 
-    pub fn save_global_settings(&self) {
-        let lang_str = match self.language {
-            Language::English => "en",
-            Language::Turkish => "tr",
-        };
-        let vol_int = (self.volume * 100.0) as u32;
+    /// The Create Save rules for a profile name. `except` is the profile
+    /// being renamed, which may keep its own name.
+    pub fn check_username(&self, name: &str, except: Option<usize>) -> Result<(), String> {
+        let lower_name = name.to_lowercase();
+        if name.trim().is_empty() {
+            return Err("Name cannot be empty".to_string());
+        }
+        if !crate::profile_file::is_valid_username(name) {
+            return Err("Name has invalid characters".to_string());
+        }
+        if lower_name == "gece" || lower_name == "gecee" {
+            let warnings = [
+                "Bu isim yasaklı bölgede.",
+                "Gece çöktü, ama bu isim olmaz.",
+                "Başka bir isim dene, karanlık yolcu.",
+                "Sistem bu ismi reddediyor.",
+            ];
+            let mut rng = rand::rng();
+            return Err(warnings[rng.random_range(0..warnings.len())].to_string());
+        }
+        let taken = self
+            .users
+            .iter()
+            .enumerate()
+            .any(|(i, u)| Some(i) != except && u.username == name);
+        if taken {
+            return Err("Name already exists".to_string());
+        }
+        Ok(())
+    }
+
+    /// Renames a profile in place and saves. The playing profile follows
+    /// along, the save only matches it up by name.
+    pub fn rename_user(&mut self, index: usize, new_name: &str) -> Result<(), String> {
+        let name = new_name.trim();
+        if index >= self.users.len() {
+            return Err("Profile not found".to_string());
+        }
+        self.check_username(name, Some(index))?;
+
+        let old_name = std::mem::replace(&mut self.users[index].username, name.to_string());
+        #[allow(clippy::collapsible_if)]
+        if let Some(user) = &mut self.current_user {
+            if user.username == old_name {
+                user.username = name.to_string();
+            }
+        }
+        log::info!("Renamed profile '{}' to '{}'", old_name, name);
+        self.save_users();
+        Ok(())
+    }
 
-        let settings = GlobalSettings {
-            language: lang_str.to_string(),
-            volume: vol_int,
+    /// Adds an imported profile at the top, taking the first free "name_2",
+    /// "name_3"... if the name is already used. Returns the name it got.
+    pub fn import_user(&mut self, mut user: User) -> String {
+        let base = user.username.clone();
+        let mut suffix = 2;
+        while self.users.iter().any(|u| u.username == user.username) {
+            user.username = format!("{}_{}", base, suffix);
+            suffix += 1;
+        }
+        let username = user.username.clone();
+        self.users.insert(0, user);
+        self.save_users();
+        username
+    }
+
+    /// Counts the death on the current profile and saves. The run goes on
+    /// the leaderboard as it stood when it ended.
+    pub fn record_death(&mut self, cause: DeathCause) {
+        if let Some(user) = &mut self.current_user {
+            user.deaths[cause.index()] += 1;
+            crate::highscores::record_run(user);
+        }
+        self.save_users();
+    }
+
+    /// The kernel log for a player death, worded after what killed them.
+    pub fn generate_kernel_panic(&mut self, user: &Option<User>, cause: DeathCause) {
+        match cause.panic_report() {
+            Some((module, oops, trace)) => {
+                self.write_kernel_panic(user, oops, Some((module, trace)))
+            }
+            None => self.write_kernel_panic(user, DEATH_PANIC_CAUSE, None),
+        }
+    }
+
+    /// The generic kernel log with `message` as the first line, for errors.
+    pub fn generate_error_panic(&mut self, user: &Option<User>, message: &str) {
+        self.write_kernel_panic(user, message, None);
+    }
+
+    /// Builds the fake kernel log for the game over screen. The user's
+    /// stats are baked in now so the report matches the run that died.
+    /// `cause` is the first log line, what actually brought the system down.
+    /// `report` adds the module's RIP and call trace.
+    fn write_kernel_panic(
+        &mut self,
+        user: &Option<User>,
+        cause: &str,
+        report: Option<(&str, [&str; 3])>,
+    ) {
+        let mut rng = rand::rng();
+        let mut time = rng.random_range(10.0..100.0);
+        let mut timestamp = || {
+            time += rng.random_range(0.0001..0.5);
+            format!("[{:>12.6}]", time)
         };
-        settings.save();
+
+        let (username, teblig, cihad, tekfir, stage) =
+            user.as_ref()
+                .map_or(("unknown".to_string(), 0, 0, 0, 1), |u| {
+                    (
+                        u.username.clone(),
+                        u.teblig_count,
+                        u.cihad_count,
+                        u.tekfir_count,
+                        u.current_stage,
+                    )
+                });
+
+        let mut lines = vec![
+            format!("{} {}", timestamp(), cause),
+            format!(
+                "{} CPU: 0 PID: 1 Comm: gorkitale User: {}",
+                timestamp(),
+                username
+            ),
+        ];
+        if let Some((module, trace)) = report {
+            // Own generator, the timestamps hold on to the other one
+            let mut symbol_rng = rand::rng();
+            let mut symbol = |name: &str| {
+                let len: u32 = symbol_rng.random_range(0x80..0x400);
+                let offset = symbol_rng.random_range(0..len);
+                format!("{}+0x{:x}/0x{:x} [{}]", name, offset, len, module)
+            };
+            let rip = symbol(trace[0]);
+            let frames: Vec<String> = trace.iter().map(|name| symbol(name)).collect();
+            lines.push(format!("{} RIP: {}", timestamp(), rip));
+            lines.push(format!("{} Call Trace:", timestamp()));
+            for frame in frames {
+                lines.push(format!("{}  {}", timestamp(), frame));
+            }
+        }
+        lines.push(format!(
+            "{} user_data: teblig={} cihad={} tekfir={} stage={}",
+            timestamp(),
+            teblig,
+            cihad,
+            tekfir,
+            stage
+        ));
+        lines.push(match report {
+            Some((module, _)) => format!(
+                "{} Kernel panic - not syncing: Fatal exception in {}",
+                timestamp(),
+                module
+            ),
+            None => format!(
+                "{} Kernel panic - not syncing: Attempted to kill init!",
+                timestamp()
+            ),
+        });
+        self.kernel_panic_lines = lines;
+    }
+
+    pub fn save_global_settings(&mut self) {
+        // Update the loaded settings so fields we don't mirror survive the save
+        self.global_settings.language = self.language;
+        self.global_settings.volume = (self.volume * 100.0).round() as u32;
+        self.global_settings.hard_mode = self.hard_mode;
+        self.global_settings.clamp();
+        self.global_settings.save();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncated_users_db_is_restored_from_backup() {
+        let path = storage::tests::temp_dir("users").join("users.db");
+        storage::write_atomic(&path, "alice,1,0,0,2\n").unwrap();
+        storage::write_atomic(&path, "alice,3,0,1,3\nbob,0,0,0,1\n").unwrap();
+        // A crash mid-write cut the second line short
+        std::fs::write(&path, "alice,3,0,1,3\nbob,0").unwrap();
+
+        let (users, restored) = storage::read_with_backup(&path, parse_users).unwrap();
+        assert!(restored);
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].username, "alice");
+        assert_eq!(users[0].teblig_count, 1);
+        assert_eq!(users[0].current_stage, 2);
     }
 }
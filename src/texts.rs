@@ -1,8 +1,57 @@
+use std::fmt::Display;
+
+use crate::defs::Scene;
+use crate::objectives::ObjectiveId;
+use crate::system::{DeathCause, RestoreNotice};
+
 pub struct TextResources {
     pub combat_actions: CombatActionTexts,
     pub gaster_dialogues: Vec<String>,
+    pub gaster_idle_lines: Vec<String>, // Muttered to nobody in particular
+    pub gaster_dialogues_post_tekfir: Vec<String>, // Once the profile has a tekfir
+    pub gaster_apologize: String,
+    pub gaster_apology_reply: String,
+    pub gaster_leave: String,
+    pub gaster_hat_remark: String, // The first time he sees the player in a hat
+    pub gaster_bone_bounce: String, // A thrown bone bounced off him
+    pub sans_dialogues: SansDialogues,
+    pub sans_greeting_lines: SansGreetingLines,
+    pub sans_combat_lines: SansCombatLines,
+    pub crowd_barks: Vec<String>, // One-liners from the stage 3 crowd
+    pub survivor_help: String,    // The escort out of the dead space starts
+    pub survivor_collapsed: String,
+    pub survivor_thanks: Vec<String>, // In stage 3, after the rescue
     pub game_over_messages: GameOverMessages,
+    pub objectives: ObjectiveTexts,
     pub ui: UiTexts,
+    pub presence: PresenceTexts,
+}
+
+pub struct ObjectiveTexts {
+    pub header: String,
+    pub all_done: String,
+    pub find_music_box: String,
+    pub talk_to_skeleton: String,
+    pub find_music_box_randomized: String, // Randomizer profiles, it's not in stage 1
+    pub find_skeleton_randomized: String,
+    pub reach_cold_district: String,
+    pub enter_ayasofya: String,
+    pub confront_red_zone: String,
+}
+
+impl ObjectiveTexts {
+    /// Randomizer profiles get vaguer wording for the NPCs that moved.
+    pub fn title(&self, id: ObjectiveId, randomized: bool) -> &str {
+        match id {
+            ObjectiveId::FindMusicBox if randomized => &self.find_music_box_randomized,
+            ObjectiveId::TalkToSkeleton if randomized => &self.find_skeleton_randomized,
+            ObjectiveId::FindMusicBox => &self.find_music_box,
+            ObjectiveId::TalkToSkeleton => &self.talk_to_skeleton,
+            ObjectiveId::ReachColdDistrict => &self.reach_cold_district,
+            ObjectiveId::EnterAyasofya => &self.enter_ayasofya,
+            ObjectiveId::ConfrontRedZone => &self.confront_red_zone,
+        }
+    }
 }
 
 pub struct CombatActionTexts {
@@ -13,22 +62,195 @@ pub struct CombatActionTexts {
     pub deccal: Vec<String>,
     pub ebu_cehil: Vec<String>,
     pub yecuc: Vec<String>,
+    pub cant_escape: String, // A failed flee
+}
+
+pub struct SansDialogues {
+    pub first_meeting: String,
+    pub after_handshake: Vec<String>, // Used once teblig_count > 0
+    pub shake_hands: String,
+    pub fight: String,
+}
+
+/// Sans sizing the player up before his greeting, one bucket per HP tier.
+pub struct SansGreetingLines {
+    pub confident: Vec<String>, // HP above 15
+    pub hurt: Vec<String>,      // HP 10-15
+    pub critical: Vec<String>,  // HP below 10
+}
+
+/// Said between turns of the Sans fight, queued as his phases change.
+pub struct SansCombatLines {
+    pub sans_phase1_lines: Vec<String>, // Queued when the fight starts
+    pub sans_phase2_lines: Vec<String>,
+    pub sans_phase3_lines: Vec<String>,
+    pub sans_spare_line: String, // After a Tebliğ
+}
+
+impl SansCombatLines {
+    pub fn for_phase(&self, phase: u32) -> &[String] {
+        match phase {
+            1 => &self.sans_phase1_lines,
+            2 => &self.sans_phase2_lines,
+            _ => &self.sans_phase3_lines,
+        }
+    }
 }
 
 pub struct GameOverMessages {
     pub teblig_high: Vec<String>,
     pub tekfir_high: Vec<String>,
     pub equal: Vec<String>,
+    // About what killed the player, mixed in with the lines above
+    pub dead_zone: Vec<String>,
+    pub sans: Vec<String>,
+    pub eilish: Vec<String>,
+}
+
+impl GameOverMessages {
+    /// None for an unknown cause, which only gets the stat based lines.
+    pub fn for_cause(&self, cause: DeathCause) -> Option<&Vec<String>> {
+        match cause {
+            DeathCause::DeadZone => Some(&self.dead_zone),
+            DeathCause::SansBone | DeathCause::SansChase => Some(&self.sans),
+            DeathCause::Eilish => Some(&self.eilish),
+            DeathCause::Unknown => None,
+        }
+    }
 }
 
 pub struct UiTexts {
     pub game_over_title: String,
     pub return_menu: String,
     pub quit_game: String,
-    pub user_label: String,
+    pub export_report: String,
+    pub report_exported: String, // Followed by the log path
+    pub report_failed: String,
+    pub user_line: String, // {user}
+    pub unknown_user: String,
+    pub stage_indicator: String, // {stage_current}, {stage_total}
+    pub hp_label: String,        // {hp}, {max_hp}
+    pub dialogue_log_title: String,
+    pub dialogue_log_empty: String,
+    pub dialogue_log_hint: String,
+    pub nothing_that_way: String,
+    pub whats_new_title: String, // {version}
+    pub whats_new_hint: String,
+    pub whats_new_hint_scroll: String,
+    pub credits_hint: String,
+    pub credits_done_hint: String,
+    pub tooltip_health: String,
+    pub tooltip_stage: String,
+    pub tooltip_fps: String,
+    pub objective_complete: String, // {objective}
+    pub wardrobe_prompt: String,    // {outfit}
+    pub outfit_names: Vec<String>,  // Indexed by PlayerState::outfit
+    pub bone_scold: String,         // Trying to throw a bone inside Ayasofya
+    pub bone_achievement: String,
+    pub profile_limit: String, // {max}
+    pub gas_mask_received: String,
+    pub users_restored: String, // Restore notices on the main menu
+    pub settings_restored: String,
+    pub crash_recovered: String, // {seconds}, how old the snapshot can be
+    pub crash_unsaved: String,
+    pub combat_spared: String,   // Banner after a pacifist fight
+    pub combat_defeated: String, // Banner after bringing the enemy down
+    pub travel_map_title: String,
+    pub travel_map_hint: String,
+    pub travel_here: String, // Under the current stage on the map
+    pub travel_locked: String,
+    pub travel_unlocked: String,
+    pub travel_blocked_ayasofya: String,
+    pub travel_blocked_chase: String,
+    pub travel_blocked_exposure: String,
+    pub tutorial_move: String, // Combat tutorial cards, see scenes::combat_tutorial
+    pub tutorial_bones: String,
+    pub tutorial_gravity: String, // Only on a turn where the soul falls
+    pub tutorial_turn: String,
+    pub tutorial_hint: String,
+}
+
+/// Discord presence, the details line and the state line under it for
+/// every scene. The desktop state takes {stage_current} and {stage_total}.
+pub struct PresenceTexts {
+    pub boot: PresenceLine,
+    pub menu: PresenceLine,
+    pub profile: String, // Menu state once a profile is picked, {username}
+    pub transition_to_desktop: PresenceLine,
+    pub desktop: PresenceLine,
+    pub combat_transition: PresenceLine,
+    pub combat: PresenceLine,
+    pub kernel_panic: PresenceLine,
+    pub ayasofya_inside: PresenceLine,
+    pub credits: PresenceLine,
+    pub statistics: PresenceLine,
+    pub session_summary: PresenceLine,
+}
+
+impl PresenceTexts {
+    pub fn line(&self, scene: Scene) -> &PresenceLine {
+        match scene {
+            Scene::Boot => &self.boot,
+            Scene::Menu => &self.menu,
+            Scene::TransitionToDesktop => &self.transition_to_desktop,
+            Scene::Desktop => &self.desktop,
+            Scene::CombatTransition => &self.combat_transition,
+            Scene::Combat => &self.combat,
+            Scene::KernelPanic => &self.kernel_panic,
+            Scene::AyasofyaInside => &self.ayasofya_inside,
+            Scene::Credits => &self.credits,
+            Scene::Statistics => &self.statistics,
+            Scene::SessionSummary => &self.session_summary,
+        }
+    }
+}
+
+pub struct PresenceLine {
+    pub details: String,
+    pub state: String,
+}
+
+impl PresenceLine {
+    fn new(details: &str, state: &str) -> Self {
+        Self {
+            details: details.to_string(),
+            state: state.to_string(),
+        }
+    }
 }
 
 impl TextResources {
+    /// Renders the template for `key`, replacing each `{name}` with the
+    /// matching argument so translations can order them however they need.
+    pub fn fill(&self, key: TextKey, args: &[(&str, &dyn Display)]) -> String {
+        let template = match key {
+            TextKey::UserLine => &self.ui.user_line,
+            TextKey::StageIndicator => &self.ui.stage_indicator,
+            TextKey::HpLabel => &self.ui.hp_label,
+            TextKey::WhatsNewTitle => &self.ui.whats_new_title,
+            TextKey::ObjectiveComplete => &self.ui.objective_complete,
+            TextKey::WardrobePrompt => &self.ui.wardrobe_prompt,
+            TextKey::ProfileLimit => &self.ui.profile_limit,
+            TextKey::CrashRecovered => &self.ui.crash_recovered,
+            TextKey::PresenceDetails(scene) => &self.presence.line(scene).details,
+            TextKey::PresenceState(scene) => &self.presence.line(scene).state,
+            TextKey::PresenceProfile => &self.presence.profile,
+        };
+        interpolate(template, args)
+    }
+
+    pub fn restore_notice(&self, notice: RestoreNotice) -> String {
+        match notice {
+            RestoreNotice::UsersBackup => self.ui.users_restored.clone(),
+            RestoreNotice::SettingsBackup => self.ui.settings_restored.clone(),
+            RestoreNotice::Crash { saved: true } => self.fill(
+                TextKey::CrashRecovered,
+                &[("seconds", &crate::crash::SNAPSHOT_SECONDS)],
+            ),
+            RestoreNotice::Crash { saved: false } => self.ui.crash_unsaved.clone(),
+        }
+    }
+
     pub fn new_turkish() -> Self {
         Self {
             combat_actions: CombatActionTexts {
@@ -67,12 +289,80 @@ impl TextResources {
                     "Ona Yecüc dedin.\nDuvarı kemirmeye başladı.".to_string(),
                     "Ona Yecüc dedin.\nSürü halinde saldırdı.".to_string(),
                 ],
+                cant_escape: "Kaçamazsın!".to_string(),
             },
             gaster_dialogues: vec![
                 "çakar çakmaz çakan çakmak...".to_string(),
                 "bir berber bir berbere...".to_string(),
                 "şu köşe yaz köşesi...".to_string(),
             ],
+            gaster_idle_lines: vec![
+                "dal sarkar kartal kalkar...".to_string(),
+                "şu yoğurdu sarımsaklasak da mı saklasak...".to_string(),
+                "kırk küp, kırkının da kulpu kırık...".to_string(),
+                "karanlık... daha da karanlık...".to_string(),
+                "ölçümler yine tutmuyor.".to_string(),
+                "on yedinci kayıt...".to_string(),
+                "gölgeler derinleşiyor.".to_string(),
+                "orada çok durma.".to_string(),
+                "hala buradayım. parça parça.".to_string(),
+                "çekirdek uğulduyor... dinle.".to_string(),
+            ],
+            gaster_dialogues_post_tekfir: vec![
+                "birine kafir dedin.\nkayıtlarım bunu unutmaz.".to_string(),
+                "elindeki leke... ölçümlerime bile bulaştı.".to_string(),
+                "karanlık seni tanıyor artık.\nben de.".to_string(),
+            ],
+            gaster_apologize: "Özür dile".to_string(),
+            gaster_apology_reply: "özür...\nkayda geçirdim. silinmeyecek.".to_string(),
+            gaster_leave: "...".to_string(),
+            gaster_hat_remark: "o başlık...\nölçümlerime uymuyor.".to_string(),
+            gaster_bone_bounce: "...bir kemik.\nhiç bilimsel değil.".to_string(),
+            sans_dialogues: SansDialogues {
+                first_meeting: "heh. yeni misin buralarda?\nel sıkışalım mı, yoksa...".to_string(),
+                after_handshake: vec![
+                    "yine sen. elin hala sıcak mı?".to_string(),
+                    "geçen sefer iyi sıkıştın. tekrar?".to_string(),
+                    "seni hatırlıyorum. osuruk yastığını unutmak zor.".to_string(),
+                ],
+                shake_hands: "El sıkış".to_string(),
+                fight: "Savaş".to_string(),
+            },
+            sans_greeting_lines: SansGreetingLines {
+                confident: vec!["Kendinden epey eminsin.".to_string()],
+                hurt: vec!["Pek iyi görünmüyorsun.".to_string()],
+                critical: vec!["Zar zor ayaktasın. Bu çabuk biter.".to_string()],
+            },
+            sans_combat_lines: SansCombatLines {
+                sans_phase1_lines: vec![
+                    "acele etme, evlat. günümüz uzun.".to_string(),
+                    "ketçap molası verebiliriz istersen.".to_string(),
+                ],
+                sans_phase2_lines: vec![
+                    "heh. bu kadar ciddi olacağını sanmıyordum.".to_string(),
+                    "tamam, tamam. artık şaka yapmıyorum.".to_string(),
+                ],
+                sans_phase3_lines: vec![
+                    "ikimiz de yorulduk, değil mi?".to_string(),
+                    "bırakmayacaksın, değil mi? ben de.".to_string(),
+                ],
+                sans_spare_line: "...bunu duymak hoşuma gitti, evlat.".to_string(),
+            },
+            crowd_barks: vec![
+                "Simit! Taze simit!".to_string(),
+                "Kuyruk yine kapıya kadar...".to_string(),
+                "Müze miydi, cami mi? Karıştırdım.".to_string(),
+                "Ayakkabıları çıkarmayı unutma.".to_string(),
+                "Martılar simidimi çaldı.".to_string(),
+                "Bir fotoğraf çeker misin?".to_string(),
+            ],
+            survivor_help: "Biri mi var...? Beni buradan çıkar, lütfen.".to_string(),
+            survivor_collapsed: "Olmuyor... nefes alamıyorum...".to_string(),
+            survivor_thanks: vec![
+                "Sen olmasan hâlâ orada yatıyordum.".to_string(),
+                "Maskeyi iyi kullan, bana artık lazım değil.".to_string(),
+                "Burada hava daha temiz. Sağ ol.".to_string(),
+            ],
             game_over_messages: GameOverMessages {
                 teblig_high: vec![
                     "Tebliğin yetersiz kaldı...".to_string(),
@@ -93,12 +383,106 @@ impl TextResources {
                     "Ortada kaldın, arafta yandın.".to_string(),
                     "Skill issue.".to_string(),
                 ],
+                dead_zone: vec![
+                    "Ölü bölge seni yuttu.".to_string(),
+                    "Kırmızı bölgede fazla oyalandın.".to_string(),
+                    "Ruhun o havayı kaldıramadı.".to_string(),
+                ],
+                sans: vec![
+                    "Kemiklerden kaçamadın.".to_string(),
+                    "Kötü bir zaman geçirdin.".to_string(),
+                    "İskelet bile senden hızlıydı.".to_string(),
+                ],
+                eilish: vec![
+                    "Şarkı seni bitirdi.".to_string(),
+                    "Duh.".to_string(),
+                    "Fısıltı çığlığa dönüştü.".to_string(),
+                ],
+            },
+            objectives: ObjectiveTexts {
+                header: "Görevler".to_string(),
+                all_done: "Keşfetmeye devam et".to_string(),
+                find_music_box: "Müzik kutusunu bul".to_string(),
+                talk_to_skeleton: "İskeletle konuş".to_string(),
+                find_music_box_randomized: "Müzik kutusunu bul, yeri değişmiş".to_string(),
+                find_skeleton_randomized: "İskeleti bul — buralarda bir yerde".to_string(),
+                reach_cold_district: "Soğuk mahalleye ulaş".to_string(),
+                enter_ayasofya: "Ayasofya'ya gir".to_string(),
+                confront_red_zone: "Kırmızı bölgedeki sesle yüzleş".to_string(),
             },
             ui: UiTexts {
                 game_over_title: "OYUN BİTTİ".to_string(),
                 return_menu: "Menüye Dön".to_string(),
                 quit_game: "Oyundan Çık".to_string(),
-                user_label: "Kullanıcı: ".to_string(),
+                export_report: "Raporu Dışa Aktar".to_string(),
+                report_exported: "Rapor kaydedildi: ".to_string(),
+                report_failed: "Rapor kaydedilemedi.".to_string(),
+                user_line: "Kullanıcı: {user}".to_string(),
+                unknown_user: "Bilinmiyor".to_string(),
+                stage_indicator: "Aşama: {stage_current}/{stage_total}".to_string(),
+                hp_label: "Can: {hp}/{max_hp}".to_string(),
+                dialogue_log_title: "Konuşma Geçmişi".to_string(),
+                dialogue_log_empty: "Henüz kimseyle konuşmadın.".to_string(),
+                dialogue_log_hint: "Yukarı/Aşağı, PgUp/PgDn: Kaydır   L/Esc: Kapat".to_string(),
+                nothing_that_way: "O tarafta bir şey yok.".to_string(),
+                whats_new_title: "{version} sürümünde neler yeni".to_string(),
+                whats_new_hint: "Enter/Esc: Kapat".to_string(),
+                whats_new_hint_scroll: "Yukarı/Aşağı: Kaydır   Enter/Esc: Kapat".to_string(),
+                credits_hint: "Esc: Geç   Enter: Neler yeni".to_string(),
+                credits_done_hint: "Menüye dönmek için bir tuşa bas".to_string(),
+                tooltip_health: "Oyuncu Canı".to_string(),
+                tooltip_stage: "Şu Anki Aşama / Toplam Aşama".to_string(),
+                tooltip_fps: "Saniyedeki Kare Sayısı".to_string(),
+                objective_complete: "Görev tamamlandı: {objective}".to_string(),
+                wardrobe_prompt: "F: Gardırop ({outfit})".to_string(),
+                outfit_names: vec![
+                    "Şapkasız".to_string(),
+                    "Fes".to_string(),
+                    "Takke".to_string(),
+                ],
+                bone_scold: "Burada olmaz. Biraz saygı.".to_string(),
+                bone_achievement: "Başarım: Kemik Kafa (üst üste 10 ıska)".to_string(),
+                profile_limit: "En fazla {max} profil olabilir".to_string(),
+                gas_mask_received: "Gaz maskesi aldın, maruziyet yarı hızda dolacak".to_string(),
+                users_restored: "users.db bozulmuştu, profiller yedekten geri yüklendi."
+                    .to_string(),
+                settings_restored: "global.db bozulmuştu, ayarlar yedekten geri yüklendi."
+                    .to_string(),
+                crash_recovered: "Oyun bir çökmeden kurtarıldı, ilerleme en fazla {seconds} \
+                                  saniye önceki son anlık kayıttan geri yüklendi."
+                    .to_string(),
+                crash_unsaved:
+                    "Oyun bir çökmeden kurtarıldı, son kayıttan sonraki ilerleme kayboldu."
+                        .to_string(),
+                combat_spared: "Bağışlandı!".to_string(),
+                combat_defeated: "Yenildi!".to_string(),
+                travel_map_title: "Hızlı Seyahat".to_string(),
+                travel_map_hint: "Sol/Sağ: Seç   Enter: Git   M: Kapat".to_string(),
+                travel_here: "Buradasın".to_string(),
+                travel_locked: "Hızlı seyahat için önce her aşamayı gez".to_string(),
+                travel_unlocked: "Hızlı seyahat açıldı, haritayı M ile aç".to_string(),
+                travel_blocked_ayasofya: "Ayasofya'nın içinden olmaz".to_string(),
+                travel_blocked_chase: "Sans peşindeyken bir yere gidemezsin".to_string(),
+                travel_blocked_exposure: "Önce maruziyetin geçmesini bekle".to_string(),
+                tutorial_move: "* Kırmızı kalp senin ruhun.\n* Ok tuşlarıyla kutunun içinde gezdir.".to_string(),
+                tutorial_bones: "* Beyaz kemiklere değme, canını götürürler.\n* Yanlarından sıyrılmak TP doldurur.".to_string(),
+                tutorial_gravity: "* Bu turda ruhun ağır, aşağı düşüyor.\n* Yukarı ile zıpla, Aşağı ile hızlı in.".to_string(),
+                tutorial_turn: "* Sıra sana gelince Sol/Sağ ile seç:\n* CİHAD, TEKFİR, ITEM, TEBLİĞ ya da TP.".to_string(),
+                tutorial_hint: "Z / Enter: Devam".to_string(),
+            },
+            presence: PresenceTexts {
+                boot: PresenceLine::new("Açılıyor...", "Sistem Başlatılıyor"),
+                menu: PresenceLine::new("Ana Menüde", "Seçim Yapıyor"),
+                profile: "Profil: {username}".to_string(),
+                transition_to_desktop: PresenceLine::new("Masaüstü Yükleniyor...", "Lütfen bekleyin..."),
+                desktop: PresenceLine::new("Masaüstünü Geziyor", "Aşama {stage_current}/{stage_total}"),
+                combat_transition: PresenceLine::new("Karşılaşma!", "Savaşa hazırlanıyor"),
+                combat: PresenceLine::new("Savaşıyor!", "Çatışmada"),
+                kernel_panic: PresenceLine::new("Sistem Çöktü!", "Kritik Hata"),
+                ayasofya_inside: PresenceLine::new("Ayasofya'yı Ziyaret Ediyor", "Namaz kılıyor"),
+                credits: PresenceLine::new("Jeneriği İzliyor", "Jenerik akıyor"),
+                statistics: PresenceLine::new("İstatistiklere Bakıyor", "Sevapları sayıyor"),
+                session_summary: PresenceLine::new("Hesap Kapatıyor", "Oturuma geri bakıyor"),
             },
         }
     }
@@ -141,12 +525,80 @@ impl TextResources {
                     "You called him Gog.\nHe started gnawing the wall.".to_string(),
                     "You called him Gog.\nHe attacked in a swarm.".to_string(),
                 ],
+                cant_escape: "Can't escape!".to_string(),
             },
             gaster_dialogues: vec![
                 "dark darker yet darker...".to_string(),
                 "the man who speaks in hands...".to_string(),
                 "beware the man who came from the other world...".to_string(),
             ],
+            gaster_idle_lines: vec![
+                "the readings... are off again.".to_string(),
+                "entry number seventeen...".to_string(),
+                "...hmm.".to_string(),
+                "the shadows cut deeper.".to_string(),
+                "photon readings negative.".to_string(),
+                "do not stand there for long.".to_string(),
+                "i am still here. in pieces.".to_string(),
+                "this next experiment... seems very interesting.".to_string(),
+                "who let you in...".to_string(),
+                "the core hums... listen.".to_string(),
+            ],
+            gaster_dialogues_post_tekfir: vec![
+                "you called someone a kafir.\nmy records do not forget.".to_string(),
+                "that stain on your hands...\nit is in my readings too.".to_string(),
+                "the dark knows you now.\nso do i.".to_string(),
+            ],
+            gaster_apologize: "Apologize".to_string(),
+            gaster_apology_reply: "an apology...\nnoted. it will not be erased.".to_string(),
+            gaster_leave: "...".to_string(),
+            gaster_hat_remark: "that headwear...\nit does not fit my measurements.".to_string(),
+            gaster_bone_bounce: "...a bone.\nhow unscientific.".to_string(),
+            sans_dialogues: SansDialogues {
+                first_meeting: "heh. new around here?\nwanna shake hands, or...".to_string(),
+                after_handshake: vec![
+                    "oh, it's you again. hand still warm?".to_string(),
+                    "good grip last time. once more?".to_string(),
+                    "i remember you. hard to forget a whoopee cushion.".to_string(),
+                ],
+                shake_hands: "Shake hands".to_string(),
+                fight: "Fight".to_string(),
+            },
+            sans_greeting_lines: SansGreetingLines {
+                confident: vec!["You look pretty confident.".to_string()],
+                hurt: vec!["You don't look so good.".to_string()],
+                critical: vec!["You're barely standing. This'll be quick.".to_string()],
+            },
+            sans_combat_lines: SansCombatLines {
+                sans_phase1_lines: vec![
+                    "take your time, kid. we've got all day.".to_string(),
+                    "we could take a ketchup break if you want.".to_string(),
+                ],
+                sans_phase2_lines: vec![
+                    "heh. didn't think you'd take this so seriously.".to_string(),
+                    "ok, ok. no more jokes.".to_string(),
+                ],
+                sans_phase3_lines: vec![
+                    "we're both getting tired, huh?".to_string(),
+                    "you're not gonna stop, are you? me neither.".to_string(),
+                ],
+                sans_spare_line: "...that's nice to hear, kid.".to_string(),
+            },
+            crowd_barks: vec![
+                "Simit! Fresh simit!".to_string(),
+                "The line goes all the way to the door again...".to_string(),
+                "Museum or mosque? I lost track.".to_string(),
+                "Don't forget to take your shoes off.".to_string(),
+                "The seagulls stole my simit.".to_string(),
+                "Could you take a photo of us?".to_string(),
+            ],
+            survivor_help: "Is someone there...? Get me out of here, please.".to_string(),
+            survivor_collapsed: "I can't... can't breathe...".to_string(),
+            survivor_thanks: vec![
+                "I'd still be lying out there if it weren't for you.".to_string(),
+                "Put that mask to good use, I won't need it.".to_string(),
+                "The air's cleaner here. Thank you.".to_string(),
+            ],
             game_over_messages: GameOverMessages {
                 teblig_high: vec![
                     "Your preaching was insufficient...".to_string(),
@@ -167,13 +619,213 @@ impl TextResources {
                     "Stuck in the middle, burned in limbo.".to_string(),
                     "Skill issue.".to_string(),
                 ],
+                dead_zone: vec![
+                    "The dead space swallowed you.".to_string(),
+                    "You lingered in the red zone too long.".to_string(),
+                    "Your soul couldn't take the air.".to_string(),
+                ],
+                sans: vec![
+                    "You couldn't dodge the bones.".to_string(),
+                    "You had a bad time.".to_string(),
+                    "Even a lazy skeleton was faster.".to_string(),
+                ],
+                eilish: vec![
+                    "The song finished you.".to_string(),
+                    "Duh.".to_string(),
+                    "The whisper turned into a scream.".to_string(),
+                ],
+            },
+            objectives: ObjectiveTexts {
+                header: "Objectives".to_string(),
+                all_done: "Keep exploring".to_string(),
+                find_music_box: "Find the music box".to_string(),
+                talk_to_skeleton: "Talk to the skeleton".to_string(),
+                find_music_box_randomized: "Find the music box, it's been moved".to_string(),
+                find_skeleton_randomized: "Find the skeleton — he's around here somewhere"
+                    .to_string(),
+                reach_cold_district: "Reach the cold district".to_string(),
+                enter_ayasofya: "Enter Ayasofya".to_string(),
+                confront_red_zone: "Confront the voice in the red zone".to_string(),
             },
             ui: UiTexts {
                 game_over_title: "GAME OVER".to_string(),
                 return_menu: "Return to Menu".to_string(),
                 quit_game: "Quit Game".to_string(),
-                user_label: "User: ".to_string(),
+                export_report: "Export Report".to_string(),
+                report_exported: "Report saved to ".to_string(),
+                report_failed: "Could not save the report.".to_string(),
+                user_line: "User: {user}".to_string(),
+                unknown_user: "Unknown".to_string(),
+                stage_indicator: "Stage: {stage_current}/{stage_total}".to_string(),
+                hp_label: "HP: {hp}/{max_hp}".to_string(),
+                dialogue_log_title: "Dialogue Log".to_string(),
+                dialogue_log_empty: "Nobody has said anything yet.".to_string(),
+                dialogue_log_hint: "Up/Down, PgUp/PgDn: Scroll   L/Esc: Close".to_string(),
+                nothing_that_way: "There's nothing that way.".to_string(),
+                whats_new_title: "What's new in {version}".to_string(),
+                whats_new_hint: "Enter/Esc: Close".to_string(),
+                whats_new_hint_scroll: "Up/Down: Scroll   Enter/Esc: Close".to_string(),
+                credits_hint: "Press Esc to skip, Enter for what's new".to_string(),
+                credits_done_hint: "Press any key to return".to_string(),
+                tooltip_health: "Player Health".to_string(),
+                tooltip_stage: "Current Stage / Total Stages".to_string(),
+                tooltip_fps: "Frames Per Second".to_string(),
+                objective_complete: "Objective complete: {objective}".to_string(),
+                wardrobe_prompt: "F: Wardrobe ({outfit})".to_string(),
+                outfit_names: vec!["No hat".to_string(), "Fes".to_string(), "Takke".to_string()],
+                bone_scold: "Not in here. Have some respect.".to_string(),
+                bone_achievement: "Achievement: Bad to the Bone (10 misses in a row)".to_string(),
+                profile_limit: "Profile limit reached ({max})".to_string(),
+                gas_mask_received: "Got a gas mask, exposure fills half as fast".to_string(),
+                users_restored: "users.db was damaged, profiles restored from backup.".to_string(),
+                settings_restored: "global.db was damaged, settings restored from backup."
+                    .to_string(),
+                crash_recovered: "The game recovered from a crash, progress was restored from \
+                                  the last snapshot (up to {seconds} seconds old)."
+                    .to_string(),
+                crash_unsaved:
+                    "The game recovered from a crash, progress since the last save was lost."
+                        .to_string(),
+                combat_spared: "Spared!".to_string(),
+                combat_defeated: "Defeated!".to_string(),
+                travel_map_title: "Fast Travel".to_string(),
+                travel_map_hint: "Left/Right: Choose   Enter: Travel   M: Close".to_string(),
+                travel_here: "You are here".to_string(),
+                travel_locked: "Visit every stage to unlock fast travel".to_string(),
+                travel_unlocked: "Fast travel unlocked, press M for the map".to_string(),
+                travel_blocked_ayasofya: "Not from inside Ayasofya".to_string(),
+                travel_blocked_chase: "Not with Sans on your tail".to_string(),
+                travel_blocked_exposure: "Wait for the exposure to wear off".to_string(),
+                tutorial_move: "* The red heart is your soul.\n* Move it around the box with the arrow keys.".to_string(),
+                tutorial_bones: "* Don't touch the white bones, they hurt.\n* Slipping past them closely fills TP.".to_string(),
+                tutorial_gravity: "* Your soul is heavy this turn and falls.\n* Up jumps, Down drops faster.".to_string(),
+                tutorial_turn: "* On your turn, pick with Left/Right:\n* CİHAD, TEKFİR, ITEM, TEBLİĞ or TP.".to_string(),
+                tutorial_hint: "Z / Enter: Next".to_string(),
+            },
+            presence: PresenceTexts {
+                boot: PresenceLine::new("Booting up...", "System Initialization"),
+                menu: PresenceLine::new("In Main Menu", "Selecting Option"),
+                profile: "Profile: {username}".to_string(),
+                transition_to_desktop: PresenceLine::new("Loading Desktop...", "Please wait..."),
+                desktop: PresenceLine::new("Exploring Desktop", "Stage {stage_current}/{stage_total}"),
+                combat_transition: PresenceLine::new("Encounter!", "Preparing for battle"),
+                combat: PresenceLine::new("Fighting!", "In Combat"),
+                kernel_panic: PresenceLine::new("System Crash!", "Critical Error"),
+                ayasofya_inside: PresenceLine::new("Visiting Ayasofya", "Praying"),
+                credits: PresenceLine::new("Watching Credits", "Rolling credits"),
+                statistics: PresenceLine::new("Checking Statistics", "Counting deeds"),
+                session_summary: PresenceLine::new("Taking Stock", "Looking back on a session"),
             },
         }
     }
 }
+
+/// Templates with named placeholders, rendered through `TextResources::fill`.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum TextKey {
+    UserLine,
+    StageIndicator,
+    HpLabel,
+    WhatsNewTitle,
+    ObjectiveComplete,
+    WardrobePrompt,
+    ProfileLimit,
+    CrashRecovered,
+    PresenceDetails(Scene),
+    PresenceState(Scene),
+    PresenceProfile,
+}
+
+/// Replaces `{name}` placeholders in `template` with `args`. A placeholder
+/// without an argument is a bug in the template, it asserts in debug builds
+/// and is left as written otherwise.
+pub fn interpolate(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            // Unclosed, the brace and everything after it stay as written
+            rest = &rest[open..];
+            break;
+        };
+        let name = &rest[open + 1..open + close];
+        match args.iter().find(|(key, _)| *key == name) {
+            Some((_, value)) => out.push_str(&value.to_string()),
+            None => {
+                debug_assert!(
+                    false,
+                    "Template {:?} has no argument for {{{}}}",
+                    template, name
+                );
+                out.push_str(&rest[open..=open + close]);
+            }
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fills_placeholders() {
+        let filled = interpolate("{name} has {hp} HP", &[("name", &"Sans"), ("hp", &1)]);
+        assert_eq!(filled, "Sans has 1 HP");
+    }
+
+    #[test]
+    fn reordered_template_uses_the_same_args() {
+        let args: &[(&str, &dyn Display)] = &[("stage", &2), ("total", &4)];
+        assert_eq!(interpolate("Stage {stage}/{total}", args), "Stage 2/4");
+        assert_eq!(
+            interpolate("{total} bölümden {stage}.", args),
+            "4 bölümden 2."
+        );
+    }
+
+    #[test]
+    fn unclosed_brace_is_kept_once() {
+        assert_eq!(interpolate("a{b", &[]), "a{b");
+        assert_eq!(interpolate("{x} and {y", &[("x", &1)]), "1 and {y");
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "has no argument for {hp}")]
+    fn missing_placeholder_asserts_in_debug() {
+        interpolate("{name} has {hp} HP", &[("name", &"Sans")]);
+    }
+
+    #[test]
+    #[cfg(not(debug_assertions))]
+    fn missing_placeholder_is_left_as_written() {
+        let filled = interpolate("{name} has {hp} HP", &[("name", &"Sans")]);
+        assert_eq!(filled, "Sans has {hp} HP");
+    }
+
+    #[test]
+    fn presence_lines_fill_in_both_languages() {
+        let stage: &[(&str, &dyn Display)] = &[("stage_current", &2), ("stage_total", &4)];
+        for texts in [TextResources::new_english(), TextResources::new_turkish()] {
+            assert!(
+                texts
+                    .fill(TextKey::PresenceState(Scene::Desktop), stage)
+                    .contains("2/4")
+            );
+            assert!(
+                texts
+                    .fill(TextKey::PresenceProfile, &[("username", &"Ayse")])
+                    .contains("Ayse")
+            );
+            assert!(
+                !texts
+                    .fill(TextKey::PresenceDetails(Scene::Combat), &[])
+                    .is_empty()
+            );
+        }
+    }
+}
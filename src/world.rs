@@ -1,24 +1,532 @@
+use crate::audio::SoundQueue;
+use crate::combat::{CombatEnemy, PlayStyle};
+use crate::defs::{Direction, NpcId, SCREEN_HEIGHT, SCREEN_WIDTH};
+use crate::dialogue::DialogueTree;
+use crate::objectives::{ObjectiveId, WorldFlag};
+use crate::particles::Particles;
+use crate::physics::ColliderCache;
+use crate::randomizer::Layout;
+use crate::scenes::desktop::SpawnOverlay;
+use crate::subtitles::SubtitleCue;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::f32::consts::TAU;
 use tetra::audio::{Sound, SoundInstance};
-use tetra::graphics::{Color, Texture};
+use tetra::graphics::mesh::Mesh;
+use tetra::graphics::{Color, Rectangle, Texture};
 use tetra::math::Vec2;
 
+/// Per-stage layout data. Index 0-3 are desktop stages 1-4, index 4 is the
+/// Ayasofya interior.
+pub struct StageDef {
+    pub top_bound: f32,
+    pub bottom_bound: f32,
+    pub spawns: SpawnPoints,
+    pub npc_positions: &'static [(NpcId, Vec2<f32>)],
+    pub gaster_teleport_points: &'static [Vec2<f32>],
+    pub crowd: &'static [CrowdSpawn],
+    pub hazards: &'static [HazardZone],
+    pub safe_pockets: &'static [Rectangle], // Inside the hazards, nothing hurts here
+    pub walls: &'static [Rectangle],
+    // Drawn back to front
+    pub bg_layers: &'static [BgLayer],
+    pub bg_tile: bool,   // Scroll and tile the background instead of stretching it
+    pub left_exit: bool, // Walking off the left edge leads to another stage
+    pub right_exit: bool,
+}
+
+impl StageDef {
+    pub fn spawn(&self, entry: Entry) -> SpawnPoint {
+        match entry {
+            Entry::FromLeft => self.spawns.from_left,
+            Entry::FromRight => self.spawns.from_right,
+            Entry::FromInterior => self.spawns.from_interior,
+            Entry::FastTravel => self.spawns.fast_travel,
+        }
+    }
+
+    /// Footsteps kick up snow where the snow layer is drawn.
+    pub fn has_snow(&self) -> bool {
+        self.bg_layers
+            .iter()
+            .any(|layer| layer.texture == LayerTexture::Snow)
+    }
+}
+
+/// How the player got into a stage, each way in has its own spawn point.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum Entry {
+    FromLeft, // Walked in over the left edge
+    FromRight,
+    FromInterior, // Back out of the Ayasofya or a fight
+    FastTravel,
+}
+
+impl Entry {
+    pub const ALL: [Self; 4] = [
+        Self::FromLeft,
+        Self::FromRight,
+        Self::FromInterior,
+        Self::FastTravel,
+    ];
+}
+
+/// Where the player appears on entering a stage, and which way they face.
+/// Blocked points fall back to the nearest free spot, see
+/// `physics::nearest_free_spot`.
+#[derive(Clone, Copy)]
+pub struct SpawnPoint {
+    pub pos: Vec2<f32>,
+    pub facing: Direction,
+}
+
+pub struct SpawnPoints {
+    pub from_left: SpawnPoint,
+    pub from_right: SpawnPoint,
+    pub from_interior: SpawnPoint,
+    pub fast_travel: SpawnPoint,
+}
+
+/// Textures a background layer can use. City is the old single background.
+#[derive(PartialEq, Clone, Copy)]
+pub enum LayerTexture {
+    City,
+    Snow,
+    Haze,
+}
+
+/// One background layer. `parallax` is how many pixels it scrolls per pixel
+/// the player moves, `drift` how many it slides by itself each second.
+/// `tint` is multiplied with the stage tint.
+pub struct BgLayer {
+    pub texture: LayerTexture,
+    pub parallax: f32,
+    pub drift: f32,
+    pub tint: Color,
+}
+
+const CITY_LAYER: BgLayer = BgLayer {
+    texture: LayerTexture::City,
+    parallax: 0.5,
+    drift: 0.0,
+    tint: Color::WHITE,
+};
+
+// Near layers, in front of the city and slightly faster than the player
+const SNOW_LAYER: BgLayer = BgLayer {
+    texture: LayerTexture::Snow,
+    parallax: 1.2,
+    drift: -25.0,
+    tint: Color::rgba(1.0, 1.0, 1.0, 0.7),
+};
+const HAZE_LAYER: BgLayer = BgLayer {
+    texture: LayerTexture::Haze,
+    parallax: 1.1,
+    drift: 12.0,
+    tint: Color::rgba(1.0, 1.0, 1.0, 0.5),
+};
+
+/// The city on its own, for stages without anything in front of it.
+pub const DEFAULT_BG_LAYERS: &[BgLayer] = &[CITY_LAYER];
+
+/// Background pedestrian, see scenes::crowd.
+pub struct CrowdSpawn {
+    pub home: Vec2<f32>,
+    pub sprite: usize, // Index into WorldState::pedestrian_textures
+    pub wander_radius: f32,
+}
+
+/// How a hazard zone moves. Driven only by the stage clock, so the same
+/// moment in a stage always has the same layout.
+#[derive(Clone, Copy)]
+pub enum HazardMotion {
+    Static,
+    Oscillate { amplitude: f32, period: f32 }, // Side to side, period in seconds
+    Sweep { distance: f32, period: f32 },      // Top to bottom, then starts over
+}
+
+/// Area that fills the exposure meter and then hurts. `intensity` scales
+/// the damage per second.
+pub struct HazardZone {
+    pub rect: Rectangle, // Where it sits at clock 0
+    pub motion: HazardMotion,
+    pub intensity: f32,
+}
+
+impl HazardZone {
+    /// Where the zone is `clock` seconds into the stage.
+    pub fn rect_at(&self, clock: f32) -> Rectangle {
+        let mut rect = self.rect;
+        match self.motion {
+            HazardMotion::Static => {}
+            HazardMotion::Oscillate { amplitude, period } => {
+                rect.x += (clock / period * TAU).sin() * amplitude;
+            }
+            HazardMotion::Sweep { distance, period } => {
+                rect.y += clock % period / period * distance;
+            }
+        }
+        rect
+    }
+}
+
+const fn spawn(x: f32, y: f32, facing: Direction) -> SpawnPoint {
+    SpawnPoint {
+        pos: Vec2::new(x, y),
+        facing,
+    }
+}
+
+const fn area(x: f32, y: f32, width: f32, height: f32) -> Rectangle {
+    Rectangle {
+        x,
+        y,
+        width,
+        height,
+    }
+}
+
+pub const AYASOFYA_STAGE_INDEX: usize = 4;
+pub const STAGE_COUNT: usize = AYASOFYA_STAGE_INDEX; // Walkable stages, the interior isn't one
+
+/// Background tint per stage, same indexing as `STAGE_DEFS`. Stage 3 draws
+/// the Ayasofya entrance untinted, its entry is only there to keep the slots
+/// lined up.
+pub const STAGE_TINTS: [Color; 5] = [
+    Color::WHITE,
+    Color::rgb(0.8, 0.8, 1.0), // Blueish
+    Color::rgb(1.0, 0.8, 0.8), // Reddish
+    Color::rgb(1.0, 0.8, 0.8),
+    Color::WHITE,
+];
+
+pub const STAGE_DEFS: [StageDef; 5] = [
+    // Stage 1
+    StageDef {
+        top_bound: 150.0,
+        bottom_bound: SCREEN_HEIGHT as f32 - 50.0,
+        spawns: SpawnPoints {
+            from_left: spawn(40.0, 400.0, Direction::Right),
+            from_right: spawn(SCREEN_WIDTH as f32 - 40.0, 400.0, Direction::Left),
+            from_interior: spawn(700.0, 400.0, Direction::Left),
+            fast_travel: spawn(40.0, 400.0, Direction::Right),
+        },
+        npc_positions: &[
+            (NpcId::Sans, Vec2::new(600.0, 300.0)),
+            (NpcId::MusicBox, Vec2::new(200.0, 300.0)),
+        ],
+        gaster_teleport_points: &[],
+        crowd: &[],
+        hazards: &[],
+        safe_pockets: &[],
+        walls: &[],
+        bg_layers: DEFAULT_BG_LAYERS,
+        bg_tile: false,
+        left_exit: false,
+        right_exit: true,
+    },
+    // Stage 2
+    StageDef {
+        top_bound: 150.0,
+        bottom_bound: SCREEN_HEIGHT as f32 - 50.0,
+        spawns: SpawnPoints {
+            from_left: spawn(40.0, 400.0, Direction::Right),
+            from_right: spawn(SCREEN_WIDTH as f32 - 40.0, 400.0, Direction::Left),
+            from_interior: spawn(700.0, 400.0, Direction::Left),
+            fast_travel: spawn(40.0, 400.0, Direction::Right),
+        },
+        npc_positions: &[
+            (NpcId::Rarity, Vec2::new(150.0, 300.0)),
+            (NpcId::Gaster, Vec2::new(600.0, 300.0)),
+        ],
+        gaster_teleport_points: &[
+            Vec2::new(600.0, 300.0),
+            Vec2::new(650.0, 480.0),
+            Vec2::new(400.0, 220.0),
+            Vec2::new(380.0, 500.0),
+            Vec2::new(720.0, 200.0),
+        ],
+        crowd: &[],
+        hazards: &[],
+        safe_pockets: &[],
+        walls: &[],
+        bg_layers: &[CITY_LAYER, SNOW_LAYER],
+        bg_tile: true,
+        left_exit: true,
+        right_exit: true,
+    },
+    // Stage 3
+    StageDef {
+        top_bound: 150.0,
+        bottom_bound: SCREEN_HEIGHT as f32 - 50.0,
+        spawns: SpawnPoints {
+            from_left: spawn(330.0, 530.0, Direction::Right),
+            from_right: spawn(470.0, 530.0, Direction::Left),
+            from_interior: spawn(400.0, 400.0, Direction::Front), // Below the door
+            fast_travel: spawn(330.0, 530.0, Direction::Right),
+        },
+        // The rescued survivor, below the door area so F doesn't open it
+        npc_positions: &[(NpcId::Survivor, Vec2::new(560.0, 540.0))],
+        gaster_teleport_points: &[],
+        // Along both sides of the lane up to the door (X: 300-500), off the
+        // walls even at the end of their leash, the middle is the player's
+        crowd: &[
+            CrowdSpawn {
+                home: Vec2::new(340.0, 230.0),
+                sprite: 0,
+                wander_radius: 10.0,
+            },
+            CrowdSpawn {
+                home: Vec2::new(460.0, 290.0),
+                sprite: 1,
+                wander_radius: 10.0,
+            },
+            CrowdSpawn {
+                home: Vec2::new(340.0, 420.0),
+                sprite: 1,
+                wander_radius: 10.0,
+            },
+            CrowdSpawn {
+                home: Vec2::new(460.0, 460.0),
+                sprite: 0,
+                wander_radius: 10.0,
+            },
+        ],
+        hazards: &[],
+        safe_pockets: &[],
+        // Walls outside the Ayasofya door
+        walls: &[
+            area(0.0, 0.0, 300.0, 600.0),
+            area(500.0, 0.0, 300.0, 600.0),
+            area(300.0, 0.0, 200.0, 150.0), // Above the door
+        ],
+        bg_layers: DEFAULT_BG_LAYERS,
+        bg_tile: true,
+        left_exit: true,
+        right_exit: true,
+    },
+    // Stage 4
+    StageDef {
+        top_bound: 150.0,
+        bottom_bound: SCREEN_HEIGHT as f32 - 50.0,
+        spawns: SpawnPoints {
+            from_left: spawn(40.0, 400.0, Direction::Right),
+            from_right: spawn(725.0, 465.0, Direction::Left), // Safe pocket, the edge is dead space
+            from_interior: spawn(300.0, 450.0, Direction::Left),
+            fast_travel: spawn(40.0, 400.0, Direction::Right),
+        },
+        npc_positions: &[
+            (NpcId::Eilish, Vec2::new(150.0, 300.0)),
+            (NpcId::Survivor, Vec2::new(750.0, 370.0)), // Between the safe pockets
+        ],
+        gaster_teleport_points: &[],
+        crowd: &[],
+        hazards: &[
+            // The dead space itself, the whole right side
+            HazardZone {
+                rect: area(500.0, 0.0, 300.0, SCREEN_HEIGHT as f32),
+                motion: HazardMotion::Static,
+                intensity: 1.0,
+            },
+            // Spills over the border and back
+            HazardZone {
+                rect: area(400.0, 200.0, 80.0, 140.0),
+                motion: HazardMotion::Oscillate {
+                    amplitude: 70.0,
+                    period: 6.0,
+                },
+                intensity: 0.5,
+            },
+            // A hotter band combing down through the dead space
+            HazardZone {
+                rect: area(500.0, 110.0, 300.0, 50.0),
+                motion: HazardMotion::Sweep {
+                    distance: 440.0,
+                    period: 5.0,
+                },
+                intensity: 2.0,
+            },
+        ],
+        safe_pockets: &[
+            area(600.0, 230.0, 70.0, 70.0),
+            area(690.0, 430.0, 70.0, 70.0),
+        ],
+        walls: &[],
+        bg_layers: &[CITY_LAYER, HAZE_LAYER],
+        bg_tile: true,
+        left_exit: true,
+        right_exit: true,
+    },
+    // Ayasofya Interior
+    StageDef {
+        top_bound: 150.0,
+        bottom_bound: SCREEN_HEIGHT as f32 - 50.0,
+        // The door only leads in on the left
+        spawns: SpawnPoints {
+            from_left: spawn(100.0, 300.0, Direction::Right),
+            from_right: spawn(SCREEN_WIDTH as f32 - 100.0, 300.0, Direction::Left),
+            from_interior: spawn(100.0, 300.0, Direction::Right),
+            fast_travel: spawn(100.0, 300.0, Direction::Right),
+        },
+        npc_positions: &[],
+        gaster_teleport_points: &[],
+        crowd: &[],
+        hazards: &[],
+        safe_pockets: &[],
+        // Bases of the arcade on the left and the marble kiosk on the right,
+        // kept below the y = 300 lane the player enters and leaves along
+        walls: &[
+            area(50.0, 320.0, 170.0, 35.0),
+            area(590.0, 320.0, 210.0, 30.0),
+        ],
+        bg_layers: DEFAULT_BG_LAYERS,
+        bg_tile: false,
+        left_exit: true, // Both sides lead back out to the stage 3 door
+        right_exit: true,
+    },
+];
+
+const GASTER_RNG_SEED: u64 = 0x6a57e2;
+const IDLE_RNG_SEED: u64 = 0x1d1e;
+const PARTICLE_RNG_SEED: u64 = 0xb125;
+const PARTICLE_POOL_SIZE: usize = 128;
+
+// NPC moves while the music box plays, see scenes::disco
+const FOOT_TAP_RATE: f32 = 8.0; // Radians per second
+const FOOT_TAP_HEIGHT: f32 = 3.0;
+const DANCE_SWAY_RATE: f32 = 5.0;
+const DANCE_SWAY_WIDTH: f32 = 6.0;
+const DANCE_BOUNCE_HEIGHT: f32 = 4.0;
+
+pub struct CrowdNpc {
+    pub pos: Vec2<f32>,
+    pub home: Vec2<f32>,
+    pub target: Vec2<f32>,
+    pub sprite: usize,
+    pub wander_radius: f32,
+    pub wander_timer: f32, // Seconds until a new target is picked
+    pub bark: Option<String>,
+    pub bark_timer: f32,    // Seconds the speech bubble stays up
+    pub bark_cooldown: f32, // Seconds until this NPC can bark again
+    pub stagger_timer: f32, // Seconds left reeling from a thrown bone
+}
+
+/// Bone thrown in the overworld, see scenes::bone.
+pub struct BoneProjectile {
+    pub pos: Vec2<f32>,
+    pub velocity: Vec2<f32>, // Pixels per second
+    pub age: f32,
+    pub hit: bool,     // Hit someone, so it doesn't count as a miss
+    pub bounced: bool, // Already came off Gaster
+}
+
+/// One NPC as seen from outside, put together from its flat fields in
+/// WorldState. See `WorldState::npc_state`.
+#[derive(Clone, Copy)]
+pub struct NpcState {
+    pub pos: Vec2<f32>,
+    pub alive: bool,
+    #[allow(dead_code)]
+    pub facing: Direction, // Front for NPCs that don't turn
+}
+
+/// Parts of the desktop HUD that explain themselves on hover.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum HudElement {
+    Health,
+    Stage,
+    Fps,
+}
+
+/// Overworld hunt after the player attacks him mid-mercy.
+#[derive(PartialEq, Clone, Copy)]
+pub enum SansChase {
+    Idle,
+    Chasing,
+    Shrugging,
+    Returning,
+}
+
 pub struct WorldState {
     pub current_stage: u8,
+    pub layout: Layout, // Which stage holds what, shuffled on randomizer profiles
     pub bg_texture: Option<Texture>,
+    pub snow_layer_texture: Option<Texture>,
+    pub haze_layer_texture: Option<Texture>,
+    pub bg_tile: bool, // Copied from the current StageDef
+    pub trust: HashMap<NpcId, f32>,
+    pub dead_zone_time: f32, // Seconds spent taking dead space damage, resets on leaving
+    pub stage_clock: f32,    // Seconds since entering the stage, drives the hazards
+    pub screen_shake: f32,   // Seconds of shake left
+    pub idle_rng: StdRng,    // Blinks, shrugs and flickers, see scenes::idle
+    pub nearest_npc: Option<NpcId>, // Only this one shows a prompt and reacts to F
+    pub edge_push_timer: f32, // Seconds spent pushing past an exit edge
+    pub edge_bark_timer: f32, // > 0 while "nothing that way" shows
+    pub edge_bark_shown: bool, // Once per session
+    pub tooltip_target: Option<HudElement>, // HUD element under the mouse
+    pub tooltip_hover_timer: f32, // Seconds the mouse has stayed on tooltip_target
+    pub flags: u32,          // WorldFlag bits, saved per profile
+    pub objective_banner: Option<ObjectiveId>, // Just completed, shown for a moment
+    pub objective_banner_timer: f32,
+    pub toast: Option<String>, // Short note over the player's head
+    pub toast_timer: f32,
+    pub npc_fade_alpha: f32, // NPC sprite opacity, back to 0 on entering a stage
+    pub map_open: bool,
+    pub map_selection: u8,         // Stage highlighted on the travel map
+    pub travel_target: Option<u8>, // Where the current fade ends up, for fast travel
+    pub transition_direction: Option<Direction>, // Edge the current fade leaves through
+    pub outcome_banner: Option<PlayStyle>, // How the last fight went, shown for a moment
+    pub outcome_banner_timer: f32,
+
+    // Built once per stage by physics, only positions change after that
+    pub cached_colliders: RefCell<Option<ColliderCache>>,
+    pub spawn_overlay: RefCell<Option<SpawnOverlay>>, // F3 markers, see scenes::desktop
+
+    // Stab, pickup and footstep bursts, see particles
+    pub particles: Particles,
+    pub footstep_distance: f32, // Walked since the last puff in the snow
+
+    // Bone throw, unlocked by beating Sans
+    pub bones: Vec<BoneProjectile>,
+    pub bone_cooldown: f32,
+    pub bone_miss_streak: u32, // Throws in a row that hit nobody
 
     // Gaster
     pub gaster_pos: Vec2<f32>,
     pub gaster_talking: bool,
+    // He remarks on the player's hat once per session
+    pub gaster_hat_remarked: bool,
+    pub gaster_talk_blend: f32, // 0.0: Standing, 1.0: Talking
+    pub gaster_talk_blend_speed: f32,
     pub gaster_dialogues: Vec<String>,
     pub current_gaster_dialogue: String,
+    pub gaster_dialogue: Option<DialogueTree>, // His darker lines, which come with a choice
     pub npc_gaster_standing: Option<Texture>,
     pub npc_gaster_talking: Option<Texture>,
+    pub gaster_rng: StdRng,
+    pub gaster_flicker_timer: f32, // Seconds until the talking sprite flashes unprompted
+    pub gaster_flicker: f32,       // Seconds left of the current flash
+    pub gaster_teleport_timer: f32, // Seconds until the next teleport
+    pub gaster_vanish_timer: f32,  // > 0 while the static effect plays
+    pub gaster_static_texture: Option<Texture>,
+    pub gaster_static_sound: Option<Sound>,
+    pub gaster_idle_timer: f32, // Seconds until he mutters the next idle line
+    pub gaster_idle_line: String,
+    pub gaster_idle_fade: f32, // Seconds the current idle line stays visible, also its alpha
 
     // Rarity
     pub rarity_pos: Vec2<f32>,
     pub rarity_alive: bool,
     pub rarity_stabbed_timer: f32,
+    pub rarity_stab_frame: u8, // 1-3 while the stab animation plays, 0 otherwise
+    pub rarity_stab_frame_timer: f32, // Seconds left on the current frame
+    pub rarity_dancing: bool,
     pub rarity_texture: Option<Texture>,
+    pub rarity_stab_textures: [Option<Texture>; 3],
+    pub stab_sound: Option<Sound>,
+    pub sound_queue: SoundQueue,
 
     // Eilish
     pub eilish_pos: Vec2<f32>,
@@ -26,22 +534,55 @@ pub struct WorldState {
     pub eilish_dialogue_timer: f32,
     pub eilish_current_dialogue: String,
     pub eilish_texture: Option<Texture>,
+    pub eilish_texture_left: Option<Texture>,
+    pub eilish_texture_right: Option<Texture>,
+    pub shadow_mesh: Option<Mesh>, // See render_util::shadow_mesh
+    pub eilish_facing: Direction,
+    pub eilish_song: Option<Sound>,
+    pub eilish_song_instance: Option<SoundInstance>,
+    pub eilish_song_cues: Vec<SubtitleCue>,
+    pub eilish_listening: bool,
+    pub eilish_song_timer: f32,
+    pub eilish_song_heard: bool,
+    pub eilish_song_skipped: bool,
 
     // MusicBox
     pub musicbox_pos: Vec2<f32>,
     pub music_playing: bool,
     pub disco_color: Color,
     pub disco_timer: f32,
+    pub dance_time: f32, // Seconds the NPCs have been dancing, 0 when they aren't
     pub musicbox_texture: Option<Texture>,
     pub music_track: Option<Sound>,
     pub music_instance: Option<SoundInstance>,
+    pub music_resume: bool, // The loaded save had the music on, start it once the track is there
 
     // Sans
     pub sans_pos: Vec2<f32>,
     pub sans_texture: Option<Texture>,
     pub sans_combat_texture: Option<Texture>,
-    pub _sans_shrug_texture: Option<Texture>,
+    pub sans_shrug_texture: Option<Texture>,
     pub sans_handshake_texture: Option<Texture>,
+    pub sans_met: bool,
+    pub sans_dialogue: Option<DialogueTree>,
+    pub sans_chase: SansChase,
+    pub sans_chase_timer: f32, // Seconds left in the current chase step
+    pub sans_velocity: Vec2<f32>,
+    pub sans_idle_timer: f32, // Seconds until his next idle shrug
+    pub sans_idle_shrug: f32, // Seconds left of the current one
+    pub pending_combat_enemy: Option<CombatEnemy>, // Taken by CombatTransition to set up the fight
+
+    // Dead space survivor, see scenes::rescue
+    pub survivor_pos: Vec2<f32>,
+    pub survivor_escort: bool,
+    pub survivor_exposure: f32, // Their own meter, only fills during the escort
+    pub survivor_line: Option<String>,
+    pub survivor_line_timer: f32,
+    pub survivor_texture: Option<Texture>,
+
+    // Crowd (Stage 3)
+    pub crowd: Vec<CrowdNpc>,
+    pub pedestrian_textures: [Option<Texture>; 2],
 
     // Ayasofya
     pub ayasofya_giris_texture: Option<Texture>,
@@ -52,42 +593,376 @@ impl WorldState {
     pub fn new() -> Self {
         Self {
             current_stage: 1,
+            layout: Layout::standard(),
             bg_texture: None,
+            snow_layer_texture: None,
+            haze_layer_texture: None,
+            bg_tile: false,
+            trust: HashMap::new(),
+            dead_zone_time: 0.0,
+            stage_clock: 0.0,
+            screen_shake: 0.0,
+            idle_rng: StdRng::seed_from_u64(IDLE_RNG_SEED),
+            nearest_npc: None,
+            edge_push_timer: 0.0,
+            edge_bark_timer: 0.0,
+            edge_bark_shown: false,
+            tooltip_target: None,
+            tooltip_hover_timer: 0.0,
+            flags: 0,
+            objective_banner: None,
+            objective_banner_timer: 0.0,
+            toast: None,
+            toast_timer: 0.0,
+            npc_fade_alpha: 1.0,
+            map_open: false,
+            map_selection: 1,
+            travel_target: None,
+            transition_direction: None,
+            outcome_banner: None,
+            outcome_banner_timer: 0.0,
+
+            cached_colliders: RefCell::new(None),
+            spawn_overlay: RefCell::new(None),
+
+            particles: Particles::new(PARTICLE_POOL_SIZE, PARTICLE_RNG_SEED),
+            footstep_distance: 0.0,
+
+            bones: Vec::new(),
+            bone_cooldown: 0.0,
+            bone_miss_streak: 0,
 
             gaster_pos: Vec2::new(600.0, 300.0),
             gaster_talking: false,
+            gaster_hat_remarked: false,
+            gaster_talk_blend: 0.0,
+            gaster_talk_blend_speed: 4.0,
             gaster_dialogues: Vec::new(),
             current_gaster_dialogue: String::new(),
+            gaster_dialogue: None,
             npc_gaster_standing: None,
             npc_gaster_talking: None,
+            gaster_rng: StdRng::seed_from_u64(GASTER_RNG_SEED),
+            gaster_flicker_timer: 10.0,
+            gaster_flicker: 0.0,
+            gaster_teleport_timer: 30.0,
+            gaster_vanish_timer: 0.0,
+            gaster_static_texture: None,
+            gaster_static_sound: None,
+            gaster_idle_timer: 5.0,
+            gaster_idle_line: String::new(),
+            gaster_idle_fade: 0.0,
 
             rarity_pos: Vec2::new(150.0, 300.0),
             rarity_alive: true,
             rarity_stabbed_timer: 0.0,
+            rarity_stab_frame: 0,
+            rarity_stab_frame_timer: 0.0,
+            rarity_dancing: false,
             rarity_texture: None,
+            rarity_stab_textures: [None, None, None],
+            stab_sound: None,
+            sound_queue: SoundQueue::new(),
 
             eilish_pos: Vec2::new(150.0, 300.0),
             eilish_talking: false,
             eilish_dialogue_timer: 0.0,
             eilish_current_dialogue: String::new(),
             eilish_texture: None,
+            eilish_texture_left: None,
+            shadow_mesh: None,
+            eilish_texture_right: None,
+            eilish_facing: Direction::Front,
+            eilish_song: None,
+            eilish_song_instance: None,
+            eilish_song_cues: Vec::new(),
+            eilish_listening: false,
+            eilish_song_timer: 0.0,
+            eilish_song_heard: false,
+            eilish_song_skipped: false,
 
             musicbox_pos: Vec2::new(200.0, 300.0),
             music_playing: false,
             disco_color: Color::WHITE,
             disco_timer: 0.0,
+            dance_time: 0.0,
             musicbox_texture: None,
             music_track: None,
             music_instance: None,
+            music_resume: false,
 
             sans_pos: Vec2::new(600.0, 300.0),
             sans_texture: None,
             sans_combat_texture: None,
-            _sans_shrug_texture: None,
+            sans_shrug_texture: None,
             sans_handshake_texture: None,
+            sans_met: false,
+            sans_dialogue: None,
+            sans_chase: SansChase::Idle,
+            sans_chase_timer: 0.0,
+            sans_velocity: Vec2::zero(),
+            sans_idle_timer: 10.0,
+            sans_idle_shrug: 0.0,
+            pending_combat_enemy: None,
+
+            survivor_pos: Vec2::new(750.0, 370.0),
+            survivor_escort: false,
+            survivor_exposure: 0.0,
+            survivor_line: None,
+            survivor_line_timer: 0.0,
+            survivor_texture: None,
+
+            crowd: Vec::new(),
+            pedestrian_textures: [None, None],
 
             ayasofya_giris_texture: None,
             ayasofya_ici_texture: None,
         }
     }
+
+    pub fn stage_def(&self) -> &'static StageDef {
+        let index = (self.current_stage as usize).clamp(1, 4) - 1;
+        &STAGE_DEFS[index]
+    }
+
+    /// The current stage's hazards and safe pockets, which move with the
+    /// dead zone on randomizer profiles.
+    pub fn hazard_def(&self) -> &'static StageDef {
+        let stage = self.layout.hazard_source(self.current_stage);
+        &STAGE_DEFS[(stage as usize).clamp(1, 4) - 1]
+    }
+
+    /// Exits go by where the stage sits in the order, not which stage it is.
+    pub fn left_exit(&self) -> bool {
+        STAGE_DEFS[self.layout.position(self.current_stage)].left_exit
+    }
+
+    pub fn right_exit(&self) -> bool {
+        STAGE_DEFS[self.layout.position(self.current_stage)].right_exit
+    }
+
+    pub fn stage_tint(stage: u8) -> Color {
+        STAGE_TINTS[(stage as usize).clamp(1, STAGE_TINTS.len()) - 1]
+    }
+
+    pub fn layer_texture(&self, texture: LayerTexture) -> Option<&Texture> {
+        match texture {
+            LayerTexture::City => self.bg_texture.as_ref(),
+            LayerTexture::Snow => self.snow_layer_texture.as_ref(),
+            LayerTexture::Haze => self.haze_layer_texture.as_ref(),
+        }
+    }
+
+    pub fn trust(&self, npc: NpcId) -> f32 {
+        self.trust.get(&npc).copied().unwrap_or(0.0)
+    }
+
+    /// Position, whether it's alive and which way it faces. None for a crowd
+    /// index that isn't there.
+    pub fn npc_state(&self, npc: NpcId) -> Option<NpcState> {
+        let pos = match npc {
+            NpcId::Sans => self.sans_pos,
+            NpcId::MusicBox => self.musicbox_pos,
+            NpcId::Gaster => self.gaster_pos,
+            NpcId::Rarity => self.rarity_pos,
+            NpcId::Eilish => self.eilish_pos,
+            NpcId::Survivor => self.survivor_pos,
+            NpcId::Crowd(index) => self.crowd.get(index)?.pos,
+        };
+        Some(NpcState {
+            pos: pos + self.dance_offset(npc),
+            alive: npc != NpcId::Rarity || self.rarity_alive,
+            facing: match npc {
+                NpcId::Eilish => self.eilish_facing,
+                _ => Direction::Front,
+            },
+        })
+    }
+
+    /// How far the music moves an NPC off its position, so sprites and
+    /// colliders both follow the dance.
+    pub fn dance_offset(&self, npc: NpcId) -> Vec2<f32> {
+        let t = self.dance_time;
+        match npc {
+            NpcId::Sans if self.music_playing => {
+                Vec2::new(0.0, -(t * FOOT_TAP_RATE).sin().abs() * FOOT_TAP_HEIGHT)
+            }
+            NpcId::Rarity if self.rarity_dancing => Vec2::new(
+                (t * DANCE_SWAY_RATE).sin() * DANCE_SWAY_WIDTH,
+                -(t * DANCE_SWAY_RATE * 2.0).sin().abs() * DANCE_BOUNCE_HEIGHT,
+            ),
+            _ => Vec2::zero(),
+        }
+    }
+
+    pub fn npc_pos_mut(&mut self, npc: NpcId) -> &mut Vec2<f32> {
+        match npc {
+            NpcId::Sans => &mut self.sans_pos,
+            NpcId::MusicBox => &mut self.musicbox_pos,
+            NpcId::Gaster => &mut self.gaster_pos,
+            NpcId::Rarity => &mut self.rarity_pos,
+            NpcId::Eilish => &mut self.eilish_pos,
+            NpcId::Survivor => &mut self.survivor_pos,
+            NpcId::Crowd(index) => &mut self.crowd[index].pos,
+        }
+    }
+
+    /// Stops and drops the music box instance. Every place that silences the
+    /// music goes through here so a stopped instance never lingers.
+    pub fn stop_music(&mut self) {
+        if let Some(instance) = self.music_instance.take() {
+            instance.stop();
+        }
+        self.music_playing = false;
+    }
+
+    /// The survivor waits in the dead space until rescued, then in stage 3.
+    pub fn survivor_stage(&self) -> u8 {
+        if WorldFlag::RescuedSurvivor.is_set(self.flags) {
+            3
+        } else {
+            self.layout.dead_zone_stage
+        }
+    }
+
+    pub fn is_randomized(&self) -> bool {
+        self.layout != Layout::standard()
+    }
+
+    /// The stage `npc` spawns in.
+    pub fn npc_stage(&self, npc: NpcId) -> u8 {
+        match npc {
+            NpcId::Survivor => self.survivor_stage(),
+            NpcId::Crowd(_) => crate::scenes::crowd::CROWD_STAGE,
+            _ => self.layout.npc_stage(npc).unwrap_or(0),
+        }
+    }
+
+    pub fn in_current_stage(&self, npc: NpcId) -> bool {
+        self.npc_stage(npc) == self.current_stage
+    }
+
+    pub fn is_nearest(&self, npc: NpcId) -> bool {
+        self.nearest_npc == Some(npc)
+    }
+
+    /// The closest NPC to `pos` if it's within `radius`, center to center.
+    /// What F acts on when standing at `pos`.
+    pub fn interactable_at(&self, pos: Vec2<f32>, radius: f32) -> Option<NpcId> {
+        crate::physics::query_nearest_npc(pos, self)
+            .filter(|(_, distance)| *distance < radius)
+            .map(|(npc, _)| npc)
+    }
+
+    /// Where the NPC stands in the current stage, if it appears there. An
+    /// NPC the randomizer moved keeps its spot from its standard stage.
+    pub fn npc_home(&self, npc: NpcId) -> Option<Vec2<f32>> {
+        if !self.in_current_stage(npc) {
+            return None;
+        }
+        let listed = |def: &StageDef| {
+            def.npc_positions
+                .iter()
+                .find(|(id, _)| *id == npc)
+                .map(|(_, pos)| *pos)
+        };
+        let home_stage = match npc {
+            NpcId::Survivor => crate::randomizer::DEAD_ZONE_HOME,
+            _ => Layout::standard().npc_stage(npc)?,
+        };
+        listed(self.stage_def()).or_else(|| listed(&STAGE_DEFS[home_stage as usize - 1]))
+    }
+
+    /// How strongly the current stage's hazards hurt at `pos`, the strongest
+    /// zone wins. 0.0 outside every zone or inside a safe pocket.
+    pub fn hazard_intensity(&self, pos: Vec2<f32>) -> f32 {
+        let stage_def = self.hazard_def();
+        if stage_def
+            .safe_pockets
+            .iter()
+            .any(|pocket| pocket.contains_point(pos))
+        {
+            return 0.0;
+        }
+        stage_def
+            .hazards
+            .iter()
+            .filter(|zone| zone.rect_at(self.stage_clock).contains_point(pos))
+            .map(|zone| zone.intensity)
+            .fold(0.0, f32::max)
+    }
+
+    /// Enters `stage` with everything that belongs to a stage as it was on
+    /// the first visit: NPCs at their initial positions, timers and talk
+    /// state cleared, the stage's background mode and a fresh stage clock.
+    /// The one place per-stage runtime state is set up, on stage changes and
+    /// when a save is loaded. An unfinished escort is dropped with the rest.
+    pub fn reset_for_stage(&mut self, stage: u8) {
+        self.current_stage = stage;
+        self.bg_tile = self.stage_def().bg_tile;
+        self.stage_clock = 0.0;
+        self.dead_zone_time = 0.0;
+        self.screen_shake = 0.0;
+        self.nearest_npc = None;
+        self.edge_push_timer = 0.0;
+        *self.cached_colliders.get_mut() = None; // The crowd is about to change
+        *self.spawn_overlay.get_mut() = None;
+        self.particles.clear();
+        self.footstep_distance = 0.0;
+        self.bones.clear();
+        self.npc_fade_alpha = 0.0;
+
+        self.gaster_talking = false;
+        self.gaster_dialogue = None;
+        self.gaster_talk_blend = 0.0;
+        self.gaster_flicker = 0.0;
+        self.gaster_vanish_timer = 0.0;
+        self.gaster_idle_fade = 0.0;
+
+        self.rarity_alive = true;
+        self.rarity_stabbed_timer = 0.0;
+        self.rarity_stab_frame = 0;
+        self.rarity_stab_frame_timer = 0.0;
+        self.rarity_dancing = false;
+
+        self.eilish_talking = false;
+        self.eilish_facing = Direction::Front;
+
+        self.sans_dialogue = None;
+        self.sans_chase = SansChase::Idle;
+        self.sans_chase_timer = 0.0;
+        self.sans_velocity = Vec2::zero();
+        self.sans_idle_shrug = 0.0;
+
+        self.survivor_escort = false;
+        self.survivor_exposure = 0.0;
+        self.survivor_line = None;
+
+        let spawns = crate::randomizer::SHUFFLED_NPCS
+            .into_iter()
+            .chain([NpcId::Survivor])
+            .filter_map(|npc| Some((npc, self.npc_home(npc)?)))
+            .collect::<Vec<_>>();
+        for (npc, pos) in spawns {
+            *self.npc_pos_mut(npc) = pos;
+        }
+
+        self.crowd = self
+            .stage_def()
+            .crowd
+            .iter()
+            .map(|spawn| CrowdNpc {
+                pos: spawn.home,
+                home: spawn.home,
+                target: spawn.home,
+                sprite: spawn.sprite,
+                wander_radius: spawn.wander_radius,
+                wander_timer: 0.0,
+                bark: None,
+                bark_timer: 0.0,
+                bark_cooldown: 0.0,
+                stagger_timer: 0.0,
+            })
+            .collect();
+    }
 }